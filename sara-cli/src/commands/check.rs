@@ -1,5 +1,6 @@
 //! Implementation of the check command.
 
+use std::collections::HashSet;
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -7,14 +8,20 @@ use std::process::ExitCode;
 use std::time::{Duration, Instant};
 
 use clap::Args;
+use sara_core::error::SaraError;
 use sara_core::graph::{KnowledgeGraph, KnowledgeGraphBuilder};
-use sara_core::model::{Item, ItemType};
-use sara_core::validation::{ValidationReport, pre_validate, validate};
+use sara_core::model::{Item, ItemId, ItemType};
+use sara_core::repository::{GitRef, qualify_items, resolve_references};
+use sara_core::schema;
+use sara_core::service::{find_id_collisions, parse_branches};
+use sara_core::validation::{ValidationIssue, ValidationReport, Validator, pre_validate};
 use serde::Serialize;
 
-use sara_core::config::{Config, OutputConfig};
+use sara_core::config::{Config, OutputConfig, ValidationConfig};
 
-use crate::output::{format_error, format_success, format_warning, print_error, print_warning};
+use crate::output::{
+    format_error, format_success, format_warning, print_error, print_success, print_warning,
+};
 
 /// Output format for check command.
 #[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
@@ -31,6 +38,16 @@ pub struct CheckArgs {
     #[arg(long, value_name = "GIT_REF", help_heading = "Input")]
     pub at: Option<String>,
 
+    /// Check the changes staged for commit, reporting only the issues they
+    /// introduce over HEAD
+    #[arg(long, conflicts_with = "at", help_heading = "Input")]
+    pub staged: bool,
+
+    /// Report identifiers defined differently on several local or
+    /// remote-tracking branches
+    #[arg(long, conflicts_with_all = ["at", "staged"], help_heading = "Input")]
+    pub across_branches: bool,
+
     /// Output format
     #[arg(long, default_value = "text", help_heading = "Output")]
     pub format: CheckFormat,
@@ -73,6 +90,13 @@ struct CheckResult {
 
 /// Runs the check command.
 pub fn run(args: &CheckArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    if args.staged {
+        return run_staged(args, config);
+    }
+    if args.across_branches {
+        return run_across_branches(args, config);
+    }
+
     let start = Instant::now();
     let output_config = &config.output;
 
@@ -103,18 +127,201 @@ pub fn run(args: &CheckArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>
         return Ok(ExitCode::SUCCESS);
     }
 
-    let pre_report = pre_validate(&items, strict);
+    let (graph, report) = check_items(items, strict, config)?;
+    let parse_time = start.elapsed();
+    handle_output(args, graph.as_ref(), &report, &parse_time, output_config)
+}
+
+/// Checks the documents staged for commit, as a pre-commit hook does.
+///
+/// The graph is built from the index (HEAD with the staged changes) and
+/// only the issues HEAD does not already have are reported, so a commit is
+/// never blocked by problems it did not introduce. HEAD differs from the
+/// index only in the staged files, so only those are read again at HEAD,
+/// and HEAD is validated only when the index has issues to compare. A
+/// staged document that cannot be parsed always fails the check.
+fn run_staged(args: &CheckArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let start = Instant::now();
+    let output_config = &config.output;
+    let strict = args.strict || config.validation.strict_mode;
+
+    let repos = super::resolve_repositories(config)?;
+    let mut staged = Vec::new();
+    super::for_each_git_repository(&repos, |_, reader, scope| {
+        staged.push(reader.list_staged_files(scope)?);
+        Ok(())
+    })?;
+    if staged.iter().all(Vec::is_empty) {
+        print_success(output_config, "No staged documents to check");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let ctx = super::parse_context(config);
+    let mut indexed = Vec::new();
+    let mut committed = Vec::new();
+    let mut unparsable = Vec::new();
+    let mut staged = staged.into_iter();
+    super::for_each_git_repository(&repos, |repo_path, reader, scope| {
+        let files = staged.next().unwrap_or_default();
+        let index = reader.parse_index(scope, &ctx)?;
+        for (path, error) in index.failures {
+            if files.contains(&path) {
+                unparsable.push(ValidationIssue::error(error));
+            } else {
+                tracing::warn!("Failed to parse {}: {}", path.display(), error);
+            }
+        }
+        let mut index_items = index.items;
+        let mut head_items: Vec<Item> = index_items
+            .iter()
+            .filter(|item| !files.contains(&item.source.file_path))
+            .cloned()
+            .collect();
+        if reader.has_commits() {
+            let head = reader.parse_files_at(&GitRef::Head, &files, &ctx)?;
+            for (path, error) in head.failures {
+                print_warning(
+                    output_config,
+                    &format!(
+                        "Cannot parse {} at HEAD, its issues there are not known: {error}",
+                        path.display()
+                    ),
+                );
+            }
+            head_items.extend(head.items);
+        }
+        if let Some(namespace) = repos.namespace_of(repo_path) {
+            qualify_items(&mut index_items, namespace);
+            qualify_items(&mut head_items, namespace);
+        }
+        indexed.extend(index_items);
+        committed.extend(head_items);
+        Ok(())
+    })?;
+    resolve_references(&mut indexed);
+
+    let pre_report = pre_validate(&indexed, ctx.schema(), strict);
+    let (graph, mut report) = validate_items(indexed, pre_report, strict, config)?;
+    if !report.issues.is_empty() {
+        resolve_references(&mut committed);
+        let pre_report = pre_validate(&committed, ctx.schema(), strict);
+        let (_, baseline) = validate_items(committed, pre_report, strict, config)?;
+        let known: HashSet<String> = baseline
+            .issues
+            .iter()
+            .map(|issue| issue_key(&issue.error))
+            .collect();
+        report
+            .issues
+            .retain(|issue| !known.contains(&issue_key(&issue.error)));
+    }
+    unparsable.append(&mut report.issues);
+    report.issues = unparsable;
+
+    let parse_time = start.elapsed();
+    handle_output(args, Some(&graph), &report, &parse_time, output_config)
+}
+
+/// Identifies an issue whatever the lines of the documents involved.
+///
+/// Issues are compared by kind and by the items, relations and files they
+/// name. Lines are left out, so an issue HEAD already has is still known
+/// once an edit shifts the item it is about within its file.
+fn issue_key(error: &SaraError) -> String {
+    let mut key = serde_json::to_value(error).unwrap_or_default();
+    if let Some(details) = key.get_mut("details").and_then(|d| d.as_object_mut()) {
+        for field in ["file", "locations"] {
+            if let Some(serde_json::Value::String(locations)) = details.get_mut(field) {
+                *locations = locations
+                    .split(", ")
+                    .map(|location| match location.rsplit_once(':') {
+                        Some((path, line)) if line.parse::<usize>().is_ok() => path,
+                        _ => location,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+            }
+        }
+    }
+    key.to_string()
+}
+
+/// Checks that no identifier is defined differently on several branches.
+///
+/// Branches that independently create the same identifier only clash once
+/// merged; this reports them beforehand. Identifiers inherited from the
+/// common ancestor of the branches and edited since are not collisions.
+fn run_across_branches(args: &CheckArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let start = Instant::now();
+    let repos = super::resolve_repositories(config)?;
+    let ctx = super::parse_context(config);
+    let mut ids = HashSet::new();
+    let mut report = ValidationReport::new();
+
+    super::for_each_git_repository(&repos, |repo_path, reader, scope| {
+        let namespace = repos.namespace_of(repo_path);
+        let branches = parse_branches(reader, scope, &ctx)?;
+        for branch in &branches {
+            let mut items = branch.items.clone();
+            if let Some(namespace) = namespace {
+                qualify_items(&mut items, namespace);
+            }
+            ids.extend(items.into_iter().map(|item| item.id));
+        }
+        for mut collision in find_id_collisions(reader, scope, &branches, &ctx)? {
+            if let Some(namespace) = namespace {
+                collision.id = ItemId::qualified(namespace, collision.id.as_str());
+            }
+            report
+                .issues
+                .push(ValidationIssue::error(SaraError::from(collision)));
+        }
+        Ok(())
+    })?;
+
+    report.items_checked = ids.len();
+    let parse_time = start.elapsed();
+    handle_output(args, None, &report, &parse_time, &config.output)
+}
+
+/// Validates items, then the graph they form when they pass
+/// pre-validation.
+///
+/// Returns the graph, if built, along with the consolidated report.
+fn check_items(
+    items: Vec<Item>,
+    strict: bool,
+    config: &Config,
+) -> Result<(Option<KnowledgeGraph>, ValidationReport), Box<dyn Error>> {
+    let pre_report = pre_validate(&items, schema::active(), strict);
     if !pre_report.is_valid() {
-        let parse_time = start.elapsed();
-        return handle_output(args, None, &pre_report, &parse_time, output_config);
+        return Ok((None, pre_report));
     }
+    let (graph, report) = validate_items(items, pre_report, strict, config)?;
+    Ok((Some(graph), report))
+}
 
+/// Validates the graph formed by items, adding the issues of their
+/// pre-validation.
+///
+/// The graph is validated whatever the pre-validation found, which lets the
+/// staged check compare every issue of HEAD and the index: a pre-validation
+/// error HEAD already has must not hide the graph issues behind it.
+fn validate_items(
+    items: Vec<Item>,
+    pre_report: ValidationReport,
+    strict: bool,
+    config: &Config,
+) -> Result<(KnowledgeGraph, ValidationReport), Box<dyn Error>> {
     let graph = KnowledgeGraphBuilder::new().add_items(items).build()?;
 
-    let report = validate(&graph, strict);
+    let report = Validator::new(ValidationConfig {
+        strict_mode: strict,
+        ..config.validation.clone()
+    })
+    .validate(&graph);
     let report = consolidate_reports(report, pre_report);
-    let parse_time = start.elapsed();
-    handle_output(args, Some(&graph), &report, &parse_time, output_config)
+    Ok((graph, report))
 }
 
 /// Consolidates two validation reports, keeping all data from the main report
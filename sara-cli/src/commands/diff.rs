@@ -9,7 +9,7 @@ use sara_core::service::{DiffOptions, DiffResult, DiffService};
 
 use sara_core::config::{Config, OutputConfig};
 
-use crate::output::{Color, Style, colorize, print_error, print_success};
+use crate::output::{Color, Style, colorize, print_error, print_success, print_warning};
 
 /// Output format for diff command.
 #[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
@@ -39,13 +39,14 @@ pub struct DiffArgs {
 
 /// Runs the diff command.
 pub fn run(args: &DiffArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
-    let opts = DiffOptions::new(&args.ref1, &args.ref2)
-        .with_repositories(config.repositories.paths.clone())
-        .with_stat(args.stat);
+    let opts = diff_options(config, &args.ref1, &args.ref2).with_stat(args.stat);
     let service = DiffService::new();
 
     match service.diff(&opts) {
         Ok(result) => {
+            for warning in &result.warnings {
+                print_warning(&config.output, warning);
+            }
             match args.format {
                 DiffFormat::Text => print_diff_text(&result, &opts, &config.output),
                 DiffFormat::Json => print_diff_json(&result.diff),
@@ -64,6 +65,18 @@ pub fn run(args: &DiffArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>>
     }
 }
 
+/// Returns the options comparing the configured repositories between two
+/// references.
+pub(super) fn diff_options(config: &Config, ref1: &str, ref2: &str) -> DiffOptions {
+    let opts = DiffOptions::new(ref1, ref2)
+        .with_repositories(config.repositories.paths.clone())
+        .with_namespaces(config.repositories.namespaces.clone());
+    match &config.model_schema {
+        Some(path) => opts.with_model_schema(path),
+        None => opts,
+    }
+}
+
 fn print_diff_text(result: &DiffResult, opts: &DiffOptions, config: &OutputConfig) {
     print_diff_header(result, config);
 
@@ -15,8 +15,7 @@ use inquire::{Confirm, InquireError};
 use sara_core::error::SaraError;
 use sara_core::graph::KnowledgeGraph;
 use sara_core::model::{
-    EditSummary, FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FieldChange, FieldValue, ItemType,
-    RelationshipType,
+    EditSummary, FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FieldChange, ItemType, RelationshipType,
 };
 use sara_core::schema::{FieldDef, FieldType};
 use sara_core::service::{EditOptions, EditService, EditedValues, FieldInput, ItemContext};
@@ -24,7 +23,7 @@ use sara_core::service::{EditOptions, EditService, EditedValues, FieldInput, Ite
 use sara_core::config::{Config, OutputConfig};
 
 use super::EXIT_CANCELLED;
-use super::init::field_help;
+use super::init::field_arg;
 use super::interactive::{
     PromptError, prompt_description, prompt_field_edits, prompt_name, prompt_traceability,
 };
@@ -107,14 +106,7 @@ impl Args for EditArgs {
             );
 
         for field in declared_fields_union() {
-            let mut arg = Arg::new(field.name.clone())
-                .long(field.name.replace('_', "-"))
-                .help_heading(HEADING_TYPE_SPECIFIC)
-                .help(field_help(field));
-            if matches!(field.field_type, FieldType::List(_)) {
-                arg = arg.num_args(1..).action(ArgAction::Append);
-            }
-            command = command.arg(arg);
+            command = command.arg(field_arg(field).help_heading(HEADING_TYPE_SPECIFIC));
         }
 
         for relation in primary_relations() {
@@ -366,7 +358,7 @@ fn run_edit_prompts(
     for (field, value) in prompt_field_edits(item.item_type, &item.attributes)? {
         match value {
             Some(value) => {
-                attributes.insert(field, FieldValue::Text(value));
+                attributes.insert(field, value);
             }
             None => {
                 attributes.remove(&field);
@@ -0,0 +1,90 @@
+//! Export command implementation.
+
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Subcommand};
+use sara_core::config::Config;
+use sara_core::interchange::{csv, reqif};
+
+use super::{build_graph, resolve_item_type};
+use crate::output::print_success;
+
+/// Arguments for the export command.
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Interchange format of the document
+    #[command(subcommand)]
+    pub format: ExportFormat,
+}
+
+/// Supported export formats.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum ExportFormat {
+    /// Export the items of one type as CSV, one row per item
+    ///
+    /// Columns are the id, name and description, then every declared field
+    /// and primary relation of the type. Edit the sheet and apply it back
+    /// with `sara import csv`.
+    Csv {
+        /// Item type to export (schema id or prefix, e.g. software_requirement or SWREQ)
+        #[arg(short = 't', long = "type", value_name = "TYPE")]
+        item_type: String,
+
+        /// Write the sheet to a file instead of standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Export the knowledge graph as a ReqIF document
+    Reqif {
+        /// Title of the exported specification
+        #[arg(long, default_value = "SARA export")]
+        title: String,
+
+        /// Write the document to a file instead of standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Runs the export command.
+pub fn run(args: &ExportArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    match &args.format {
+        ExportFormat::Csv { item_type, output } => {
+            let item_type = resolve_item_type(item_type)?;
+            let graph = build_graph(config)?;
+            let sheet = csv::export(&graph, item_type);
+            let count = graph.items_by_type(item_type).len();
+            write_output(&sheet, output.as_ref(), config, count)
+        }
+        ExportFormat::Reqif { title, output } => {
+            let graph = build_graph(config)?;
+            let document = reqif::export(&graph, title)?;
+            write_output(&document, output.as_ref(), config, graph.item_count())
+        }
+    }
+}
+
+/// Writes the exported document to a file or standard output.
+fn write_output(
+    document: &str,
+    output: Option<&PathBuf>,
+    config: &Config,
+    item_count: usize,
+) -> Result<ExitCode, Box<dyn Error>> {
+    match output {
+        Some(path) => {
+            fs::write(path, document)?;
+            print_success(
+                &config.output,
+                &format!("Exported {item_count} item(s) to {}", path.display()),
+            );
+        }
+        None => print!("{document}"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,73 @@
+//! Hook command implementation.
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process::ExitCode;
+
+use clap::{Args, Subcommand};
+use sara_core::config::Config;
+use sara_core::repository::GitReader;
+
+use crate::output::{print_error, print_success};
+
+/// Pre-commit hook checking the staged documents. Git runs hooks from the
+/// root of the working tree, where `sara.toml` is looked up.
+const PRE_COMMIT_HOOK: &str = "#!/bin/sh
+# Installed by `sara hook install`: checks the documents staged for commit.
+exec sara check --staged
+";
+
+/// Arguments for the hook command.
+#[derive(Args, Debug)]
+pub struct HookArgs {
+    /// Hook subcommand
+    #[command(subcommand)]
+    pub command: HookCommand,
+}
+
+/// Hook subcommands.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum HookCommand {
+    /// Install a pre-commit hook running `sara check --staged`
+    Install {
+        /// Replace an existing pre-commit hook
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Runs the hook command.
+pub fn run(args: &HookArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let HookCommand::Install { force } = args.command;
+
+    let reader = GitReader::discover(&env::current_dir()?)?;
+    let hooks_dir = reader.hooks_dir();
+    let path = hooks_dir.join("pre-commit");
+
+    if path.exists() && !force {
+        print_error(
+            &config.output,
+            &format!(
+                "A pre-commit hook already exists at {} (use --force to replace it)",
+                path.display()
+            ),
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+
+    fs::create_dir_all(&hooks_dir)?;
+    fs::write(&path, PRE_COMMIT_HOOK)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    print_success(
+        &config.output,
+        &format!("Installed pre-commit hook at {}", path.display()),
+    );
+    Ok(ExitCode::SUCCESS)
+}
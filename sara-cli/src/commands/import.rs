@@ -0,0 +1,165 @@
+//! Import command implementation.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Subcommand};
+use sara_core::config::Config;
+use sara_core::interchange::{csv, reqif};
+use sara_core::service::ImportService;
+
+use super::{build_graph, resolve_item_type};
+use crate::output::{print_error, print_success, print_warning};
+
+/// Arguments for the import command.
+#[derive(Args, Debug)]
+pub struct ImportArgs {
+    /// Interchange format of the document
+    #[command(subcommand)]
+    pub format: ImportFormat,
+}
+
+/// Supported import formats.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum ImportFormat {
+    /// Apply an edited CSV sheet of items of one type
+    ///
+    /// Rows naming an existing item update it; other rows create a new item
+    /// in --into, with a generated ID when the id cell is empty. Empty field
+    /// cells leave the current value as is, while an empty relation cell
+    /// removes the links of that relation.
+    Csv {
+        /// CSV sheet to import, as written by `sara export csv`
+        file: PathBuf,
+
+        /// Item type of the rows (schema id or prefix)
+        #[arg(short = 't', long = "type", value_name = "TYPE")]
+        item_type: String,
+
+        /// Directory new item documents are written to
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        into: PathBuf,
+    },
+
+    /// Import a ReqIF document as Markdown items
+    ///
+    /// Object and relation types are matched to the active model by
+    /// identifier or name; unmatched ones are reported and skipped. Use
+    /// --schema-out to write the model the document describes, as a starting
+    /// point for a custom schema.
+    Reqif {
+        /// ReqIF document to import
+        file: PathBuf,
+
+        /// Directory the item documents are written to
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        into: PathBuf,
+
+        /// Write the model described by the document to a schema file
+        #[arg(long, value_name = "FILE")]
+        schema_out: Option<PathBuf>,
+
+        /// Overwrite existing documents
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+/// Runs the import command.
+pub fn run(args: &ImportArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    match &args.format {
+        ImportFormat::Csv {
+            file,
+            item_type,
+            into,
+        } => run_csv(file, item_type, into, config),
+        ImportFormat::Reqif {
+            file,
+            into,
+            schema_out,
+            force,
+        } => {
+            let import = reqif::import(&fs::read_to_string(file)?, into)?;
+            for warning in &import.warnings {
+                print_warning(&config.output, warning);
+            }
+            if let Some(path) = schema_out {
+                fs::write(path, import.schema.to_yaml()?)?;
+                print_success(
+                    &config.output,
+                    &format!("Schema written to {}", path.display()),
+                );
+            }
+
+            let result = ImportService::new().write(&import.items, *force)?;
+            for path in &result.skipped {
+                print_warning(
+                    &config.output,
+                    &format!(
+                        "{} already exists (use --force to overwrite)",
+                        path.display()
+                    ),
+                );
+            }
+            print_success(
+                &config.output,
+                &format!(
+                    "Imported {} item(s) into {}",
+                    result.written.len(),
+                    into.display()
+                ),
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// Applies a CSV sheet, reporting each created, updated and failed row.
+fn run_csv(
+    file: &Path,
+    item_type: &str,
+    into: &Path,
+    config: &Config,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let item_type = resolve_item_type(item_type)?;
+    let records = csv::import(&fs::read_to_string(file)?, item_type)?;
+    let graph = build_graph(config)?;
+    let result = ImportService::new().apply_records(&graph, item_type, &records, into);
+
+    for created in &result.created {
+        print_success(
+            &config.output,
+            &format!("Created {} in {}", created.id, created.file.display()),
+        );
+    }
+    for updated in &result.updated {
+        print_success(
+            &config.output,
+            &format!(
+                "Updated {} ({} field{} changed)",
+                updated.item_id,
+                updated.change_count(),
+                if updated.change_count() == 1 { "" } else { "s" }
+            ),
+        );
+    }
+    for (line, reason) in &result.failed {
+        print_error(&config.output, &format!("Line {line}: {reason}"));
+    }
+    println!(
+        "{} created, {} updated, {} unchanged, {} failed",
+        result.created.len(),
+        result.updated.len(),
+        result.unchanged.len(),
+        result.failed.len()
+    );
+
+    if result.failed.is_empty() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
@@ -13,7 +13,7 @@ use std::process::ExitCode;
 use clap::{Arg, ArgAction, ArgMatches, Args, Command, FromArgMatches, value_parser};
 use sara_core::graph::KnowledgeGraph;
 use sara_core::model::{FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, ItemType};
-use sara_core::schema::FieldType;
+use sara_core::schema::{FieldDef, FieldType};
 use sara_core::service::{InitError, InitOptions, InitResult, InitService, TypeConfig};
 
 use sara_core::config::{Config, OutputConfig};
@@ -33,7 +33,7 @@ pub struct InitArgs {
 /// Parsed `init <type>` subcommand, resolved against the active schema.
 #[derive(Debug)]
 pub struct InitSubcommand {
-    /// Markdown file to initialize.
+    /// Document file to initialize (Markdown, AsciiDoc or reStructuredText).
     file: PathBuf,
     /// Item identifier (auto-generated if not provided).
     id: Option<String>,
@@ -87,7 +87,7 @@ fn type_command(item_type: ItemType) -> Command {
                 .value_name("FILE")
                 .value_parser(value_parser!(PathBuf))
                 .required(true)
-                .help("Markdown file to initialize"),
+                .help("Document file to initialize (.md, .adoc or .rst)"),
         )
         .arg(
             Arg::new(FIELD_ID)
@@ -118,15 +118,10 @@ fn type_command(item_type: ItemType) -> Command {
     }
 
     for field in item_type.declared_fields() {
-        let mut arg = Arg::new(field.name.clone())
-            .long(field.name.replace('_', "-"))
-            .help(field_help(field));
+        let mut arg = field_arg(field);
         if let Some(short) = short_flag(&field.name) {
             arg = arg.short(short);
         }
-        if matches!(field.field_type, FieldType::List(_)) {
-            arg = arg.num_args(1..).action(ArgAction::Append);
-        }
         command = command.arg(arg);
     }
 
@@ -149,13 +144,48 @@ fn type_command(item_type: ItemType) -> Command {
     command
 }
 
-/// Returns the help text of a declared field, listing enum values when known.
-pub(super) fn field_help(field: &sara_core::schema::FieldDef) -> String {
+/// Builds the flag of a declared field.
+///
+/// List fields take several values. Values of numbers, integers, booleans,
+/// URLs and pattern-constrained text are checked while parsing, so a typo is
+/// reported before any file is touched.
+pub(super) fn field_arg(field: &FieldDef) -> Arg {
+    let mut arg = Arg::new(field.name.clone())
+        .long(field.name.replace('_', "-"))
+        .help(field_help(field));
+    if matches!(field.field_type, FieldType::List(_)) {
+        arg = arg.num_args(1..).action(ArgAction::Append);
+    }
+
+    let scalar = match &field.field_type {
+        FieldType::List(inner) => inner.as_ref(),
+        scalar => scalar,
+    };
+    let value_name = match scalar {
+        FieldType::Number { .. } => "NUMBER",
+        FieldType::Integer { .. } => "INTEGER",
+        FieldType::Boolean => "BOOL",
+        FieldType::Url => "URL",
+        FieldType::Text { pattern: Some(_) } => "TEXT",
+        _ => return arg,
+    };
+    let field_type = scalar.clone();
+    arg.value_name(value_name)
+        .value_parser(move |value: &str| field_type.parse_value(value).map(|_| value.to_string()))
+}
+
+/// Returns the help text of a declared field, listing enum values and the
+/// expected form of typed values.
+pub(super) fn field_help(field: &FieldDef) -> String {
     match &field.field_type {
         FieldType::Enum { values } => {
             format!("{} ({})", field.display_name, values.join(", "))
         }
-        _ => field.display_name.clone(),
+        FieldType::Text { pattern: None }
+        | FieldType::ItemRef
+        | FieldType::Date
+        | FieldType::List(_) => field.display_name.clone(),
+        typed => format!("{} ({})", field.display_name, typed.describe()),
     }
 }
 
@@ -276,7 +306,14 @@ pub fn run(args: &InitArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>>
         None => run_interactive(config),
         Some(subcommand) => {
             let graph = load_init_graph(config);
-            run_with_options(subcommand.to_init_options(), graph.as_ref(), config)
+            let mut opts = subcommand.to_init_options();
+            if opts.id.is_none() {
+                let repos = super::resolve_repositories(config)?;
+                let id =
+                    super::suggest_id(opts.item_type(), graph.as_ref(), &repos, &config.output);
+                opts = opts.with_id(id);
+            }
+            run_with_options(opts, graph.as_ref(), config)
         }
     }
 }
@@ -284,7 +321,7 @@ pub fn run(args: &InitArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>>
 fn run_interactive(config: &Config) -> Result<ExitCode, Box<dyn Error>> {
     let mut session = InteractiveSession {
         graph: None,
-        repositories: &config.repositories.paths,
+        repositories: &config.repositories,
         output: &config.output,
     };
 
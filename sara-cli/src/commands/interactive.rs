@@ -9,8 +9,10 @@ use std::path::PathBuf;
 
 use inquire::validator::{StringValidator, Validation};
 use inquire::{Confirm, InquireError, MultiSelect, Select, Text};
+use sara_core::config::RepositoryConfig;
 use sara_core::graph::KnowledgeGraph;
 use sara_core::model::{FieldValue, ItemAttributes, ItemType, RelationshipType, TraceabilityLinks};
+use sara_core::parser::ParseContext;
 use sara_core::schema::{FieldDef, FieldType};
 use sara_core::service::{FieldInput, load_graph};
 use thiserror::Error;
@@ -22,8 +24,8 @@ pub struct InteractiveSession<'a> {
     /// Pre-parsed knowledge graph for traceability lookups.
     pub graph: Option<KnowledgeGraph>,
 
-    /// Repository paths for graph building.
-    pub repositories: &'a [PathBuf],
+    /// Repositories for graph building.
+    pub repositories: &'a RepositoryConfig,
 
     /// Output configuration for colors and emojis.
     pub output: &'a OutputConfig,
@@ -119,6 +121,30 @@ impl StringValidator for NameValidator {
     }
 }
 
+/// Validator for typed field values (numbers, URLs, patterns).
+#[derive(Clone)]
+struct FieldValueValidator {
+    field_type: FieldType,
+    required: bool,
+}
+
+impl StringValidator for FieldValueValidator {
+    fn validate(&self, input: &str) -> Result<Validation, inquire::CustomUserError> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Ok(if self.required {
+                Validation::Invalid("A value is required".into())
+            } else {
+                Validation::Valid
+            });
+        }
+        Ok(match self.field_type.parse_value(trimmed) {
+            Ok(_) => Validation::Valid,
+            Err(reason) => Validation::Invalid(reason.into()),
+        })
+    }
+}
+
 /// Prompts for item type selection (FR-041).
 fn prompt_item_type() -> Result<ItemType, PromptError> {
     let options: Vec<ItemType> = ItemType::all();
@@ -167,15 +193,14 @@ pub fn prompt_description(default: Option<&str>) -> Result<Option<String>, Promp
 
 /// Prompts for identifier with suggested default (FR-044).
 fn prompt_identifier(
-    item_type: ItemType,
+    suggested: &str,
     graph: Option<&KnowledgeGraph>,
 ) -> Result<String, PromptError> {
-    let suggested = item_type.suggest_next_id(graph);
     let existing_ids: HashSet<String> = graph
         .map(|g| g.items().map(|item| item.id.as_str().to_string()).collect())
         .unwrap_or_default();
     let id = Text::new("Identifier:")
-        .with_default(&suggested)
+        .with_default(suggested)
         .with_validator(IdValidator)
         .with_validator(move |input: &str| {
             if existing_ids.contains(input.trim()) {
@@ -313,24 +338,36 @@ pub fn prompt_traceability(
     Ok(input)
 }
 
-/// Prompts for each declared text field with the current value as default
+/// Prompts for each declared scalar field with the current value as default
 /// (FR-046, FR-056).
 ///
-/// Returns the new value per field; `None` clears an optional field. Other
-/// field kinds (enums, lists, dates, item references) pass through the edit
-/// untouched.
+/// Returns the new value per field; `None` clears an optional field. Enum,
+/// list, date and item-reference fields pass through the edit untouched.
 pub fn prompt_field_edits(
     item_type: ItemType,
     attributes: &ItemAttributes,
-) -> Result<Vec<(String, Option<String>)>, PromptError> {
+) -> Result<Vec<(String, Option<FieldValue>)>, PromptError> {
     let mut edits = Vec::new();
 
     for field in item_type.declared_fields() {
-        if !matches!(field.field_type, FieldType::Text) {
-            continue;
-        }
-        let current = attributes.get(&field.name).and_then(FieldValue::as_text);
-        let value = prompt_text_field(field, current.map(String::as_str))?;
+        let current = attributes.get(&field.name);
+        let value = match &field.field_type {
+            FieldType::Text { pattern: None } => {
+                let current = current.and_then(FieldValue::as_text);
+                prompt_text_field(field, current.map(String::as_str))?.map(FieldValue::Text)
+            }
+            FieldType::Boolean => prompt_bool_field(field, current.and_then(FieldValue::as_bool))?
+                .map(FieldValue::Boolean),
+            FieldType::Text { .. }
+            | FieldType::Number { .. }
+            | FieldType::Integer { .. }
+            | FieldType::Url => {
+                let current = current.map(ToString::to_string);
+                prompt_typed_field(field, current.as_deref())?
+                    .and_then(|value| field.field_type.parse_value(&value).ok())
+            }
+            _ => continue,
+        };
         edits.push((field.name.clone(), value));
     }
 
@@ -457,11 +494,11 @@ pub fn run_interactive_session(
 
 /// Ensures the knowledge graph is loaded for traceability suggestions.
 fn ensure_graph_loaded(session: &mut InteractiveSession<'_>) {
-    if session.graph.is_some() || session.repositories.is_empty() {
+    if session.graph.is_some() || session.repositories.paths.is_empty() {
         return;
     }
 
-    match load_graph(session.repositories) {
+    match load_graph(session.repositories, &ParseContext::default()) {
         Ok((graph, warnings)) => {
             for warning in &warnings {
                 print_warning(session.output, &warning.to_string());
@@ -481,11 +518,17 @@ fn collect_item_input(
     item_type: ItemType,
 ) -> Result<InteractiveInput, PromptError> {
     let name = prompt_name(None)?;
-    let id = prompt_identifier(item_type, session.graph.as_ref())?;
+    let suggested = super::suggest_id(
+        item_type,
+        session.graph.as_ref(),
+        session.repositories,
+        session.output,
+    );
+    let id = prompt_identifier(&suggested, session.graph.as_ref())?;
     let description = prompt_description(None)?;
     let traceability = prompt_traceability(item_type, session.graph.as_ref(), None, Some(&id))?;
     let type_specific = collect_type_specific_input(item_type)?;
-    let file = prompt_file(&id, session.repositories)?;
+    let file = prompt_file(&id, &session.repositories.paths)?;
 
     Ok(InteractiveInput {
         file,
@@ -501,10 +544,12 @@ fn collect_item_input(
 /// Collects the declared type-specific fields through prompts.
 ///
 /// Text fields are prompted individually (required ones must be non-empty),
-/// text-list fields are collected one entry at a time and enum fields are
-/// selected from their allowed values. Other field kinds (dates, item
-/// references) are left to their schema defaults, like the peer-reference
-/// lists already covered by the traceability prompts.
+/// text-list fields are collected one entry at a time, enum fields are
+/// selected from their allowed values, booleans are confirmed and numbers,
+/// URLs and pattern-constrained text are checked as they are typed. Other
+/// field kinds (dates, item references) are left to their schema defaults,
+/// like the peer-reference lists already covered by the traceability
+/// prompts.
 fn collect_type_specific_input(
     item_type: ItemType,
 ) -> Result<Vec<(String, FieldInput)>, PromptError> {
@@ -512,12 +557,12 @@ fn collect_type_specific_input(
 
     for field in item_type.declared_fields() {
         match &field.field_type {
-            FieldType::Text => {
+            FieldType::Text { pattern: None } => {
                 if let Some(value) = prompt_text_field(field, None)? {
                     inputs.push((field.name.clone(), FieldInput::Text(value)));
                 }
             }
-            FieldType::List(inner) if matches!(**inner, FieldType::Text) => {
+            FieldType::List(inner) if matches!(**inner, FieldType::Text { .. }) => {
                 let values = prompt_list_field(field)?;
                 if !values.is_empty() {
                     inputs.push((field.name.clone(), FieldInput::List(values)));
@@ -528,6 +573,19 @@ fn collect_type_specific_input(
                     inputs.push((field.name.clone(), FieldInput::Text(value)));
                 }
             }
+            FieldType::Boolean => {
+                if let Some(value) = prompt_bool_field(field, None)? {
+                    inputs.push((field.name.clone(), FieldInput::Text(value.to_string())));
+                }
+            }
+            FieldType::Text { .. }
+            | FieldType::Number { .. }
+            | FieldType::Integer { .. }
+            | FieldType::Url => {
+                if let Some(value) = prompt_typed_field(field, None)? {
+                    inputs.push((field.name.clone(), FieldInput::Text(value)));
+                }
+            }
             _ => {}
         }
     }
@@ -570,6 +628,47 @@ fn prompt_text_field(
     }
 }
 
+/// Prompts for a number, integer, URL or pattern-constrained text field,
+/// rejecting values the field type does not accept.
+///
+/// The expected form of the value serves as the help message.
+fn prompt_typed_field(
+    field: &FieldDef,
+    default: Option<&str>,
+) -> Result<Option<String>, PromptError> {
+    let message = if field.required {
+        format!("{}:", field.display_name)
+    } else {
+        format!("{} (optional):", field.display_name)
+    };
+    let help = field.field_type.describe();
+    let mut prompt = Text::new(&message)
+        .with_validator(FieldValueValidator {
+            field_type: field.field_type.clone(),
+            required: field.required,
+        })
+        .with_help_message(&help);
+    if let Some(def) = default {
+        prompt = prompt.with_default(def);
+    }
+    let value = prompt.prompt()?;
+    let trimmed = value.trim();
+    Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
+}
+
+/// Prompts for a boolean field as a yes/no question.
+///
+/// Optional fields can be skipped with Esc, leaving the field unset.
+fn prompt_bool_field(field: &FieldDef, default: Option<bool>) -> Result<Option<bool>, PromptError> {
+    let message = format!("{}?", field.display_name);
+    let prompt = Confirm::new(&message).with_default(default.unwrap_or(false));
+    if field.required {
+        Ok(Some(prompt.prompt()?))
+    } else {
+        Ok(prompt.with_help_message("Esc to skip").prompt_skippable()?)
+    }
+}
+
 /// Prompts for an enum field by selecting one of its allowed values.
 ///
 /// Required fields must pick a value; optional fields can be skipped with
@@ -705,6 +804,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_field_value_validator() {
+        let validator = FieldValueValidator {
+            field_type: FieldType::Integer {
+                min: Some(1),
+                max: Some(5),
+            },
+            required: false,
+        };
+        assert!(matches!(validator.validate("3"), Ok(Validation::Valid)));
+        assert!(matches!(validator.validate(""), Ok(Validation::Valid)));
+        assert!(matches!(
+            validator.validate("9"),
+            Ok(Validation::Invalid(_))
+        ));
+        assert!(matches!(
+            validator.validate("three"),
+            Ok(Validation::Invalid(_))
+        ));
+    }
+
     #[test]
     fn test_required_parent_type() {
         assert_eq!(builtin::SOLUTION.required_parent_type(), None);
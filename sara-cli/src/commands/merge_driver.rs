@@ -0,0 +1,64 @@
+//! Merge driver command implementation.
+
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Args;
+use sara_core::config::Config;
+use sara_core::service::merge_document;
+
+use crate::output::print_warning;
+
+/// Arguments for the merge-driver command.
+#[derive(Args, Debug)]
+pub struct MergeDriverArgs {
+    /// Common ancestor of both versions (%O)
+    #[arg(value_name = "BASE")]
+    pub base: PathBuf,
+
+    /// Current version, replaced with the merge result (%A)
+    #[arg(value_name = "CURRENT")]
+    pub current: PathBuf,
+
+    /// Version being merged in (%B)
+    #[arg(value_name = "OTHER")]
+    pub other: PathBuf,
+
+    /// Path of the document in the repository, telling its format (%P);
+    /// Markdown when omitted
+    #[arg(value_name = "PATH")]
+    pub path: Option<PathBuf>,
+}
+
+/// Runs the merge-driver command.
+///
+/// Exits with a failure status when conflicts remain, so that Git reports
+/// the document as conflicted.
+pub fn run(args: &MergeDriverArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let base = fs::read_to_string(&args.base)?;
+    let current = fs::read_to_string(&args.current)?;
+    let other = fs::read_to_string(&args.other)?;
+    // Git hands the versions over as temporary files without extension.
+    let path = args
+        .path
+        .clone()
+        .unwrap_or_else(|| args.current.with_extension("md"));
+
+    let result = merge_document(&base, &current, &other, &path);
+    fs::write(&args.current, &result.content)?;
+
+    if result.is_clean() {
+        return Ok(ExitCode::SUCCESS);
+    }
+    print_warning(
+        &config.output,
+        &format!(
+            "Conflicting changes to {}: {}",
+            path.display(),
+            result.conflicts.join(", ")
+        ),
+    );
+    Ok(ExitCode::FAILURE)
+}
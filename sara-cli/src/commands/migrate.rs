@@ -0,0 +1,98 @@
+//! Migrate command implementation.
+
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Args;
+use sara_core::config::Config;
+use sara_core::schema::Schema;
+use sara_core::service::{MigrateOptions, MigrateService, MigrationMap};
+
+use super::resolve_repositories;
+use crate::output::{print_success, print_warning};
+
+/// Arguments for the migrate command.
+#[derive(Args, Debug)]
+pub struct MigrateArgs {
+    /// Schema the documents currently follow
+    #[arg(long, value_name = "FILE")]
+    pub from: PathBuf,
+
+    /// Schema to migrate the documents to
+    #[arg(long, value_name = "FILE")]
+    pub to: PathBuf,
+
+    /// Renames of item types, relations, fields and enum values (YAML)
+    #[arg(long, value_name = "FILE")]
+    pub map: Option<PathBuf>,
+
+    /// Apply the proposed renames along with those of --map
+    #[arg(long)]
+    pub accept_proposals: bool,
+
+    /// Report the changes without writing any document
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Runs the migrate command.
+pub fn run(args: &MigrateArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let repos = resolve_repositories(config)?;
+    let from = Schema::from_path(&args.from)?;
+    let to = Schema::from_path(&args.to)?;
+    let map = match &args.map {
+        Some(path) => MigrationMap::from_path(path)?,
+        None => MigrationMap::default(),
+    };
+
+    let opts = MigrateOptions::new(from, to)
+        .with_map(map)
+        .with_accept_proposals(args.accept_proposals)
+        .with_dry_run(args.dry_run);
+    let result = MigrateService::new().migrate(&repos.paths, &opts)?;
+
+    if !result.changes.is_empty() {
+        println!("Schema changes:");
+        for change in &result.changes {
+            println!("   {change}");
+        }
+    }
+    if !result.proposed.is_empty() {
+        if args.accept_proposals {
+            println!("Proposed renames (applied; copy them into --map to adjust):");
+        } else {
+            println!(
+                "Proposed renames (not applied; pass --accept-proposals or copy them into --map):"
+            );
+        }
+        for line in result.proposed.to_yaml()?.lines() {
+            println!("   {line}");
+        }
+    }
+
+    let verb = if args.dry_run {
+        "Would migrate"
+    } else {
+        "Migrated"
+    };
+    print_success(
+        &config.output,
+        &format!("{verb} {} document(s)", result.migrated.len()),
+    );
+    for path in &result.migrated {
+        println!("   {}", path.display());
+    }
+
+    if result.unmigrated.is_empty() {
+        return Ok(ExitCode::SUCCESS);
+    }
+    print_warning(
+        &config.output,
+        &format!("{} item(s) need manual migration", result.unmigrated.len()),
+    );
+    for item in &result.unmigrated {
+        println!("   {item}");
+    }
+    Ok(ExitCode::FAILURE)
+}
@@ -3,77 +3,160 @@
 mod check;
 mod diff;
 mod edit;
+mod export;
+mod hook;
+mod import;
 mod init;
 mod interactive;
+mod merge_driver;
+mod migrate;
 mod query;
+mod rename;
 mod report;
 mod schema;
+mod serve;
 
 use std::env;
 use std::error::Error;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use clap::Subcommand;
-use sara_core::config::Config;
+use sara_core::config::{Config, OutputConfig, RepositoryConfig};
+use sara_core::error::SaraError;
 use sara_core::graph::KnowledgeGraph;
-use sara_core::model::Item;
-use sara_core::repository::{GitReader, GitRef, ScanWarning, parse_repositories};
-use sara_core::service::load_graph;
+use sara_core::model::{Item, ItemType};
+use sara_core::parser::{MentionPattern, ParseContext};
+use sara_core::repository::{
+    GitReader, GitRef, ScanWarning, apply_namespaces, parse_repositories, qualify_items,
+    resolve_references,
+};
+use sara_core::service::{load_graph, parse_branches, parse_item_type};
 
 use self::check::CheckArgs;
 use self::diff::DiffArgs;
 use self::edit::EditArgs;
+use self::export::ExportArgs;
+use self::hook::HookArgs;
+use self::import::ImportArgs;
 use self::init::InitArgs;
+use self::merge_driver::MergeDriverArgs;
+use self::migrate::MigrateArgs;
 use self::query::QueryArgs;
+use self::rename::RenameArgs;
 use self::report::ReportArgs;
 use self::schema::SchemaArgs;
+use self::serve::ServeArgs;
 use crate::Cli;
 use crate::output::print_warning;
 
 /// Exit code reported when the user cancels an interactive prompt (128 + SIGINT).
 const EXIT_CANCELLED: u8 = 130;
 
-/// Returns the resolved repository paths, falling back to the current directory.
-fn resolve_repositories(config: &Config) -> Result<Vec<PathBuf>, io::Error> {
-    if config.repositories.paths.is_empty() {
-        Ok(vec![env::current_dir()?])
-    } else {
-        Ok(config.repositories.paths.clone())
+/// Returns the repository configuration, falling back to the current
+/// directory when no path is configured.
+fn resolve_repositories(config: &Config) -> Result<RepositoryConfig, io::Error> {
+    let mut repositories = config.repositories.clone();
+    if repositories.paths.is_empty() {
+        repositories.paths.push(env::current_dir()?);
     }
+    Ok(repositories)
+}
+
+/// Returns the context documents are parsed in: the active schema and the
+/// configured mention pattern.
+fn parse_context(config: &Config) -> ParseContext {
+    ParseContext::default().with_mention_pattern(mention_pattern(config))
+}
+
+/// Returns the configured mention pattern.
+fn mention_pattern(config: &Config) -> MentionPattern {
+    // `load_config` already rejected invalid patterns.
+    config.mentions.compile().unwrap_or_default()
 }
 
 /// Parses items from the configured repositories, returning the warnings
 /// for skipped paths alongside the items.
 fn parse_items(config: &Config) -> Result<(Vec<Item>, Vec<ScanWarning>), Box<dyn Error>> {
     let repos = resolve_repositories(config)?;
-    let scan = parse_repositories(&repos);
+    let mut scan = parse_repositories(&repos.paths, &parse_context(config));
+    apply_namespaces(&mut scan.items, &repos);
     Ok((scan.items, scan.warnings))
 }
 
 /// Builds the knowledge graph from the items of the configured repositories,
 /// printing a warning for every path skipped during the scan.
 fn build_graph(config: &Config) -> Result<KnowledgeGraph, Box<dyn Error>> {
+    build_graph_in(config, &parse_context(config))
+}
+
+/// Builds the knowledge graph as [`build_graph`] does, parsing documents in
+/// `ctx`.
+fn build_graph_in(config: &Config, ctx: &ParseContext) -> Result<KnowledgeGraph, Box<dyn Error>> {
     let repos = resolve_repositories(config)?;
-    let (graph, warnings) = load_graph(&repos)?;
+    let (graph, warnings) = load_graph(&repos, ctx)?;
     for warning in &warnings {
         print_warning(&config.output, &warning.to_string());
     }
     Ok(graph)
 }
 
+/// Resolves an item type given on the command line, accepting the forms
+/// [`parse_item_type`] does.
+fn resolve_item_type(name: &str) -> Result<ItemType, Box<dyn Error>> {
+    parse_item_type(name).ok_or_else(|| {
+        let known: Vec<&str> = ItemType::all().iter().map(ItemType::as_str).collect();
+        format!(
+            "Unknown item type '{name}', expected one of: {}",
+            known.join(", ")
+        )
+        .into()
+    })
+}
+
 /// Parses items from the configured repositories at a specific Git reference.
 ///
 /// Each path is resolved to its enclosing Git repository and the scan is
 /// scoped to that path, so files outside the configured repositories are
 /// never parsed.
 fn parse_items_at(config: &Config, git_ref: &str) -> Result<Vec<Item>, Box<dyn Error>> {
-    let repos = resolve_repositories(config)?;
     let git_ref = GitRef::parse(git_ref);
+    let ctx = parse_context(config);
+    parse_git_items(config, |reader, scope| {
+        reader.parse_commit(&git_ref, scope, &ctx)
+    })
+}
+
+/// Parses items from the configured repositories with `parse`, given the
+/// enclosing Git repository of each path and the path as a scope of it.
+fn parse_git_items(
+    config: &Config,
+    mut parse: impl FnMut(&GitReader, &Path) -> Result<Vec<Item>, SaraError>,
+) -> Result<Vec<Item>, Box<dyn Error>> {
+    let repos = resolve_repositories(config)?;
     let mut all_items = Vec::new();
 
-    for repo_path in &repos {
+    for_each_git_repository(&repos, |repo_path, reader, scope| {
+        let mut items = parse(reader, scope)?;
+        if let Some(namespace) = repos.namespace_of(repo_path) {
+            qualify_items(&mut items, namespace);
+        }
+        all_items.extend(items);
+        Ok(())
+    })?;
+
+    resolve_references(&mut all_items);
+    Ok(all_items)
+}
+
+/// Calls `f` with each existing repository path, its enclosing Git
+/// repository and the path as a scope of that repository.
+fn for_each_git_repository(
+    repos: &RepositoryConfig,
+    mut f: impl FnMut(&Path, &GitReader, &Path) -> Result<(), Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    for repo_path in &repos.paths {
         if !repo_path.exists() {
             tracing::warn!("Repository path does not exist: {}", repo_path.display());
             continue;
@@ -81,11 +164,52 @@ fn parse_items_at(config: &Config, git_ref: &str) -> Result<Vec<Item>, Box<dyn E
 
         let reader = GitReader::discover(repo_path)?;
         let scope = reader.scope_from_path(repo_path)?;
-        let items = reader.parse_commit(&git_ref, &scope)?;
-        all_items.extend(items);
+        f(repo_path, &reader, &scope)?;
     }
+    Ok(())
+}
 
-    Ok(all_items)
+/// Suggests the next identifier of `item_type`, free in the graph and on
+/// every local and remote-tracking branch, so that two branches do not both
+/// create the same identifier.
+///
+/// Paths outside any Git repository have no branches to scan. A failed scan
+/// is not fatal: it is reported and the suggestion considers the graph only.
+fn suggest_id(
+    item_type: ItemType,
+    graph: Option<&KnowledgeGraph>,
+    repos: &RepositoryConfig,
+    output: &OutputConfig,
+) -> String {
+    let branch_items = match parse_branch_items(repos) {
+        Ok(items) => items,
+        Err(error) => {
+            tracing::warn!("Failed to scan branches: {error}");
+            print_warning(
+                output,
+                "Failed to scan branches: the suggested identifier may be taken on another branch",
+            );
+            Vec::new()
+        }
+    };
+    let graph_items = graph.into_iter().flat_map(KnowledgeGraph::items);
+    item_type.suggest_next_id_among(graph_items.chain(&branch_items))
+}
+
+/// Parses the items defined on the branches of the Git repositories
+/// enclosing the configured paths, each scoped to its path.
+fn parse_branch_items(repos: &RepositoryConfig) -> Result<Vec<Item>, SaraError> {
+    let mut items = Vec::new();
+    for repo_path in &repos.paths {
+        let Ok(reader) = GitReader::discover(repo_path) else {
+            continue;
+        };
+        let scope = reader.scope_from_path(repo_path)?;
+        for branch in parse_branches(&reader, &scope, &ParseContext::default())? {
+            items.extend(branch.items);
+        }
+    }
+    Ok(items)
 }
 
 /// Available CLI commands.
@@ -108,6 +232,28 @@ pub enum Commands {
     ///   sara edit SREQ-001 --name "New Name"  # Non-interactive mode
     Edit(EditArgs),
 
+    /// Export the knowledge graph to an interchange format
+    ///
+    /// Examples:
+    ///   sara export reqif -o requirements.reqif
+    Export(ExportArgs),
+
+    /// Manage the Git hooks running SARA
+    ///
+    /// The pre-commit hook runs `sara check --staged`, which rejects a
+    /// commit introducing validation issues.
+    ///
+    /// Examples:
+    ///   sara hook install
+    ///   sara hook install --force    # Replace an existing pre-commit hook
+    Hook(HookArgs),
+
+    /// Import items from an interchange format
+    ///
+    /// Examples:
+    ///   sara import reqif customer.reqif --into docs/customer
+    Import(ImportArgs),
+
     /// Initialize metadata in a Markdown file
     ///
     /// When no subcommand is provided, enters interactive mode which guides you through
@@ -123,19 +269,77 @@ pub enum Commands {
     ///   sara init sysreq doc.md --specification "" # Create system requirement
     Init(InitArgs),
 
+    /// Merge two versions of a document, as a Git merge driver
+    ///
+    /// Merges the metadata field by field, and relation lists link by link,
+    /// so that changes to different fields or links never conflict. Bodies
+    /// merge line by line. Conflict markers are left only where both sides
+    /// changed the same thing differently.
+    ///
+    /// Register it for Markdown documents:
+    ///   git config merge.sara.driver "sara merge-driver %O %A %B %P"
+    ///   echo "*.md merge=sara" >> .gitattributes
+    MergeDriver(MergeDriverArgs),
+
+    /// Migrate documents from one model schema to another
+    ///
+    /// Compares both schemas, proposes renames of item types, relations,
+    /// fields and enum values the mapping file leaves out, and rewrites the
+    /// metadata of every document. Items that cannot be migrated are
+    /// reported and left as written.
+    ///
+    /// Examples:
+    ///   sara migrate --from old.yaml --to new.yaml --dry-run
+    ///   sara migrate --from old.yaml --to new.yaml --map mapping.yaml
+    Migrate(MigrateArgs),
+
     /// Query items and traceability chains
     Query(QueryArgs),
 
+    /// Change the identifier of an item
+    ///
+    /// Rewrites the item's id, the frontmatter references of other items
+    /// and their body mentions. A document named after the old identifier
+    /// is renamed too.
+    ///
+    /// Examples:
+    ///   sara rename SYSREQ-001 SYSREQ-100
+    Rename(RenameArgs),
+
     /// Generate coverage and traceability reports
     Report(ReportArgs),
 
-    /// Export the active model schema as YAML
+    /// Export the active model schema, as YAML, JSON Schema or a model guide
     ///
-    /// Prints the schema in use (the configured model_schema file, or the
-    /// built-in default model when none is configured). Use it as the
-    /// starting point for a custom model:
+    /// Prints the schema in use (the configured model_schema file, fully
+    /// resolved when it extends another schema, or the built-in default
+    /// model when none is configured). Use it as the starting point for a
+    /// custom model:
     ///   sara schema -o model.yaml
+    ///
+    /// The JSON Schema of item metadata lets editors complete and check
+    /// frontmatter:
+    ///   sara schema --format json-schema -o sara.schema.json
+    ///
+    /// The model guide documents the item types, relations and validity
+    /// matrix, with an entity-relationship diagram:
+    ///   sara schema --format html -o model.html
+    ///
+    /// `sara schema check` reports every error and warning of a schema
+    /// file, with its line:
+    ///   sara schema check model.yaml
     Schema(SchemaArgs),
+
+    /// Serve the knowledge graph as a local JSON REST API
+    ///
+    /// Loads the graph once and reloads it when a document changes.
+    /// Endpoints: /api/items/{id}, /api/items/{id}/upstream,
+    /// /api/items/{id}/downstream, /api/validation, /api/reports/coverage,
+    /// /api/reports/matrix and /api/diff?from=REF&to=REF.
+    ///
+    /// Examples:
+    ///   sara serve --port 8080
+    Serve(ServeArgs),
 }
 
 /// Returns repositories: CLI args take precedence, then config file, then current directory.
@@ -161,6 +365,15 @@ fn build_config(cli: &Cli, file_config: Option<&Config>) -> Result<Config, io::E
     Ok(config)
 }
 
+impl Commands {
+    /// Tells whether the command runs without the configured schema:
+    /// checking a schema file or exporting the built-in model, which stay
+    /// available when the configured schema fails to load.
+    pub fn runs_without_schema(&self) -> bool {
+        matches!(self, Self::Schema(args) if args.command.is_some() || args.builtin)
+    }
+}
+
 /// Runs the appropriate command.
 pub fn run(cli: &Cli, file_config: Option<&Config>) -> Result<ExitCode, Box<dyn Error>> {
     let config = build_config(cli, file_config)?;
@@ -169,9 +382,16 @@ pub fn run(cli: &Cli, file_config: Option<&Config>) -> Result<ExitCode, Box<dyn
         Commands::Check(args) => check::run(args, &config),
         Commands::Diff(args) => diff::run(args, &config),
         Commands::Edit(args) => edit::run(args, &config),
+        Commands::Export(args) => export::run(args, &config),
+        Commands::Hook(args) => hook::run(args, &config),
+        Commands::Import(args) => import::run(args, &config),
         Commands::Init(args) => init::run(args, &config),
+        Commands::MergeDriver(args) => merge_driver::run(args, &config),
+        Commands::Migrate(args) => migrate::run(args, &config),
         Commands::Query(args) => query::run(args, &config),
+        Commands::Rename(args) => rename::run(args, &config),
         Commands::Report(args) => report::run(args, &config),
         Commands::Schema(args) => schema::run(args, &config),
+        Commands::Serve(args) => serve::run(args, cli, &config),
     }
 }
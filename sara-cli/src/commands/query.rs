@@ -46,6 +46,10 @@ pub struct QueryArgs {
     #[arg(short, long, help_heading = "Traversal")]
     pub upstream: bool,
 
+    /// Show the items whose document body mentions this item
+    #[arg(short = 'm', long, help_heading = "Traversal")]
+    pub mentioned_by: bool,
+
     /// Output format
     #[arg(long, default_value = "tree", help_heading = "Output")]
     pub format: QueryFormat,
@@ -70,6 +74,11 @@ fn handle_found_item(
     item: &Item,
     graph: &KnowledgeGraph,
 ) -> Result<ExitCode, Box<dyn Error>> {
+    if args.mentioned_by {
+        print_mentioned_by(args, config, item, graph);
+        return Ok(ExitCode::SUCCESS);
+    }
+
     print_item_info(config, item, graph);
 
     if args.upstream || args.downstream {
@@ -163,18 +172,40 @@ fn print_item_info(config: &OutputConfig, item: &Item, _graph: &KnowledgeGraph)
    Type: {item_type}
    File: {file}{desc}",
         name = item.name,
-        file = item.source.file_path.display(),
+        file = item.source,
     );
+
+    for section in item.item_type.declared_sections() {
+        let Some(text) = item
+            .attributes
+            .get(&section.field)
+            .and_then(|v| v.as_text())
+        else {
+            continue;
+        };
+        let heading = section.heading.trim_start_matches('#').trim();
+        let label = colorize(config, &format!("{heading}:"), Color::None, Style::Bold);
+        println!("\n   {label}");
+        for line in text.lines() {
+            println!("     {line}");
+        }
+    }
 }
 
 fn print_direct_relationships(config: &OutputConfig, item: &Item, graph: &KnowledgeGraph) {
-    for (rel_type, related) in graph.direct_relationships(&item.id) {
-        let label = colorize(
-            config,
-            &format!("{}:", rel_type.display_name()),
-            Color::None,
-            Style::Bold,
-        );
+    let groups = graph
+        .direct_relationships(&item.id)
+        .into_iter()
+        .map(|(rel_type, related)| (rel_type.display_name(), related))
+        .chain([
+            ("Mentions", graph.mentions(&item.id)),
+            ("Mentioned in", graph.mentioned_by(&item.id)),
+        ]);
+    for (label, related) in groups {
+        if related.is_empty() {
+            continue;
+        }
+        let label = colorize(config, &format!("{label}:"), Color::None, Style::Bold);
         println!("\n   {label}");
         for (i, related_item) in related.iter().enumerate() {
             let branch = format_tree_branch(i == related.len() - 1);
@@ -184,6 +215,59 @@ fn print_direct_relationships(config: &OutputConfig, item: &Item, graph: &Knowle
     }
 }
 
+/// Prints the items whose body mentions an item.
+fn print_mentioned_by(
+    args: &QueryArgs,
+    config: &OutputConfig,
+    item: &Item,
+    graph: &KnowledgeGraph,
+) {
+    let types = parse_item_types(&args.item_types);
+    let mentioning: Vec<&Item> = graph
+        .mentioned_by(&item.id)
+        .into_iter()
+        .filter(|other| types.is_empty() || types.contains(&other.item_type))
+        .collect();
+
+    if let QueryFormat::Json = args.format {
+        let items: Vec<_> = mentioning
+            .iter()
+            .map(|other| {
+                serde_json::json!({
+                    "id": other.id.as_str(),
+                    "name": other.name,
+                    "item_type": other.item_type.display_name(),
+                    "file": other.source.file_path.display().to_string(),
+                })
+            })
+            .collect();
+        let json_output = serde_json::json!({
+            "origin": item.id.as_str(),
+            "mentioned_by": items
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json_output).unwrap_or_else(|_| "{}".to_string())
+        );
+        return;
+    }
+
+    print_header(config, &format!("Mentions of {}", item.id));
+    if mentioning.is_empty() {
+        println!("   No document mentions {}", item.id);
+        return;
+    }
+    for (i, other) in mentioning.iter().enumerate() {
+        let branch = format_tree_branch(i == mentioning.len() - 1);
+        let id = colorize(config, other.id.as_str(), Color::Cyan, Style::None);
+        println!(
+            "{branch} {id}: {name} ({file})",
+            name = other.name,
+            file = other.source
+        );
+    }
+}
+
 fn print_traversal(
     config: &OutputConfig,
     result: &TraversalResult,
@@ -279,6 +363,18 @@ fn print_tree_node(
 }
 
 fn print_traversal_json(result: &TraversalResult, graph: &KnowledgeGraph) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&traversal_json(result, graph))
+            .unwrap_or_else(|_| "{}".to_string())
+    );
+}
+
+/// Returns the JSON form of a traversal, as printed by `--format json`.
+pub(super) fn traversal_json(
+    result: &TraversalResult,
+    graph: &KnowledgeGraph,
+) -> serde_json::Value {
     #[derive(serde::Serialize)]
     struct JsonNode {
         id: String,
@@ -302,16 +398,11 @@ fn print_traversal_json(result: &TraversalResult, graph: &KnowledgeGraph) {
         })
         .collect();
 
-    let json_output = serde_json::json!({
+    serde_json::json!({
         "origin": result.origin.as_str(),
         "max_depth": result.max_depth,
         "items": nodes
-    });
-
-    println!(
-        "{}",
-        serde_json::to_string_pretty(&json_output).unwrap_or_else(|_| "{}".to_string())
-    );
+    })
 }
 
 /// Parses item type strings into item types known to the active schema.
@@ -0,0 +1,52 @@
+//! Rename command implementation.
+
+use std::error::Error;
+use std::process::ExitCode;
+
+use clap::Args;
+use sara_core::config::Config;
+use sara_core::service::{RenameOptions, RenameService};
+
+use super::build_graph;
+use crate::output::print_success;
+
+/// Arguments for the rename command.
+#[derive(Args, Debug)]
+pub struct RenameArgs {
+    /// The item identifier to rename
+    pub item_id: String,
+
+    /// The new identifier (the item keeps its namespace)
+    pub new_id: String,
+
+    /// Leave body mentions of the item (e.g. [[ID]]) unchanged
+    #[arg(long)]
+    pub keep_mentions: bool,
+}
+
+/// Runs the rename command.
+pub fn run(args: &RenameArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let graph = build_graph(config)?;
+    let opts = RenameOptions::new(&args.item_id, &args.new_id)
+        .with_mentions(!args.keep_mentions)
+        .with_mention_pattern(super::mention_pattern(config));
+    let result = RenameService::new().rename(&graph, &opts)?;
+
+    print_success(
+        &config.output,
+        &format!(
+            "Renamed {} to {} ({})",
+            result.old_id,
+            result.new_id,
+            result.file_path.display()
+        ),
+    );
+    for path in &result.references {
+        println!("   Updated references in {}", path.display());
+    }
+    for path in &result.mentions {
+        println!("   Updated mentions in {}", path.display());
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
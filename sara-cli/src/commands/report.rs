@@ -260,10 +260,16 @@ fn format_matrix_text(matrix: &TraceabilityMatrix, _config: &OutputConfig) -> St
                 .targets
                 .iter()
                 .map(|t| {
-                    format!(
+                    let line = format!(
                         "  └─ {} {} ({}) [{}]",
                         t.relationship, t.id, t.target_type, t.name
-                    )
+                    );
+                    let attributes = t.attributes_summary();
+                    if attributes.is_empty() {
+                        line
+                    } else {
+                        format!("{line} {{{attributes}}}")
+                    }
                 })
                 .collect::<Vec<_>>()
                 .join("\n");
@@ -293,7 +299,7 @@ fn format_matrix_html(matrix: &TraceabilityMatrix) -> String {
         .flat_map(|row| {
             if row.targets.is_empty() {
                 vec![format!(
-                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>-</td><td>-</td><td>-</td></tr>",
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>-</td><td>-</td><td>-</td><td>-</td></tr>",
                     row.source_id, row.source_name, row.source_type
                 )]
             } else {
@@ -301,8 +307,8 @@ fn format_matrix_html(matrix: &TraceabilityMatrix) -> String {
                     .iter()
                     .map(|t| {
                         format!(
-                            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
-                            row.source_id, row.source_name, row.source_type, t.id, t.name, t.relationship
+                            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                            row.source_id, row.source_name, row.source_type, t.id, t.name, t.relationship, t.attributes_summary()
                         )
                     })
                     .collect()
@@ -328,7 +334,7 @@ th {{ background-color: #f4f4f4; }}
 <p><strong>Total Relationships:</strong> {}</p>
 <table>
 <thead>
-<tr><th>Source ID</th><th>Source Name</th><th>Source Type</th><th>Target ID</th><th>Target Name</th><th>Relationship</th></tr>
+<tr><th>Source ID</th><th>Source Name</th><th>Source Type</th><th>Target ID</th><th>Target Name</th><th>Relationship</th><th>Attributes</th></tr>
 </thead>
 <tbody>
 {rows}
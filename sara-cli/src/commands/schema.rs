@@ -2,37 +2,191 @@
 
 use std::error::Error;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
-use clap::Args;
+use clap::{Args, Subcommand};
 use sara_core::config::Config;
-use sara_core::schema::{self, Schema};
+use sara_core::schema::{self, Schema, SchemaReport};
+use sara_core::validation::Severity;
+use serde::Serialize;
+
+use super::check::CheckFormat;
+use crate::output::{format_error, format_warning, print_error, print_success};
+
+/// Schema output format.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum SchemaFormat {
+    /// Model schema, loadable as `model_schema`
+    #[default]
+    Yaml,
+    /// JSON Schema of item metadata, for editor validation
+    JsonSchema,
+    /// Model guide: diagram, item types, relations and validity matrix
+    Markdown,
+    /// Entity-relationship diagram of the item types
+    Mermaid,
+    /// Model guide as a standalone HTML page
+    Html,
+}
 
 /// Arguments for the schema command.
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct SchemaArgs {
+    /// Schema subcommand
+    #[command(subcommand)]
+    pub command: Option<SchemaCommand>,
+
     /// Export the built-in default model instead of the active one
     #[arg(long)]
     pub builtin: bool,
 
+    /// Output format
+    #[arg(long, default_value = "yaml")]
+    pub format: SchemaFormat,
+
+    /// Only describe the metadata of this item type (JSON Schema)
+    #[arg(short = 't', long = "type", value_name = "TYPE")]
+    pub item_type: Option<String>,
+
     /// Write the schema to a file instead of standard output
     #[arg(short, long, value_name = "FILE")]
     pub output: Option<PathBuf>,
 }
 
+/// Schema subcommands.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum SchemaCommand {
+    /// Check a schema file, reporting every error and warning
+    Check(SchemaCheckArgs),
+}
+
+/// Arguments for the schema check subcommand.
+#[derive(Args, Debug)]
+pub struct SchemaCheckArgs {
+    /// Schema file to check (default: the configured model_schema)
+    #[arg(value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, default_value = "text", help_heading = "Output")]
+    pub format: CheckFormat,
+
+    /// Treat warnings as errors
+    #[arg(long, help_heading = "Validation")]
+    pub strict: bool,
+}
+
+/// JSON result of the schema check subcommand.
+#[derive(Debug, Serialize)]
+struct SchemaCheckResult<'a> {
+    /// Whether the check passed.
+    valid: bool,
+    /// The checked file.
+    file: &'a Path,
+    /// Issues found.
+    #[serde(flatten)]
+    report: &'a SchemaReport,
+}
+
 /// Runs the schema command.
-pub fn run(args: &SchemaArgs, _config: &Config) -> Result<ExitCode, Box<dyn Error>> {
-    let yaml = if args.builtin {
-        Schema::builtin().to_yaml()?
+pub fn run(args: &SchemaArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    if let Some(SchemaCommand::Check(check)) = &args.command {
+        return run_check(check, config);
+    }
+
+    let builtin;
+    let model = if args.builtin {
+        builtin = Schema::builtin();
+        &builtin
     } else {
-        schema::active().to_yaml()?
+        schema::active()
+    };
+
+    if args.item_type.is_some() && !matches!(args.format, SchemaFormat::JsonSchema) {
+        return Err("--type requires --format json-schema".into());
+    }
+
+    let output = match args.format {
+        SchemaFormat::Yaml => model.to_yaml()?,
+        SchemaFormat::JsonSchema => {
+            let json_schema = match &args.item_type {
+                Some(name) => {
+                    let def = model
+                        .item_type_by_name(name)
+                        .ok_or_else(|| format!("unknown item type '{name}'"))?;
+                    model
+                        .item_type_json_schema(&def.id)
+                        .ok_or_else(|| format!("unknown item type '{name}'"))?
+                }
+                None => model.to_json_schema(),
+            };
+            format!("{}\n", serde_json::to_string_pretty(&json_schema)?)
+        }
+        SchemaFormat::Markdown => model.to_markdown_guide(),
+        SchemaFormat::Mermaid => model.to_mermaid(),
+        SchemaFormat::Html => model.to_html_guide(),
     };
 
     match &args.output {
-        Some(path) => fs::write(path, yaml)?,
-        None => print!("{yaml}"),
+        Some(path) => fs::write(path, output)?,
+        None => print!("{output}"),
     }
 
     Ok(ExitCode::SUCCESS)
 }
+
+/// Runs the schema check subcommand.
+fn run_check(args: &SchemaCheckArgs, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let file = match (&args.file, &config.model_schema) {
+        (Some(file), _) => file.clone(),
+        (None, Some(path)) => PathBuf::from(path),
+        (None, None) => return Err("no schema file given and no model_schema configured".into()),
+    };
+    let report = Schema::check_path(&file)?;
+    let strict = args.strict || config.validation.strict_mode;
+    let valid = report.passes(strict);
+
+    match args.format {
+        CheckFormat::Text => {
+            let output = &config.output;
+            for issue in &report.issues {
+                let location = match issue.line {
+                    Some(line) => format!("{}:{line}", file.display()),
+                    None => file.display().to_string(),
+                };
+                let message = format!("{location}: {}", issue.message);
+                match issue.severity {
+                    Severity::Error => println!("{}", format_error(output, &message)),
+                    Severity::Warning => println!("{}", format_warning(output, &message)),
+                }
+            }
+            let summary = format!(
+                "{} error(s), {} warning(s)",
+                report.error_count(),
+                report.warning_count()
+            );
+            if valid {
+                print_success(output, &format!("Schema check passed: {summary}"));
+            } else {
+                print_error(output, &format!("Schema check failed: {summary}"));
+            }
+        }
+        CheckFormat::Json => {
+            let result = SchemaCheckResult {
+                valid,
+                file: &file,
+                report: &report,
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+    }
+
+    Ok(if valid {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
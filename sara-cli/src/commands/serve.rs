@@ -0,0 +1,435 @@
+//! Serve command implementation: a local JSON REST API over the graph.
+//!
+//! The graph is loaded once and reloaded on the next request after a
+//! document, the configuration file or the model schema changes, so
+//! dashboards and bots can query it without running the CLI and parsing its
+//! text output.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use clap::Args;
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use sara_core::config::{Config, ValidationConfig, load_config};
+use sara_core::error::SaraError;
+use sara_core::graph::{KnowledgeGraph, TraversalOptions, traverse_downstream, traverse_upstream};
+use sara_core::parser::ParseContext;
+use sara_core::report::{CoverageReport, TraceabilityMatrix};
+use sara_core::repository::scan_directory;
+use sara_core::schema::{self, Schema};
+use sara_core::service::DiffService;
+use sara_core::validation::Validator;
+
+use super::diff::diff_options;
+use super::query::{parse_item_types, traversal_json};
+use crate::Cli;
+use crate::output::{print_error, print_success, print_warning};
+
+/// Arguments for the serve command.
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    /// Port to listen on (0 picks a free port)
+    #[arg(short, long, default_value_t = 8080)]
+    pub port: u16,
+
+    /// Address to bind
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+}
+
+/// Shortest time between two checks for changed files, so that a burst of
+/// requests does not rescan the repositories for each of them.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Modification time and size of every file the graph is built from, to
+/// detect changes.
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+/// The graph being served, with the configuration it was built with and the
+/// state of the files it was built from.
+struct Served {
+    config_path: PathBuf,
+    config: Config,
+    /// Schema files read, bases included.
+    schema_files: Vec<PathBuf>,
+    graph: KnowledgeGraph,
+    fingerprint: Fingerprint,
+    /// When the files were last checked for changes.
+    checked: Instant,
+}
+
+impl Served {
+    /// Builds the graph from the configured repositories, read with
+    /// `schema`.
+    fn load(
+        config_path: PathBuf,
+        config: Config,
+        schema: Arc<Schema>,
+        schema_files: Vec<PathBuf>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let fingerprint = fingerprint(&config_path, &config, &schema_files);
+        let ctx = ParseContext::new(schema).with_mention_pattern(super::mention_pattern(&config));
+        let graph = super::build_graph_in(&config, &ctx)?;
+        Ok(Self {
+            config_path,
+            config,
+            schema_files,
+            graph,
+            fingerprint,
+            checked: Instant::now(),
+        })
+    }
+
+    /// Reads the configuration file and the schema again, then rebuilds the
+    /// graph.
+    ///
+    /// A schema that fails to load is an error under `strict_schema`, and
+    /// replaced by the built-in model otherwise, as at startup.
+    fn reload(&self, cli: &Cli) -> Result<Self, Box<dyn Error>> {
+        let file_config = if self.config_path.exists() {
+            Some(load_config(&self.config_path)?)
+        } else {
+            None
+        };
+        let config = super::build_config(cli, file_config.as_ref())?;
+        let (schema, schema_files) = load_schema(&config);
+        let schema = match schema {
+            Ok(schema) => schema,
+            Err(e) if config.strict_schema => return Err(e.into()),
+            Err(e) => {
+                print_warning(
+                    &config.output,
+                    &format!(
+                        "Failed to load model schema: {e}; continuing with the built-in model"
+                    ),
+                );
+                Schema::builtin()
+            }
+        };
+        Self::load(
+            self.config_path.clone(),
+            config,
+            Arc::new(schema),
+            schema_files,
+        )
+    }
+
+    /// Reloads the graph when a document, the configuration file or a
+    /// schema file was added, removed or modified since it was built.
+    ///
+    /// Files are checked at most once per [`REFRESH_INTERVAL`].
+    fn refresh(&mut self, cli: &Cli) -> Result<(), Box<dyn Error>> {
+        if self.checked.elapsed() < REFRESH_INTERVAL {
+            return Ok(());
+        }
+        self.checked = Instant::now();
+        if fingerprint(&self.config_path, &self.config, &self.schema_files) != self.fingerprint {
+            *self = self.reload(cli)?;
+            print_success(
+                &self.config.output,
+                &format!("Reloaded {} items", self.graph.item_count()),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A JSON response.
+#[derive(Debug)]
+struct Reply {
+    status: u16,
+    body: Value,
+}
+
+impl Reply {
+    fn ok(body: impl serde::Serialize) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_value(body).unwrap_or(Value::Null),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Runs the serve command.
+pub fn run(args: &ServeArgs, cli: &Cli, config: &Config) -> Result<ExitCode, Box<dyn Error>> {
+    let server = Server::http((args.host.as_str(), args.port))
+        .map_err(|e| format!("Cannot listen on {}:{}: {e}", args.host, args.port))?;
+    // The schema installed at startup, which already stands in for a schema
+    // that failed to load.
+    let (_, schema_files) = load_schema(config);
+    let mut served = Served::load(
+        cli.config_path(),
+        config.clone(),
+        schema::shared(),
+        schema_files,
+    )?;
+    print_success(
+        &config.output,
+        &format!(
+            "Serving {} items on http://{}",
+            served.graph.item_count(),
+            server.server_addr()
+        ),
+    );
+
+    for request in server.incoming_requests() {
+        let reply = match served.refresh(cli) {
+            Ok(()) => handle(&request, &served.graph, &served.config),
+            Err(e) => Reply::error(500, format!("Failed to reload the graph: {e}")),
+        };
+        respond(request, reply, &served.config);
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Sends a reply, logging delivery failures.
+fn respond(request: Request, reply: Reply, config: &Config) {
+    let body = serde_json::to_string_pretty(&reply.body).unwrap_or_else(|_| "{}".to_string());
+    let response = Response::from_string(body)
+        .with_status_code(reply.status)
+        .with_header(
+            Header::from_bytes("Content-Type", "application/json").expect("static header is valid"),
+        );
+    if let Err(e) = request.respond(response) {
+        print_error(&config.output, &format!("Failed to send response: {e}"));
+    }
+}
+
+/// Answers a request.
+fn handle(request: &Request, graph: &KnowledgeGraph, config: &Config) -> Reply {
+    if request.method() != &Method::Get {
+        return Reply::error(405, "Only GET requests are supported");
+    }
+    let (path, params) = split_url(request.url());
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match segments.as_slice() {
+        ["api", "items", id] => item(graph, &decode(id)),
+        ["api", "items", id, direction @ ("upstream" | "downstream")] => {
+            traversal(graph, &decode(id), direction, &params)
+        }
+        ["api", "validation"] => {
+            let strict = match flag(&params, "strict") {
+                Ok(strict) => strict.unwrap_or(config.validation.strict_mode),
+                Err(reply) => return reply,
+            };
+            Reply::ok(
+                Validator::new(ValidationConfig {
+                    strict_mode: strict,
+                    ..config.validation.clone()
+                })
+                .validate(graph),
+            )
+        }
+        ["api", "reports", "coverage"] => Reply::ok(CoverageReport::generate(graph)),
+        ["api", "reports", "matrix"] => Reply::ok(TraceabilityMatrix::generate(graph)),
+        ["api", "diff"] => diff(&params, config),
+        _ => Reply::error(404, format!("No such endpoint: {path}")),
+    }
+}
+
+/// Looks up an item, suggesting similar ids when it does not exist.
+fn item(graph: &KnowledgeGraph, id: &str) -> Reply {
+    match graph.lookup_or_suggest(id) {
+        Ok(item) => Reply::ok(item),
+        Err(e) => not_found(e),
+    }
+}
+
+/// Traverses the graph upstream or downstream of an item.
+fn traversal(
+    graph: &KnowledgeGraph,
+    id: &str,
+    direction: &str,
+    params: &[(String, String)],
+) -> Reply {
+    let item = match graph.lookup_or_suggest(id) {
+        Ok(item) => item,
+        Err(e) => return not_found(e),
+    };
+
+    let mut options = TraversalOptions::new();
+    if let Some(depth) = param(params, "depth") {
+        match depth.parse() {
+            Ok(depth) => options = options.with_max_depth(depth),
+            Err(_) => return Reply::error(400, format!("Invalid depth: {depth}")),
+        }
+    }
+    let types: Vec<String> = params
+        .iter()
+        .filter(|(key, _)| key == "type")
+        .map(|(_, value)| value.clone())
+        .collect();
+    if !types.is_empty() {
+        options = options.with_types(parse_item_types(&types));
+    }
+
+    let result = if direction == "upstream" {
+        traverse_upstream(graph, &item.id, &options)
+    } else {
+        traverse_downstream(graph, &item.id, &options)
+    };
+    match result {
+        Some(result) => Reply::ok(traversal_json(&result, graph)),
+        None => Reply::error(404, format!("Item not found: {id}")),
+    }
+}
+
+/// Compares the graph between the `from` and `to` references.
+fn diff(params: &[(String, String)], config: &Config) -> Reply {
+    let (Some(from), Some(to)) = (param(params, "from"), param(params, "to")) else {
+        return Reply::error(400, "Both `from` and `to` references are required");
+    };
+    match DiffService::new().diff(&diff_options(config, from, to)) {
+        Ok(result) => Reply::ok(json!({
+            "ref1": result.ref1,
+            "ref2": result.ref2,
+            "diff": result.diff,
+            "warnings": result.warnings,
+        })),
+        Err(e) => Reply::error(400, e.to_string()),
+    }
+}
+
+/// Replies to a failed lookup, with the suggested ids if any.
+fn not_found(error: SaraError) -> Reply {
+    let suggestions = match &error {
+        SaraError::ItemNotFound { suggestions, .. } => suggestions.clone(),
+        _ => Vec::new(),
+    };
+    Reply {
+        status: 404,
+        body: json!({ "error": error.to_string(), "suggestions": suggestions }),
+    }
+}
+
+/// Returns the value of a query parameter.
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Returns the value of a boolean query parameter; a bare `?name` is true.
+fn flag(params: &[(String, String)], name: &str) -> Result<Option<bool>, Reply> {
+    match param(params, name) {
+        None => Ok(None),
+        Some("" | "true" | "1") => Ok(Some(true)),
+        Some("false" | "0") => Ok(Some(false)),
+        Some(value) => Err(Reply::error(400, format!("Invalid {name}: {value}"))),
+    }
+}
+
+/// Splits a request URL into its path and decoded query parameters.
+fn split_url(url: &str) -> (&str, Vec<(String, String)>) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+        .collect();
+    (path, params)
+}
+
+/// Decodes a percent-encoded URL component, with `+` standing for a space.
+fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len()
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit() =>
+            {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                i += 3;
+                continue;
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Loads the configured schema, returning it along with the files read,
+/// bases included, even when loading fails.
+fn load_schema(config: &Config) -> (Result<Schema, SaraError>, Vec<PathBuf>) {
+    let Some(path) = &config.model_schema else {
+        return (Ok(Schema::builtin()), Vec::new());
+    };
+    let files = RefCell::new(Vec::new());
+    let read = |file: &Path| {
+        files.borrow_mut().push(file.to_path_buf());
+        fs::read_to_string(file).map_err(|e| SaraError::ConfigRead {
+            path: file.to_path_buf(),
+            reason: e.to_string(),
+        })
+    };
+    let schema = Schema::from_path_with(Path::new(path), &read);
+    (schema, files.into_inner())
+}
+
+/// Returns the modification time and size of the configuration file, the
+/// schema files and every document file of the configured repositories.
+fn fingerprint(config_path: &Path, config: &Config, schema_files: &[PathBuf]) -> Fingerprint {
+    let documents = config
+        .repositories
+        .paths
+        .iter()
+        .flat_map(|path| scan_directory(path).unwrap_or_default());
+    std::iter::once(config_path.to_path_buf())
+        .chain(schema_files.iter().cloned())
+        .chain(documents)
+        .map(|file| {
+            let metadata = fs::metadata(&file).ok();
+            let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+            let len = metadata.map_or(0, |m| m.len());
+            (file, modified, len)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_url_decodes_query_parameters() {
+        let (path, params) = split_url("/api/diff?from=HEAD%7E1&to=origin/main&strict");
+        assert_eq!(path, "/api/diff");
+        assert_eq!(param(&params, "from"), Some("HEAD~1"));
+        assert_eq!(param(&params, "to"), Some("origin/main"));
+        assert_eq!(flag(&params, "strict").unwrap(), Some(true));
+        assert!(flag(&[("strict".into(), "maybe".into())], "strict").is_err());
+    }
+
+    #[test]
+    fn test_decode_keeps_malformed_escapes() {
+        assert_eq!(decode("platform%3ASOL-001"), "platform:SOL-001");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%zz"), "%zz");
+    }
+}
@@ -180,6 +180,7 @@ fn main() -> ExitCode {
         None
     };
 
+    let mut schema_error = None;
     if let Some(cfg) = file_config.as_ref() {
         match cfg.load_schema() {
             Ok(schema) => {
@@ -187,11 +188,17 @@ fn main() -> ExitCode {
                 // (no-op for the CLI entry point but defensive).
                 let _ = sara_core::schema::install(schema);
             }
+            Err(e) if cfg.strict_schema => {
+                // Reported once the command is known: `sara schema check`
+                // still runs, to diagnose the schema.
+                schema_error = Some(e);
+            }
             Err(e) => {
                 output::print_warning(
                     &cfg.output,
                     &format!(
-                        "Failed to load model schema: {}; continuing with the built-in model",
+                        "Failed to load model schema: {}; continuing with the built-in model \
+                         (run `sara schema check` to list every problem)",
                         e
                     ),
                 );
@@ -213,6 +220,20 @@ fn main() -> ExitCode {
     let cli = Cli::parse();
     logging::set_verbosity(cli.verbosity());
 
+    if let Some(e) = schema_error
+        && !cli.command.runs_without_schema()
+    {
+        let config = cli.output_config(file_config.as_ref());
+        output::print_error(
+            &config,
+            &format!(
+                "Failed to load model schema: {}; run `sara schema check` to list every problem",
+                e
+            ),
+        );
+        return ExitCode::FAILURE;
+    }
+
     // Run the command
     let result = commands::run(&cli, file_config.as_ref());
 
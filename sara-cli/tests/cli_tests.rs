@@ -155,6 +155,130 @@ mod check_command {
             .stdout(predicate::str::contains("CORRUPT-001.md"))
             .stdout(predicate::str::contains("skipped during scan"));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_reports_plugin_issues() {
+        let fixtures = fixtures_path().join("valid_graph");
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("sara.toml");
+        std::fs::write(
+            &config_path,
+            r#"[[validation.plugins]]
+name = "naming"
+command = "sh"
+args = ["-c", "grep -q SWDD-001 && echo '{\"issues\": [{\"severity\": \"error\", \"item\": \"SWDD-001\", \"message\": \"Module names must end in Service\"}]}'"]
+"#,
+        )
+        .unwrap();
+
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .arg("check")
+            .arg("-r")
+            .arg(&fixtures)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains(
+                "naming: SWDD-001: Module names must end in Service",
+            ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_runs_plugins_relative_to_the_config_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let fixtures = fixtures_path().join("valid_graph");
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let plugin_path = temp_dir.path().join("tools/naming.sh");
+        std::fs::create_dir(temp_dir.path().join("tools")).unwrap();
+        std::fs::write(
+            &plugin_path,
+            "#!/bin/sh\necho '{\"issues\": [{\"severity\": \"warning\", \"message\": \"Checked\"}]}'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&plugin_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let config_path = temp_dir.path().join("sara.toml");
+        std::fs::write(
+            &config_path,
+            "[[validation.plugins]]\nname = \"naming\"\ncommand = \"./tools/naming.sh\"\n",
+        )
+        .unwrap();
+
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .arg("check")
+            .arg("-r")
+            .arg(&fixtures)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("naming: Checked"));
+    }
+}
+
+mod namespaces {
+    use super::*;
+
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    /// Writes a solution document with the given ID into a repository.
+    fn write_solution(repo: &Path, id: &str, name: &str) {
+        std::fs::create_dir_all(repo).unwrap();
+        std::fs::write(
+            repo.join(format!("{id}.md")),
+            format!("---\nid: \"{id}\"\ntype: solution\nname: \"{name}\"\n---\n"),
+        )
+        .unwrap();
+    }
+
+    /// Creates two namespaced repositories that both define `SOL-001`.
+    fn colliding_repositories() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let platform = temp_dir.path().join("platform");
+        let product = temp_dir.path().join("product");
+        write_solution(&platform, "SOL-001", "Platform Solution");
+        write_solution(&product, "SOL-001", "Product Solution");
+        std::fs::write(
+            temp_dir.path().join("sara.toml"),
+            format!(
+                "[repositories]\npaths = [{platform:?}, {product:?}]\n\n\
+                 [repositories.namespaces]\nplatform = {platform:?}\nproduct = {product:?}\n"
+            ),
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_check_accepts_the_same_id_in_different_namespaces() {
+        let temp_dir = colliding_repositories();
+
+        sara()
+            .current_dir(temp_dir.path())
+            .arg("check")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Duplicate identifier").not());
+    }
+
+    #[test]
+    fn test_query_shows_qualified_ids() {
+        let temp_dir = colliding_repositories();
+
+        sara()
+            .current_dir(temp_dir.path())
+            .arg("query")
+            .arg("platform:SOL-001")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("platform:SOL-001"))
+            .stdout(predicate::str::contains("Platform Solution"));
+    }
 }
 
 mod query_command {
@@ -572,7 +696,7 @@ name: "Reporting Service"
 
     /// Runs a Git command in the repository, isolated from the user and
     /// system Git configuration.
-    fn git(repo: &Path, args: &[&str]) {
+    pub(super) fn git(repo: &Path, args: &[&str]) {
         let output = SystemCommand::new("git")
             .arg("-C")
             .arg(repo)
@@ -860,173 +984,578 @@ name: "Reporting Service"
     }
 }
 
-mod cycles_detection {
+mod staged_check {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::diff_command::git;
     use super::*;
 
-    #[test]
-    fn test_check_detects_cycles() {
-        let fixtures = fixtures_path().join("cycles");
+    /// Writes a use case refining the given solution.
+    fn write_use_case(repo: &Path, id: &str, solution: &str) {
+        fs::write(
+            repo.join(format!("{id}.md")),
+            format!("---\nid: \"{id}\"\ntype: use_case\nname: \"{id}\"\nrefines: [\"{solution}\"]\n---\n"),
+        )
+        .unwrap();
+    }
 
-        // Error details go to stdout, status summary to stderr
+    /// Creates a Git repository whose only commit holds UC-001, refining the
+    /// missing SOL-404.
+    fn broken_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+
+        git(repo, &["init"]);
+        git(repo, &["config", "user.name", "Sara Tests"]);
+        git(repo, &["config", "user.email", "tests@example.com"]);
+        write_use_case(repo, "UC-001", "SOL-404");
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-m", "baseline"]);
+
+        temp_dir
+    }
+
+    fn check_staged(repo: &Path) -> assert_cmd::assert::Assert {
         sara()
+            .current_dir(repo)
+            .arg("--no-color")
             .arg("check")
-            .arg("-r")
-            .arg(&fixtures)
+            .arg("--staged")
             .assert()
-            .failure()
-            .stdout(predicate::str::contains("Circular").or(predicate::str::contains("cycle")));
     }
-}
 
-mod csv_output {
-    use tempfile::TempDir;
+    #[test]
+    fn test_check_staged_reports_only_introduced_issues() {
+        let repo = broken_repo();
+        write_use_case(repo.path(), "UC-002", "SOL-405");
+        git(repo.path(), &["add", "UC-002.md"]);
+        // Not staged: must not be checked.
+        write_use_case(repo.path(), "UC-003", "SOL-406");
 
-    use super::*;
+        check_staged(repo.path())
+            .failure()
+            .stdout(predicate::str::contains("SOL-405"))
+            .stdout(predicate::str::contains("SOL-404").not())
+            .stdout(predicate::str::contains("SOL-406").not());
+    }
 
     #[test]
-    fn test_report_csv_format() {
-        let fixtures = fixtures_path().join("valid_graph");
+    fn test_check_staged_ignores_issues_already_committed() {
+        let repo = broken_repo();
+        fs::write(
+            repo.path().join("SOL-001.md"),
+            "---\nid: \"SOL-001\"\ntype: solution\nname: \"Platform\"\n---\n",
+        )
+        .unwrap();
+        git(repo.path(), &["add", "SOL-001.md"]);
 
-        sara()
-            .current_dir(&fixtures)
-            .arg("report")
-            .arg("matrix")
-            .arg("--format")
-            .arg("csv")
-            .assert()
+        check_staged(repo.path())
             .success()
-            .stdout(predicate::str::contains(","));
+            .stdout(predicate::str::contains("SOL-404").not());
     }
 
+    /// Fixing a pre-validation failure of HEAD must not turn the graph
+    /// issues HEAD also has into new ones.
     #[test]
-    fn test_report_coverage_csv() {
-        let fixtures = fixtures_path().join("valid_graph");
+    fn test_check_staged_fixing_a_duplicate_keeps_committed_issues_known() {
+        let repo = broken_repo();
+        let solution =
+            |id: &str| format!("---\nid: \"{id}\"\ntype: solution\nname: \"{id}\"\n---\n");
+        fs::write(repo.path().join("a.md"), solution("SOL-009")).unwrap();
+        fs::write(repo.path().join("b.md"), solution("SOL-009")).unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-m", "duplicate"]);
+        fs::write(repo.path().join("b.md"), solution("SOL-010")).unwrap();
+        git(repo.path(), &["add", "b.md"]);
 
-        sara()
-            .current_dir(&fixtures)
-            .arg("report")
-            .arg("coverage")
-            .arg("--format")
-            .arg("csv")
-            .assert()
-            .success();
+        check_staged(repo.path())
+            .success()
+            .stdout(predicate::str::contains("SOL-404").not());
     }
 
+    /// A pre-validation failure HEAD already has must not hide the graph
+    /// issues the staged changes introduce.
     #[test]
-    fn test_report_output_to_file() {
-        let fixtures = fixtures_path().join("valid_graph");
-        let temp_dir = TempDir::new().unwrap();
-        let output_file = temp_dir.path().join("report.csv");
-
-        sara()
-            .current_dir(&fixtures)
-            .arg("report")
-            .arg("matrix")
-            .arg("--format")
-            .arg("csv")
-            .arg("-o")
-            .arg(&output_file)
-            .assert()
-            .success();
+    fn test_check_staged_reports_new_issues_behind_a_committed_duplicate() {
+        let repo = broken_repo();
+        for file in ["a.md", "b.md"] {
+            fs::write(
+                repo.path().join(file),
+                "---\nid: \"SOL-009\"\ntype: solution\nname: \"Twin\"\n---\n",
+            )
+            .unwrap();
+        }
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-m", "duplicate"]);
+        write_use_case(repo.path(), "UC-002", "SOL-405");
+        git(repo.path(), &["add", "UC-002.md"]);
 
-        assert!(output_file.exists());
+        check_staged(repo.path())
+            .failure()
+            .stdout(predicate::str::contains("SOL-405"))
+            .stdout(predicate::str::contains("SOL-404").not());
+    }
+
+    /// Moving an item within its file must not turn the issues HEAD has
+    /// about it into new ones.
+    #[test]
+    fn test_check_staged_ignores_committed_issues_whose_lines_moved() {
+        let repo = broken_repo();
+        let spec = "# Spec\n\n\
+                    ## SOL-009: First\n\n```sara\ntype: solution\n```\n\n\
+                    ## SOL-009: Second\n\n```sara\ntype: solution\n```\n";
+        fs::write(repo.path().join("spec.md"), spec).unwrap();
+        git(repo.path(), &["add", "."]);
+        git(repo.path(), &["commit", "-m", "duplicate"]);
+        let spec = spec.replace("# Spec\n", "# Spec\n\nAn introduction.\n");
+        fs::write(repo.path().join("spec.md"), spec).unwrap();
+        git(repo.path(), &["add", "spec.md"]);
+
+        check_staged(repo.path())
+            .success()
+            .stdout(predicate::str::contains("Duplicate identifier").not());
     }
-}
 
-mod query_formats {
-    use super::*;
+    #[test]
+    fn test_check_staged_fails_on_unparsable_document() {
+        let repo = broken_repo();
+        fs::write(
+            repo.path().join("SOL-001.md"),
+            "---\nid: \"SOL-001\ntype: solution\n---\n",
+        )
+        .unwrap();
+        git(repo.path(), &["add", "SOL-001.md"]);
+
+        check_staged(repo.path())
+            .failure()
+            .stdout(predicate::str::contains("SOL-001.md"));
+    }
 
     #[test]
-    fn test_query_json_format() {
-        let fixtures = fixtures_path().join("valid_graph");
+    fn test_check_staged_without_staged_documents() {
+        let repo = broken_repo();
 
-        sara()
-            .current_dir(&fixtures)
-            .arg("query")
-            .arg("SOL-001")
-            .arg("--format")
-            .arg("json")
-            .arg("--downstream")
-            .assert()
+        check_staged(repo.path())
             .success()
-            .stdout(predicate::str::contains("{"));
+            .stdout(predicate::str::contains("No staged documents to check"));
     }
 
     #[test]
-    fn test_query_with_depth_limit() {
-        let fixtures = fixtures_path().join("valid_graph");
-
+    fn test_check_staged_conflicts_with_at() {
         sara()
-            .current_dir(&fixtures)
-            .arg("query")
-            .arg("SOL-001")
-            .arg("--downstream")
-            .arg("--depth")
-            .arg("1")
+            .arg("check")
+            .arg("--staged")
+            .arg("--at")
+            .arg("HEAD")
             .assert()
-            .success();
+            .failure();
     }
 
     #[test]
-    fn test_query_with_type_filter() {
-        let fixtures = fixtures_path().join("valid_graph");
+    fn test_hook_install_writes_pre_commit_hook() {
+        let repo = broken_repo();
+        let hook = repo.path().join(".git/hooks/pre-commit");
+        let install = |force: bool| {
+            let mut command = sara();
+            command
+                .current_dir(repo.path())
+                .arg("--no-color")
+                .arg("hook")
+                .arg("install");
+            if force {
+                command.arg("--force");
+            }
+            command.assert()
+        };
+
+        install(false).success();
+        assert!(
+            fs::read_to_string(&hook)
+                .unwrap()
+                .contains("sara check --staged")
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&hook).unwrap().permissions().mode();
+            assert_ne!(mode & 0o111, 0, "hook is not executable");
+        }
 
-        sara()
-            .current_dir(&fixtures)
-            .arg("query")
-            .arg("SOL-001")
-            .arg("--downstream")
-            .arg("--type")
-            .arg("use_case")
-            .assert()
-            .success();
+        install(false)
+            .failure()
+            .stdout(predicate::str::contains("--force"));
+        install(true).success();
     }
 }
 
-mod interactive_mode {
+mod merge_driver {
     use std::fs;
+    use std::path::Path;
 
     use tempfile::TempDir;
 
+    use super::diff_command::git;
     use super::*;
 
-    #[test]
-    fn test_init_without_subcommand_fails_in_non_tty() {
-        // When no subcommand is provided, enters interactive mode which fails without TTY
-        sara()
-            .arg("init")
-            .assert()
-            .failure()
-            .stdout(predicate::str::contains("terminal").or(predicate::str::contains("TTY")));
+    /// Writes SYSREQ-001 satisfied by the given architectures.
+    fn write_requirement(repo: &Path, specification: &str, satisfied_by: &[&str]) {
+        let links: String = satisfied_by
+            .iter()
+            .map(|id| format!("  - \"{id}\"\n"))
+            .collect();
+        fs::write(
+            repo.join("SYSREQ-001.md"),
+            format!(
+                "---\nid: \"SYSREQ-001\"\ntype: system_requirement\nname: \"Response Time\"\n\
+                 specification: \"{specification}\"\nis_satisfied_by:\n{links}---\n# Response Time\n"
+            ),
+        )
+        .unwrap();
     }
 
     #[test]
-    fn test_init_help_shows_subcommands() {
-        // The help text should show available subcommands
-        sara()
-            .arg("init")
-            .arg("--help")
-            .assert()
-            .success()
-            .stdout(predicate::str::contains("interactive mode"))
-            .stdout(predicate::str::contains("adr"))
-            .stdout(predicate::str::contains("solution"))
-            .stdout(predicate::str::contains("system-requirement"));
+    fn test_git_merge_keeps_links_added_on_both_branches() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        git(repo, &["init", "-b", "main"]);
+        git(repo, &["config", "user.name", "Sara Tests"]);
+        git(repo, &["config", "user.email", "tests@example.com"]);
+        let driver = format!(
+            "\"{}\" merge-driver %O %A %B %P",
+            env!("CARGO_BIN_EXE_sara")
+        );
+        git(repo, &["config", "merge.sara.driver", &driver]);
+        fs::write(repo.join(".gitattributes"), "*.md merge=sara\n").unwrap();
+        write_requirement(repo, "Answer within 2 seconds.", &["SYSARCH-001"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-m", "baseline"]);
+
+        git(repo, &["checkout", "-b", "gateway"]);
+        write_requirement(
+            repo,
+            "Answer within 2 seconds.",
+            &["SYSARCH-001", "SYSARCH-002"],
+        );
+        git(repo, &["commit", "-am", "satisfy by the gateway"]);
+
+        git(repo, &["checkout", "main"]);
+        write_requirement(
+            repo,
+            "Answer within 1 second.",
+            &["SYSARCH-001", "SYSARCH-003"],
+        );
+        git(repo, &["commit", "-am", "satisfy by the cache"]);
+
+        git(repo, &["merge", "--no-edit", "gateway"]);
+
+        let merged = fs::read_to_string(repo.join("SYSREQ-001.md")).unwrap();
+        assert!(merged.contains("Answer within 1 second."), "{merged}");
+        for id in ["SYSARCH-001", "SYSARCH-002", "SYSARCH-003"] {
+            assert!(merged.contains(id), "{merged}");
+        }
+        assert!(!merged.contains("<<<<<<<"), "{merged}");
     }
 
     #[test]
-    fn test_init_with_subcommand_bypasses_interactive() {
-        // When subcommand is provided, should work without TTY
+    fn test_merge_driver_marks_true_conflicts() {
         let temp_dir = TempDir::new().unwrap();
-        let test_file = temp_dir.path().join("NONINTERACTIVE.md");
-        fs::write(&test_file, "# Non-Interactive Test\n").unwrap();
+        let dir = temp_dir.path();
+        let version = |name: &str, specification: &str| {
+            let path = dir.join(name);
+            fs::create_dir(&path).unwrap();
+            write_requirement(&path, specification, &["SYSARCH-001"]);
+            path.join("SYSREQ-001.md")
+        };
+        let base = version("base", "Answer within 2 seconds.");
+        let current = version("current", "Answer within 1 second.");
+        let other = version("other", "Answer within 3 seconds.");
 
         sara()
-            .arg("init")
-            .arg("solution")
-            .arg(&test_file)
+            .arg("--no-color")
+            .arg("merge-driver")
+            .arg(&base)
+            .arg(&current)
+            .arg(&other)
+            .arg("SYSREQ-001.md")
             .assert()
-            .success();
+            .failure()
+            .stdout(predicate::str::contains(
+                "Conflicting changes to SYSREQ-001.md: specification",
+            ));
+
+        let merged = fs::read_to_string(&current).unwrap();
+        assert!(merged.contains(
+            "<<<<<<< ours\nspecification: \"Answer within 1 second.\"\n=======\n\
+             specification: \"Answer within 3 seconds.\"\n>>>>>>> theirs\n"
+        ));
+    }
+}
+
+mod across_branches {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::diff_command::git;
+    use super::*;
+
+    /// Writes a solution document and commits it on the current branch.
+    fn commit_solution(repo: &Path, file: &str, id: &str, name: &str) {
+        fs::write(
+            repo.join(file),
+            format!("---\nid: \"{id}\"\ntype: solution\nname: \"{name}\"\n---\n"),
+        )
+        .unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-m", name]);
+    }
+
+    /// Creates a Git repository holding SOL-001 on `master`, and SOL-002 on
+    /// the `login` branch.
+    fn branched_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+
+        git(repo, &["init", "-b", "master"]);
+        git(repo, &["config", "user.name", "Sara Tests"]);
+        git(repo, &["config", "user.email", "tests@example.com"]);
+        commit_solution(repo, "SOL-001.md", "SOL-001", "Platform");
+        git(repo, &["checkout", "-b", "login"]);
+        commit_solution(repo, "login.md", "SOL-002", "Login");
+        git(repo, &["checkout", "master"]);
+
+        temp_dir
+    }
+
+    #[test]
+    fn test_init_skips_ids_taken_on_other_branches() {
+        let repo = branched_repo();
+        let file = repo.path().join("cache.md");
+
+        sara()
+            .current_dir(repo.path())
+            .arg("init")
+            .arg("solution")
+            .arg(&file)
+            .arg("--name")
+            .arg("Cache")
+            .assert()
+            .success();
+
+        let content = fs::read_to_string(&file).unwrap();
+        assert!(
+            content.contains("id: \"SOL-003\""),
+            "unexpected id in {content}"
+        );
+    }
+
+    #[test]
+    fn test_check_across_branches_reports_colliding_ids() {
+        let repo = branched_repo();
+        git(repo.path(), &["checkout", "-b", "cache"]);
+        commit_solution(repo.path(), "cache.md", "SOL-002", "Cache");
+
+        sara()
+            .current_dir(repo.path())
+            .arg("--no-color")
+            .arg("check")
+            .arg("--across-branches")
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains(
+                "SOL-002 defined differently on cache: \"Cache\" in cache.md; \
+                 login: \"Login\" in login.md",
+            ));
+    }
+
+    #[test]
+    fn test_check_across_branches_accepts_diverging_edits() {
+        let repo = branched_repo();
+        git(repo.path(), &["checkout", "-b", "rename"]);
+        commit_solution(repo.path(), "SOL-001.md", "SOL-001", "Core Platform");
+
+        sara()
+            .current_dir(repo.path())
+            .arg("--no-color")
+            .arg("check")
+            .arg("--across-branches")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Check passed"));
+    }
+}
+
+mod cycles_detection {
+    use super::*;
+
+    #[test]
+    fn test_check_detects_cycles() {
+        let fixtures = fixtures_path().join("cycles");
+
+        // Error details go to stdout, status summary to stderr
+        sara()
+            .arg("check")
+            .arg("-r")
+            .arg(&fixtures)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("Circular").or(predicate::str::contains("cycle")));
+    }
+}
+
+mod csv_output {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_report_csv_format() {
+        let fixtures = fixtures_path().join("valid_graph");
+
+        sara()
+            .current_dir(&fixtures)
+            .arg("report")
+            .arg("matrix")
+            .arg("--format")
+            .arg("csv")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(","));
+    }
+
+    #[test]
+    fn test_report_coverage_csv() {
+        let fixtures = fixtures_path().join("valid_graph");
+
+        sara()
+            .current_dir(&fixtures)
+            .arg("report")
+            .arg("coverage")
+            .arg("--format")
+            .arg("csv")
+            .assert()
+            .success();
+    }
+
+    #[test]
+    fn test_report_output_to_file() {
+        let fixtures = fixtures_path().join("valid_graph");
+        let temp_dir = TempDir::new().unwrap();
+        let output_file = temp_dir.path().join("report.csv");
+
+        sara()
+            .current_dir(&fixtures)
+            .arg("report")
+            .arg("matrix")
+            .arg("--format")
+            .arg("csv")
+            .arg("-o")
+            .arg(&output_file)
+            .assert()
+            .success();
+
+        assert!(output_file.exists());
+    }
+}
+
+mod query_formats {
+    use super::*;
+
+    #[test]
+    fn test_query_json_format() {
+        let fixtures = fixtures_path().join("valid_graph");
+
+        sara()
+            .current_dir(&fixtures)
+            .arg("query")
+            .arg("SOL-001")
+            .arg("--format")
+            .arg("json")
+            .arg("--downstream")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("{"));
+    }
+
+    #[test]
+    fn test_query_with_depth_limit() {
+        let fixtures = fixtures_path().join("valid_graph");
+
+        sara()
+            .current_dir(&fixtures)
+            .arg("query")
+            .arg("SOL-001")
+            .arg("--downstream")
+            .arg("--depth")
+            .arg("1")
+            .assert()
+            .success();
+    }
+
+    #[test]
+    fn test_query_with_type_filter() {
+        let fixtures = fixtures_path().join("valid_graph");
+
+        sara()
+            .current_dir(&fixtures)
+            .arg("query")
+            .arg("SOL-001")
+            .arg("--downstream")
+            .arg("--type")
+            .arg("use_case")
+            .assert()
+            .success();
+    }
+}
+
+mod interactive_mode {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_init_without_subcommand_fails_in_non_tty() {
+        // When no subcommand is provided, enters interactive mode which fails without TTY
+        sara()
+            .arg("init")
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("terminal").or(predicate::str::contains("TTY")));
+    }
+
+    #[test]
+    fn test_init_help_shows_subcommands() {
+        // The help text should show available subcommands
+        sara()
+            .arg("init")
+            .arg("--help")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("interactive mode"))
+            .stdout(predicate::str::contains("adr"))
+            .stdout(predicate::str::contains("solution"))
+            .stdout(predicate::str::contains("system-requirement"));
+    }
+
+    #[test]
+    fn test_init_with_subcommand_bypasses_interactive() {
+        // When subcommand is provided, should work without TTY
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("NONINTERACTIVE.md");
+        fs::write(&test_file, "# Non-Interactive Test\n").unwrap();
+
+        sara()
+            .arg("init")
+            .arg("solution")
+            .arg(&test_file)
+            .assert()
+            .success();
 
         let content = fs::read_to_string(&test_file).unwrap();
         assert!(content.contains("type: solution"));
@@ -1590,23 +2119,186 @@ refines: SOL-001
     }
 
     #[test]
-    fn test_init_subcommand_for_custom_type() {
+    fn test_schema_exports_json_schema() {
+        let output = sara()
+            .args(["schema", "--format", "json-schema"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let combined: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(
+            combined["$defs"]["use_case"]["properties"]["type"]["enum"][0],
+            "use_case"
+        );
+
+        sara()
+            .args(["schema", "--format", "json-schema", "-t", "use_case"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("\"refines\""))
+            .stdout(predicate::str::contains("$defs").not());
+
+        sara().args(["schema", "-t", "use_case"]).assert().failure();
+    }
+
+    #[test]
+    fn test_schema_renders_model_guide() {
         let temp_dir = TempDir::new().unwrap();
         let config_path = write_extended_schema(&temp_dir);
-        let test_file = temp_dir.path().join("STKREQ-001.md");
 
         sara()
             .arg("--config")
             .arg(&config_path)
-            .arg("init")
-            .arg("stakeholder-requirement")
-            .arg(&test_file)
-            .arg("--id")
-            .arg("STKREQ-001")
-            .arg("--name")
-            .arg("Operator overview")
-            .arg("--rationale")
-            .arg("Operators need a single pane of glass.")
+            .args(["schema", "--format", "markdown"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("```mermaid\nerDiagram\n"))
+            .stdout(predicate::str::contains("## Validity Matrix"))
+            .stdout(predicate::str::contains("stakeholder_requirement"));
+
+        sara()
+            .args(["schema", "--format", "mermaid"])
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with("erDiagram\n"));
+
+        sara()
+            .args(["schema", "--format", "html"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("<pre class=\"mermaid\">"));
+    }
+
+    /// YAML for a type with two inconsistencies and a colliding prefix.
+    const BROKEN_TYPE_YAML: &str = r#"- id: stakeholder_requirement
+  display_name: Stakeholder Requirement
+  prefix: SOL
+  id_format: "{prefix}-{seq:03}"
+  parent_types:
+  - stakeholder
+  allowed_targets:
+  - relation: refines
+    targets:
+    - stakeholder
+"#;
+
+    #[test]
+    fn test_schema_check_reports_every_problem() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_schema_with_type(&temp_dir, BROKEN_TYPE_YAML);
+
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .args(["schema", "check"])
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains(
+                "references unknown parent type 'stakeholder'",
+            ))
+            .stdout(predicate::str::contains(
+                "references unknown target type 'stakeholder'",
+            ))
+            .stdout(predicate::str::contains("prefix 'SOL' already used"))
+            .stdout(predicate::str::contains("Schema check failed: 3 error(s)"));
+
+        // A loadable schema with warnings passes, unless strict.
+        let schema_path = temp_dir.path().join("model.yaml");
+        let fixed = fs::read_to_string(&schema_path)
+            .unwrap()
+            .replace("    - stakeholder\n", "    - use_case\n")
+            .replace("  - stakeholder\n", "  - solution\n")
+            .replace(
+                "Requirement\n  prefix: SOL\n",
+                "Requirement\n  prefix: STK\n",
+            );
+        fs::write(&schema_path, fixed).unwrap();
+        sara()
+            .args(["schema", "check"])
+            .arg(&schema_path)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("model.yaml:"));
+        sara()
+            .args(["schema", "check", "--strict"])
+            .arg(&schema_path)
+            .assert()
+            .failure();
+    }
+
+    #[test]
+    fn test_strict_schema_aborts_instead_of_falling_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_schema_with_type(&temp_dir, BROKEN_TYPE_YAML);
+
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .args(["schema", "--builtin"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "continuing with the built-in model",
+            ));
+
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(&config_path, format!("{config}strict_schema = true\n")).unwrap();
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .arg("check")
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("Failed to load model schema"));
+
+        // The schema can still be diagnosed.
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .args(["schema", "check"])
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("unknown parent type"));
+    }
+
+    #[test]
+    fn test_readme_diagram_matches_builtin_schema() {
+        let output = sara()
+            .args(["schema", "--builtin", "--format", "mermaid"])
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let diagram = format!("```mermaid\n{}```", String::from_utf8(output).unwrap());
+        let readme = include_str!("../../README.md");
+        assert!(
+            readme.contains(&diagram),
+            "README diagram is out of date, regenerate it with \
+             `sara schema --builtin --format mermaid`:\n{diagram}"
+        );
+    }
+
+    #[test]
+    fn test_init_subcommand_for_custom_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_extended_schema(&temp_dir);
+        let test_file = temp_dir.path().join("STKREQ-001.md");
+
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .arg("init")
+            .arg("stakeholder-requirement")
+            .arg(&test_file)
+            .arg("--id")
+            .arg("STKREQ-001")
+            .arg("--name")
+            .arg("Operator overview")
+            .arg("--rationale")
+            .arg("Operators need a single pane of glass.")
             .assert()
             .success();
 
@@ -1780,3 +2472,447 @@ relations: []
             .failure();
     }
 }
+
+mod interchange {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reqif_export_then_import() {
+        let fixtures = fixtures_path().join("valid_graph");
+        let temp_dir = TempDir::new().unwrap();
+        let document = temp_dir.path().join("export.reqif");
+        let imported = temp_dir.path().join("imported");
+
+        sara()
+            .arg("export")
+            .arg("reqif")
+            .arg("-r")
+            .arg(&fixtures)
+            .arg("-o")
+            .arg(&document)
+            .assert()
+            .success();
+        let xml = std::fs::read_to_string(&document).unwrap();
+        assert!(xml.contains("<SPEC-OBJECT "));
+
+        sara()
+            .arg("import")
+            .arg("reqif")
+            .arg(&document)
+            .arg("--into")
+            .arg(&imported)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Imported"));
+        assert!(imported.join("SOL-001.md").exists());
+
+        sara()
+            .arg("check")
+            .arg("-r")
+            .arg(&imported)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Broken reference").not());
+    }
+
+    #[test]
+    fn test_reqif_import_keeps_existing_documents() {
+        let fixtures = fixtures_path().join("valid_graph");
+        let temp_dir = TempDir::new().unwrap();
+        let document = temp_dir.path().join("export.reqif");
+        let existing = temp_dir.path().join("SOL-001.md");
+        std::fs::write(&existing, "unchanged").unwrap();
+
+        sara()
+            .arg("export")
+            .arg("reqif")
+            .arg("-r")
+            .arg(&fixtures)
+            .arg("-o")
+            .arg(&document)
+            .assert()
+            .success();
+
+        sara()
+            .arg("import")
+            .arg("reqif")
+            .arg(&document)
+            .arg("--into")
+            .arg(temp_dir.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("already exists"));
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "unchanged");
+    }
+
+    #[test]
+    fn test_reqif_import_rejects_invalid_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let document = temp_dir.path().join("broken.reqif");
+        std::fs::write(&document, "<NOT-REQIF/>").unwrap();
+
+        sara()
+            .arg("import")
+            .arg("reqif")
+            .arg(&document)
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("Invalid ReqIF document"));
+    }
+
+    /// Copies the valid graph fixture into a temporary repository.
+    fn copy_valid_graph() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        for entry in std::fs::read_dir(fixtures_path().join("valid_graph")).unwrap() {
+            let entry = entry.unwrap();
+            std::fs::copy(entry.path(), temp_dir.path().join(entry.file_name())).unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_csv_export_lists_items_of_the_type() {
+        sara()
+            .arg("export")
+            .arg("csv")
+            .arg("--type")
+            .arg("SWREQ")
+            .arg("-r")
+            .arg(fixtures_path().join("valid_graph"))
+            .assert()
+            .success()
+            .stdout(predicate::str::starts_with(
+                "id,name,description,specification,derives_from,depends_on\n",
+            ))
+            .stdout(predicate::str::contains(
+                "SWREQ-001,JWT Token Generation,Generate secure JWT tokens for authenticated users,",
+            ))
+            .stdout(predicate::str::contains("SYSREQ-001").not());
+    }
+
+    #[test]
+    fn test_csv_import_updates_and_creates_items() {
+        let repo = copy_valid_graph();
+        let sheet = repo.path().join("swreq.csv");
+
+        sara()
+            .arg("export")
+            .arg("csv")
+            .arg("--type")
+            .arg("software_requirement")
+            .arg("-r")
+            .arg(repo.path())
+            .arg("-o")
+            .arg(&sheet)
+            .assert()
+            .success();
+
+        let edited = std::fs::read_to_string(&sheet)
+            .unwrap()
+            .replace("JWT Token Generation", "JWT Issuance")
+            + ",Token refresh,,The software SHALL refresh tokens.,SYSARCH-001,\n";
+        std::fs::write(&sheet, edited).unwrap();
+
+        sara()
+            .arg("import")
+            .arg("csv")
+            .arg(&sheet)
+            .arg("--type")
+            .arg("software_requirement")
+            .arg("--into")
+            .arg(repo.path())
+            .arg("-r")
+            .arg(repo.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Updated SWREQ-001"))
+            .stdout(predicate::str::contains("Created SWREQ-002"));
+
+        let updated = std::fs::read_to_string(repo.path().join("SWREQ-001.md")).unwrap();
+        assert!(updated.contains("name: \"JWT Issuance\""));
+        assert!(updated.contains("## Acceptance Criteria"));
+
+        sara()
+            .arg("query")
+            .arg("SWREQ-002")
+            .arg("-r")
+            .arg(repo.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Token refresh"));
+    }
+
+    #[test]
+    fn test_csv_import_reports_failed_rows() {
+        let repo = copy_valid_graph();
+        let sheet = repo.path().join("adr.csv");
+        std::fs::write(&sheet, "id,name,status\nADR-001,Decision,rejected\n").unwrap();
+
+        sara()
+            .arg("import")
+            .arg("csv")
+            .arg(&sheet)
+            .arg("--type")
+            .arg("adr")
+            .arg("-r")
+            .arg(repo.path())
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("Line 2"))
+            .stdout(predicate::str::contains("1 failed"));
+    }
+}
+
+mod mentions {
+    use super::*;
+
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    fn mentioning_repository(mention: &str) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("SOL-001.md"),
+            "---\nid: \"SOL-001\"\ntype: solution\nname: \"Hub\"\n---\n# Hub\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("SOL-002.md"),
+            format!(
+                "---\nid: \"SOL-002\"\ntype: solution\nname: \"Gateway\"\n---\n\
+                 # Gateway\n\nReuses the pairing flow of {mention}.\n"
+            ),
+        )
+        .unwrap();
+        temp_dir
+    }
+
+    fn check(repo: &Path) -> assert_cmd::assert::Assert {
+        sara().arg("check").arg("-r").arg(repo).assert()
+    }
+
+    #[test]
+    fn test_check_reports_broken_mentions() {
+        let repo = mentioning_repository("[[SOL-009]]");
+
+        check(repo.path())
+            .failure()
+            .stdout(predicate::str::contains(
+                "SOL-002 mentions non-existent item SOL-009",
+            ));
+    }
+
+    #[test]
+    fn test_query_lists_where_an_item_is_mentioned() {
+        let repo = mentioning_repository("[[SOL-001]]");
+
+        sara()
+            .arg("query")
+            .arg("SOL-001")
+            .arg("--mentioned-by")
+            .arg("-r")
+            .arg(repo.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("SOL-002: Gateway"));
+    }
+
+    #[test]
+    fn test_rename_updates_body_mentions() {
+        let repo = mentioning_repository("[[SOL-001]]");
+
+        sara()
+            .arg("rename")
+            .arg("SOL-001")
+            .arg("SOL-010")
+            .arg("-r")
+            .arg(repo.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Renamed SOL-001 to SOL-010"));
+
+        assert!(repo.path().join("SOL-010.md").exists());
+        let gateway = std::fs::read_to_string(repo.path().join("SOL-002.md")).unwrap();
+        assert!(gateway.contains("pairing flow of [[SOL-010]]."));
+        check(repo.path())
+            .success()
+            .stdout(predicate::str::contains("Broken").not());
+    }
+
+    #[test]
+    fn test_rename_follows_the_configured_pattern() {
+        let repo = mentioning_repository("@SOL-001");
+        let config_dir = TempDir::new().unwrap();
+        let config_path = config_dir.path().join("sara.toml");
+        std::fs::write(&config_path, "[mentions]\npattern = \"@{id}\"\n").unwrap();
+
+        sara()
+            .arg("--config")
+            .arg(&config_path)
+            .arg("rename")
+            .arg("SOL-001")
+            .arg("SOL-010")
+            .arg("-r")
+            .arg(repo.path())
+            .assert()
+            .success();
+
+        let gateway = std::fs::read_to_string(repo.path().join("SOL-002.md")).unwrap();
+        assert!(gateway.contains("pairing flow of @SOL-010."));
+    }
+}
+
+mod serve_command {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::path::Path;
+    use std::process::{Child, ChildStdout, Stdio};
+    use std::time::Duration;
+
+    use tempfile::TempDir;
+
+    /// Longer than the interval between two checks for changed files.
+    const REFRESH_WAIT: Duration = Duration::from_millis(1100);
+
+    /// A running `sara serve`, stopped when dropped.
+    struct Server {
+        child: Child,
+        address: String,
+        /// Kept open so that the server can keep logging.
+        _stdout: BufReader<ChildStdout>,
+    }
+
+    impl Server {
+        /// Starts serving `repo` on a free port.
+        fn start(repo: &Path) -> Self {
+            Self::start_with_config(repo, &repo.join("sara.toml"))
+        }
+
+        /// Starts serving `repo` on a free port, configured by `config`.
+        fn start_with_config(repo: &Path, config: &Path) -> Self {
+            let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_sara"))
+                .arg("--config")
+                .arg(config)
+                .args(["--no-color", "--no-emoji", "serve", "--port", "0", "-r"])
+                .arg(repo)
+                .stdout(Stdio::piped())
+                .spawn()
+                .expect("start sara serve");
+            let mut stdout = BufReader::new(child.stdout.take().unwrap());
+            // Warnings about skipped documents come before the banner.
+            let mut banner = String::new();
+            while !banner.contains("http://") {
+                banner.clear();
+                let read = stdout.read_line(&mut banner).unwrap();
+                assert!(read > 0, "sara serve exited before listening");
+            }
+            let address = banner.split("http://").nth(1).unwrap().trim().to_string();
+            Self {
+                child,
+                address,
+                _stdout: stdout,
+            }
+        }
+
+        /// Sends a GET request, returning the status code and JSON body.
+        fn get(&self, path: &str) -> (u16, serde_json::Value) {
+            let mut stream = TcpStream::connect(&self.address).unwrap();
+            write!(stream, "GET {path} HTTP/1.0\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+            (status, serde_json::from_str(body).unwrap())
+        }
+    }
+
+    impl Drop for Server {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    fn write_item(repo: &Path, id: &str, item_type: &str, extra: &str) {
+        std::fs::write(
+            repo.join(format!("{id}.md")),
+            format!("---\nid: \"{id}\"\ntype: {item_type}\nname: \"{id}\"\n{extra}---\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_serve_answers_queries_and_reloads_changed_documents() {
+        let repo = TempDir::new().unwrap();
+        write_item(repo.path(), "SOL-001", "solution", "");
+        write_item(
+            repo.path(),
+            "UC-001",
+            "use_case",
+            "refines: [\"SOL-001\"]\n",
+        );
+        let server = Server::start(repo.path());
+
+        let (status, item) = server.get("/api/items/SOL-001");
+        assert_eq!(status, 200);
+        assert_eq!(item["id"], "SOL-001");
+
+        let (status, missing) = server.get("/api/items/SOL-01");
+        assert_eq!(status, 404);
+        assert_eq!(missing["suggestions"][0], "SOL-001");
+
+        let (status, downstream) = server.get("/api/items/SOL-001/downstream?depth=1");
+        assert_eq!(status, 200);
+        assert_eq!(downstream["items"][1]["id"], "UC-001");
+
+        let (status, report) = server.get("/api/validation");
+        assert_eq!(status, 200);
+        assert!(report.is_object(), "{report}");
+        assert_eq!(server.get("/api/reports/coverage").0, 200);
+        assert_eq!(server.get("/api/reports/matrix").0, 200);
+        assert_eq!(server.get("/api/diff").0, 400);
+        assert_eq!(server.get("/api/unknown").0, 404);
+
+        write_item(
+            repo.path(),
+            "UC-002",
+            "use_case",
+            "refines: [\"SOL-001\"]\n",
+        );
+        std::thread::sleep(REFRESH_WAIT);
+        let (status, added) = server.get("/api/items/UC-002");
+        assert_eq!(status, 200, "{added}");
+    }
+
+    #[test]
+    fn test_serve_reloads_a_changed_schema() {
+        let repo = TempDir::new().unwrap();
+        let model = TempDir::new().unwrap();
+        let schema = model.path().join("model.yaml");
+        std::fs::write(&schema, "extends: builtin\n").unwrap();
+        let config = model.path().join("sara.toml");
+        std::fs::write(
+            &config,
+            format!("model_schema = {:?}\n", schema.display().to_string()),
+        )
+        .unwrap();
+        write_item(repo.path(), "SOL-001", "solution", "");
+        write_item(repo.path(), "TC-001", "test_case", "");
+        let server = Server::start_with_config(repo.path(), &config);
+
+        assert_eq!(server.get("/api/items/TC-001").0, 404);
+
+        std::fs::write(
+            &schema,
+            "extends: builtin\nitem_types:\n- id: test_case\n  display_name: Test Case\n  \
+             prefix: TC\n  id_format: \"{prefix}-{seq:03}\"\n",
+        )
+        .unwrap();
+        std::thread::sleep(REFRESH_WAIT);
+        let (status, item) = server.get("/api/items/TC-001");
+        assert_eq!(status, 200, "{item}");
+    }
+}
@@ -4,9 +4,15 @@ mod settings;
 
 use std::path::Path;
 
-pub use settings::{Config, OutputConfig, RepositoryConfig, TemplatesConfig, ValidationConfig};
+#[cfg(feature = "git")]
+pub(crate) use settings::namespace_of;
+pub use settings::{
+    Config, MentionsConfig, OutputConfig, PluginConfig, RepositoryConfig, TemplatesConfig,
+    ValidationConfig,
+};
 
 use crate::error::SaraError;
+use crate::model::ItemId;
 
 /// Default configuration file name.
 pub const DEFAULT_CONFIG_FILE: &str = "sara.toml";
@@ -18,10 +24,33 @@ pub fn load_config(path: &Path) -> Result<Config, SaraError> {
         reason: e.to_string(),
     })?;
 
-    toml::from_str(&content).map_err(|e| SaraError::InvalidConfig {
+    let mut config: Config = toml::from_str(&content).map_err(|e| SaraError::InvalidConfig {
         path: path.to_path_buf(),
         reason: e.to_string(),
-    })
+    })?;
+
+    // A namespace prefixes item IDs, so it must itself be a valid ID.
+    for namespace in config.repositories.namespaces.keys() {
+        ItemId::new(namespace.as_str()).map_err(|e| SaraError::InvalidConfig {
+            path: path.to_path_buf(),
+            reason: format!("invalid repository namespace `{namespace}`: {e}"),
+        })?;
+    }
+
+    config
+        .mentions
+        .compile()
+        .map_err(|reason| SaraError::InvalidConfig {
+            path: path.to_path_buf(),
+            reason,
+        })?;
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for plugin in &mut config.validation.plugins {
+        plugin.resolve_command(dir);
+    }
+
+    Ok(config)
 }
 
 /// Loads configuration from the default location or returns default config.
@@ -1,9 +1,12 @@
 //! Configuration settings structures.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+use crate::parser::{DEFAULT_MENTION_PATTERN, MentionPattern};
+
 /// Main configuration structure.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -23,9 +26,18 @@ pub struct Config {
     #[serde(default)]
     pub templates: TemplatesConfig,
 
+    /// Inline mention settings.
+    #[serde(default)]
+    pub mentions: MentionsConfig,
+
     /// Optional path to a YAML file defining the domain model schema.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model_schema: Option<String>,
+
+    /// Fail when the model schema cannot be loaded, instead of falling back
+    /// to the built-in model.
+    #[serde(default)]
+    pub strict_schema: bool,
 }
 
 impl Config {
@@ -64,6 +76,40 @@ pub struct RepositoryConfig {
     /// List of repository paths to scan.
     #[serde(default)]
     pub paths: Vec<PathBuf>,
+
+    /// Namespaces of the repositories, keyed by namespace name.
+    ///
+    /// Items of a namespaced repository are identified as
+    /// `namespace:LOCAL-ID`, so independent repositories may reuse the same
+    /// numbering. Each value names an entry of [`Self::paths`].
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub namespaces: IndexMap<String, PathBuf>,
+}
+
+impl RepositoryConfig {
+    /// Returns the namespace configured for a repository path, if any.
+    ///
+    /// Paths are compared as written first, then by their canonical form so
+    /// `./platform` and `platform` designate the same repository.
+    #[must_use]
+    pub fn namespace_of(&self, path: &Path) -> Option<&str> {
+        namespace_of(&self.namespaces, path)
+    }
+}
+
+/// Looks up the namespace of a repository path in a namespace table.
+pub(crate) fn namespace_of<'a>(
+    namespaces: &'a IndexMap<String, PathBuf>,
+    path: &Path,
+) -> Option<&'a str> {
+    if let Some((name, _)) = namespaces.iter().find(|(_, p)| p.as_path() == path) {
+        return Some(name);
+    }
+    let canonical = path.canonicalize().ok()?;
+    namespaces
+        .iter()
+        .find(|(_, p)| p.canonicalize().is_ok_and(|p| p == canonical))
+        .map(|(name, _)| name.as_str())
 }
 
 /// Validation settings.
@@ -77,6 +123,38 @@ pub struct ValidationConfig {
     /// List of allowed custom fields in frontmatter.
     #[serde(default)]
     pub allowed_custom_fields: Vec<String>,
+
+    /// External checkers run after the built-in rules (see
+    /// [`crate::validation`]).
+    #[serde(default)]
+    pub plugins: Vec<PluginConfig>,
+}
+
+/// An external validation plugin: an executable that reads the graph as
+/// JSON on stdin and answers the issues it finds on stdout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginConfig {
+    /// Name of the plugin, shown with the issues it reports.
+    pub name: String,
+    /// Executable to run, looked up in `PATH` unless it is a path. A
+    /// relative path is resolved against the directory of the configuration
+    /// file declaring the plugin.
+    pub command: String,
+    /// Arguments passed to the executable.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl PluginConfig {
+    /// Resolves a command given as a relative path against `dir`, the
+    /// directory of the configuration file declaring the plugin; bare names
+    /// are left to the `PATH` lookup.
+    pub(crate) fn resolve_command(&mut self, dir: &Path) {
+        let command = Path::new(&self.command);
+        if command.is_relative() && command.components().count() > 1 {
+            self.command = dir.join(command).to_string_lossy().into_owned();
+        }
+    }
 }
 
 /// Output settings.
@@ -115,6 +193,39 @@ pub struct TemplatesConfig {
     pub paths: Vec<String>,
 }
 
+/// Inline mention settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentionsConfig {
+    /// Pattern of a mention in a document body: literal text around a single
+    /// `{id}` placeholder (e.g. `[[{id}]]` or `@{id}`).
+    #[serde(default = "default_mention_pattern")]
+    pub pattern: String,
+}
+
+impl MentionsConfig {
+    /// Compiles the configured pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem when the pattern is invalid
+    /// (see [`MentionPattern::parse`]).
+    pub fn compile(&self) -> Result<MentionPattern, String> {
+        MentionPattern::parse(&self.pattern)
+    }
+}
+
+impl Default for MentionsConfig {
+    fn default() -> Self {
+        Self {
+            pattern: default_mention_pattern(),
+        }
+    }
+}
+
+fn default_mention_pattern() -> String {
+    DEFAULT_MENTION_PATTERN.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +237,7 @@ mod tests {
         assert!(!config.validation.strict_mode);
         assert!(config.output.colors);
         assert!(config.output.emojis);
+        assert_eq!(config.mentions.pattern, "[[{id}]]");
     }
 
     #[test]
@@ -135,6 +247,46 @@ mod tests {
         assert_eq!(config.repositories.paths.len(), 1);
     }
 
+    #[test]
+    fn test_repository_namespaces() {
+        let config: Config = toml::from_str(
+            r#"
+[repositories]
+paths = ["../platform", "../product"]
+
+[repositories.namespaces]
+platform = "../platform"
+"#,
+        )
+        .unwrap();
+
+        let repos = &config.repositories;
+        assert_eq!(
+            repos.namespace_of(Path::new("../platform")),
+            Some("platform")
+        );
+        assert_eq!(repos.namespace_of(Path::new("../product")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_command_paths_resolve_against_the_config_directory() {
+        let plugin = |command: &str| {
+            let mut plugin = PluginConfig {
+                name: "lint".to_string(),
+                command: command.to_string(),
+                args: Vec::new(),
+            };
+            plugin.resolve_command(Path::new("/project"));
+            plugin.command
+        };
+
+        assert_eq!(plugin("./lint.sh"), "/project/./lint.sh");
+        assert_eq!(plugin("tools/lint.sh"), "/project/tools/lint.sh");
+        assert_eq!(plugin("/usr/bin/lint"), "/usr/bin/lint");
+        assert_eq!(plugin("lint"), "lint");
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::new();
@@ -101,6 +101,15 @@ pub enum SaraError {
         reason: String,
     },
 
+    /// Invalid TOML syntax in frontmatter or an item file.
+    #[error("Invalid TOML in {file}: {reason}")]
+    InvalidToml {
+        /// Path to the file with invalid TOML.
+        file: PathBuf,
+        /// TOML parsing error details.
+        reason: String,
+    },
+
     /// Missing required field in frontmatter.
     #[error("Missing required field '{field}' in {file}")]
     MissingField {
@@ -129,6 +138,15 @@ pub enum SaraError {
         to: ItemId,
     },
 
+    /// Body mention of a non-existent item.
+    #[error("Broken mention: {from} mentions non-existent item {to}")]
+    BrokenMention {
+        /// The item whose body holds the mention.
+        from: ItemId,
+        /// The non-existent item being mentioned.
+        to: ItemId,
+    },
+
     /// Orphan item with no upstream parent.
     #[error("Orphan item: {id} ({item_type}) has no upstream parent")]
     OrphanItem {
@@ -138,11 +156,22 @@ pub enum SaraError {
         item_type: ItemType,
     },
 
-    /// Duplicate identifier found in multiple files.
-    #[error("Duplicate identifier: {id} defined in multiple files")]
+    /// Duplicate identifier defined by several items.
+    #[error("Duplicate identifier: {id} defined in {locations}")]
     DuplicateIdentifier {
         /// The duplicated ID.
         id: ItemId,
+        /// Where the ID is defined, comma-separated.
+        locations: String,
+    },
+
+    /// Identifier introduced independently on several branches.
+    #[error("Branch collision: {id} defined differently on {definitions}")]
+    BranchCollision {
+        /// The colliding ID.
+        id: ItemId,
+        /// The branches and their definitions, semicolon-separated.
+        definitions: String,
     },
 
     /// Circular reference detected in the graph.
@@ -187,6 +216,82 @@ pub enum SaraError {
         to_id: ItemId,
     },
 
+    /// Item with fewer or more links of a relation than its type allows.
+    #[error("Cardinality violation: {id} has {count} '{relation}' link(s), expected {expected}")]
+    RelationCardinality {
+        /// The item ID.
+        id: ItemId,
+        /// The constrained relation.
+        relation: RelationshipType,
+        /// Number of links the item has.
+        count: usize,
+        /// Allowed number of links (e.g. `exactly 1`).
+        expected: String,
+    },
+
+    /// Item targeted by a unique-target relation from several items.
+    #[error(
+        "Shared target: {to_id} is the target of '{relation}' from {sources}, only one is allowed"
+    )]
+    SharedRelationTarget {
+        /// The targeted item ID.
+        to_id: ItemId,
+        /// The unique-target relation.
+        relation: RelationshipType,
+        /// Items linking to the target, comma-separated.
+        sources: String,
+    },
+
+    /// Link of a transitive relation already implied by a chain of links.
+    #[error(
+        "Implied relationship: {from_id} {relation} {to_id} already follows through {via} (the direct link is not needed)"
+    )]
+    ImpliedRelationship {
+        /// Source item ID.
+        from_id: ItemId,
+        /// Target item ID.
+        to_id: ItemId,
+        /// The transitive relation.
+        relation: RelationshipType,
+        /// Intermediate item the chain goes through.
+        via: ItemId,
+    },
+
+    /// Document naming a type, field or relation through a schema alias.
+    #[error("Deprecated name in {file}: {id} uses '{alias}', an alias of '{canonical}'")]
+    DeprecatedAlias {
+        /// File containing the item.
+        file: String,
+        /// The item ID.
+        id: ItemId,
+        /// The name written in the document.
+        alias: String,
+        /// The canonical name to write instead.
+        canonical: String,
+    },
+
+    // ==================== Validation Plugins ====================
+    /// Issue reported by an external validation plugin.
+    #[error("{plugin}: {}{message}", .item.as_ref().map(|id| format!("{id}: ")).unwrap_or_default())]
+    PluginIssue {
+        /// Name of the plugin.
+        plugin: String,
+        /// The item the issue is about, if any.
+        item: Option<ItemId>,
+        /// Description of the issue.
+        message: String,
+    },
+
+    /// External validation plugin that could not run or answered malformed
+    /// output.
+    #[error("Validation plugin {plugin} failed: {reason}")]
+    PluginFailed {
+        /// Name of the plugin.
+        plugin: String,
+        /// Reason for the failure.
+        reason: String,
+    },
+
     // ==================== Configuration ====================
     /// Configuration file could not be read.
     #[error("Failed to read config file {path}: {reason}")]
@@ -232,6 +337,16 @@ pub enum SaraError {
     #[error("Edit failed: {0}")]
     EditFailed(String),
 
+    // ==================== Interchange ====================
+    /// Document in an interchange format (ReqIF, CSV) could not be read.
+    #[error("Invalid {format} document: {reason}")]
+    InvalidInterchange {
+        /// Name of the interchange format.
+        format: String,
+        /// Description of the problem.
+        reason: String,
+    },
+
     // ==================== Wrapped Errors ====================
     /// Standard I/O error.
     #[error("I/O error: {0}")]
@@ -0,0 +1,273 @@
+//! AsciiDoc and reStructuredText document generation.
+//!
+//! Both formats carry item metadata as `:key: value` header entries, read
+//! back by the matching parsers: list values are comma-separated and values
+//! are kept on a single line. Rendering is driven by the active schema like
+//! the Markdown generator: declared fields first, then upstream and peer
+//! relations, in declaration order. The document body holds an overview and
+//! the body sections the schema declares for the type.
+
+use crate::model::{
+    FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE, FieldValue, Item, Relationship,
+};
+use crate::schema;
+
+use super::Links;
+
+/// Markup language of a field-list document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Markup {
+    /// AsciiDoc: `= Title` followed by header attribute entries.
+    AsciiDoc,
+    /// reStructuredText: underlined title followed by a field list.
+    Rst,
+}
+
+impl Markup {
+    /// Renders a section title at the given level (1 for the document title).
+    fn heading(self, text: &str, level: usize) -> String {
+        match self {
+            Self::AsciiDoc => format!("{} {text}", "=".repeat(level)),
+            Self::Rst => {
+                let mark = ['=', '-', '~', '^'][(level - 1).min(3)];
+                let width = text.chars().count().max(1);
+                format!("{text}\n{}", mark.to_string().repeat(width))
+            }
+        }
+    }
+}
+
+/// Value of a metadata entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum EntryValue {
+    /// A single value, folded onto one line.
+    Scalar(String),
+    /// A non-empty list of values.
+    List(Vec<String>),
+    /// A non-empty list of links, some carrying attributes: the target id
+    /// and the attribute entries of each link.
+    Links(Vec<(String, Vec<(String, EntryValue)>)>),
+}
+
+/// Renders the metadata entries of an item, one `:key: value` line each.
+pub(super) fn generate_fields(item: &Item, links: Links) -> String {
+    metadata_entries(item, links)
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                EntryValue::Scalar(value) => value,
+                EntryValue::List(values) => values.join(", "),
+                // Header entries hold plain values: link attributes need
+                // YAML or TOML metadata.
+                EntryValue::Links(links) => links
+                    .into_iter()
+                    .map(|(id, _)| id)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            if value.is_empty() {
+                format!(":{key}:\n")
+            } else {
+                format!(":{key}: {value}\n")
+            }
+        })
+        .collect()
+}
+
+/// Renders a complete document: title, metadata and a skeleton body.
+pub(super) fn generate_document(item: &Item, markup: Markup) -> String {
+    let mut document = markup.heading(&single_line(&item.name), 1);
+    document.push('\n');
+    if markup == Markup::Rst {
+        document.push('\n');
+    }
+    document.push_str(&generate_fields(item, Links::Declared));
+
+    let def = schema::item_type_def(item.item_type.as_str());
+    let display_name = def.map_or(item.item_type.as_str(), |d| d.display_name.as_str());
+    document.push('\n');
+    document.push_str(&markup.heading("Overview", 2));
+    document.push_str(&format!(
+        "\n\n[High-level description of this {}]\n",
+        display_name.to_lowercase()
+    ));
+
+    for section in def.iter().flat_map(|d| &d.sections) {
+        let heading = section.heading.trim_start_matches(['#', '=']).trim();
+        document.push('\n');
+        document.push_str(&markup.heading(heading, 2));
+        document.push_str(&format!("\n\n[{heading} for this item]\n"));
+    }
+    document
+}
+
+/// Collects the metadata entries of an item, driven by the active schema.
+///
+/// Shared with the TOML generator, which keeps lists as arrays.
+pub(super) fn metadata_entries(item: &Item, links: Links) -> Vec<(String, EntryValue)> {
+    let scalar = |key: &str, value: String| (key.to_string(), EntryValue::Scalar(value));
+    let mut entries = vec![
+        scalar(FIELD_ID, item.id.as_str().to_string()),
+        scalar(FIELD_TYPE, item.item_type.as_str().to_string()),
+        scalar(FIELD_NAME, single_line(&item.name)),
+    ];
+    if let Some(desc) = &item.description {
+        entries.push(scalar(FIELD_DESCRIPTION, single_line(desc)));
+    }
+
+    let Some(def) = schema::item_type_def(item.item_type.as_str()) else {
+        return entries;
+    };
+
+    for field in &def.fields {
+        if let Some(value) = item.attributes.get(&field.name).and_then(entry_value) {
+            entries.push((field.name.clone(), value));
+        }
+    }
+
+    // Downstream relations are derived and only written back from a document
+    // declaring them.
+    for rel_type in super::written_relations(item, links) {
+        let entry_links: Vec<(String, Vec<(String, EntryValue)>)> = item
+            .relationships
+            .iter()
+            .filter(|rel| rel.relationship_type == rel_type)
+            .map(|rel| (rel.to.as_str().to_string(), link_attribute_entries(rel)))
+            .collect();
+        if entry_links
+            .iter()
+            .any(|(_, attributes)| !attributes.is_empty())
+        {
+            entries.push((
+                rel_type.as_str().to_string(),
+                EntryValue::Links(entry_links),
+            ));
+        } else if !entry_links.is_empty() {
+            let ids = entry_links.into_iter().map(|(id, _)| id).collect();
+            entries.push((rel_type.as_str().to_string(), EntryValue::List(ids)));
+        }
+    }
+
+    entries
+}
+
+/// Collects the attribute entries of a link, in the order the schema
+/// declares them.
+fn link_attribute_entries(rel: &Relationship) -> Vec<(String, EntryValue)> {
+    schema::active()
+        .link_attributes(rel.relationship_type.as_str())
+        .iter()
+        .filter_map(|attribute| {
+            let value = rel.attributes.get(&attribute.name).and_then(entry_value)?;
+            Some((attribute.name.clone(), value))
+        })
+        .collect()
+}
+
+/// Converts a field value to an entry value; empty lists yield `None` and
+/// nested lists are dropped.
+fn entry_value(value: &FieldValue) -> Option<EntryValue> {
+    match value {
+        FieldValue::List(values) => {
+            let values: Vec<String> = values.iter().filter_map(scalar_string).collect();
+            (!values.is_empty()).then_some(EntryValue::List(values))
+        }
+        _ => scalar_string(value).map(EntryValue::Scalar),
+    }
+}
+
+/// Renders a scalar field value on one line; lists yield `None`.
+fn scalar_string(value: &FieldValue) -> Option<String> {
+    match value {
+        FieldValue::Text(s) => Some(single_line(s)),
+        FieldValue::Enum(s) | FieldValue::Date(s) | FieldValue::Url(s) => Some(s.clone()),
+        FieldValue::ItemRef(id) => Some(id.as_str().to_string()),
+        FieldValue::Number(_) | FieldValue::Integer(_) | FieldValue::Boolean(_) => {
+            Some(value.to_string())
+        }
+        FieldValue::List(_) => None,
+    }
+}
+
+/// Folds a multi-line text onto a single line, as header entries require.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+
+    use crate::model::{ItemBuilder, ItemId, Relationship, SourceLocation};
+    use crate::parser::{InputFormat, ParseContext, parse_metadata};
+    use crate::schema::builtin;
+
+    fn requirement() -> Item {
+        ItemBuilder::new()
+            .id(ItemId::new_unchecked("SYSREQ-001"))
+            .item_type(builtin::SYSTEM_REQUIREMENT)
+            .name("Response time")
+            .source(SourceLocation::new(PathBuf::from("/repo"), "SYSREQ-001"))
+            .attribute(
+                "specification",
+                FieldValue::text("The hub SHALL respond\nwithin 100ms."),
+            )
+            .relationships(vec![
+                Relationship::new(ItemId::new_unchecked("SCEN-001"), builtin::DERIVES_FROM),
+                Relationship::new(ItemId::new_unchecked("SCEN-002"), builtin::DERIVES_FROM),
+                Relationship::new(
+                    ItemId::new_unchecked("SYSARCH-001"),
+                    builtin::IS_SATISFIED_BY,
+                ),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_fields() {
+        let fields = generate_fields(&requirement(), Links::Declared);
+        assert!(fields.starts_with(":id: SYSREQ-001\n:type: system_requirement\n"));
+        assert!(fields.contains(":specification: The hub SHALL respond within 100ms.\n"));
+        assert!(fields.contains(":derives_from: SCEN-001, SCEN-002\n"));
+        assert!(!fields.contains("is_satisfied_by"));
+    }
+
+    #[test]
+    fn test_generated_documents_parse_back() {
+        for (markup, format, title) in [
+            (
+                Markup::AsciiDoc,
+                InputFormat::AsciiDoc,
+                "= Response time\n:id:",
+            ),
+            (
+                Markup::Rst,
+                InputFormat::Rst,
+                "Response time\n=============\n\n:id:",
+            ),
+        ] {
+            let item = requirement();
+            let document = generate_document(&item, markup);
+            assert!(document.starts_with(title), "{document}");
+
+            let parsed = parse_metadata(
+                &document,
+                Path::new("SYSREQ-001"),
+                Path::new("/repo"),
+                format,
+                &ParseContext::default(),
+            )
+            .unwrap();
+            assert_eq!(parsed.id, item.id);
+            assert_eq!(parsed.name, item.name);
+            assert_eq!(
+                parsed.attributes.get("specification"),
+                Some(&FieldValue::text("The hub SHALL respond within 100ms."))
+            );
+            assert_eq!(parsed.relationship_ids(builtin::DERIVES_FROM).count(), 2);
+        }
+    }
+}
@@ -20,9 +20,11 @@ use tera::{Context, Tera};
 use crate::config::TemplatesConfig;
 use crate::error::SaraError;
 use crate::model::{
-    FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE, FieldValue, Item, RelationshipType,
+    FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE, FieldValue, Item, Relationship,
 };
-use crate::schema::{self, FieldDef, FieldType, RelationDirection};
+
+use super::Links;
+use crate::schema::{self, FieldDef, FieldType};
 
 /// Tera registration name of the generic frontmatter partial.
 const FRONTMATTER_TEMPLATE: &str = "frontmatter.tera";
@@ -215,7 +217,7 @@ fn get_registry() -> &'static TemplateRegistry {
 #[must_use]
 pub fn generate_document(item: &Item) -> String {
     let registry = get_registry();
-    let context = build_context(item);
+    let context = build_context(item, Links::Declared);
     let template = registry
         .documents
         .get(item.item_type.as_str())
@@ -228,9 +230,9 @@ pub fn generate_document(item: &Item) -> String {
 
 /// Renders only the YAML frontmatter block from an `Item`.
 #[must_use]
-pub fn generate_frontmatter(item: &Item) -> String {
+pub fn generate_frontmatter(item: &Item, links: Links) -> String {
     let registry = get_registry();
-    let context = build_context(item);
+    let context = build_context(item, links);
     registry
         .tera
         .render(FRONTMATTER_TEMPLATE, &context)
@@ -250,6 +252,9 @@ enum EntryKind {
     Raw,
     /// Rendered as a block sequence of quoted values.
     List,
+    /// Rendered as a block sequence of links, those carrying attributes as
+    /// `id:` mappings.
+    Links,
 }
 
 /// One frontmatter line group prepared for the generic template.
@@ -259,6 +264,14 @@ struct FrontmatterEntry {
     kind: EntryKind,
     value: String,
     values: Vec<String>,
+    links: Vec<LinkEntry>,
+}
+
+/// One link of a `links` entry: the target id and its attribute entries.
+#[derive(Debug, Serialize)]
+struct LinkEntry {
+    id: String,
+    attributes: Vec<FrontmatterEntry>,
 }
 
 impl FrontmatterEntry {
@@ -269,6 +282,7 @@ impl FrontmatterEntry {
             kind: EntryKind::Scalar,
             value,
             values: Vec::new(),
+            links: Vec::new(),
         }
     }
 
@@ -279,6 +293,7 @@ impl FrontmatterEntry {
             kind: EntryKind::Raw,
             value,
             values: Vec::new(),
+            links: Vec::new(),
         }
     }
 
@@ -289,6 +304,18 @@ impl FrontmatterEntry {
             kind: EntryKind::List,
             value: String::new(),
             values,
+            links: Vec::new(),
+        }
+    }
+
+    /// Creates a block sequence of links.
+    fn links(name: &str, links: Vec<LinkEntry>) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: EntryKind::Links,
+            value: String::new(),
+            values: links.iter().map(|link| link.id.clone()).collect(),
+            links,
         }
     }
 }
@@ -305,11 +332,15 @@ struct FieldMeta {
 /// Returns the template-facing kind identifier for a field type.
 fn field_kind(field_type: &FieldType) -> &'static str {
     match field_type {
-        FieldType::Text => "text",
+        FieldType::Text { .. } => "text",
         FieldType::Enum { .. } => "enum",
         FieldType::ItemRef => "item_ref",
         FieldType::List(_) => "list",
         FieldType::Date => "date",
+        FieldType::Number { .. } => "number",
+        FieldType::Integer { .. } => "integer",
+        FieldType::Boolean => "boolean",
+        FieldType::Url => "url",
     }
 }
 
@@ -320,7 +351,7 @@ fn field_kind(field_type: &FieldType) -> &'static str {
 /// level under its own name (for document bodies) and in the ordered
 /// `entries` sequence consumed by the generic frontmatter template. Also
 /// exposes `display_name` and the `fields` metadata used by the generic body.
-fn build_context(item: &Item) -> Context {
+fn build_context(item: &Item, links: Links) -> Context {
     let mut context = Context::new();
     let type_id = item.item_type.as_str();
 
@@ -358,28 +389,28 @@ fn build_context(item: &Item) -> Context {
     }
 
     // Upstream and peer relations read the item's relationships; downstream
-    // relations are derived and never declared as primary.
-    for target in &def.allowed_targets {
-        let Some(rel) = schema::relation_def(&target.relation) else {
-            continue;
-        };
-        if rel.direction == RelationDirection::Downstream {
-            continue;
-        }
-        let Some(rel_type) = RelationshipType::from_id(&target.relation) else {
-            continue;
-        };
-        let ids: Vec<String> = item
-            .relationship_ids(rel_type)
-            .map(|id| id.as_str().to_string())
+    // relations are derived and only written back from a document declaring
+    // them.
+    for rel_type in super::written_relations(item, links) {
+        let entry_links: Vec<LinkEntry> = item
+            .relationships
+            .iter()
+            .filter(|rel| rel.relationship_type == rel_type)
+            .map(|rel| LinkEntry {
+                id: rel.to.as_str().to_string(),
+                attributes: link_attribute_entries(rel),
+            })
             .collect();
-        if !ids.is_empty() {
-            entries.push(FrontmatterEntry::list(&target.relation, ids));
+        if entry_links.iter().any(|link| !link.attributes.is_empty()) {
+            entries.push(FrontmatterEntry::links(rel_type.as_str(), entry_links));
+        } else if !entry_links.is_empty() {
+            let ids = entry_links.into_iter().map(|link| link.id).collect();
+            entries.push(FrontmatterEntry::list(rel_type.as_str(), ids));
         }
     }
 
     for entry in &entries {
-        if entry.kind == EntryKind::List {
+        if matches!(entry.kind, EntryKind::List | EntryKind::Links) {
             context.insert(entry.name.clone(), &entry.values);
         } else {
             context.insert(entry.name.clone(), &entry.value);
@@ -393,11 +424,31 @@ fn build_context(item: &Item) -> Context {
 /// Builds the frontmatter entry for one declared field, if the item holds a
 /// renderable value for it. Empty lists are omitted.
 fn field_entry(item: &Item, field: &FieldDef) -> Option<FrontmatterEntry> {
-    let value = item.attributes.get(&field.name)?;
+    value_entry(field, item.attributes.get(&field.name)?)
+}
+
+/// Builds the entries of the attributes of a link, in the order the schema
+/// declares them.
+fn link_attribute_entries(rel: &Relationship) -> Vec<FrontmatterEntry> {
+    schema::active()
+        .link_attributes(rel.relationship_type.as_str())
+        .iter()
+        .filter_map(|attribute| value_entry(attribute, rel.attributes.get(&attribute.name)?))
+        .collect()
+}
+
+/// Builds the frontmatter entry of a value of a declared field.
+fn value_entry(field: &FieldDef, value: &FieldValue) -> Option<FrontmatterEntry> {
     match &field.field_type {
-        FieldType::Text => value
+        FieldType::Text { .. } => value
             .as_text()
             .map(|s| FrontmatterEntry::scalar(&field.name, escape_yaml_string(s))),
+        FieldType::Url => value
+            .as_url()
+            .map(|s| FrontmatterEntry::scalar(&field.name, escape_yaml_string(s))),
+        FieldType::Number { .. } | FieldType::Integer { .. } | FieldType::Boolean => {
+            Some(FrontmatterEntry::raw(&field.name, value.to_string()))
+        }
         FieldType::Enum { .. } => value
             .as_enum()
             .map(|s| FrontmatterEntry::raw(&field.name, s.clone())),
@@ -426,9 +477,12 @@ fn field_entry(item: &Item, field: &FieldDef) -> Option<FrontmatterEntry> {
 /// representable in frontmatter and yield `None`.
 fn list_item_string(value: &FieldValue) -> Option<String> {
     match value {
-        FieldValue::Text(s) => Some(escape_yaml_string(s)),
+        FieldValue::Text(s) | FieldValue::Url(s) => Some(escape_yaml_string(s)),
         FieldValue::Enum(s) | FieldValue::Date(s) => Some(s.clone()),
         FieldValue::ItemRef(id) => Some(id.as_str().to_string()),
+        FieldValue::Number(_) | FieldValue::Integer(_) | FieldValue::Boolean(_) => {
+            Some(value.to_string())
+        }
         FieldValue::List(_) => None,
     }
 }
@@ -455,6 +509,7 @@ mod tests {
             repository: PathBuf::from("/repo"),
             file_path: PathBuf::from("docs/test.md"),
             git_ref: None,
+            lines: None,
         }
     }
 
@@ -567,7 +622,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let fm = generate_frontmatter(&item);
+        let fm = generate_frontmatter(&item, Links::Declared);
 
         assert!(fm.starts_with("---"));
         assert!(fm.ends_with("---"));
@@ -619,7 +674,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let fm = generate_frontmatter(&item);
+        let fm = generate_frontmatter(&item, Links::Declared);
 
         let spec = fm.find("specification:").unwrap();
         let derives = fm.find("derives_from:").unwrap();
@@ -641,7 +696,7 @@ mod tests {
         let registry = get_registry();
         let doc = registry
             .tera
-            .render(GENERIC_TEMPLATE, &build_context(&item))
+            .render(GENERIC_TEMPLATE, &build_context(&item, Links::Declared))
             .unwrap();
 
         assert!(doc.contains("# System Requirement: Fallback"));
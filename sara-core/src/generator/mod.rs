@@ -7,9 +7,15 @@
 //! Use [`OutputFormat`] with [`generate_document`] or [`generate_metadata`]
 //! to produce output without depending on format-specific functions.
 
+mod fieldlist;
 mod markdown;
+mod toml_table;
 
-use crate::model::Item;
+use std::path::Path;
+
+use crate::model::{Item, RelationshipType};
+use crate::parser::InputFormat;
+use crate::schema;
 
 #[doc(inline)]
 pub use markdown::{TemplateOverride, discover_overrides, install_overrides};
@@ -19,6 +25,29 @@ pub use markdown::{TemplateOverride, discover_overrides, install_overrides};
 pub enum OutputFormat {
     /// Markdown with YAML frontmatter.
     Markdown,
+    /// AsciiDoc with metadata in the document header attributes.
+    AsciiDoc,
+    /// reStructuredText with metadata in a leading field list.
+    Rst,
+    /// Standalone `.sara.yaml` item file.
+    Yaml,
+    /// Standalone `.sara.toml` item file, also the body of `+++` frontmatter.
+    Toml,
+}
+
+impl OutputFormat {
+    /// Returns the format of a document from its file extension, or `None`
+    /// when the file is not a supported document.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        InputFormat::from_path(path).map(|format| match format {
+            InputFormat::Markdown => Self::Markdown,
+            InputFormat::AsciiDoc => Self::AsciiDoc,
+            InputFormat::Rst => Self::Rst,
+            InputFormat::Yaml => Self::Yaml,
+            InputFormat::Toml => Self::Toml,
+        })
+    }
 }
 
 /// Generates a complete document (frontmatter + body) from an [`Item`].
@@ -28,26 +57,89 @@ pub enum OutputFormat {
 pub fn generate_document(item: &Item, format: OutputFormat) -> String {
     match format {
         OutputFormat::Markdown => markdown::generate_document(item),
+        OutputFormat::AsciiDoc => fieldlist::generate_document(item, fieldlist::Markup::AsciiDoc),
+        OutputFormat::Rst => fieldlist::generate_document(item, fieldlist::Markup::Rst),
+        OutputFormat::Yaml | OutputFormat::Toml => generate_metadata(item, format),
     }
 }
 
-/// Generates YAML frontmatter (including `---` delimiters) from an [`Item`].
+/// Generates the metadata block of an [`Item`]: YAML frontmatter (including
+/// `---` delimiters) for Markdown, `:key: value` header entries for AsciiDoc
+/// and reStructuredText, a bare YAML mapping or TOML table for standalone
+/// item files.
 ///
 /// Dispatches to the appropriate format-specific generator based on `format`.
 #[must_use]
 pub fn generate_metadata(item: &Item, format: OutputFormat) -> String {
+    metadata(item, format, Links::Declared)
+}
+
+/// Regenerates the metadata block of an [`Item`] read from a document, like
+/// [`generate_metadata`], also writing the inverse links it holds.
+///
+/// A document may declare links on the parent side (such as
+/// `is_satisfied_by`); rewriting it must keep them.
+#[must_use]
+pub fn regenerate_metadata(item: &Item, format: OutputFormat) -> String {
+    metadata(item, format, Links::Held)
+}
+
+fn metadata(item: &Item, format: OutputFormat, links: Links) -> String {
     match format {
-        OutputFormat::Markdown => markdown::generate_frontmatter(item),
+        OutputFormat::Markdown => markdown::generate_frontmatter(item, links),
+        OutputFormat::AsciiDoc | OutputFormat::Rst => fieldlist::generate_fields(item, links),
+        OutputFormat::Yaml => {
+            let frontmatter = markdown::generate_frontmatter(item, links);
+            let mapping = frontmatter
+                .trim()
+                .trim_start_matches("---")
+                .trim_end_matches("---")
+                .trim();
+            format!("{mapping}\n")
+        }
+        OutputFormat::Toml => toml_table::generate_table(item, links),
     }
 }
 
+/// Which links of an item its metadata lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Links {
+    /// The links of the upstream and peer relations its type declares.
+    Declared,
+    /// Also the links of inverse relations the item holds.
+    Held,
+}
+
+/// Returns the relations written in the metadata of an item, in order: the
+/// upstream and peer relations its type declares, then, for
+/// [`Links::Held`], the other relations it holds links of, in catalog order.
+fn written_relations(item: &Item, links: Links) -> Vec<RelationshipType> {
+    let mut relations: Vec<RelationshipType> = item
+        .item_type
+        .declared_relations()
+        .into_iter()
+        .filter(|relation| !relation.is_downstream())
+        .collect();
+    if links == Links::Held {
+        for def in &schema::active().relations {
+            if let Some(relation) = RelationshipType::from_id(&def.id)
+                && !relations.contains(&relation)
+                && item.relationship_ids(relation).next().is_some()
+            {
+                relations.push(relation);
+            }
+        }
+    }
+    relations
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
 
     use super::*;
 
-    use crate::model::{ItemBuilder, ItemId, SourceLocation};
+    use crate::model::{FieldValue, ItemBuilder, ItemId, Relationship, SourceLocation};
     use crate::schema::builtin;
 
     fn test_source() -> SourceLocation {
@@ -55,6 +147,7 @@ mod tests {
             repository: PathBuf::from("/repo"),
             file_path: PathBuf::from("docs/test.md"),
             git_ref: None,
+            lines: None,
         }
     }
 
@@ -92,6 +185,39 @@ mod tests {
         assert!(!fm.contains("## Overview"));
     }
 
+    #[test]
+    fn test_regenerate_metadata_keeps_inverse_links() {
+        let item = ItemBuilder::new()
+            .id(ItemId::new_unchecked("SYSREQ-001"))
+            .item_type(builtin::SYSTEM_REQUIREMENT)
+            .name("Response Time")
+            .source(test_source())
+            .attribute("specification", FieldValue::text("Fast."))
+            .relationships(vec![
+                Relationship::new(
+                    ItemId::new_unchecked("SYSARCH-001"),
+                    builtin::IS_SATISFIED_BY,
+                ),
+                Relationship::new(ItemId::new_unchecked("SCEN-001"), builtin::DERIVES_FROM),
+            ])
+            .build()
+            .unwrap();
+
+        for format in [
+            OutputFormat::Markdown,
+            OutputFormat::Rst,
+            OutputFormat::Toml,
+        ] {
+            assert!(!generate_metadata(&item, format).contains("is_satisfied_by"));
+
+            let metadata = regenerate_metadata(&item, format);
+            let derives = metadata.find("derives_from").unwrap();
+            let satisfied = metadata.find("is_satisfied_by").unwrap();
+            assert!(derives < satisfied, "{metadata}");
+            assert!(metadata.contains("SYSARCH-001"), "{metadata}");
+        }
+    }
+
     #[test]
     fn test_output_format_debug() {
         assert_eq!(format!("{:?}", OutputFormat::Markdown), "Markdown");
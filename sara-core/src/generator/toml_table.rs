@@ -0,0 +1,106 @@
+//! TOML metadata generation, for `+++` frontmatter and `.sara.toml` files.
+//!
+//! Entries follow the schema-driven order of the other generators: core
+//! fields, declared fields, then upstream and peer relations as arrays,
+//! with links carrying attributes written as inline tables.
+
+use crate::model::Item;
+
+use super::Links;
+use super::fieldlist::{EntryValue, metadata_entries};
+
+/// Renders the metadata of an item as a TOML table body, without delimiters.
+pub(super) fn generate_table(item: &Item, links: Links) -> String {
+    metadata_entries(item, links)
+        .into_iter()
+        .map(|(key, value)| format!("{key} = {}\n", toml_value(value)))
+        .collect()
+}
+
+/// Renders an entry value: scalars as strings, lists as arrays, links
+/// carrying attributes as inline tables.
+fn toml_value(value: EntryValue) -> String {
+    match value {
+        EntryValue::Scalar(value) => quote(value),
+        EntryValue::List(values) => {
+            let values: Vec<String> = values.into_iter().map(quote).collect();
+            format!("[{}]", values.join(", "))
+        }
+        EntryValue::Links(links) => {
+            let links: Vec<String> = links
+                .into_iter()
+                .map(|(id, attributes)| {
+                    if attributes.is_empty() {
+                        return quote(id);
+                    }
+                    let entries: Vec<String> = std::iter::once(format!("id = {}", quote(id)))
+                        .chain(
+                            attributes
+                                .into_iter()
+                                .map(|(name, value)| format!("{name} = {}", toml_value(value))),
+                        )
+                        .collect();
+                    format!("{{ {} }}", entries.join(", "))
+                })
+                .collect();
+            format!("[{}]", links.join(", "))
+        }
+    }
+}
+
+/// Renders a TOML basic string.
+fn quote(value: String) -> String {
+    toml::Value::String(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+
+    use crate::model::{FieldValue, ItemBuilder, ItemId, Relationship, SourceLocation};
+    use crate::parser::{InputFormat, ParseContext, parse_metadata};
+    use crate::schema::builtin;
+
+    #[test]
+    fn test_generated_table_parses_back() {
+        let item = ItemBuilder::new()
+            .id(ItemId::new_unchecked("SWREQ-001"))
+            .item_type(builtin::SOFTWARE_REQUIREMENT)
+            .name("Baud \"rate\"")
+            .source(SourceLocation::new(
+                PathBuf::from("/repo"),
+                "uart.sara.toml",
+            ))
+            .attribute(
+                "specification",
+                FieldValue::text("The UART SHALL run at 115200 baud."),
+            )
+            .relationships(vec![Relationship::new(
+                ItemId::new_unchecked("SYSREQ-001"),
+                builtin::DERIVES_FROM,
+            )])
+            .build()
+            .unwrap();
+
+        let table = generate_table(&item, Links::Declared);
+        assert!(table.starts_with("id = \"SWREQ-001\"\ntype = \"software_requirement\"\n"));
+        assert!(table.contains("derives_from = [\"SYSREQ-001\"]\n"));
+
+        let parsed = parse_metadata(
+            &table,
+            Path::new("uart.sara.toml"),
+            Path::new("/repo"),
+            InputFormat::Toml,
+            &ParseContext::default(),
+        )
+        .unwrap();
+        assert_eq!(parsed.name, item.name);
+        assert_eq!(
+            parsed.attributes.get("specification"),
+            item.attributes.get("specification")
+        );
+        assert_eq!(parsed.relationships, item.relationships);
+    }
+}
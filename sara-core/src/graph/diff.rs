@@ -6,6 +6,7 @@ use serde::Serialize;
 
 use crate::graph::KnowledgeGraph;
 use crate::model::{Item, ItemId};
+use crate::schema::{self, Schema};
 
 /// A diff between two knowledge graphs.
 #[derive(Debug, Clone, Serialize)]
@@ -33,17 +34,24 @@ pub struct ItemDiff {
     pub file_path: String,
 }
 
-impl From<&Item> for ItemDiff {
-    fn from(item: &Item) -> Self {
+impl ItemDiff {
+    /// Describes `item`, naming its type as `schema` does.
+    fn new(item: &Item, schema: &Schema) -> Self {
         Self {
             id: item.id.as_str().to_string(),
             name: item.name.clone(),
-            item_type: item.item_type.display_name().to_string(),
+            item_type: item.item_type.display_name_in(schema).to_string(),
             file_path: item.source.file_path.display().to_string(),
         }
     }
 }
 
+impl From<&Item> for ItemDiff {
+    fn from(item: &Item) -> Self {
+        Self::new(item, schema::active())
+    }
+}
+
 /// A modification to an item.
 #[derive(Debug, Clone, Serialize)]
 pub struct ItemModification {
@@ -84,6 +92,9 @@ impl GraphDiff {
     ///
     /// `old_graph` is the baseline (e.g., main branch).
     /// `new_graph` is the target (e.g., current HEAD).
+    ///
+    /// Each graph's items are described with that graph's schema, so types
+    /// renamed between the two states keep their name on either side.
     pub fn compute(old_graph: &KnowledgeGraph, new_graph: &KnowledgeGraph) -> Self {
         let mut added_items = Vec::new();
         let mut removed_items = Vec::new();
@@ -96,26 +107,34 @@ impl GraphDiff {
         // Find added items (in new but not in old)
         for id in new_ids.difference(&old_ids) {
             if let Some(item) = new_graph.get(id) {
-                added_items.push(ItemDiff::from(item));
+                added_items.push(ItemDiff::new(item, new_graph.schema()));
             }
         }
 
         // Find removed items (in old but not in new)
         for id in old_ids.difference(&new_ids) {
             if let Some(item) = old_graph.get(id) {
-                removed_items.push(ItemDiff::from(item));
+                removed_items.push(ItemDiff::new(item, old_graph.schema()));
             }
         }
 
         // Find modified items (in both, check for changes)
         for id in old_ids.intersection(&new_ids) {
             if let (Some(old_item), Some(new_item)) = (old_graph.get(id), new_graph.get(id)) {
-                let changes = Self::compute_item_changes(old_item, new_item);
+                let changes = Self::compute_item_changes(
+                    old_item,
+                    new_item,
+                    old_graph.schema(),
+                    new_graph.schema(),
+                );
                 if !changes.is_empty() {
                     modified_items.push(ItemModification {
                         id: id.as_str().to_string(),
                         name: new_item.name.clone(),
-                        item_type: new_item.item_type.display_name().to_string(),
+                        item_type: new_item
+                            .item_type
+                            .display_name_in(new_graph.schema())
+                            .to_string(),
                         changes,
                     });
                 }
@@ -162,8 +181,14 @@ impl GraphDiff {
         }
     }
 
-    /// Computes changes between two versions of the same item.
-    fn compute_item_changes(old: &Item, new: &Item) -> Vec<FieldChange> {
+    /// Computes changes between two versions of the same item, each read
+    /// against the schema of its graph.
+    fn compute_item_changes(
+        old: &Item,
+        new: &Item,
+        old_schema: &Schema,
+        new_schema: &Schema,
+    ) -> Vec<FieldChange> {
         let mut changes = Vec::new();
 
         // Check name change
@@ -216,13 +241,13 @@ impl GraphDiff {
         let old_upstream = Self::refs_to_string(
             old.relationships
                 .iter()
-                .filter(|r| r.relationship_type.is_upstream())
+                .filter(|r| r.relationship_type.is_upstream_in(old_schema))
                 .map(|r| &r.to),
         );
         let new_upstream = Self::refs_to_string(
             new.relationships
                 .iter()
-                .filter(|r| r.relationship_type.is_upstream())
+                .filter(|r| r.relationship_type.is_upstream_in(new_schema))
                 .map(|r| &r.to),
         );
         if old_upstream != new_upstream {
@@ -237,13 +262,13 @@ impl GraphDiff {
         let old_downstream = Self::refs_to_string(
             old.relationships
                 .iter()
-                .filter(|r| r.relationship_type.is_downstream())
+                .filter(|r| r.relationship_type.is_downstream_in(old_schema))
                 .map(|r| &r.to),
         );
         let new_downstream = Self::refs_to_string(
             new.relationships
                 .iter()
-                .filter(|r| r.relationship_type.is_downstream())
+                .filter(|r| r.relationship_type.is_downstream_in(new_schema))
                 .map(|r| &r.to),
         );
         if old_downstream != new_downstream {
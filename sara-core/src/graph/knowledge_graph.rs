@@ -1,6 +1,7 @@
 //! Knowledge graph implementation using petgraph.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use petgraph::Direction;
 use petgraph::graph::{DiGraph, NodeIndex};
@@ -9,6 +10,7 @@ use strsim::levenshtein;
 
 use crate::error::SaraError;
 use crate::model::{Item, ItemId, ItemType, RelationshipType};
+use crate::schema::{self, Schema};
 
 /// Result of looking up an item.
 #[derive(Debug)]
@@ -30,17 +32,42 @@ pub struct KnowledgeGraph {
 
     /// Index for O(1) lookup by ItemId.
     index: HashMap<ItemId, NodeIndex>,
+
+    /// Mention edges, from the mentioning item to the mentioned one.
+    ///
+    /// Kept apart from the relationship edges: a mention is prose, not
+    /// traceability, so traversal, cycle and orphan checks ignore it.
+    mentions: Vec<(NodeIndex, NodeIndex)>,
+
+    /// Schema the items were read against.
+    schema: Arc<Schema>,
 }
 
 impl KnowledgeGraph {
-    /// Creates a new empty knowledge graph.
+    /// Creates a new empty knowledge graph over the active schema.
     pub fn new() -> Self {
+        Self::with_schema(schema::shared())
+    }
+
+    /// Creates a new empty knowledge graph over the given schema.
+    fn with_schema(schema: Arc<Schema>) -> Self {
         Self {
             graph: DiGraph::new(),
             index: HashMap::new(),
+            mentions: Vec::new(),
+            schema,
         }
     }
 
+    /// Returns the schema the graph was built against.
+    ///
+    /// Pass it to the `_in` domain methods of its items (such as
+    /// [`ItemType::display_name_in`]) to resolve them against this schema
+    /// rather than the process-wide one.
+    pub fn schema(&self) -> &Arc<Schema> {
+        &self.schema
+    }
+
     /// Returns the number of items in the graph.
     pub fn item_count(&self) -> usize {
         self.graph.node_count()
@@ -66,6 +93,50 @@ impl KnowledgeGraph {
         }
     }
 
+    /// Adds a mention between two items.
+    fn add_mention(&mut self, from: &ItemId, to: &ItemId) {
+        if let (Some(from_idx), Some(to_idx)) = (self.index.get(from), self.index.get(to)) {
+            self.mentions.push((*from_idx, *to_idx));
+        }
+    }
+
+    /// Returns the number of mentions between items of the graph.
+    pub fn mention_count(&self) -> usize {
+        self.mentions.len()
+    }
+
+    /// Returns the items the body of an item mentions, sorted by ID.
+    pub fn mentions(&self, id: &ItemId) -> Vec<&Item> {
+        let Some(idx) = self.index.get(id) else {
+            return Vec::new();
+        };
+        self.mention_ends(|(from, to)| (from == idx).then_some(*to))
+    }
+
+    /// Returns the items whose body mentions an item, sorted by ID.
+    pub fn mentioned_by(&self, id: &ItemId) -> Vec<&Item> {
+        let Some(idx) = self.index.get(id) else {
+            return Vec::new();
+        };
+        self.mention_ends(|(from, to)| (to == idx).then_some(*from))
+    }
+
+    /// Collects the items at the selected end of matching mention edges.
+    fn mention_ends(
+        &self,
+        select: impl Fn(&(NodeIndex, NodeIndex)) -> Option<NodeIndex>,
+    ) -> Vec<&Item> {
+        let mut items: Vec<&Item> = self
+            .mentions
+            .iter()
+            .filter_map(select)
+            .filter_map(|idx| self.graph.node_weight(idx))
+            .collect();
+        items.sort_by_key(|item| item.id.as_str());
+        items.dedup_by(|a, b| a.id == b.id);
+        items
+    }
+
     /// Gets an item by ID.
     pub fn get(&self, id: &ItemId) -> Option<&Item> {
         let idx = self.index.get(id)?;
@@ -124,7 +195,7 @@ impl KnowledgeGraph {
 
         self.graph
             .edges_directed(*idx, Direction::Outgoing)
-            .filter(|edge| edge.weight().is_upstream())
+            .filter(|edge| edge.weight().is_upstream_in(&self.schema))
             .filter_map(|edge| self.graph.node_weight(edge.target()))
             .collect()
     }
@@ -137,7 +208,7 @@ impl KnowledgeGraph {
 
         self.graph
             .edges_directed(*idx, Direction::Incoming)
-            .filter(|edge| edge.weight().is_upstream())
+            .filter(|edge| edge.weight().is_upstream_in(&self.schema))
             .filter_map(|edge| self.graph.node_weight(edge.source()))
             .collect()
     }
@@ -147,7 +218,7 @@ impl KnowledgeGraph {
     /// Outgoing edges are grouped under their own relation and incoming
     /// edges under their relation's inverse, so a declared link appears
     /// from both sides even when the builder materialized no inverse edge.
-    /// Groups follow the active schema's relation declaration order; within
+    /// Groups follow the graph schema's relation declaration order; within
     /// a group, items are sorted by ID and duplicates from materialized
     /// inverse edges are collapsed.
     pub fn direct_relationships(&self, id: &ItemId) -> Vec<(RelationshipType, Vec<&Item>)> {
@@ -163,11 +234,11 @@ impl KnowledgeGraph {
         }
         for edge in self.graph.edges_directed(*idx, Direction::Incoming) {
             if let Some(source) = self.graph.node_weight(edge.source()) {
-                related.push((edge.weight().inverse(), source));
+                related.push((edge.weight().inverse_in(&self.schema), source));
             }
         }
 
-        RelationshipType::all()
+        RelationshipType::all_in(&self.schema)
             .into_iter()
             .filter_map(|rel_type| {
                 let mut items: Vec<&Item> = related
@@ -188,11 +259,11 @@ impl KnowledgeGraph {
             .node_weights()
             .filter(|item| {
                 // Solutions are allowed to have no parents (root of hierarchy)
-                if item.item_type.is_root() {
+                if item.item_type.is_root_in(&self.schema) {
                     return false;
                 }
                 // Check if item has any upstream relationships
-                !item.has_upstream()
+                !item.has_upstream_in(&self.schema)
             })
             .collect()
     }
@@ -216,13 +287,32 @@ impl KnowledgeGraph {
         })
     }
 
+    /// Returns the only namespaced item whose local ID is `id`.
+    ///
+    /// Lets users omit the namespace of an ID no other repository reuses.
+    fn get_unique_local(&self, id: &ItemId) -> Option<&Item> {
+        if id.namespace().is_some() {
+            return None;
+        }
+        let mut matches = self
+            .items()
+            .filter(|item| item.id.namespace().is_some() && item.id.local() == id.as_str());
+        let item = matches.next()?;
+        matches.next().is_none().then_some(item)
+    }
+
     /// Looks up an item by ID.
     ///
-    /// If the item is not found, returns suggestions for similar IDs.
+    /// An unqualified ID also finds a namespaced item when no other
+    /// repository reuses it. If the item is not found, returns suggestions
+    /// for similar IDs.
     pub fn lookup(&self, id: &str) -> LookupResult<'_> {
         let item_id = ItemId::new_unchecked(id);
 
-        if let Some(item) = self.get(&item_id) {
+        if let Some(item) = self
+            .get(&item_id)
+            .or_else(|| self.get_unique_local(&item_id))
+        {
             return LookupResult::Found(item);
         }
 
@@ -236,12 +326,16 @@ impl KnowledgeGraph {
 
     /// Looks up an item by ID, returning suggestions if not found.
     ///
-    /// Returns a `SaraError::ItemNotFound` with similar ID suggestions
-    /// when the item is missing.
+    /// Resolves unqualified IDs like [`Self::lookup`]. Returns a
+    /// `SaraError::ItemNotFound` with similar ID suggestions when the item is
+    /// missing.
     pub fn lookup_or_suggest(&self, id: &str) -> Result<&Item, SaraError> {
         let item_id = ItemId::new_unchecked(id);
 
-        if let Some(item) = self.get(&item_id) {
+        if let Some(item) = self
+            .get(&item_id)
+            .or_else(|| self.get_unique_local(&item_id))
+        {
             return Ok(item);
         }
 
@@ -270,7 +364,7 @@ impl KnowledgeGraph {
     /// exists, so the caller decides how to report the gap.
     #[must_use]
     pub fn missing_parent_type(&self, item_type: ItemType) -> Option<ItemType> {
-        let parent_type = item_type.required_parent_type()?;
+        let parent_type = item_type.required_parent_type_in(&self.schema)?;
         let has_parents = self.items().any(|item| item.item_type == parent_type);
         (!has_parents).then_some(parent_type)
     }
@@ -286,8 +380,10 @@ impl KnowledgeGraph {
         let mut scored: Vec<_> = self
             .item_ids()
             .map(|id| {
-                let id_lower = id.as_str().to_lowercase();
-                let distance = levenshtein(&query_lower, &id_lower);
+                // Score namespaced IDs on their local part too, so an
+                // unqualified query suggests every qualified match.
+                let distance = levenshtein(&query_lower, &id.as_str().to_lowercase())
+                    .min(levenshtein(&query_lower, &id.local().to_lowercase()));
                 (id, distance)
             })
             .collect();
@@ -312,6 +408,7 @@ impl Default for KnowledgeGraph {
 #[derive(Debug, Default)]
 pub struct KnowledgeGraphBuilder {
     items: Vec<Item>,
+    schema: Option<Arc<Schema>>,
 }
 
 impl KnowledgeGraphBuilder {
@@ -332,14 +429,31 @@ impl KnowledgeGraphBuilder {
         self
     }
 
+    /// Sets the schema the items were read against (default: the active
+    /// schema when building).
+    pub fn schema(mut self, schema: Arc<Schema>) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
     /// Builds the knowledge graph.
     pub fn build(self) -> Result<KnowledgeGraph, SaraError> {
-        let mut graph = KnowledgeGraph::new();
+        let schema = self.schema.unwrap_or_else(schema::shared);
 
         // First pass: collect all relationship edges from items (before moving them)
-        let edges: Vec<_> = self.items.iter().flat_map(Self::collect_edges).collect();
+        let edges: Vec<_> = self
+            .items
+            .iter()
+            .flat_map(|item| Self::collect_edges(item, &schema))
+            .collect();
+        let mentions: Vec<(ItemId, ItemId)> = self
+            .items
+            .iter()
+            .flat_map(|item| item.mentions.iter().map(|to| (item.id.clone(), to.clone())))
+            .collect();
 
         // Second pass: move items into the graph (no clone needed)
+        let mut graph = KnowledgeGraph::with_schema(schema);
         for item in self.items {
             graph.add_item(item);
         }
@@ -349,17 +463,23 @@ impl KnowledgeGraphBuilder {
             graph.add_relationship(&from, &to, rel_type);
         }
 
+        // Fourth pass: add mention edges; mentions of unknown items are left
+        // to the broken-reference rule.
+        for (from, to) in mentions {
+            graph.add_mention(&from, &to);
+        }
+
         Ok(graph)
     }
 
     /// Collects all relationship edges for an item as (from, to, type) tuples.
-    fn collect_edges(item: &Item) -> Vec<(ItemId, ItemId, RelationshipType)> {
+    fn collect_edges(item: &Item, schema: &Schema) -> Vec<(ItemId, ItemId, RelationshipType)> {
         let mut edges = Vec::new();
 
         // A detached item type (no required parent, like ADRs) links into the
         // hierarchy only through its upstream relations, so those also need
         // the inverse edge for bidirectional traversal.
-        let detached = item.item_type.required_parent_type().is_none();
+        let detached = item.item_type.required_parent_type_in(schema).is_none();
 
         for rel in &item.relationships {
             edges.push((item.id.clone(), rel.to.clone(), rel.relationship_type));
@@ -368,11 +488,11 @@ impl KnowledgeGraphBuilder {
             // upstream ones on detached types get an inverse edge so
             // traversal works from either side.
             let rel_type = rel.relationship_type;
-            if rel_type.is_downstream()
-                || (rel_type.is_peer() && rel_type.is_primary())
-                || (detached && rel_type.is_upstream())
+            if rel_type.is_downstream_in(schema)
+                || (rel_type.is_peer_in(schema) && rel_type.is_primary_in(schema))
+                || (detached && rel_type.is_upstream_in(schema))
             {
-                edges.push((rel.to.clone(), item.id.clone(), rel_type.inverse()));
+                edges.push((rel.to.clone(), item.id.clone(), rel_type.inverse_in(schema)));
             }
         }
 
@@ -631,6 +751,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_lookup_unqualified_id_across_namespaces() {
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(create_test_item("platform:SOL-001", builtin::SOLUTION))
+            .add_item(create_test_item("platform:UC-001", builtin::USE_CASE))
+            .add_item(create_test_item("product:UC-001", builtin::USE_CASE))
+            .build()
+            .unwrap();
+
+        let solution = graph.lookup_or_suggest("SOL-001").unwrap();
+        assert_eq!(solution.id.as_str(), "platform:SOL-001");
+
+        match graph.lookup("UC-001") {
+            LookupResult::Found(_) => panic!("UC-001 is ambiguous across namespaces"),
+            LookupResult::NotFound { suggestions } => {
+                let ids: Vec<_> = suggestions.iter().map(|id| id.as_str()).collect();
+                assert!(ids.contains(&"platform:UC-001"));
+                assert!(ids.contains(&"product:UC-001"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mentions_are_kept_apart_from_relationships() {
+        let mut use_case = create_test_item("UC-001", builtin::USE_CASE);
+        use_case.mentions = vec![
+            ItemId::new_unchecked("SOL-001"),
+            ItemId::new_unchecked("SOL-MISSING"),
+        ];
+        let mut scenario = create_test_item("SCEN-001", builtin::SCENARIO);
+        scenario.mentions = vec![ItemId::new_unchecked("SOL-001")];
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(create_test_item("SOL-001", builtin::SOLUTION))
+            .add_item(use_case)
+            .add_item(scenario)
+            .build()
+            .unwrap();
+
+        assert_eq!(graph.mention_count(), 2);
+        assert_eq!(graph.relationship_count(), 0);
+        let solution = ItemId::new_unchecked("SOL-001");
+        let mentioned_by: Vec<_> = graph
+            .mentioned_by(&solution)
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+        assert_eq!(mentioned_by, ["SCEN-001", "UC-001"]);
+        let mentions = graph.mentions(&ItemId::new_unchecked("UC-001"));
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].id, solution);
+        assert!(graph.parents(&ItemId::new_unchecked("UC-001")).is_empty());
+    }
+
     #[test]
     fn test_missing_parent_type_is_none_for_a_root_type() {
         let graph = KnowledgeGraphBuilder::new().build().unwrap();
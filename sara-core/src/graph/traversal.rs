@@ -103,6 +103,7 @@ fn traverse_graph(
 ) -> Option<TraversalResult> {
     let start_idx = graph.node_index(start)?;
     let inner = graph.inner();
+    let schema = graph.schema();
 
     let mut visited: HashSet<NodeIndex> = HashSet::new();
     // Queue contains: (node_idx, depth, relationship, parent_for_display)
@@ -153,7 +154,7 @@ fn traverse_graph(
                     // Follow outgoing edges with upstream relationship types
                     inner
                         .edges_directed(node_idx, Direction::Outgoing)
-                        .filter(|e| e.weight().is_upstream())
+                        .filter(|e| e.weight().is_upstream_in(schema))
                         .map(|e| (e.target(), *e.weight()))
                         .collect::<Vec<_>>()
                 }
@@ -164,14 +165,14 @@ fn traverse_graph(
 
                     // Items that refine/derive from/satisfy this item
                     for edge in inner.edges_directed(node_idx, Direction::Incoming) {
-                        if edge.weight().is_upstream() {
-                            edges.push((edge.source(), edge.weight().inverse()));
+                        if edge.weight().is_upstream_in(schema) {
+                            edges.push((edge.source(), edge.weight().inverse_in(schema)));
                         }
                     }
 
                     // Or explicit downstream references from this item
                     for edge in inner.edges_directed(node_idx, Direction::Outgoing) {
-                        if edge.weight().is_downstream() {
+                        if edge.weight().is_downstream_in(schema) {
                             edges.push((edge.target(), *edge.weight()));
                         }
                     }
@@ -0,0 +1,440 @@
+//! CSV export and import of the items of one type.
+//!
+//! A sheet holds one row per item with an `id`, `name` and `description`
+//! column, one column per declared field and body section and one column per
+//! primary relation the type declares, headed by their schema ids. List fields
+//! and relation targets hold their entries separated by [`LIST_SEPARATOR`].
+//!
+//! Body sections are exported for reading only: they are edited in the
+//! document body, so their columns are ignored on import.
+
+use indexmap::IndexMap;
+
+use crate::error::SaraError;
+use crate::graph::KnowledgeGraph;
+use crate::model::{FieldValue, Item, ItemType, RelationshipType, TraceabilityLinks};
+use crate::schema::FieldType;
+
+/// Name of the format, as reported in errors.
+const FORMAT: &str = "CSV";
+
+/// Separator of the entries of list fields and relation targets in a cell.
+pub const LIST_SEPARATOR: char = ';';
+
+/// Column holding the item identifier.
+const ID_COLUMN: &str = "id";
+/// Column holding the item name.
+const NAME_COLUMN: &str = "name";
+/// Column holding the item description.
+const DESCRIPTION_COLUMN: &str = "description";
+
+/// One row of an imported sheet.
+///
+/// Empty cells are absent: an empty field or description leaves the item's
+/// value as is. Relation columns are always present when the sheet has them,
+/// since an empty cell there means the item has no such link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRecord {
+    /// Line of the row in the sheet (1-based, the header being line 1).
+    pub line: usize,
+    /// Identifier; absent for a new item whose identifier is to be generated.
+    pub id: Option<String>,
+    /// Name.
+    pub name: Option<String>,
+    /// Description.
+    pub description: Option<String>,
+    /// Field values keyed by field name, list entries already split.
+    pub fields: IndexMap<String, Vec<String>>,
+    /// Relation targets keyed by relation.
+    pub relations: IndexMap<RelationshipType, Vec<String>>,
+}
+
+/// Returns the primary relations declared by a type, one column each.
+fn relation_columns(item_type: ItemType) -> Vec<RelationshipType> {
+    item_type
+        .declared_relations()
+        .into_iter()
+        .filter(RelationshipType::is_primary)
+        .collect()
+}
+
+/// Writes the items of one type as CSV, sorted by identifier.
+///
+/// Cells are trimmed as [`import`] trims them, so that importing an
+/// unedited sheet reads back the values it was written with.
+pub fn export(graph: &KnowledgeGraph, item_type: ItemType) -> String {
+    let fields = item_type.declared_fields();
+    let sections = item_type.declared_sections();
+    let relations = relation_columns(item_type);
+
+    let header: Vec<&str> = [ID_COLUMN, NAME_COLUMN, DESCRIPTION_COLUMN]
+        .into_iter()
+        .chain(fields.iter().map(|f| f.name.as_str()))
+        .chain(sections.iter().map(|s| s.field.as_str()))
+        .chain(relations.iter().map(RelationshipType::as_str))
+        .collect();
+
+    let mut items: Vec<&Item> = graph.items_by_type(item_type);
+    items.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+
+    let mut csv = String::new();
+    write_row(&mut csv, header.iter().copied());
+    for item in items {
+        let links = TraceabilityLinks::from_item(item);
+        let cells: Vec<String> = [
+            item.id.as_str().to_string(),
+            item.name.clone(),
+            item.description.clone().unwrap_or_default(),
+        ]
+        .into_iter()
+        .chain(
+            fields
+                .iter()
+                .map(|f| item.attributes.get(&f.name).map(cell).unwrap_or_default()),
+        )
+        .chain(
+            sections
+                .iter()
+                .map(|s| item.attributes.get(&s.field).map(cell).unwrap_or_default()),
+        )
+        .chain(relations.iter().map(|r| join_list(links.get(*r))))
+        .collect();
+        write_row(&mut csv, cells.iter().map(|c| c.trim()));
+    }
+    csv
+}
+
+/// Reads the rows of a sheet of items of one type.
+///
+/// # Errors
+///
+/// Returns [`SaraError::InvalidInterchange`] if the sheet is malformed, lacks
+/// the `id` or `name` column, or has a column that is neither a field, a body
+/// section nor a primary relation of the type.
+pub fn import(content: &str, item_type: ItemType) -> Result<Vec<CsvRecord>, SaraError> {
+    let mut rows = read_rows(content)?.into_iter();
+    let Some((_, header)) = rows.next() else {
+        return Err(invalid("empty sheet"));
+    };
+
+    let relations = relation_columns(item_type);
+    let mut columns = Vec::with_capacity(header.len());
+    for name in &header {
+        let name = name.trim();
+        let column = match name {
+            ID_COLUMN => Column::Id,
+            NAME_COLUMN => Column::Name,
+            DESCRIPTION_COLUMN => Column::Description,
+            _ => {
+                if let Some(field) = item_type.declared_field(name) {
+                    Column::Field(
+                        name.to_string(),
+                        matches!(field.field_type, FieldType::List(_)),
+                    )
+                } else if item_type
+                    .declared_sections()
+                    .iter()
+                    .any(|s| s.field == name)
+                {
+                    Column::Section(name.to_string())
+                } else if let Some(relation) = relations.iter().find(|r| r.as_str() == name) {
+                    Column::Relation(*relation)
+                } else {
+                    return Err(invalid(format!(
+                        "column `{name}` is neither a field nor a relation of {}",
+                        item_type.display_name()
+                    )));
+                }
+            }
+        };
+        columns.push(column);
+    }
+    for required in [Column::Id, Column::Name] {
+        if !columns.contains(&required) {
+            return Err(invalid(format!("missing column `{}`", required.label())));
+        }
+    }
+
+    let mut records = Vec::new();
+    for (line, row) in rows {
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        if row.len() > columns.len() {
+            return Err(invalid(format!(
+                "line {line}: {} cells for {} columns",
+                row.len(),
+                columns.len()
+            )));
+        }
+
+        let mut record = CsvRecord {
+            line,
+            id: None,
+            name: None,
+            description: None,
+            fields: IndexMap::new(),
+            relations: IndexMap::new(),
+        };
+        for (index, column) in columns.iter().enumerate() {
+            let value = row.get(index).map_or("", |cell| cell.trim());
+            let text = (!value.is_empty()).then(|| value.to_string());
+            match column {
+                Column::Id => record.id = text,
+                Column::Name => record.name = text,
+                Column::Description => record.description = text,
+                Column::Field(name, is_list) => {
+                    let entries = if *is_list {
+                        split_list(value)
+                    } else {
+                        text.into_iter().collect()
+                    };
+                    if !entries.is_empty() {
+                        record.fields.insert(name.clone(), entries);
+                    }
+                }
+                Column::Relation(relation) => {
+                    record.relations.insert(*relation, split_list(value));
+                }
+                Column::Section(_) => {}
+            }
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Role of a sheet column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Column {
+    Id,
+    Name,
+    Description,
+    /// A declared field, and whether it is a list.
+    Field(String, bool),
+    /// A body section, read-only in a sheet.
+    Section(String),
+    Relation(RelationshipType),
+}
+
+impl Column {
+    fn label(&self) -> &str {
+        match self {
+            Self::Id => ID_COLUMN,
+            Self::Name => NAME_COLUMN,
+            Self::Description => DESCRIPTION_COLUMN,
+            Self::Field(name, _) | Self::Section(name) => name,
+            Self::Relation(relation) => relation.as_str(),
+        }
+    }
+}
+
+/// Renders a field value as a cell.
+fn cell(value: &FieldValue) -> String {
+    match value {
+        FieldValue::List(values) => {
+            join_list(&values.iter().map(ToString::to_string).collect::<Vec<_>>())
+        }
+        other => other.to_string(),
+    }
+}
+
+fn join_list(entries: &[String]) -> String {
+    entries.join(&format!("{LIST_SEPARATOR} "))
+}
+
+/// Splits a cell into its list entries, dropping empty ones.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(LIST_SEPARATOR)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Appends one row, quoting the cells that need it.
+fn write_row<'a>(csv: &mut String, cells: impl Iterator<Item = &'a str>) {
+    let escaped: Vec<String> = cells.map(escape).collect();
+    csv.push_str(&escaped.join(","));
+    csv.push('\n');
+}
+
+/// Escapes a value for CSV output.
+fn escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits CSV content into rows of cells, each with the line it starts on.
+///
+/// Follows RFC 4180: quoted cells may hold separators, doubled quotes and
+/// line breaks. A leading byte order mark, as written by spreadsheets, is
+/// skipped.
+fn read_rows(content: &str) -> Result<Vec<(usize, Vec<String>)>, SaraError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => quoted = false,
+                '\n' => {
+                    line += 1;
+                    cell.push(c);
+                }
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() => quoted = true,
+            ',' => row.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut cell));
+                rows.push((row_line, std::mem::take(&mut row)));
+                line += 1;
+                row_line = line;
+            }
+            _ => cell.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(invalid(format!(
+            "line {row_line}: unterminated quoted cell"
+        )));
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push((row_line, row));
+    }
+    Ok(rows)
+}
+
+/// Builds the error reported for malformed sheets.
+fn invalid(reason: impl Into<String>) -> SaraError {
+    SaraError::InvalidInterchange {
+        format: FORMAT.to_string(),
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::graph::KnowledgeGraphBuilder;
+    use crate::model::{ItemId, Relationship};
+    use crate::schema::builtin;
+    use crate::test_utils::{
+        create_test_adr, create_test_item, create_test_item_with_relationships,
+    };
+
+    fn graph() -> KnowledgeGraph {
+        let mut requirement = create_test_item_with_relationships(
+            "SYSREQ-001",
+            builtin::SYSTEM_REQUIREMENT,
+            vec![Relationship::new(
+                ItemId::new_unchecked("SCEN-001"),
+                builtin::DERIVES_FROM,
+            )],
+        );
+        requirement.description = Some("Brakes, \"quickly\"".to_string());
+        KnowledgeGraphBuilder::new()
+            .add_item(create_test_item("SCEN-001", builtin::SCENARIO))
+            .add_item(requirement)
+            .add_item(create_test_adr("ADR-001", &["SYSREQ-001"], &[]))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_export_writes_fields_and_primary_relations() {
+        let csv = export(&graph(), builtin::SYSTEM_REQUIREMENT);
+        let mut lines = csv.lines();
+
+        let header = lines.next().unwrap();
+        assert!(header.starts_with("id,name,description,specification"));
+        assert!(header.ends_with("derives_from,depends_on"));
+        assert!(!header.contains("is_satisfied_by"));
+
+        let row = lines.next().unwrap();
+        assert!(row.starts_with("SYSREQ-001,Test SYSREQ-001,\"Brakes, \"\"quickly\"\"\","));
+        assert!(row.ends_with(",SCEN-001,"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_export_then_import_round_trips() {
+        let csv = export(&graph(), builtin::ARCHITECTURE_DECISION_RECORD);
+        let records = import(&csv, builtin::ARCHITECTURE_DECISION_RECORD).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.line, 2);
+        assert_eq!(record.id.as_deref(), Some("ADR-001"));
+        assert_eq!(record.name.as_deref(), Some("Test ADR-001"));
+        assert_eq!(record.description, None);
+        assert_eq!(record.fields["status"], ["proposed"]);
+        assert_eq!(record.fields["deciders"], ["Test Decider"]);
+        assert_eq!(record.relations[&builtin::JUSTIFIES], ["SYSREQ-001"]);
+        assert!(record.relations[&builtin::SUPERSEDES].is_empty());
+    }
+
+    #[test]
+    fn test_import_reads_quoted_cells_and_lists() {
+        let csv = "\u{feff}id,name,deciders,justifies\r\n\
+                   ,\"Use, \"\"Rust\"\"\nfor tooling\",Alice; Bob ;,SYSREQ-001; SYSREQ-002\r\n\
+                   \n\
+                   ADR-002,Second,,\n";
+        let records = import(csv, builtin::ARCHITECTURE_DECISION_RECORD).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, None);
+        assert_eq!(
+            records[0].name.as_deref(),
+            Some("Use, \"Rust\"\nfor tooling")
+        );
+        assert_eq!(records[0].fields["deciders"], ["Alice", "Bob"]);
+        assert_eq!(
+            records[0].relations[&builtin::JUSTIFIES],
+            ["SYSREQ-001", "SYSREQ-002"]
+        );
+        assert_eq!(records[1].line, 5);
+        assert!(records[1].fields.is_empty());
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_or_missing_columns() {
+        let unknown = import("id,name,rationale\n", builtin::SOLUTION);
+        assert!(
+            matches!(unknown, Err(SaraError::InvalidInterchange { reason, .. }) if reason.contains("rationale"))
+        );
+
+        let missing = import("id,description\n", builtin::SOLUTION);
+        assert!(
+            matches!(missing, Err(SaraError::InvalidInterchange { reason, .. }) if reason.contains("name"))
+        );
+
+        let unterminated = import("id,name\nSOL-001,\"open\n", builtin::SOLUTION);
+        assert!(matches!(
+            unterminated,
+            Err(SaraError::InvalidInterchange { .. })
+        ));
+    }
+}
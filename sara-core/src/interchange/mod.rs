@@ -0,0 +1,8 @@
+//! Interchange formats for exchanging the knowledge graph with other tools.
+//!
+//! Unlike the [`parser`](crate::parser) and [`generator`](crate::generator)
+//! adapters, which handle one document at a time, interchange formats carry
+//! a whole set of items in a single file.
+
+pub mod csv;
+pub mod reqif;
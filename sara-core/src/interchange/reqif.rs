@@ -0,0 +1,1796 @@
+//! ReqIF (Requirements Interchange Format) import and export.
+//!
+//! The ReqIF model maps onto the schema: SPEC-OBJECT-TYPEs are item types,
+//! their attribute definitions are fields and SPEC-RELATION-TYPEs are
+//! relations. The standard `ReqIF.ForeignID`, `ReqIF.Name` and `ReqIF.Text`
+//! attributes carry the item id, name and description, so documents
+//! exported here round-trip through requirement management tools.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use chrono::{NaiveDate, SecondsFormat, Utc};
+use indexmap::IndexMap;
+use quick_xml::Writer;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::reader::Reader;
+
+use crate::error::SaraError;
+use crate::graph::KnowledgeGraph;
+use crate::model::{
+    FieldValue, Item, ItemAttributes, ItemBuilder, ItemId, ItemType, Relationship,
+    RelationshipType, SourceLocation,
+};
+use crate::schema::{
+    AllowedTarget, FieldDef, FieldType, ItemTypeDef, RelationDef, RelationDirection, Schema,
+};
+
+/// Name of the format, as reported in errors.
+const FORMAT: &str = "ReqIF";
+
+/// XML namespace of ReqIF 1.0 documents.
+const REQIF_NAMESPACE: &str = "http://www.omg.org/spec/ReqIF/20110401/reqif.xsd";
+
+/// XML namespace of the XHTML content embedded in ReqIF documents.
+const XHTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+/// Standard attribute carrying the item identifier.
+const ATTR_FOREIGN_ID: &str = "ReqIF.ForeignID";
+/// Standard attributes carrying the item name.
+const ATTR_NAMES: [&str; 2] = ["ReqIF.Name", "ReqIF.ChapterName"];
+/// Standard attributes carrying the item description.
+const ATTR_DESCRIPTIONS: [&str; 2] = ["ReqIF.Text", "ReqIF.Description"];
+
+/// Maximum length declared for string datatypes on export.
+const STRING_MAX_LENGTH: &str = "65535";
+
+/// Items and model read from a ReqIF document.
+#[derive(Debug, Clone)]
+pub struct ReqIfImport {
+    /// Items whose type maps to the active schema, in document order.
+    pub items: Vec<Item>,
+    /// Model described by the document's type definitions, independent of
+    /// the active schema.
+    pub schema: Schema,
+    /// Objects, values and relations that could not be mapped.
+    pub warnings: Vec<String>,
+}
+
+/// Reads a ReqIF document into items of the active schema.
+///
+/// SPEC-OBJECT-TYPEs, attribute definitions and SPEC-RELATION-TYPEs are
+/// matched against the active schema by id, snake_case long name or display
+/// name; whatever does not match is reported in [`ReqIfImport::warnings`].
+/// The returned items are located in `repository`, one `<ID>.md` file each.
+///
+/// # Errors
+///
+/// Returns [`SaraError::InvalidInterchange`] if the content is not a
+/// well-formed ReqIF document.
+pub fn import(xml: &str, repository: &Path) -> Result<ReqIfImport, SaraError> {
+    let document = Document::read(xml)?;
+    let schema = document.derive_schema();
+    let mut import = Importer::new(&document, repository);
+    import.run();
+    Ok(ReqIfImport {
+        items: import.items,
+        schema,
+        warnings: import.warnings,
+    })
+}
+
+/// Serializes the knowledge graph into a ReqIF document.
+///
+/// Every type of the active schema becomes a SPEC-OBJECT-TYPE, every
+/// primary relation a SPEC-RELATION-TYPE, every item a SPEC-OBJECT and every
+/// primary link between two items of the graph a SPEC-RELATION.
+///
+/// # Errors
+///
+/// Returns [`SaraError::Io`] if the document cannot be written.
+pub fn export(graph: &KnowledgeGraph, title: &str) -> Result<String, SaraError> {
+    Exporter::new(graph).write(title)
+}
+
+// ==================== XML tree ====================
+
+/// A node of the parsed XML tree.
+#[derive(Debug, Clone)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// An XML element, named by its local name (namespace prefix stripped).
+#[derive(Debug, Clone, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    nodes: Vec<Node>,
+}
+
+impl Element {
+    /// Parses an XML document into its root element.
+    fn parse(xml: &str) -> Result<Self, SaraError> {
+        let mut reader = Reader::from_str(xml);
+        let mut stack = vec![Element::default()];
+
+        loop {
+            let event = reader
+                .read_event()
+                .map_err(|e| invalid(format!("at byte {}: {e}", reader.error_position())))?;
+            match event {
+                Event::Start(start) => stack.push(Element::open(&start)?),
+                Event::Empty(start) => {
+                    let element = Element::open(&start)?;
+                    current(&mut stack).nodes.push(Node::Element(element));
+                }
+                Event::End(_) => {
+                    let element = stack.pop().filter(|_| !stack.is_empty());
+                    let element = element.ok_or_else(|| invalid("unbalanced closing tag"))?;
+                    current(&mut stack).nodes.push(Node::Element(element));
+                }
+                Event::Text(text) => {
+                    let text = text.decode().map_err(|e| invalid(e.to_string()))?;
+                    current(&mut stack).push_text(&text);
+                }
+                Event::CData(data) => {
+                    let text = data.decode().map_err(|e| invalid(e.to_string()))?;
+                    current(&mut stack).push_text(&text);
+                }
+                Event::GeneralRef(reference) => {
+                    let resolved = match reference
+                        .resolve_char_ref()
+                        .map_err(|e| invalid(e.to_string()))?
+                    {
+                        Some(c) => c.to_string(),
+                        None => {
+                            let name = reference.decode().map_err(|e| invalid(e.to_string()))?;
+                            resolve_predefined_entity(&name)
+                                .ok_or_else(|| invalid(format!("unknown entity `&{name};`")))?
+                                .to_string()
+                        }
+                    };
+                    current(&mut stack).push_text(&resolved);
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if stack.len() != 1 {
+            return Err(invalid("unexpected end of document"));
+        }
+        stack
+            .pop()
+            .and_then(|root| root.elements().next().cloned())
+            .ok_or_else(|| invalid("empty document"))
+    }
+
+    /// Creates an element from its start tag.
+    fn open(start: &BytesStart<'_>) -> Result<Self, SaraError> {
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| invalid(e.to_string()))?;
+            let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| invalid(e.to_string()))?
+                .into_owned();
+            attributes.push((key, value));
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(start.local_name().as_ref()).into_owned(),
+            attributes,
+            nodes: Vec::new(),
+        })
+    }
+
+    /// Appends text, merging it with a preceding text node.
+    fn push_text(&mut self, text: &str) {
+        if let Some(Node::Text(last)) = self.nodes.last_mut() {
+            last.push_str(text);
+        } else {
+            self.nodes.push(Node::Text(text.to_string()));
+        }
+    }
+
+    /// Returns the value of an attribute.
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the child elements.
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.nodes.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// Returns the child elements with the given name.
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements().filter(move |e| e.name == name)
+    }
+
+    /// Returns the first child element with the given name.
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|e| e.name == name)
+    }
+
+    /// Follows a path of child element names.
+    fn path(&self, names: &[&str]) -> Option<&Element> {
+        names
+            .iter()
+            .try_fold(self, |element, name| element.child(name))
+    }
+
+    /// Returns the elements nested in a container child, such as the
+    /// SPEC-OBJECT entries of SPEC-OBJECTS.
+    fn entries<'a>(&'a self, container: &str) -> impl Iterator<Item = &'a Element> {
+        self.child(container)
+            .into_iter()
+            .flat_map(Element::elements)
+    }
+
+    /// Returns the trimmed text of the reference held by a child element,
+    /// as in `<TYPE><SPEC-OBJECT-TYPE-REF>id</SPEC-OBJECT-TYPE-REF></TYPE>`.
+    fn reference(&self, name: &str) -> Option<String> {
+        self.child(name)?
+            .elements()
+            .next()
+            .map(|r| r.text().trim().to_string())
+    }
+
+    /// Returns the text directly held by the element.
+    fn text(&self) -> String {
+        self.nodes
+            .iter()
+            .filter_map(|node| match node {
+                Node::Text(text) => Some(text.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the text of the element and its descendants, with block
+    /// elements (paragraphs, list entries, line breaks) on their own line.
+    fn text_content(&self) -> String {
+        let mut out = String::new();
+        self.collect_text(&mut out);
+        out.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn collect_text(&self, out: &mut String) {
+        for node in &self.nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Element(element) => {
+                    let block = matches!(element.name.as_str(), "p" | "div" | "li" | "br");
+                    if block {
+                        out.push('\n');
+                    }
+                    element.collect_text(out);
+                    if block {
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the innermost open element of the parse stack.
+fn current(stack: &mut [Element]) -> &mut Element {
+    stack
+        .last_mut()
+        .expect("the parse stack always holds the document root")
+}
+
+/// Builds the error reported for malformed documents.
+fn invalid(reason: impl Into<String>) -> SaraError {
+    SaraError::InvalidInterchange {
+        format: FORMAT.to_string(),
+        reason: reason.into(),
+    }
+}
+
+// ==================== ReqIF model ====================
+
+/// Kind of a ReqIF datatype definition.
+#[derive(Debug, Clone)]
+enum DatatypeKind {
+    Text,
+    Date,
+    /// Enumeration values, keyed by identifier, with their long names.
+    Enumeration(IndexMap<String, String>),
+}
+
+/// An attribute definition of a SPEC-OBJECT-TYPE.
+#[derive(Debug, Clone)]
+struct AttributeDefinition {
+    identifier: String,
+    long_name: String,
+    desc: Option<String>,
+    datatype: Option<String>,
+    multi_valued: bool,
+}
+
+/// A SPEC-OBJECT-TYPE or SPEC-RELATION-TYPE.
+#[derive(Debug, Clone)]
+struct SpecType {
+    identifier: String,
+    long_name: String,
+    attributes: Vec<AttributeDefinition>,
+}
+
+impl SpecType {
+    /// Returns the snake_case id naming this type in a schema.
+    fn schema_id(&self) -> String {
+        snake_case(&self.long_name)
+    }
+}
+
+/// A value of a SPEC-OBJECT attribute.
+#[derive(Debug, Clone)]
+enum Value {
+    Text(String),
+    /// Identifiers of the selected enumeration values.
+    Enumeration(Vec<String>),
+}
+
+/// A SPEC-OBJECT.
+#[derive(Debug, Clone)]
+struct SpecObject {
+    identifier: String,
+    long_name: Option<String>,
+    desc: Option<String>,
+    object_type: String,
+    /// Values keyed by attribute definition identifier.
+    values: IndexMap<String, Value>,
+}
+
+/// A SPEC-RELATION between two SPEC-OBJECTs.
+#[derive(Debug, Clone)]
+struct SpecRelation {
+    relation_type: String,
+    source: String,
+    target: String,
+}
+
+/// The content of a ReqIF document.
+#[derive(Debug, Clone, Default)]
+struct Document {
+    datatypes: HashMap<String, DatatypeKind>,
+    object_types: Vec<SpecType>,
+    relation_types: Vec<SpecType>,
+    objects: Vec<SpecObject>,
+    relations: Vec<SpecRelation>,
+}
+
+impl Document {
+    /// Reads the content of a ReqIF document.
+    fn read(xml: &str) -> Result<Self, SaraError> {
+        let root = Element::parse(xml)?;
+        if root.name != "REQ-IF" {
+            return Err(invalid(format!(
+                "expected a REQ-IF root element, found `{}`",
+                root.name
+            )));
+        }
+        let content = root
+            .path(&["CORE-CONTENT", "REQ-IF-CONTENT"])
+            .ok_or_else(|| invalid("missing CORE-CONTENT/REQ-IF-CONTENT"))?;
+
+        let mut document = Self::default();
+        for datatype in content.entries("DATATYPES") {
+            let Some(identifier) = datatype.attr("IDENTIFIER") else {
+                continue;
+            };
+            let kind = match datatype.name.as_str() {
+                "DATATYPE-DEFINITION-DATE" => DatatypeKind::Date,
+                "DATATYPE-DEFINITION-ENUMERATION" => DatatypeKind::Enumeration(
+                    datatype
+                        .entries("SPECIFIED-VALUES")
+                        .filter_map(|value| {
+                            let id = value.attr("IDENTIFIER")?;
+                            let name = value.attr("LONG-NAME").unwrap_or(id);
+                            Some((id.to_string(), name.to_string()))
+                        })
+                        .collect(),
+                ),
+                _ => DatatypeKind::Text,
+            };
+            document.datatypes.insert(identifier.to_string(), kind);
+        }
+
+        for spec_type in content.entries("SPEC-TYPES") {
+            let Some(identifier) = spec_type.attr("IDENTIFIER") else {
+                continue;
+            };
+            let parsed = SpecType {
+                identifier: identifier.to_string(),
+                long_name: spec_type
+                    .attr("LONG-NAME")
+                    .unwrap_or(identifier)
+                    .to_string(),
+                attributes: spec_type
+                    .entries("SPEC-ATTRIBUTES")
+                    .filter_map(read_attribute_definition)
+                    .collect(),
+            };
+            match spec_type.name.as_str() {
+                "SPEC-OBJECT-TYPE" => document.object_types.push(parsed),
+                "SPEC-RELATION-TYPE" => document.relation_types.push(parsed),
+                _ => {}
+            }
+        }
+
+        for object in content
+            .children("SPEC-OBJECTS")
+            .flat_map(|c| c.children("SPEC-OBJECT"))
+        {
+            let (Some(identifier), Some(object_type)) =
+                (object.attr("IDENTIFIER"), object.reference("TYPE"))
+            else {
+                continue;
+            };
+            document.objects.push(SpecObject {
+                identifier: identifier.to_string(),
+                long_name: object.attr("LONG-NAME").map(str::to_string),
+                desc: object.attr("DESC").map(str::to_string),
+                object_type,
+                values: object.entries("VALUES").filter_map(read_value).collect(),
+            });
+        }
+
+        for relation in content
+            .children("SPEC-RELATIONS")
+            .flat_map(|c| c.children("SPEC-RELATION"))
+        {
+            if let (Some(relation_type), Some(source), Some(target)) = (
+                relation.reference("TYPE"),
+                relation.reference("SOURCE"),
+                relation.reference("TARGET"),
+            ) {
+                document.relations.push(SpecRelation {
+                    relation_type,
+                    source,
+                    target,
+                });
+            }
+        }
+
+        Ok(document)
+    }
+
+    fn object_type(&self, identifier: &str) -> Option<&SpecType> {
+        self.object_types
+            .iter()
+            .find(|t| t.identifier == identifier)
+    }
+
+    fn relation_type(&self, identifier: &str) -> Option<&SpecType> {
+        self.relation_types
+            .iter()
+            .find(|t| t.identifier == identifier)
+    }
+
+    /// Returns the long name of an enumeration value.
+    fn enum_value_name<'a>(&'a self, definition: &AttributeDefinition, id: &'a str) -> &'a str {
+        match definition
+            .datatype
+            .as_ref()
+            .and_then(|d| self.datatypes.get(d))
+        {
+            Some(DatatypeKind::Enumeration(values)) => values.get(id).map_or(id, String::as_str),
+            _ => id,
+        }
+    }
+
+    /// Returns the identifier of the item carried by an object: its
+    /// `ReqIF.ForeignID` value when present, its identifier otherwise.
+    fn item_id(&self, object: &SpecObject) -> String {
+        self.object_type(&object.object_type)
+            .and_then(|t| standard_value(t, object, &[ATTR_FOREIGN_ID]))
+            .unwrap_or_else(|| object.identifier.clone())
+    }
+
+    /// Derives a schema from the document's type definitions.
+    ///
+    /// Object types become item types and relation types become upstream
+    /// relations (each paired with a derived inverse); the targets each type
+    /// may link to are taken from the relations present in the document.
+    fn derive_schema(&self) -> Schema {
+        let objects: HashMap<&str, &SpecObject> = self
+            .objects
+            .iter()
+            .map(|o| (o.identifier.as_str(), o))
+            .collect();
+
+        let mut targets: IndexMap<(String, String), Vec<String>> = IndexMap::new();
+        for relation in &self.relations {
+            let (Some(source), Some(target), Some(relation_type)) = (
+                objects.get(relation.source.as_str()),
+                objects.get(relation.target.as_str()),
+                self.relation_type(&relation.relation_type),
+            ) else {
+                continue;
+            };
+            let (Some(source_type), Some(target_type)) = (
+                self.object_type(&source.object_type),
+                self.object_type(&target.object_type),
+            ) else {
+                continue;
+            };
+            let entry = targets
+                .entry((source_type.schema_id(), relation_type.schema_id()))
+                .or_default();
+            if !entry.contains(&target_type.schema_id()) {
+                entry.push(target_type.schema_id());
+            }
+        }
+
+        let item_types = self
+            .object_types
+            .iter()
+            .map(|object_type| {
+                let id = object_type.schema_id();
+                let allowed_targets: Vec<AllowedTarget> = targets
+                    .iter()
+                    .filter(|((source, _), _)| *source == id)
+                    .map(|((_, relation), targets)| AllowedTarget {
+                        relation: relation.clone(),
+                        targets: targets.clone(),
+                        min: None,
+                        max: None,
+                    })
+                    .collect();
+                ItemTypeDef {
+                    prefix: self.derive_prefix(object_type),
+                    display_name: object_type.long_name.clone(),
+                    id_format: "{prefix}-{seq:03}".to_string(),
+                    parent_types: allowed_targets
+                        .first()
+                        .map(|t| t.targets.clone())
+                        .unwrap_or_default(),
+                    fields: object_type
+                        .attributes
+                        .iter()
+                        .filter(|d| !is_standard_attribute(&d.long_name))
+                        .map(|d| self.derive_field(d))
+                        .collect(),
+                    allowed_targets,
+                    sections: Vec::new(),
+                    id,
+                    aliases: Vec::new(),
+                }
+            })
+            .collect();
+
+        let relations = self
+            .relation_types
+            .iter()
+            .flat_map(|relation_type| {
+                let id = relation_type.schema_id();
+                let inverse = format!("inverse_{id}");
+                [
+                    RelationDef {
+                        id: id.clone(),
+                        display_name: relation_type.long_name.clone(),
+                        inverse: inverse.clone(),
+                        direction: RelationDirection::Upstream,
+                        primary: true,
+                        aliases: Vec::new(),
+                        acyclic: true,
+                        transitive: false,
+                        unique_target: false,
+                        attributes: Vec::new(),
+                    },
+                    RelationDef {
+                        id: inverse,
+                        display_name: format!("Inverse of {}", relation_type.long_name),
+                        inverse: id,
+                        direction: RelationDirection::Downstream,
+                        primary: false,
+                        aliases: Vec::new(),
+                        acyclic: true,
+                        transitive: false,
+                        unique_target: false,
+                        attributes: Vec::new(),
+                    },
+                ]
+            })
+            .collect();
+
+        Schema {
+            item_types,
+            relations,
+        }
+    }
+
+    /// Derives the field declared by an attribute definition.
+    fn derive_field(&self, definition: &AttributeDefinition) -> FieldDef {
+        let field_type = match definition
+            .datatype
+            .as_ref()
+            .and_then(|d| self.datatypes.get(d))
+        {
+            Some(DatatypeKind::Date) => FieldType::Date,
+            Some(DatatypeKind::Enumeration(values)) => {
+                let values = values.values().map(|v| snake_case(v)).collect();
+                let enumeration = FieldType::Enum { values };
+                if definition.multi_valued {
+                    FieldType::List(Box::new(enumeration))
+                } else {
+                    enumeration
+                }
+            }
+            Some(DatatypeKind::Text) | None => FieldType::TEXT,
+        };
+        FieldDef {
+            name: snake_case(&definition.long_name),
+            display_name: definition
+                .desc
+                .clone()
+                .unwrap_or_else(|| definition.long_name.clone()),
+            field_type,
+            required: false,
+            placeholder: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Derives an identifier prefix for an object type from the identifier
+    /// of its first object (`SR-001` gives `SR`), or from the initials of its
+    /// name.
+    fn derive_prefix(&self, object_type: &SpecType) -> String {
+        self.objects
+            .iter()
+            .find(|o| o.object_type == object_type.identifier)
+            .map(|o| self.item_id(o))
+            .and_then(|id| {
+                let (prefix, seq) = id.rsplit_once('-')?;
+                seq.chars()
+                    .all(|c| c.is_ascii_digit())
+                    .then(|| prefix.to_string())
+            })
+            .unwrap_or_else(|| {
+                object_type
+                    .long_name
+                    .split_whitespace()
+                    .filter_map(|word| word.chars().next())
+                    .collect::<String>()
+                    .to_uppercase()
+            })
+    }
+}
+
+/// Reads an attribute definition of a SPEC-OBJECT-TYPE.
+fn read_attribute_definition(definition: &Element) -> Option<AttributeDefinition> {
+    let identifier = definition.attr("IDENTIFIER")?;
+    Some(AttributeDefinition {
+        identifier: identifier.to_string(),
+        long_name: definition
+            .attr("LONG-NAME")
+            .unwrap_or(identifier)
+            .to_string(),
+        desc: definition.attr("DESC").map(str::to_string),
+        datatype: definition.reference("TYPE"),
+        multi_valued: definition.attr("MULTI-VALUED") == Some("true"),
+    })
+}
+
+/// Reads an attribute value of a SPEC-OBJECT, keyed by its definition.
+fn read_value(value: &Element) -> Option<(String, Value)> {
+    let definition = value.reference("DEFINITION")?;
+    let parsed = match value.name.as_str() {
+        "ATTRIBUTE-VALUE-ENUMERATION" => Value::Enumeration(
+            value
+                .entries("VALUES")
+                .map(|r| r.text().trim().to_string())
+                .collect(),
+        ),
+        "ATTRIBUTE-VALUE-XHTML" => Value::Text(value.child("THE-VALUE")?.text_content()),
+        _ => Value::Text(value.attr("THE-VALUE")?.to_string()),
+    };
+    Some((definition, parsed))
+}
+
+/// Returns true for the standard attributes mapped to the item id, name and
+/// description rather than to a field.
+fn is_standard_attribute(long_name: &str) -> bool {
+    long_name == ATTR_FOREIGN_ID
+        || ATTR_NAMES.contains(&long_name)
+        || ATTR_DESCRIPTIONS.contains(&long_name)
+}
+
+/// Returns the text of the first standard attribute of `names` set on the
+/// object.
+fn standard_value(object_type: &SpecType, object: &SpecObject, names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        let definition = object_type
+            .attributes
+            .iter()
+            .find(|d| d.long_name == *name)?;
+        match object.values.get(&definition.identifier)? {
+            Value::Text(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+            _ => None,
+        }
+    })
+}
+
+/// Converts a display label to a snake_case schema id.
+fn snake_case(label: &str) -> String {
+    let mut out = String::new();
+    let mut previous: Option<char> = None;
+    for c in label.chars() {
+        if c.is_alphanumeric() {
+            if c.is_uppercase() && previous.is_some_and(char::is_lowercase) {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else if !out.is_empty() && !out.ends_with('_') {
+            out.push('_');
+        }
+        previous = Some(c);
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+/// Returns true when a schema id or label designates the ReqIF name.
+fn names_match(id: &str, display_name: &str, reqif_name: &str) -> bool {
+    id == reqif_name
+        || id == snake_case(reqif_name)
+        || display_name.eq_ignore_ascii_case(reqif_name.trim())
+}
+
+// ==================== Import ====================
+
+/// An item being assembled from a SPEC-OBJECT.
+struct Draft {
+    item_type: ItemType,
+    id: ItemId,
+    name: String,
+    description: Option<String>,
+    attributes: ItemAttributes,
+    relationships: Vec<Relationship>,
+}
+
+/// Maps the objects and relations of a document onto the active schema.
+struct Importer<'a> {
+    document: &'a Document,
+    repository: &'a Path,
+    drafts: IndexMap<String, Draft>,
+    items: Vec<Item>,
+    warnings: Vec<String>,
+    /// Definitions already reported as unmapped, to warn only once.
+    reported: HashSet<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn new(document: &'a Document, repository: &'a Path) -> Self {
+        Self {
+            document,
+            repository,
+            drafts: IndexMap::new(),
+            items: Vec::new(),
+            warnings: Vec::new(),
+            reported: HashSet::new(),
+        }
+    }
+
+    fn run(&mut self) {
+        for object in &self.document.objects {
+            if let Some(draft) = self.draft(object) {
+                self.drafts.insert(object.identifier.clone(), draft);
+            }
+        }
+        for relation in &self.document.relations {
+            self.link(relation);
+        }
+        for (_, draft) in std::mem::take(&mut self.drafts) {
+            self.build(draft);
+        }
+    }
+
+    /// Reports a problem once per key.
+    fn warn_once(&mut self, key: String, message: String) {
+        if self.reported.insert(key) {
+            self.warnings.push(message);
+        }
+    }
+
+    /// Starts an item from an object whose type maps to the active schema.
+    fn draft(&mut self, object: &SpecObject) -> Option<Draft> {
+        let document = self.document;
+        let Some(object_type) = document.object_type(&object.object_type) else {
+            self.warnings.push(format!(
+                "SPEC-OBJECT {}: unknown SPEC-OBJECT-TYPE `{}`",
+                object.identifier, object.object_type
+            ));
+            return None;
+        };
+        let Some(item_type) = resolve_item_type(object_type) else {
+            self.warn_once(
+                format!("type:{}", object_type.identifier),
+                format!(
+                    "SPEC-OBJECT-TYPE `{}` matches no item type of the active schema; its objects were skipped",
+                    object_type.long_name
+                ),
+            );
+            return None;
+        };
+
+        let raw_id = document.item_id(object);
+        let id = match ItemId::new(ItemId::new_unchecked(&raw_id).local()) {
+            Ok(id) => id,
+            Err(e) => {
+                self.warnings
+                    .push(format!("SPEC-OBJECT {}: {e}", object.identifier));
+                return None;
+            }
+        };
+        let name = standard_value(object_type, object, &ATTR_NAMES)
+            .or_else(|| object.long_name.clone())
+            .unwrap_or_else(|| id.as_str().to_string());
+        let description =
+            standard_value(object_type, object, &ATTR_DESCRIPTIONS).or_else(|| object.desc.clone());
+
+        let mut attributes = ItemAttributes::new();
+        for (definition_id, value) in &object.values {
+            let Some(definition) = object_type
+                .attributes
+                .iter()
+                .find(|d| d.identifier == *definition_id)
+            else {
+                continue;
+            };
+            if is_standard_attribute(&definition.long_name) {
+                continue;
+            }
+            let Some(field) = item_type
+                .declared_fields()
+                .iter()
+                .find(|f| names_match(&f.name, &f.display_name, &definition.long_name))
+            else {
+                self.warn_once(
+                    format!("field:{}:{}", object_type.identifier, definition.identifier),
+                    format!(
+                        "Attribute `{}` of `{}` matches no field of {}; its values were skipped",
+                        definition.long_name,
+                        object_type.long_name,
+                        item_type.display_name()
+                    ),
+                );
+                continue;
+            };
+            match self.field_value(definition, field, value) {
+                Ok(Some(value)) => {
+                    attributes.insert(field.name.clone(), value);
+                }
+                Ok(None) => {}
+                Err(reason) => self.warnings.push(format!("{id}: {reason}")),
+            }
+        }
+
+        Some(Draft {
+            item_type,
+            id,
+            name,
+            description,
+            attributes,
+            relationships: Vec::new(),
+        })
+    }
+
+    /// Converts an attribute value to the type of the declared field.
+    fn field_value(
+        &self,
+        definition: &AttributeDefinition,
+        field: &FieldDef,
+        value: &Value,
+    ) -> Result<Option<FieldValue>, String> {
+        let texts: Vec<String> = match value {
+            Value::Text(text) => text
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect(),
+            Value::Enumeration(ids) => ids
+                .iter()
+                .map(|id| self.document.enum_value_name(definition, id).to_string())
+                .collect(),
+        };
+        if texts.is_empty() {
+            return Ok(None);
+        }
+
+        match &field.field_type {
+            FieldType::List(inner) => texts
+                .iter()
+                .map(|text| scalar_value(inner, text, field))
+                .collect::<Result<Vec<_>, _>>()
+                .map(|values| Some(FieldValue::List(values))),
+            scalar => scalar_value(scalar, &texts.join("\n"), field).map(Some),
+        }
+    }
+
+    /// Records a relation on the item that declares it: the source for a
+    /// primary relation, the target (through the inverse) otherwise.
+    fn link(&mut self, relation: &SpecRelation) {
+        let Some(relation_type) = self.document.relation_type(&relation.relation_type) else {
+            return;
+        };
+        let Some(relationship_type) = resolve_relation(relation_type) else {
+            self.warn_once(
+                format!("relation:{}", relation_type.identifier),
+                format!(
+                    "SPEC-RELATION-TYPE `{}` matches no relation of the active schema; its relations were skipped",
+                    relation_type.long_name
+                ),
+            );
+            return;
+        };
+
+        let (from, to, relationship_type) = if relationship_type.is_primary() {
+            (&relation.source, &relation.target, relationship_type)
+        } else {
+            (
+                &relation.target,
+                &relation.source,
+                relationship_type.inverse(),
+            )
+        };
+        let Some(target_id) = self.drafts.get(to.as_str()).map(|d| d.id.clone()) else {
+            return;
+        };
+        if let Some(draft) = self.drafts.get_mut(from.as_str()) {
+            draft
+                .relationships
+                .push(Relationship::new(target_id, relationship_type));
+        }
+    }
+
+    /// Builds the item of a draft, filling required fields left empty with
+    /// their schema placeholder.
+    fn build(&mut self, draft: Draft) {
+        let mut attributes = draft.attributes;
+        for field in draft.item_type.declared_fields() {
+            if field.required
+                && attributes.get(&field.name).is_none()
+                && let Some(placeholder) = &field.placeholder
+            {
+                attributes.insert(field.name.clone(), FieldValue::text(placeholder));
+            }
+        }
+
+        let source = SourceLocation::new(self.repository, format!("{}.md", draft.id));
+        let mut builder = ItemBuilder::new()
+            .id(draft.id.clone())
+            .item_type(draft.item_type)
+            .name(draft.name)
+            .source(source)
+            .relationships(draft.relationships)
+            .attributes(attributes);
+        if let Some(description) = draft.description {
+            builder = builder.description(description);
+        }
+
+        match builder.build() {
+            Ok(item) => self.items.push(item),
+            Err(e) => self.warnings.push(format!("{}: {e}", draft.id)),
+        }
+    }
+}
+
+/// Converts one text value to a scalar field type.
+fn scalar_value(
+    field_type: &FieldType,
+    text: &str,
+    field: &FieldDef,
+) -> Result<FieldValue, String> {
+    match field_type {
+        FieldType::Enum { values } => values
+            .iter()
+            .find(|v| names_match(v, v, text))
+            .map(|v| FieldValue::Enum(v.clone()))
+            .ok_or_else(|| {
+                format!(
+                    "value `{text}` of `{}` is not one of: {}",
+                    field.name,
+                    values.join(", ")
+                )
+            }),
+        FieldType::ItemRef => Ok(FieldValue::ItemRef(ItemId::new_unchecked(text))),
+        // ReqIF dates are full timestamps; the schema keeps the date part.
+        FieldType::Date => Ok(FieldValue::Date(
+            text.get(..10)
+                .filter(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").is_ok())
+                .unwrap_or(text)
+                .to_string(),
+        )),
+        FieldType::List(_) => Ok(FieldValue::text(text)),
+        // Typed values travel as strings.
+        other => other
+            .parse_value(text)
+            .map_err(|e| format!("value of `{}`: {e}", field.name)),
+    }
+}
+
+/// Returns the item type of the active schema matching an object type.
+fn resolve_item_type(object_type: &SpecType) -> Option<ItemType> {
+    ItemType::from_id(&object_type.identifier).or_else(|| {
+        ItemType::all()
+            .into_iter()
+            .find(|t| names_match(t.as_str(), t.display_name(), &object_type.long_name))
+    })
+}
+
+/// Returns the relation of the active schema matching a relation type.
+fn resolve_relation(relation_type: &SpecType) -> Option<RelationshipType> {
+    RelationshipType::from_id(&relation_type.identifier).or_else(|| {
+        RelationshipType::all()
+            .into_iter()
+            .find(|r| names_match(r.as_str(), r.display_name(), &relation_type.long_name))
+    })
+}
+
+// ==================== Export ====================
+
+/// Writes a knowledge graph as a ReqIF document.
+struct Exporter<'a> {
+    graph: &'a KnowledgeGraph,
+    timestamp: String,
+}
+
+impl<'a> Exporter<'a> {
+    fn new(graph: &'a KnowledgeGraph) -> Self {
+        Self {
+            graph,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+
+    fn write(&self, title: &str) -> Result<String, SaraError> {
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+        writer.write_event(Event::Decl(quick_xml::events::BytesDecl::new(
+            "1.0",
+            Some("UTF-8"),
+            None,
+        )))?;
+        writer
+            .create_element("REQ-IF")
+            .with_attribute(("xmlns", REQIF_NAMESPACE))
+            .with_attribute(("xmlns:xhtml", XHTML_NAMESPACE))
+            .write_inner_content(|w| {
+                self.write_header(w, title)?;
+                w.create_element("CORE-CONTENT").write_inner_content(|w| {
+                    w.create_element("REQ-IF-CONTENT")
+                        .write_inner_content(|w| self.write_content(w, title))?;
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+
+        let mut xml = String::from_utf8(writer.into_inner())
+            .map_err(|e| invalid(format!("generated invalid UTF-8: {e}")))?;
+        xml.push('\n');
+        Ok(xml)
+    }
+
+    fn write_header(&self, w: &mut Writer<Vec<u8>>, title: &str) -> std::io::Result<()> {
+        w.create_element("THE-HEADER").write_inner_content(|w| {
+            w.create_element("REQ-IF-HEADER")
+                .with_attribute(("IDENTIFIER", "sara-header"))
+                .write_inner_content(|w| {
+                    text_element(w, "CREATION-TIME", &self.timestamp)?;
+                    text_element(w, "REQ-IF-TOOL-ID", "sara")?;
+                    text_element(w, "REQ-IF-VERSION", "1.0")?;
+                    text_element(
+                        w,
+                        "SOURCE-TOOL-ID",
+                        &format!("sara {}", env!("CARGO_PKG_VERSION")),
+                    )?;
+                    text_element(w, "TITLE", title)
+                })?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn write_content(&self, w: &mut Writer<Vec<u8>>, title: &str) -> std::io::Result<()> {
+        let types = ItemType::all();
+        let relations: Vec<RelationshipType> = RelationshipType::all()
+            .into_iter()
+            .filter(RelationshipType::is_primary)
+            .collect();
+        let mut items: Vec<&Item> = self.graph.items().collect();
+        items.sort_by_key(|item| {
+            (
+                types.iter().position(|t| *t == item.item_type),
+                item.id.as_str(),
+            )
+        });
+
+        w.create_element("DATATYPES").write_inner_content(|w| {
+            self.identifiable(w, "DATATYPE-DEFINITION-STRING", DT_STRING, "String")
+                .with_attribute(("MAX-LENGTH", STRING_MAX_LENGTH))
+                .write_empty()?;
+            self.identifiable(w, "DATATYPE-DEFINITION-DATE", DT_DATE, "Date")
+                .write_empty()?;
+            for item_type in &types {
+                for field in item_type.declared_fields() {
+                    if let Some(values) = enum_values(&field.field_type) {
+                        self.write_enumeration(w, *item_type, field, values)?;
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        w.create_element("SPEC-TYPES").write_inner_content(|w| {
+            for item_type in &types {
+                self.write_object_type(w, *item_type)?;
+            }
+            for relation in &relations {
+                self.identifiable(
+                    w,
+                    "SPEC-RELATION-TYPE",
+                    &xml_id(relation.as_str()),
+                    relation.display_name(),
+                )
+                .write_empty()?;
+            }
+            self.identifiable(w, "SPECIFICATION-TYPE", SPECIFICATION_TYPE, "Specification")
+                .write_empty()?;
+            Ok(())
+        })?;
+
+        w.create_element("SPEC-OBJECTS").write_inner_content(|w| {
+            for item in &items {
+                self.write_object(w, item)?;
+            }
+            Ok(())
+        })?;
+
+        w.create_element("SPEC-RELATIONS")
+            .write_inner_content(|w| {
+                for item in &items {
+                    for relationship in item
+                        .relationships
+                        .iter()
+                        .filter(|r| r.relationship_type.is_primary() && self.graph.contains(&r.to))
+                    {
+                        let relation = relationship.relationship_type.as_str();
+                        let identifier =
+                            xml_id(&format!("{}.{relation}.{}", item.id, relationship.to));
+                        w.create_element("SPEC-RELATION")
+                            .with_attribute(("IDENTIFIER", identifier.as_str()))
+                            .with_attribute(("LAST-CHANGE", self.timestamp.as_str()))
+                            .write_inner_content(|w| {
+                                reference(w, "TYPE", "SPEC-RELATION-TYPE-REF", &xml_id(relation))?;
+                                reference(
+                                    w,
+                                    "SOURCE",
+                                    "SPEC-OBJECT-REF",
+                                    &xml_id(item.id.as_str()),
+                                )?;
+                                reference(
+                                    w,
+                                    "TARGET",
+                                    "SPEC-OBJECT-REF",
+                                    &xml_id(relationship.to.as_str()),
+                                )
+                            })?;
+                    }
+                }
+                Ok(())
+            })?;
+
+        w.create_element("SPECIFICATIONS")
+            .write_inner_content(|w| {
+                self.identifiable(w, "SPECIFICATION", "sara-specification", title)
+                    .write_inner_content(|w| {
+                        reference(w, "TYPE", "SPECIFICATION-TYPE-REF", SPECIFICATION_TYPE)?;
+                        w.create_element("CHILDREN").write_inner_content(|w| {
+                            for item in &items {
+                                let object = xml_id(item.id.as_str());
+                                w.create_element("SPEC-HIERARCHY")
+                                    .with_attribute(("IDENTIFIER", format!("H.{object}").as_str()))
+                                    .with_attribute(("LAST-CHANGE", self.timestamp.as_str()))
+                                    .write_inner_content(|w| {
+                                        reference(w, "OBJECT", "SPEC-OBJECT-REF", &object)
+                                    })?;
+                            }
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                Ok(())
+            })?;
+
+        Ok(())
+    }
+
+    /// Starts an identifiable element with its identifier, long name and
+    /// last change timestamp.
+    fn identifiable<'w>(
+        &self,
+        w: &'w mut Writer<Vec<u8>>,
+        name: &'static str,
+        identifier: &str,
+        long_name: &str,
+    ) -> quick_xml::writer::ElementWriter<'w, Vec<u8>> {
+        w.create_element(name)
+            .with_attribute(("IDENTIFIER", identifier))
+            .with_attribute(("LAST-CHANGE", self.timestamp.as_str()))
+            .with_attribute(("LONG-NAME", long_name))
+    }
+
+    fn write_enumeration(
+        &self,
+        w: &mut Writer<Vec<u8>>,
+        item_type: ItemType,
+        field: &FieldDef,
+        values: &[String],
+    ) -> std::io::Result<()> {
+        let datatype = enum_datatype_id(item_type, field);
+        self.identifiable(
+            w,
+            "DATATYPE-DEFINITION-ENUMERATION",
+            &datatype,
+            &field.display_name,
+        )
+        .write_inner_content(|w| {
+            w.create_element("SPECIFIED-VALUES")
+                .write_inner_content(|w| {
+                    for (key, value) in values.iter().enumerate() {
+                        self.identifiable(
+                            w,
+                            "ENUM-VALUE",
+                            &xml_id(&format!("{datatype}.{value}")),
+                            value,
+                        )
+                        .write_inner_content(|w| {
+                            w.create_element("PROPERTIES").write_inner_content(|w| {
+                                w.create_element("EMBEDDED-VALUE")
+                                    .with_attribute(("KEY", key.to_string().as_str()))
+                                    .with_attribute(("OTHER-CONTENT", ""))
+                                    .write_empty()?;
+                                Ok(())
+                            })?;
+                            Ok(())
+                        })?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn write_object_type(
+        &self,
+        w: &mut Writer<Vec<u8>>,
+        item_type: ItemType,
+    ) -> std::io::Result<()> {
+        let type_id = xml_id(item_type.as_str());
+        self.identifiable(w, "SPEC-OBJECT-TYPE", &type_id, item_type.display_name())
+            .write_inner_content(|w| {
+                w.create_element("SPEC-ATTRIBUTES")
+                    .write_inner_content(|w| {
+                        for standard in [ATTR_FOREIGN_ID, ATTR_NAMES[0], ATTR_DESCRIPTIONS[0]] {
+                            self.write_attribute_definition(
+                                w,
+                                &format!("{type_id}.{standard}"),
+                                standard,
+                                None,
+                                &AttributeKind::String,
+                            )?;
+                        }
+                        for field in item_type.declared_fields() {
+                            let kind = attribute_kind(item_type, field);
+                            self.write_attribute_definition(
+                                w,
+                                &attribute_id(item_type, &field.name),
+                                &field.name,
+                                Some(&field.display_name),
+                                &kind,
+                            )?;
+                        }
+                        Ok(())
+                    })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    fn write_attribute_definition(
+        &self,
+        w: &mut Writer<Vec<u8>>,
+        identifier: &str,
+        long_name: &str,
+        desc: Option<&str>,
+        kind: &AttributeKind,
+    ) -> std::io::Result<()> {
+        let mut element = w
+            .create_element(format!("ATTRIBUTE-DEFINITION-{}", kind.suffix()))
+            .with_attribute(("IDENTIFIER", identifier))
+            .with_attribute(("LAST-CHANGE", self.timestamp.as_str()))
+            .with_attribute(("LONG-NAME", long_name));
+        if let Some(desc) = desc {
+            element = element.with_attribute(("DESC", desc));
+        }
+        if let AttributeKind::Enumeration { multi_valued, .. } = kind {
+            element = element
+                .with_attribute(("MULTI-VALUED", if *multi_valued { "true" } else { "false" }));
+        }
+        element.write_inner_content(|w| {
+            reference(
+                w,
+                "TYPE",
+                &format!("DATATYPE-DEFINITION-{}-REF", kind.suffix()),
+                kind.datatype(),
+            )
+        })?;
+        Ok(())
+    }
+
+    fn write_object(&self, w: &mut Writer<Vec<u8>>, item: &Item) -> std::io::Result<()> {
+        let type_id = xml_id(item.item_type.as_str());
+        self.identifiable(w, "SPEC-OBJECT", &xml_id(item.id.as_str()), &item.name)
+            .write_inner_content(|w| {
+                reference(w, "TYPE", "SPEC-OBJECT-TYPE-REF", &type_id)?;
+                w.create_element("VALUES").write_inner_content(|w| {
+                    let standard = [
+                        (ATTR_FOREIGN_ID, Some(item.id.as_str())),
+                        (ATTR_NAMES[0], Some(item.name.as_str())),
+                        (ATTR_DESCRIPTIONS[0], item.description.as_deref()),
+                    ];
+                    for (name, value) in standard {
+                        if let Some(value) = value {
+                            string_value(w, &format!("{type_id}.{name}"), value)?;
+                        }
+                    }
+                    for (name, value) in item.attributes.iter() {
+                        let Some(field) = item.item_type.declared_field(name) else {
+                            continue;
+                        };
+                        let definition = attribute_id(item.item_type, name);
+                        match attribute_kind(item.item_type, field) {
+                            AttributeKind::Enumeration { datatype, .. } => {
+                                enumeration_value(w, &definition, &datatype, value)?;
+                            }
+                            AttributeKind::Date => {
+                                if let Some(date) = value
+                                    .as_date()
+                                    .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                                {
+                                    w.create_element("ATTRIBUTE-VALUE-DATE")
+                                        .with_attribute((
+                                            "THE-VALUE",
+                                            format!("{date}T00:00:00Z").as_str(),
+                                        ))
+                                        .write_inner_content(|w| {
+                                            reference(
+                                                w,
+                                                "DEFINITION",
+                                                "ATTRIBUTE-DEFINITION-DATE-REF",
+                                                &definition,
+                                            )
+                                        })?;
+                                }
+                            }
+                            AttributeKind::String => {
+                                string_value(w, &definition, &text_of(value))?;
+                            }
+                        }
+                    }
+                    Ok(())
+                })?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+/// Identifier of the shared string datatype.
+const DT_STRING: &str = "sara.datatype.string";
+/// Identifier of the shared date datatype.
+const DT_DATE: &str = "sara.datatype.date";
+/// Identifier of the specification type.
+const SPECIFICATION_TYPE: &str = "sara.specification-type";
+
+/// How a field is exported as an attribute.
+enum AttributeKind {
+    String,
+    Date,
+    Enumeration {
+        datatype: String,
+        multi_valued: bool,
+    },
+}
+
+impl AttributeKind {
+    /// Returns the suffix of the ReqIF element names for this kind.
+    fn suffix(&self) -> &'static str {
+        match self {
+            Self::String => "STRING",
+            Self::Date => "DATE",
+            Self::Enumeration { .. } => "ENUMERATION",
+        }
+    }
+
+    /// Returns the identifier of the datatype referenced by the definition.
+    fn datatype(&self) -> &str {
+        match self {
+            Self::String => DT_STRING,
+            Self::Date => DT_DATE,
+            Self::Enumeration { datatype, .. } => datatype,
+        }
+    }
+}
+
+/// Returns how a field of a type is exported.
+fn attribute_kind(item_type: ItemType, field: &FieldDef) -> AttributeKind {
+    match &field.field_type {
+        FieldType::Date => AttributeKind::Date,
+        FieldType::Enum { .. } => AttributeKind::Enumeration {
+            datatype: enum_datatype_id(item_type, field),
+            multi_valued: false,
+        },
+        FieldType::List(inner) if matches!(**inner, FieldType::Enum { .. }) => {
+            AttributeKind::Enumeration {
+                datatype: enum_datatype_id(item_type, field),
+                multi_valued: true,
+            }
+        }
+        _ => AttributeKind::String,
+    }
+}
+
+/// Returns the values of an enumeration field, or of a list of them.
+fn enum_values(field_type: &FieldType) -> Option<&[String]> {
+    match field_type {
+        FieldType::Enum { values } => Some(values),
+        FieldType::List(inner) => enum_values(inner),
+        _ => None,
+    }
+}
+
+fn enum_datatype_id(item_type: ItemType, field: &FieldDef) -> String {
+    xml_id(&format!("{}.{}.values", item_type.as_str(), field.name))
+}
+
+fn attribute_id(item_type: ItemType, field: &str) -> String {
+    xml_id(&format!("{}.{field}", item_type.as_str()))
+}
+
+/// Renders a field value as plain text, one list entry per line.
+fn text_of(value: &FieldValue) -> String {
+    match value {
+        FieldValue::List(values) => values.iter().map(text_of).collect::<Vec<_>>().join("\n"),
+        other => other.to_string(),
+    }
+}
+
+/// Converts a raw identifier to an XML ID (an NCName): namespace separators
+/// and other invalid characters become `.`, and a leading character that
+/// cannot start a name is prefixed with `_`.
+fn xml_id(raw: &str) -> String {
+    let mut id: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    if !id.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        id.insert(0, '_');
+    }
+    id
+}
+
+fn text_element(w: &mut Writer<Vec<u8>>, name: &str, text: &str) -> std::io::Result<()> {
+    w.create_element(name)
+        .write_text_content(BytesText::new(text))?;
+    Ok(())
+}
+
+/// Writes `<container><kind>id</kind></container>`.
+fn reference(
+    w: &mut Writer<Vec<u8>>,
+    container: &str,
+    kind: &str,
+    id: &str,
+) -> std::io::Result<()> {
+    w.create_element(container).write_inner_content(|w| {
+        text_element(w, kind, id)?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+fn string_value(w: &mut Writer<Vec<u8>>, definition: &str, value: &str) -> std::io::Result<()> {
+    w.create_element("ATTRIBUTE-VALUE-STRING")
+        .with_attribute(("THE-VALUE", value))
+        .write_inner_content(|w| {
+            reference(
+                w,
+                "DEFINITION",
+                "ATTRIBUTE-DEFINITION-STRING-REF",
+                definition,
+            )
+        })?;
+    Ok(())
+}
+
+fn enumeration_value(
+    w: &mut Writer<Vec<u8>>,
+    definition: &str,
+    datatype: &str,
+    value: &FieldValue,
+) -> std::io::Result<()> {
+    let selected: Vec<&str> = match value {
+        FieldValue::List(values) => values
+            .iter()
+            .filter_map(|v| v.as_enum())
+            .map(String::as_str)
+            .collect(),
+        other => other.as_enum().map(String::as_str).into_iter().collect(),
+    };
+    w.create_element("ATTRIBUTE-VALUE-ENUMERATION")
+        .write_inner_content(|w| {
+            reference(
+                w,
+                "DEFINITION",
+                "ATTRIBUTE-DEFINITION-ENUMERATION-REF",
+                definition,
+            )?;
+            w.create_element("VALUES").write_inner_content(|w| {
+                for value in &selected {
+                    text_element(w, "ENUM-VALUE-REF", &xml_id(&format!("{datatype}.{value}")))?;
+                }
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    use crate::graph::KnowledgeGraphBuilder;
+    use crate::schema::builtin;
+    use crate::test_utils::{
+        create_test_adr, create_test_item, create_test_item_with_relationships,
+    };
+
+    /// A document as produced by a third-party tool: generated identifiers,
+    /// XHTML text and names matched by display name.
+    const THIRD_PARTY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<REQ-IF xmlns="http://www.omg.org/spec/ReqIF/20110401/reqif.xsd" xmlns:xhtml="http://www.w3.org/1999/xhtml">
+  <THE-HEADER><REQ-IF-HEADER IDENTIFIER="h"><TITLE>Customer</TITLE></REQ-IF-HEADER></THE-HEADER>
+  <CORE-CONTENT>
+    <REQ-IF-CONTENT>
+      <DATATYPES>
+        <DATATYPE-DEFINITION-STRING IDENTIFIER="_dt1" LONG-NAME="T_String" MAX-LENGTH="255"/>
+        <DATATYPE-DEFINITION-XHTML IDENTIFIER="_dt2" LONG-NAME="T_Xhtml"/>
+      </DATATYPES>
+      <SPEC-TYPES>
+        <SPEC-OBJECT-TYPE IDENTIFIER="_t1" LONG-NAME="System Requirement">
+          <SPEC-ATTRIBUTES>
+            <ATTRIBUTE-DEFINITION-STRING IDENTIFIER="_a1" LONG-NAME="ReqIF.ForeignID">
+              <TYPE><DATATYPE-DEFINITION-STRING-REF>_dt1</DATATYPE-DEFINITION-STRING-REF></TYPE>
+            </ATTRIBUTE-DEFINITION-STRING>
+            <ATTRIBUTE-DEFINITION-STRING IDENTIFIER="_a2" LONG-NAME="ReqIF.Name">
+              <TYPE><DATATYPE-DEFINITION-STRING-REF>_dt1</DATATYPE-DEFINITION-STRING-REF></TYPE>
+            </ATTRIBUTE-DEFINITION-STRING>
+            <ATTRIBUTE-DEFINITION-XHTML IDENTIFIER="_a3" LONG-NAME="Specification">
+              <TYPE><DATATYPE-DEFINITION-XHTML-REF>_dt2</DATATYPE-DEFINITION-XHTML-REF></TYPE>
+            </ATTRIBUTE-DEFINITION-XHTML>
+          </SPEC-ATTRIBUTES>
+        </SPEC-OBJECT-TYPE>
+        <SPEC-OBJECT-TYPE IDENTIFIER="_t2" LONG-NAME="Use Case">
+          <SPEC-ATTRIBUTES>
+            <ATTRIBUTE-DEFINITION-STRING IDENTIFIER="_a4" LONG-NAME="ReqIF.ForeignID">
+              <TYPE><DATATYPE-DEFINITION-STRING-REF>_dt1</DATATYPE-DEFINITION-STRING-REF></TYPE>
+            </ATTRIBUTE-DEFINITION-STRING>
+          </SPEC-ATTRIBUTES>
+        </SPEC-OBJECT-TYPE>
+        <SPEC-OBJECT-TYPE IDENTIFIER="_t3" LONG-NAME="Test Protocol"/>
+        <SPEC-RELATION-TYPE IDENTIFIER="_r1" LONG-NAME="Derives from"/>
+      </SPEC-TYPES>
+      <SPEC-OBJECTS>
+        <SPEC-OBJECT IDENTIFIER="_o1">
+          <TYPE><SPEC-OBJECT-TYPE-REF>_t1</SPEC-OBJECT-TYPE-REF></TYPE>
+          <VALUES>
+            <ATTRIBUTE-VALUE-STRING THE-VALUE="SYSREQ-010">
+              <DEFINITION><ATTRIBUTE-DEFINITION-STRING-REF>_a1</ATTRIBUTE-DEFINITION-STRING-REF></DEFINITION>
+            </ATTRIBUTE-VALUE-STRING>
+            <ATTRIBUTE-VALUE-STRING THE-VALUE="Braking &amp; steering">
+              <DEFINITION><ATTRIBUTE-DEFINITION-STRING-REF>_a2</ATTRIBUTE-DEFINITION-STRING-REF></DEFINITION>
+            </ATTRIBUTE-VALUE-STRING>
+            <ATTRIBUTE-VALUE-XHTML>
+              <DEFINITION><ATTRIBUTE-DEFINITION-XHTML-REF>_a3</ATTRIBUTE-DEFINITION-XHTML-REF></DEFINITION>
+              <THE-VALUE><xhtml:div><xhtml:p>The system <xhtml:b>SHALL</xhtml:b> brake &amp; steer.</xhtml:p></xhtml:div></THE-VALUE>
+            </ATTRIBUTE-VALUE-XHTML>
+          </VALUES>
+        </SPEC-OBJECT>
+        <SPEC-OBJECT IDENTIFIER="_o2" LONG-NAME="Drive safely">
+          <TYPE><SPEC-OBJECT-TYPE-REF>_t2</SPEC-OBJECT-TYPE-REF></TYPE>
+          <VALUES>
+            <ATTRIBUTE-VALUE-STRING THE-VALUE="UC-004">
+              <DEFINITION><ATTRIBUTE-DEFINITION-STRING-REF>_a4</ATTRIBUTE-DEFINITION-STRING-REF></DEFINITION>
+            </ATTRIBUTE-VALUE-STRING>
+          </VALUES>
+        </SPEC-OBJECT>
+        <SPEC-OBJECT IDENTIFIER="_o3" LONG-NAME="Protocol">
+          <TYPE><SPEC-OBJECT-TYPE-REF>_t3</SPEC-OBJECT-TYPE-REF></TYPE>
+        </SPEC-OBJECT>
+      </SPEC-OBJECTS>
+      <SPEC-RELATIONS>
+        <SPEC-RELATION IDENTIFIER="_l1">
+          <TYPE><SPEC-RELATION-TYPE-REF>_r1</SPEC-RELATION-TYPE-REF></TYPE>
+          <SOURCE><SPEC-OBJECT-REF>_o1</SPEC-OBJECT-REF></SOURCE>
+          <TARGET><SPEC-OBJECT-REF>_o2</SPEC-OBJECT-REF></TARGET>
+        </SPEC-RELATION>
+      </SPEC-RELATIONS>
+    </REQ-IF-CONTENT>
+  </CORE-CONTENT>
+</REQ-IF>
+"#;
+
+    fn import_third_party() -> ReqIfImport {
+        import(THIRD_PARTY, Path::new("/imported")).unwrap()
+    }
+
+    #[test]
+    fn test_import_maps_types_fields_and_relations() {
+        let import = import_third_party();
+
+        assert_eq!(import.items.len(), 2);
+        let requirement = &import.items[0];
+        assert_eq!(requirement.id.as_str(), "SYSREQ-010");
+        assert_eq!(requirement.item_type, builtin::SYSTEM_REQUIREMENT);
+        assert_eq!(requirement.name, "Braking & steering");
+        assert_eq!(
+            requirement.attributes.get("specification"),
+            Some(&FieldValue::text("The system SHALL brake & steer."))
+        );
+        assert_eq!(
+            requirement.source.full_path(),
+            PathBuf::from("/imported/SYSREQ-010.md")
+        );
+
+        let targets: Vec<_> = requirement
+            .relationship_ids(builtin::DERIVES_FROM)
+            .map(ItemId::as_str)
+            .collect();
+        assert_eq!(targets, ["UC-004"]);
+
+        let use_case = &import.items[1];
+        assert_eq!(use_case.item_type, builtin::USE_CASE);
+        assert_eq!(use_case.name, "Drive safely");
+    }
+
+    #[test]
+    fn test_import_reports_unmapped_types() {
+        let import = import_third_party();
+
+        assert_eq!(import.warnings.len(), 1);
+        assert!(import.warnings[0].contains("Test Protocol"));
+    }
+
+    #[test]
+    fn test_import_derives_the_document_schema() {
+        let schema = import_third_party().schema;
+
+        let requirement = schema.item_type("system_requirement").unwrap();
+        assert_eq!(requirement.prefix, "SYSREQ");
+        assert_eq!(requirement.parent_types, ["use_case"]);
+        assert_eq!(requirement.fields.len(), 1);
+        assert_eq!(requirement.fields[0].name, "specification");
+        assert_eq!(schema.item_type("test_protocol").unwrap().prefix, "TP");
+
+        let relation = schema.relation("derives_from").unwrap();
+        assert!(relation.primary);
+        assert_eq!(
+            schema.relation(&relation.inverse).unwrap().inverse,
+            "derives_from"
+        );
+        assert!(schema.validate(Path::new("<reqif>")).is_ok());
+    }
+
+    #[test]
+    fn test_import_rejects_malformed_documents() {
+        assert!(matches!(
+            import("<REQ-IF><CORE-CONTENT>", Path::new("/")),
+            Err(SaraError::InvalidInterchange { .. })
+        ));
+        assert!(matches!(
+            import("<SPEC/>", Path::new("/")),
+            Err(SaraError::InvalidInterchange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_export_round_trips_through_import() {
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(create_test_item("SOL-001", builtin::SOLUTION))
+            .add_item(create_test_item_with_relationships(
+                "UC-001",
+                builtin::USE_CASE,
+                vec![Relationship::new(
+                    ItemId::new_unchecked("SOL-001"),
+                    builtin::REFINES,
+                )],
+            ))
+            .add_item(create_test_adr("ADR-001", &["UC-001"], &[]))
+            .build()
+            .unwrap();
+
+        let xml = export(&graph, "Round trip").unwrap();
+        assert!(xml.contains("<REQ-IF xmlns=\"http://www.omg.org/spec/ReqIF/20110401/reqif.xsd\""));
+
+        let import = import(&xml, Path::new("/back")).unwrap();
+        assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+        assert_eq!(import.items.len(), 3);
+
+        let by_id: HashMap<&str, &Item> = import.items.iter().map(|i| (i.id.as_str(), i)).collect();
+        for original in graph.items() {
+            let back = by_id[original.id.as_str()];
+            assert_eq!(back.item_type, original.item_type);
+            assert_eq!(back.name, original.name);
+            assert_eq!(back.attributes.len(), original.attributes.len());
+            for (name, value) in original.attributes.iter() {
+                assert_eq!(back.attributes.get(name), Some(value), "{name}");
+            }
+        }
+        assert_eq!(
+            by_id["UC-001"]
+                .relationship_ids(builtin::REFINES)
+                .map(ItemId::as_str)
+                .collect::<Vec<_>>(),
+            ["SOL-001"]
+        );
+        assert_eq!(
+            by_id["ADR-001"]
+                .relationship_ids(builtin::JUSTIFIES)
+                .map(ItemId::as_str)
+                .collect::<Vec<_>>(),
+            ["UC-001"]
+        );
+    }
+
+    #[test]
+    fn test_xml_id() {
+        assert_eq!(xml_id("SOL-001"), "SOL-001");
+        assert_eq!(xml_id("platform:SOL-001"), "platform.SOL-001");
+        assert_eq!(xml_id("2024-001"), "_2024-001");
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("System Requirement"), "system_requirement");
+        assert_eq!(snake_case("Derives from"), "derives_from");
+        assert_eq!(snake_case("VerificationMethod"), "verification_method");
+    }
+}
@@ -7,6 +7,7 @@ pub mod config;
 pub mod error;
 pub mod generator;
 pub mod graph;
+pub mod interchange;
 pub mod model;
 pub mod parser;
 pub mod report;
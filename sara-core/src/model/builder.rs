@@ -4,10 +4,10 @@ use std::path::PathBuf;
 
 use super::field::FieldValue;
 use super::item::{FIELD_ID, FIELD_NAME, FIELD_TYPE, Item, ItemAttributes, ItemId, ItemType};
-use super::metadata::SourceLocation;
+use super::metadata::{AliasUse, SourceLocation};
 use super::relationship::Relationship;
 use crate::error::SaraError;
-use crate::schema;
+use crate::schema::{self, Schema};
 
 /// Builder for constructing `Item` instances from parsed frontmatter.
 ///
@@ -23,6 +23,8 @@ pub struct ItemBuilder {
     source: Option<SourceLocation>,
     relationships: Vec<Relationship>,
     attributes: ItemAttributes,
+    mentions: Vec<ItemId>,
+    aliases: Vec<AliasUse>,
 }
 
 impl ItemBuilder {
@@ -67,6 +69,18 @@ impl ItemBuilder {
         self
     }
 
+    /// Sets the items mentioned in the document body.
+    pub fn mentions(mut self, mentions: Vec<ItemId>) -> Self {
+        self.mentions = mentions;
+        self
+    }
+
+    /// Sets the schema aliases the document used.
+    pub fn aliases(mut self, aliases: Vec<AliasUse>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
     /// Sets the value of a declared field by name.
     pub fn attribute(mut self, name: impl Into<String>, value: FieldValue) -> Self {
         self.attributes.insert(name, value);
@@ -87,8 +101,9 @@ impl ItemBuilder {
         &self,
         item_type: ItemType,
         file: &str,
+        schema: &Schema,
     ) -> Result<(), SaraError> {
-        let Some(def) = schema::item_type_def(item_type.as_str()) else {
+        let Some(def) = schema.item_type(item_type.as_str()) else {
             return Ok(());
         };
 
@@ -109,13 +124,25 @@ impl ItemBuilder {
         Ok(())
     }
 
-    /// Builds the `Item`, returning an error if required fields are missing.
+    /// Builds the `Item` against the active schema, returning an error if
+    /// required fields are missing.
     ///
     /// # Errors
     ///
     /// Returns `SaraError::MissingField` if a required field (id, type,
     /// name, source, or type-specific attributes) is not set.
     pub fn build(self) -> Result<Item, SaraError> {
+        self.build_in(schema::active())
+    }
+
+    /// Builds the `Item`, checking the attributes `schema` requires for its
+    /// type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SaraError::MissingField` if a required field (id, type,
+    /// name, source, or type-specific attributes) is not set.
+    pub fn build_in(self, schema: &Schema) -> Result<Item, SaraError> {
         let id = self.id.clone().ok_or_else(|| SaraError::MissingField {
             field: FIELD_ID.to_string(),
             file: self
@@ -149,7 +176,7 @@ impl ItemBuilder {
         })?;
 
         let file_path = source.file_path.display().to_string();
-        self.validate_required_attributes(item_type, &file_path)?;
+        self.validate_required_attributes(item_type, &file_path, schema)?;
 
         Ok(Item {
             id,
@@ -159,6 +186,8 @@ impl ItemBuilder {
             source,
             relationships: self.relationships,
             attributes: self.attributes,
+            mentions: self.mentions,
+            aliases: self.aliases,
         })
     }
 }
@@ -175,6 +204,7 @@ mod tests {
             repository: PathBuf::from("/repo"),
             file_path: PathBuf::from("docs/SOL-001.md"),
             git_ref: None,
+            lines: None,
         };
 
         let item = ItemBuilder::new()
@@ -196,6 +226,7 @@ mod tests {
             repository: PathBuf::from("/repo"),
             file_path: PathBuf::from("docs/UC-001.md"),
             git_ref: None,
+            lines: None,
         };
 
         let item = ItemBuilder::new()
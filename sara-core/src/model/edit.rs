@@ -2,11 +2,12 @@
 //!
 //! Provides types for FR-054 through FR-066 (Edit Command).
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use indexmap::IndexMap;
 
-use super::{Item, RelationshipType};
+use super::{Item, ItemAttributes, ItemId, Relationship, RelationshipType};
 
 /// Summary of changes made during an edit operation.
 #[derive(Debug, Clone)]
@@ -67,10 +68,12 @@ impl FieldChange {
 /// Suitable for CLI input, interactive prompts, and frontmatter rebuilding.
 /// Entries keep their insertion order; [`Self::from_item`] inserts them in
 /// the declaration order of the active schema, so downstream output remains
-/// stable.
+/// stable. Attributes of the links read from an item follow their target
+/// through edits, so rebuilt metadata keeps them.
 #[derive(Debug, Default, Clone)]
 pub struct TraceabilityLinks {
     links: IndexMap<RelationshipType, Vec<String>>,
+    attributes: HashMap<(RelationshipType, String), ItemAttributes>,
 }
 
 impl TraceabilityLinks {
@@ -106,17 +109,53 @@ impl TraceabilityLinks {
         self.links.values().all(Vec::is_empty)
     }
 
+    /// Returns the attributes of the link to a target, if it carries any.
+    #[must_use]
+    pub fn link_attributes(&self, relation: RelationshipType, id: &str) -> Option<&ItemAttributes> {
+        self.attributes.get(&(relation, id.to_string()))
+    }
+
+    /// Converts the links to relationships, with their attributes.
+    #[must_use]
+    pub fn to_relationships(&self) -> Vec<Relationship> {
+        self.iter()
+            .flat_map(|(relation, ids)| {
+                ids.iter().map(move |id| {
+                    let rel = Relationship::new(ItemId::new_unchecked(id), relation);
+                    match self.link_attributes(relation, id) {
+                        Some(attributes) => rel.with_attributes(attributes.clone()),
+                        None => rel,
+                    }
+                })
+            })
+            .collect()
+    }
+
     /// Collects the item's targets for every relation its type declares.
+    ///
+    /// Targets in the item's own namespace are written unqualified, as in
+    /// its frontmatter.
     pub fn from_item(item: &Item) -> Self {
+        let namespace = item.id.namespace();
         let mut links = Self::new();
         for relation in item.item_type.declared_relations() {
             links.set(
                 relation,
                 item.relationship_ids(relation)
-                    .map(|id| id.as_str().to_string())
+                    .map(|id| id.relative_to(namespace).to_string())
                     .collect(),
             );
         }
+        for rel in item
+            .relationships
+            .iter()
+            .filter(|r| !r.attributes.is_empty())
+        {
+            let id = rel.to.relative_to(namespace).to_string();
+            links
+                .attributes
+                .insert((rel.relationship_type, id), rel.attributes.clone());
+        }
         links
     }
 }
@@ -190,4 +229,20 @@ mod tests {
         let links = TraceabilityLinks::from_item(&item);
         assert_eq!(links.get(builtin::REFINES), ["SOL-001"]);
     }
+
+    #[test]
+    fn test_traceability_links_from_item_strips_own_namespace() {
+        let mut item = create_test_item_with_relationships(
+            "UC-001",
+            builtin::USE_CASE,
+            vec![
+                Relationship::new(ItemId::qualified("product", "SOL-001"), builtin::REFINES),
+                Relationship::new(ItemId::qualified("platform", "SOL-001"), builtin::REFINES),
+            ],
+        );
+        item.id = ItemId::qualified("product", "UC-001");
+
+        let links = TraceabilityLinks::from_item(&item);
+        assert_eq!(links.get(builtin::REFINES), ["SOL-001", "platform:SOL-001"]);
+    }
 }
@@ -18,7 +18,11 @@ use super::item::ItemId;
 /// - [`FieldValue::ItemRef`]: a single reference to another item's id.
 /// - [`FieldValue::List`]: an ordered list of values (deciders, depends_on).
 /// - [`FieldValue::Date`]: an ISO-8601 date held as a string.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// - [`FieldValue::Number`]: a decimal number (latency, mass budget).
+/// - [`FieldValue::Integer`]: a whole number (priority, count).
+/// - [`FieldValue::Boolean`]: a flag (safety relevant).
+/// - [`FieldValue::Url`]: an absolute URL (external reference).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FieldValue {
     /// Free-form text.
     Text(String),
@@ -30,6 +34,14 @@ pub enum FieldValue {
     List(Vec<FieldValue>),
     /// An ISO-8601 date string.
     Date(String),
+    /// A decimal number.
+    Number(f64),
+    /// A whole number.
+    Integer(i64),
+    /// A boolean flag.
+    Boolean(bool),
+    /// An absolute URL.
+    Url(String),
 }
 
 impl FieldValue {
@@ -94,6 +106,47 @@ impl FieldValue {
             None
         }
     }
+
+    /// Returns the numeric value of a [`FieldValue::Number`] or a
+    /// [`FieldValue::Integer`].
+    #[must_use]
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            Self::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the inner integer if this is a [`FieldValue::Integer`].
+    #[must_use]
+    pub fn as_integer(&self) -> Option<i64> {
+        if let Self::Integer(i) = self {
+            Some(*i)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the inner flag if this is a [`FieldValue::Boolean`].
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        if let Self::Boolean(b) = self {
+            Some(*b)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the inner string if this is a [`FieldValue::Url`].
+    #[must_use]
+    pub fn as_url(&self) -> Option<&String> {
+        if let Self::Url(s) = self {
+            Some(s)
+        } else {
+            None
+        }
+    }
 }
 
 impl std::fmt::Display for FieldValue {
@@ -101,7 +154,10 @@ impl std::fmt::Display for FieldValue {
     /// scalars print their inner text, lists join their entries with `, `.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Text(s) | Self::Enum(s) | Self::Date(s) => write!(f, "{s}"),
+            Self::Text(s) | Self::Enum(s) | Self::Date(s) | Self::Url(s) => write!(f, "{s}"),
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Integer(i) => write!(f, "{i}"),
+            Self::Boolean(b) => write!(f, "{b}"),
             Self::ItemRef(id) => write!(f, "{id}"),
             Self::List(values) => {
                 let rendered: Vec<String> = values.iter().map(ToString::to_string).collect();
@@ -126,5 +182,7 @@ mod tests {
             Some(&"proposed".to_string())
         );
         assert!(FieldValue::Text("spec".to_string()).as_enum().is_none());
+        assert_eq!(FieldValue::Integer(3).as_number(), Some(3.0));
+        assert_eq!(FieldValue::Number(0.5).to_string(), "0.5");
     }
 }
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::error::SaraError;
 use crate::model::field::FieldValue;
 use crate::model::relationship::{Relationship, RelationshipType};
-use crate::schema::{self, FieldDef, IdFormat, ItemTypeDef, RelationDirection};
+use crate::schema::{self, FieldDef, IdFormat, ItemTypeDef, RelationDirection, Schema, SectionDef};
 
 /// Canonical name of the item identifier field.
 ///
@@ -26,12 +26,14 @@ pub const FIELD_DESCRIPTION: &str = "description";
 
 /// Identifies an item type by its schema id.
 ///
-/// Wraps the interned snake_case id of a type defined by the active schema
-/// (or the built-in default). All metadata — prefix, display name, declared
-/// fields, parent and relation links — is resolved against the schema, so
+/// Wraps the snake_case id of a type defined by a schema (see
+/// [`crate::schema::active`]). All metadata — prefix, display name, declared
+/// fields, parent and relation links — is resolved against a schema, so
 /// types introduced by a custom YAML schema behave exactly like the built-in
-/// ones (handles for those live in [`crate::schema::builtin`]). Types are
-/// obtained through [`ItemType::from_id`] or [`ItemType::all`].
+/// ones (handles for those live in [`crate::schema::builtin`]). Methods
+/// ending in `_in` resolve against the given schema, the others against the
+/// active one. Types are obtained through [`ItemType::from_id_in`] or
+/// [`ItemType::all_in`].
 ///
 /// Equality and hashing compare the id by content, so a handle built from a
 /// static id compares equal to the same type resolved from a schema.
@@ -41,10 +43,10 @@ pub struct ItemType(&'static str);
 impl ItemType {
     /// Creates a handle from a static schema id, without resolving it.
     ///
-    /// The id is not checked against the active schema: a handle whose id the
+    /// The id is not checked against any schema: a handle whose id the
     /// schema does not define resolves to no metadata. Reserved for the
     /// definition of well-known ids (see [`crate::schema::builtin`]); resolve
-    /// runtime ids through [`ItemType::from_id`].
+    /// runtime ids through [`ItemType::from_id_in`].
     pub(crate) const fn from_static(id: &'static str) -> Self {
         Self(id)
     }
@@ -59,22 +61,54 @@ impl ItemType {
             .collect()
     }
 
+    /// Returns all item types of `schema`, in hierarchy order.
+    #[must_use]
+    pub fn all_in(schema: &Schema) -> Vec<ItemType> {
+        schema
+            .item_types
+            .iter()
+            .map(|def| Self(schema::symbol(&def.id)))
+            .collect()
+    }
+
     /// Returns the item type definition in the active schema, if any.
     fn def(&self) -> Option<&'static ItemTypeDef> {
         schema::item_type_def(self.0)
     }
 
-    /// Returns the declaration of the given field, if the type declares it.
+    /// Returns the declaration of the given field, if the type declares it
+    /// under this name or as an alias.
     #[must_use]
     pub fn declared_field(&self, name: &str) -> Option<&'static FieldDef> {
-        self.def()
-            .and_then(|def| def.fields.iter().find(|f| f.name == name))
+        self.def().and_then(|def| def.field_by_name(name))
     }
 
     /// Returns the fields the active schema declares for this type.
     #[must_use]
     pub fn declared_fields(&self) -> &'static [FieldDef] {
-        self.def().map_or(&[], |def| def.fields.as_slice())
+        self.declared_fields_in(schema::active())
+    }
+
+    /// Returns the fields `schema` declares for this type.
+    #[must_use]
+    pub fn declared_fields_in<'s>(&self, schema: &'s Schema) -> &'s [FieldDef] {
+        schema
+            .item_type(self.0)
+            .map_or(&[], |def| def.fields.as_slice())
+    }
+
+    /// Returns the body sections the active schema declares for this type.
+    #[must_use]
+    pub fn declared_sections(&self) -> &'static [SectionDef] {
+        self.declared_sections_in(schema::active())
+    }
+
+    /// Returns the body sections `schema` declares for this type.
+    #[must_use]
+    pub fn declared_sections_in<'s>(&self, schema: &'s Schema) -> &'s [SectionDef] {
+        schema
+            .item_type(self.0)
+            .map_or(&[], |def| def.sections.as_slice())
     }
 
     /// Returns the relations this type declares toward targets, in
@@ -89,24 +123,37 @@ impl ItemType {
         })
     }
 
-    /// Returns the display name for this item type.
-    ///
-    /// Resolved from the active schema; falls back to the built-in default
-    /// when a custom schema does not redefine the type.
+    /// Returns the display name for this item type in the active schema.
     #[must_use]
     pub fn display_name(&self) -> &'static str {
-        schema::item_type_def(self.as_str())
+        self.display_name_in(schema::active())
+    }
+
+    /// Returns the display name for this item type in `schema`, or an empty
+    /// string when the schema does not define the type.
+    #[must_use]
+    pub fn display_name_in<'s>(&self, schema: &'s Schema) -> &'s str {
+        schema
+            .item_type(self.0)
             .map(|d| d.display_name.as_str())
             .unwrap_or_default()
     }
 
     /// Returns the common ID prefix for this item type.
     ///
-    /// Resolved from the active schema; falls back to the built-in default
-    /// when a custom schema does not redefine the type.
+    /// Resolved from the active schema; empty when the schema does not
+    /// define the type.
     #[must_use]
     pub fn prefix(&self) -> &'static str {
-        schema::item_type_def(self.as_str())
+        self.prefix_in(schema::active())
+    }
+
+    /// Returns the common ID prefix for this item type in `schema`, or an
+    /// empty string when the schema does not define the type.
+    #[must_use]
+    pub fn prefix_in<'s>(&self, schema: &'s Schema) -> &'s str {
+        schema
+            .item_type(self.0)
             .map(|d| d.prefix.as_str())
             .unwrap_or_default()
     }
@@ -133,14 +180,26 @@ impl ItemType {
 
     /// Suggests the next ID based on existing items in the graph.
     ///
+    /// See [`Self::suggest_next_id_among`]; if no graph is provided, returns
+    /// the first ID (e.g. "SOL-001").
+    #[must_use]
+    pub fn suggest_next_id(&self, graph: Option<&crate::graph::KnowledgeGraph>) -> String {
+        self.suggest_next_id_among(graph.into_iter().flat_map(|g| g.items()))
+    }
+
+    /// Suggests the next ID based on `items`, such as the items of the
+    /// graph together with those defined on other branches.
+    ///
     /// Scans the items of this type whose id matches the type's `id_format`
     /// with non-sequence placeholders bound to their current values (so a
     /// format embedding `{year}` restarts its counter each year), and
-    /// renders the highest matched sequence plus one. Formats without a
-    /// `{seq}` placeholder render directly (e.g. a fresh UUID). If no graph
-    /// is provided or no id matches, returns the first ID (e.g. "SOL-001").
+    /// renders the highest matched sequence plus one. Namespaced ids are
+    /// matched on their local part, so the suggestion is free in every
+    /// repository. Formats without a
+    /// `{seq}` placeholder render directly (e.g. a fresh UUID). If no id
+    /// matches, returns the first ID (e.g. "SOL-001").
     #[must_use]
-    pub fn suggest_next_id(&self, graph: Option<&crate::graph::KnowledgeGraph>) -> String {
+    pub fn suggest_next_id_among<'a>(&self, items: impl IntoIterator<Item = &'a Item>) -> String {
         let Some(format) = self.id_format() else {
             return self.generate_id(None);
         };
@@ -148,36 +207,40 @@ impl ItemType {
         if !format.has_seq() {
             return format.render(prefix, self.as_str(), 1);
         }
-        let max_seq = graph
+        let max_seq = items
             .into_iter()
-            .flat_map(|g| g.items())
             .filter(|item| item.item_type == *self)
-            .filter_map(|item| format.extract_seq(item.id.as_str(), prefix, self.as_str()))
+            .filter_map(|item| format.extract_seq(item.id.local(), prefix, self.as_str()))
             .max()
             .unwrap_or(0);
         format.render(prefix, self.as_str(), max_seq.saturating_add(1))
     }
 
-    /// Returns true if this is a root item type.
+    /// Returns true if this is a root item type of the active schema.
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.is_root_in(schema::active())
+    }
+
+    /// Returns true if this is a root item type of `schema`.
     ///
     /// A root has no required parent and declares no upstream relation, so it
     /// anchors the hierarchy (detached types like ADRs are not roots: they
     /// have no parent but justify other items).
     #[must_use]
-    pub fn is_root(&self) -> bool {
-        self.def().is_some_and(|def| {
+    pub fn is_root_in(&self, schema: &Schema) -> bool {
+        schema.item_type(self.0).is_some_and(|def| {
             def.parent_types.is_empty()
                 && !def.allowed_targets.iter().any(|t| {
-                    schema::relation_def(&t.relation)
+                    schema
+                        .relation(&t.relation)
                         .is_some_and(|r| r.direction == RelationDirection::Upstream)
                 })
         })
     }
 
-    /// Returns the required parent item type for this type, if any.
-    ///
-    /// Solution has no parent (root of the hierarchy). Resolved from the
-    /// first entry of the active schema's `parent_types` list.
+    /// Returns the required parent item type for this type in the active
+    /// schema, if any.
     #[must_use]
     pub fn required_parent_type(&self) -> Option<ItemType> {
         schema::item_type_def(self.as_str())
@@ -185,6 +248,19 @@ impl ItemType {
             .and_then(|id| ItemType::from_id(id))
     }
 
+    /// Returns the required parent item type for this type in `schema`, if
+    /// any.
+    ///
+    /// Solution has no parent (root of the hierarchy). Resolved from the
+    /// first entry of the type's `parent_types` list.
+    #[must_use]
+    pub fn required_parent_type_in(&self, schema: &Schema) -> Option<ItemType> {
+        schema
+            .item_type(self.0)
+            .and_then(|d| d.parent_types.first())
+            .and_then(|id| ItemType::from_id_in(id, schema))
+    }
+
     /// Returns the schema id (snake_case string) for this item type.
     #[must_use]
     pub const fn as_str(&self) -> &'static str {
@@ -192,15 +268,40 @@ impl ItemType {
     }
 
     /// Returns the item type with the given schema id, if the active schema
-    /// (or the built-in default) defines it.
+    /// defines it.
     ///
-    /// Inverse of [`ItemType::as_str`]. The returned value carries the id
-    /// interned in the schema, so it lives for the whole process.
+    /// Inverse of [`ItemType::as_str`].
     #[must_use]
     pub fn from_id(id: &str) -> Option<Self> {
         schema::item_type_def(id).map(|def| Self(def.id.as_str()))
     }
 
+    /// Returns the item type with the given schema id, if `schema` defines
+    /// it.
+    #[must_use]
+    pub fn from_id_in(id: &str, schema: &Schema) -> Option<Self> {
+        schema
+            .item_type(id)
+            .map(|def| Self(schema::symbol(&def.id)))
+    }
+
+    /// Returns the item type with the given schema id or alias, if any.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        schema::active()
+            .item_type_by_name(name)
+            .map(|def| Self(def.id.as_str()))
+    }
+
+    /// Returns the item type with the given schema id or alias, if `schema`
+    /// defines it.
+    #[must_use]
+    pub fn from_name_in(name: &str, schema: &Schema) -> Option<Self> {
+        schema
+            .item_type_by_name(name)
+            .map(|def| Self(schema::symbol(&def.id)))
+    }
+
     /// Returns all traceability configurations for this item type.
     ///
     /// One entry per `(relation, target type)` pair the type declares for an
@@ -247,7 +348,10 @@ pub struct TraceabilityConfig {
 
 impl fmt::Display for ItemType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.display_name())
+        match self.display_name() {
+            "" => f.write_str(self.0),
+            name => f.write_str(name),
+        }
     }
 }
 
@@ -265,7 +369,15 @@ impl<'de> Deserialize<'de> for ItemType {
     }
 }
 
+/// Separator between a repository namespace and a local identifier in a
+/// qualified reference, as in `platform:SYSREQ-001`.
+pub const NAMESPACE_SEPARATOR: char = ':';
+
 /// Unique identifier for an item across all repositories.
+///
+/// Items of a namespaced repository carry a qualified identifier
+/// (`namespace:LOCAL-ID`), so repositories may reuse the same local
+/// numbering without colliding.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ItemId(String);
@@ -305,11 +417,43 @@ impl ItemId {
         Self(id.into())
     }
 
+    /// Creates an identifier qualified by a repository namespace.
+    pub fn qualified(namespace: &str, local: &str) -> Self {
+        Self(format!("{namespace}{NAMESPACE_SEPARATOR}{local}"))
+    }
+
     /// Returns the raw identifier string.
     #[must_use]
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Returns the namespace of a qualified identifier, if any.
+    #[must_use]
+    pub fn namespace(&self) -> Option<&str> {
+        self.0
+            .split_once(NAMESPACE_SEPARATOR)
+            .map(|(namespace, _)| namespace)
+    }
+
+    /// Returns the identifier without its namespace, as written in the
+    /// item's own frontmatter.
+    #[must_use]
+    pub fn local(&self) -> &str {
+        self.0
+            .split_once(NAMESPACE_SEPARATOR)
+            .map_or(self.0.as_str(), |(_, local)| local)
+    }
+
+    /// Returns this identifier as written from within `namespace`: the local
+    /// part when both share the namespace, the full identifier otherwise.
+    #[must_use]
+    pub fn relative_to(&self, namespace: Option<&str>) -> &str {
+        match self.namespace() {
+            Some(own) if Some(own) == namespace => self.local(),
+            _ => self.as_str(),
+        }
+    }
 }
 
 impl fmt::Display for ItemId {
@@ -329,7 +473,7 @@ impl AsRef<str> for ItemId {
 /// Each declared field of the active schema maps to a [`FieldValue`] entry
 /// keyed by the field's canonical snake_case name. The map preserves
 /// declaration order so that template output remains stable.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct ItemAttributes {
     fields: IndexMap<String, FieldValue>,
@@ -376,7 +520,7 @@ impl ItemAttributes {
     }
 }
 
-use crate::model::metadata::SourceLocation;
+use crate::model::metadata::{AliasUse, SourceLocation};
 
 /// Represents a single document/node in the knowledge graph.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -404,6 +548,14 @@ pub struct Item {
     /// Type-specific attributes.
     #[serde(default)]
     pub attributes: ItemAttributes,
+
+    /// Items mentioned in the document body, in order of first mention.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<ItemId>,
+
+    /// Schema aliases the document used instead of canonical names.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<AliasUse>,
 }
 
 impl Item {
@@ -418,9 +570,15 @@ impl Item {
     /// Returns true if this item has any upstream relationships.
     #[must_use]
     pub fn has_upstream(&self) -> bool {
+        self.has_upstream_in(schema::active())
+    }
+
+    /// Returns true if this item has any upstream relationships in `schema`.
+    #[must_use]
+    pub fn has_upstream_in(&self, schema: &Schema) -> bool {
         self.relationships
             .iter()
-            .any(|r| r.relationship_type.is_upstream())
+            .any(|r| r.relationship_type.is_upstream_in(schema))
     }
 
     /// Returns an iterator over all referenced item IDs (relationships and peer refs from attributes).
@@ -451,6 +609,27 @@ mod tests {
         assert!(ItemId::new("SOL.001").is_err());
     }
 
+    #[test]
+    fn test_item_id_qualified() {
+        let id = ItemId::qualified("platform", "SYSREQ-001");
+        assert_eq!(id.as_str(), "platform:SYSREQ-001");
+        assert_eq!(id.namespace(), Some("platform"));
+        assert_eq!(id.local(), "SYSREQ-001");
+        assert!(ItemId::new("platform:SYSREQ-001").is_err());
+
+        let plain = ItemId::new_unchecked("SYSREQ-001");
+        assert_eq!(plain.namespace(), None);
+        assert_eq!(plain.local(), "SYSREQ-001");
+    }
+
+    #[test]
+    fn test_item_id_relative_to() {
+        let id = ItemId::qualified("platform", "SYSREQ-001");
+        assert_eq!(id.relative_to(Some("platform")), "SYSREQ-001");
+        assert_eq!(id.relative_to(Some("product")), "platform:SYSREQ-001");
+        assert_eq!(id.relative_to(None), "platform:SYSREQ-001");
+    }
+
     #[test]
     fn test_item_type_display() {
         assert_eq!(builtin::SOLUTION.display_name(), "Solution");
@@ -499,4 +678,13 @@ mod tests {
             .unwrap();
         assert_eq!(builtin::SOLUTION.suggest_next_id(Some(&graph)), "SOL-008");
     }
+
+    #[test]
+    fn test_suggest_next_id_among_ignores_other_types() {
+        let items = [
+            create_test_item("SOL-004", builtin::SOLUTION),
+            create_test_item("SOL-009", builtin::USE_CASE),
+        ];
+        assert_eq!(builtin::SOLUTION.suggest_next_id_among(&items), "SOL-005");
+    }
 }
@@ -1,9 +1,23 @@
 //! Metadata structures for items and source tracking.
 
+use std::fmt;
+use std::ops::Range;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+/// A former name a document used in place of a canonical one.
+///
+/// Schema aliases keep renamed types, fields and relations readable; the
+/// document still parses, but should be updated to the canonical name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasUse {
+    /// The name written in the document.
+    pub alias: String,
+    /// The canonical name it resolves to.
+    pub canonical: String,
+}
+
 /// Tracks the file origin of an item for error reporting.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceLocation {
@@ -16,6 +30,11 @@ pub struct SourceLocation {
     /// Optional Git commit/branch if reading from history.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub git_ref: Option<String>,
+
+    /// Lines spanned by the item, 1-based with an exclusive end, when the
+    /// file defines several items; `None` when the item spans the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<Range<usize>>,
 }
 
 impl SourceLocation {
@@ -25,6 +44,7 @@ impl SourceLocation {
             repository: repository.into(),
             file_path: file_path.into(),
             git_ref: None,
+            lines: None,
         }
     }
 
@@ -38,15 +58,35 @@ impl SourceLocation {
             repository: repository.into(),
             file_path: file_path.into(),
             git_ref: Some(git_ref.into()),
+            lines: None,
         }
     }
 
+    /// Sets the lines spanned by the item within its file.
+    #[must_use]
+    pub fn with_lines(mut self, lines: Range<usize>) -> Self {
+        self.lines = Some(lines);
+        self
+    }
+
     /// Returns the full path to the file.
     pub fn full_path(&self) -> PathBuf {
         self.repository.join(&self.file_path)
     }
 }
 
+impl fmt::Display for SourceLocation {
+    /// Formats the relative file path, followed by the first line of the
+    /// item when the file defines several items.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file_path.display())?;
+        if let Some(lines) = &self.lines {
+            write!(f, ":{}", lines.start)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +102,11 @@ mod tests {
         let loc = SourceLocation::with_git_ref("/repo", "docs/SOL-001.md", "main");
         assert_eq!(loc.git_ref, Some("main".to_string()));
     }
+
+    #[test]
+    fn test_source_location_display() {
+        let loc = SourceLocation::new("/repo", "docs/srs.md");
+        assert_eq!(loc.to_string(), "docs/srs.md");
+        assert_eq!(loc.with_lines(12..30).to_string(), "docs/srs.md:12");
+    }
 }
@@ -12,7 +12,7 @@ pub use edit::{EditSummary, FieldChange, TraceabilityLinks};
 pub use field::FieldValue;
 pub use item::{
     FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE, Item, ItemAttributes, ItemId, ItemType,
-    TraceabilityConfig,
+    NAMESPACE_SEPARATOR, TraceabilityConfig,
 };
-pub use metadata::SourceLocation;
+pub use metadata::{AliasUse, SourceLocation};
 pub use relationship::{Relationship, RelationshipRules, RelationshipType};
@@ -2,17 +2,18 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::item::{ItemId, ItemType};
-use crate::schema::{self, RelationDef, RelationDirection};
+use super::item::{ItemAttributes, ItemId, ItemType};
+use crate::schema::{self, RelationDirection, Schema};
 
 /// Identifies a relation by its schema id.
 ///
-/// Wraps the interned snake_case id of a relation declared by the active
-/// schema (or the built-in default). Inverse, direction and primality are
-/// resolved against the schema, so relations introduced by a custom YAML
+/// Wraps the snake_case id of a relation declared by a schema (see
+/// [`crate::schema::active`]). Inverse, direction and primality are
+/// resolved against a schema, so relations introduced by a custom YAML
 /// schema behave exactly like the built-in ones (handles for those live in
-/// [`crate::schema::builtin`]). Relations are obtained through
-/// [`RelationshipType::from_id`] or [`RelationshipType::all`].
+/// [`crate::schema::builtin`]). Methods ending in `_in` resolve against the
+/// given schema, the others against the active one. Relations are obtained
+/// through [`RelationshipType::from_id_in`] or [`RelationshipType::all_in`].
 ///
 /// Equality and hashing compare the id by content, so a handle built from a
 /// static id compares equal to the same relation resolved from a schema.
@@ -22,10 +23,10 @@ pub struct RelationshipType(&'static str);
 impl RelationshipType {
     /// Creates a handle from a static schema id, without resolving it.
     ///
-    /// The id is not checked against the active schema: a handle whose id the
+    /// The id is not checked against any schema: a handle whose id the
     /// schema does not declare resolves to no metadata. Reserved for the
     /// definition of well-known ids (see [`crate::schema::builtin`]); resolve
-    /// runtime ids through [`RelationshipType::from_id`].
+    /// runtime ids through [`RelationshipType::from_id_in`].
     pub(crate) const fn from_static(id: &'static str) -> Self {
         Self(id)
     }
@@ -40,16 +41,48 @@ impl RelationshipType {
             .collect()
     }
 
+    /// Returns all relations of `schema`, in catalog order.
+    #[must_use]
+    pub fn all_in(schema: &Schema) -> Vec<RelationshipType> {
+        schema
+            .relations
+            .iter()
+            .map(|def| Self(schema::symbol(&def.id)))
+            .collect()
+    }
+
     /// Returns the relation with the given schema id, if the active schema
-    /// (or the built-in default) defines it.
+    /// defines it.
     ///
-    /// Inverse of [`RelationshipType::as_str`]. The returned value carries
-    /// the id interned in the schema, so it lives for the whole process.
+    /// Inverse of [`RelationshipType::as_str`].
     #[must_use]
     pub fn from_id(id: &str) -> Option<Self> {
         schema::relation_def(id).map(|def| Self(def.id.as_str()))
     }
 
+    /// Returns the relation with the given schema id, if `schema` defines it.
+    #[must_use]
+    pub fn from_id_in(id: &str, schema: &Schema) -> Option<Self> {
+        schema.relation(id).map(|def| Self(schema::symbol(&def.id)))
+    }
+
+    /// Returns the relation with the given schema id or alias, if any.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        schema::active()
+            .relation_by_name(name)
+            .map(|def| Self(def.id.as_str()))
+    }
+
+    /// Returns the relation with the given schema id or alias, if `schema`
+    /// defines it.
+    #[must_use]
+    pub fn from_name_in(name: &str, schema: &Schema) -> Option<Self> {
+        schema
+            .relation_by_name(name)
+            .map(|def| Self(schema::symbol(&def.id)))
+    }
+
     /// Returns the schema id (snake_case string) for this relation.
     ///
     /// This is also the frontmatter field name carrying the relation.
@@ -58,60 +91,94 @@ impl RelationshipType {
         self.0
     }
 
-    /// Returns the relation definition in the active schema, if any.
-    fn def(&self) -> Option<&'static RelationDef> {
-        schema::relation_def(self.0)
+    /// Returns the display name for this relation in the active schema.
+    #[must_use]
+    pub fn display_name(&self) -> &'static str {
+        self.display_name_in(schema::active())
     }
 
-    /// Returns the display name for this relation.
-    ///
-    /// Resolved from the active schema; falls back to the built-in default
-    /// when a custom schema does not redefine the relation.
+    /// Returns the display name for this relation in `schema`, or an empty
+    /// string when the schema does not declare the relation.
     #[must_use]
-    pub fn display_name(&self) -> &'static str {
-        self.def().map_or("", |def| def.display_name.as_str())
+    pub fn display_name_in<'s>(&self, schema: &'s Schema) -> &'s str {
+        schema
+            .relation(self.0)
+            .map_or("", |def| def.display_name.as_str())
     }
 
-    /// Get the inverse relationship type.
+    /// Get the inverse relationship type in the active schema.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        self.inverse_in(schema::active())
+    }
+
+    /// Get the inverse relationship type in `schema`.
     ///
     /// Resolved from the schema's relation catalog; relations missing from
     /// the schema (which validation prevents) are their own inverse.
     #[must_use]
-    pub fn inverse(&self) -> Self {
-        self.def()
-            .and_then(|def| Self::from_id(&def.inverse))
+    pub fn inverse_in(&self, schema: &Schema) -> Self {
+        schema
+            .relation(self.0)
+            .and_then(|def| Self::from_id_in(&def.inverse, schema))
             .unwrap_or(*self)
     }
 
+    /// Returns the direction of this relation in `schema`, if declared.
+    fn direction_in(&self, schema: &Schema) -> Option<RelationDirection> {
+        schema.relation(self.0).map(|def| def.direction)
+    }
+
     /// Check if this is an upstream relationship (toward the hierarchy root).
     #[must_use]
     pub fn is_upstream(&self) -> bool {
-        self.def()
-            .is_some_and(|def| def.direction == RelationDirection::Upstream)
+        self.is_upstream_in(schema::active())
+    }
+
+    /// Check if this is an upstream relationship in `schema`.
+    #[must_use]
+    pub fn is_upstream_in(&self, schema: &Schema) -> bool {
+        self.direction_in(schema) == Some(RelationDirection::Upstream)
     }
 
     /// Check if this is a downstream relationship (away from the root).
     #[must_use]
     pub fn is_downstream(&self) -> bool {
-        self.def()
-            .is_some_and(|def| def.direction == RelationDirection::Downstream)
+        self.is_downstream_in(schema::active())
+    }
+
+    /// Check if this is a downstream relationship in `schema`.
+    #[must_use]
+    pub fn is_downstream_in(&self, schema: &Schema) -> bool {
+        self.direction_in(schema) == Some(RelationDirection::Downstream)
     }
 
     /// Check if this is a peer relationship (between items of the same type).
     #[must_use]
     pub fn is_peer(&self) -> bool {
-        self.def()
-            .is_some_and(|def| def.direction == RelationDirection::Peer)
+        self.is_peer_in(schema::active())
+    }
+
+    /// Check if this is a peer relationship in `schema`.
+    #[must_use]
+    pub fn is_peer_in(&self, schema: &Schema) -> bool {
+        self.direction_in(schema) == Some(RelationDirection::Peer)
     }
 
     /// Check if this is a primary relationship (not an inverse).
+    #[must_use]
+    pub fn is_primary(&self) -> bool {
+        self.is_primary_in(schema::active())
+    }
+
+    /// Check if this is a primary relationship in `schema`.
     ///
     /// Primary relations are the declared side of an inverse pair; inverse
     /// relations exist for graph traversal and are not considered when
     /// checking for cycles.
     #[must_use]
-    pub fn is_primary(&self) -> bool {
-        self.def().is_some_and(|def| def.primary)
+    pub fn is_primary_in(&self, schema: &Schema) -> bool {
+        schema.relation(self.0).is_some_and(|def| def.primary)
     }
 }
 
@@ -138,12 +205,16 @@ impl<'de> Deserialize<'de> for RelationshipType {
 /// Represents a relationship from an Item to another item.
 ///
 /// The source item is implied by the `Item` containing this relationship.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Relationship {
     /// Target item ID.
     pub to: ItemId,
     /// Type of relationship.
     pub relationship_type: RelationshipType,
+    /// Link attributes declared by the schema for the relation (see
+    /// [`crate::schema::RelationDef::attributes`]), keyed by name.
+    #[serde(default, skip_serializing_if = "ItemAttributes::is_empty")]
+    pub attributes: ItemAttributes,
 }
 
 impl Relationship {
@@ -153,8 +224,16 @@ impl Relationship {
         Self {
             to,
             relationship_type,
+            attributes: ItemAttributes::new(),
         }
     }
+
+    /// Sets the link attributes.
+    #[must_use]
+    pub fn with_attributes(mut self, attributes: ItemAttributes) -> Self {
+        self.attributes = attributes;
+        self
+    }
 }
 
 /// Valid relationship rules based on item types.
@@ -0,0 +1,346 @@
+//! Items anchored on Markdown headings.
+//!
+//! A single Markdown document, such as a specification chapter, can define
+//! many items. Each item starts at a heading made of its identifier and name,
+//! followed by a fenced `sara` block holding the rest of its metadata as YAML:
+//!
+//! ````text
+//! ## SWREQ-010: Baud rate
+//!
+//! ```sara
+//! type: software_requirement
+//! derives_from: [SYSREQ-001]
+//! ```
+//!
+//! The UART SHALL run at 115200 baud.
+//! ````
+//!
+//! The item body runs to the next heading of the same or a higher level, or
+//! to the next anchored item.
+
+use std::ops::Range;
+use std::path::Path;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::error::SaraError;
+use crate::model::{FIELD_ID, FIELD_NAME, Item, ItemId};
+use crate::parser::ParseContext;
+use crate::parser::frontmatter::body_offset;
+use crate::parser::markdown::{build_item, markdown_headings, parse_heading};
+use crate::parser::yaml::RawFrontmatter;
+
+/// Info string of the fenced blocks holding item metadata.
+const BLOCK_INFO: &str = "sara";
+
+/// Markers opening and closing fenced code blocks.
+const FENCES: [&str; 2] = ["```", "~~~"];
+
+/// An item anchored on a heading of a Markdown document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Anchor<'a> {
+    /// Identifier written in the heading.
+    pub id: &'a str,
+    /// Name written in the heading.
+    pub name: &'a str,
+    /// Heading level.
+    pub level: usize,
+    /// Byte range of the heading line, line ending excluded.
+    pub heading: Range<usize>,
+    /// Byte range of the metadata lines, between the fences.
+    pub block: Range<usize>,
+    /// Byte range of the item body, after the closing fence.
+    pub body: Range<usize>,
+    /// Lines spanned by the item, 1-based with an exclusive end.
+    pub lines: Range<usize>,
+}
+
+/// Finds the anchored items of a Markdown document, after its frontmatter.
+pub(crate) fn find_anchors(content: &str) -> Vec<Anchor<'_>> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut offsets: Vec<usize> = Vec::with_capacity(lines.len() + 1);
+    let mut offset = 0;
+    for line in &lines {
+        offsets.push(offset);
+        offset += line.len();
+    }
+    offsets.push(content.len());
+    let text = |i: usize| lines[i].trim_end_matches(['\n', '\r']);
+
+    let body_start = body_offset(content);
+    let mut index = offsets.partition_point(|offset| *offset < body_start);
+    // Headings outside code blocks, as (line, level, anchored).
+    let mut headings: Vec<(usize, usize, bool)> = Vec::new();
+    // Anchors found, as (anchor, line after the closing fence).
+    let mut found: Vec<(Anchor<'_>, usize)> = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    while index < lines.len() {
+        let trimmed = text(index).trim_start();
+        if let Some(marker) = FENCES.into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = match fence {
+                Some(open) if open == marker => None,
+                None => Some(marker),
+                other => other,
+            };
+            index += 1;
+            continue;
+        }
+        let Some((level, heading)) = fence
+            .is_none()
+            .then(|| parse_heading(text(index)))
+            .flatten()
+        else {
+            index += 1;
+            continue;
+        };
+
+        match anchor_at(&lines, index, heading, text) {
+            Some((id, name, open, close)) => {
+                let line = index;
+                headings.push((line, level, true));
+                let start = offsets[line];
+                found.push((
+                    Anchor {
+                        id,
+                        name,
+                        level,
+                        heading: start..start + text(line).len(),
+                        block: offsets[open + 1]..offsets[close],
+                        body: offsets[close + 1]..content.len(),
+                        lines: line + 1..lines.len() + 1,
+                    },
+                    close + 1,
+                ));
+                index = close + 1;
+            }
+            None => {
+                headings.push((index, level, false));
+                index += 1;
+            }
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|(mut anchor, after)| {
+            if let Some((end, _, _)) = headings.iter().find(|(line, level, anchored)| {
+                *line >= after && (*anchored || *level <= anchor.level)
+            }) {
+                anchor.body.end = offsets[*end];
+                anchor.lines.end = end + 1;
+            }
+            anchor
+        })
+        .collect()
+}
+
+/// Recognizes an anchor heading at `lines[index]`: an `ID: Name` heading
+/// followed, after blank lines, by a closed `sara` block.
+///
+/// Returns the identifier, the name and the opening and closing fence lines.
+fn anchor_at<'a>(
+    lines: &[&'a str],
+    index: usize,
+    heading: &'a str,
+    text: impl Fn(usize) -> &'a str,
+) -> Option<(&'a str, &'a str, usize, usize)> {
+    let (id, name) = heading.split_once(':')?;
+    let (id, name) = (id.trim(), name.trim());
+    if name.is_empty() || ItemId::new(id).is_err() {
+        return None;
+    }
+
+    let open = (index + 1..lines.len()).find(|i| !text(*i).trim().is_empty())?;
+    let opening = text(open).trim();
+    let marker = FENCES.into_iter().find(|m| opening.starts_with(m))?;
+    if opening[marker.len()..].trim() != BLOCK_INFO {
+        return None;
+    }
+    let close = (open + 1..lines.len()).find(|i| text(*i).trim() == marker)?;
+    Some((id, name, open, close))
+}
+
+/// Returns true if the document defines anchored items.
+pub(super) fn has_anchored_items(content: &str) -> bool {
+    !find_anchors(content).is_empty()
+}
+
+/// Parses the anchored items of a Markdown document.
+///
+/// The heading gives the identifier and the name; an `id` repeated in the
+/// block must match the heading.
+pub(super) fn parse_anchored_items(
+    content: &str,
+    file_path: &Path,
+    repository: &Path,
+    ctx: &ParseContext,
+) -> Result<Vec<Item>, SaraError> {
+    find_anchors(content)
+        .into_iter()
+        .map(|anchor| {
+            let invalid = |reason: String| SaraError::InvalidFrontmatter {
+                file: file_path.to_path_buf(),
+                reason: format!("item {} (line {}): {reason}", anchor.id, anchor.lines.start),
+            };
+
+            let block = &content[anchor.block.clone()];
+            let mut mapping = match serde_yaml::from_str(block) {
+                Ok(Value::Mapping(mapping)) => mapping,
+                Ok(Value::Null) => Mapping::new(),
+                Ok(_) => return Err(invalid("expected a metadata mapping".to_string())),
+                Err(e) => {
+                    return Err(SaraError::InvalidYaml {
+                        file: file_path.to_path_buf(),
+                        reason: format!("item {} (line {}): {e}", anchor.id, anchor.lines.start),
+                    });
+                }
+            };
+            if let Some(id) = mapping.get(FIELD_ID)
+                && id.as_str() != Some(anchor.id)
+            {
+                return Err(invalid(
+                    "the block `id` differs from the heading".to_string(),
+                ));
+            }
+            mapping.insert(FIELD_ID.into(), anchor.id.into());
+            mapping.insert(FIELD_NAME.into(), anchor.name.into());
+
+            let frontmatter = RawFrontmatter::deserialize_in(Value::Mapping(mapping), ctx.schema())
+                .map_err(|e| invalid(e.to_string()))?;
+            let body = &content[anchor.body.clone()];
+            let mut item = build_item(
+                &frontmatter,
+                body,
+                markdown_headings,
+                file_path,
+                repository,
+                ctx,
+            )?;
+            item.source = item.source.with_lines(anchor.lines);
+            Ok(item)
+        })
+        .collect()
+}
+
+/// Rewrites the heading and metadata block of an anchored item.
+///
+/// `metadata` is a YAML mapping such as a standalone item file holds; its
+/// `id` and `name` go to the heading. Returns `None` when the document does
+/// not anchor the item.
+pub(crate) fn update_anchored_item(
+    content: &str,
+    id: &str,
+    name: &str,
+    metadata: &str,
+) -> Option<String> {
+    let anchor = find_anchors(content).into_iter().find(|a| a.id == id)?;
+    let block: String = metadata
+        .lines()
+        .filter(|line| {
+            !line
+                .split_once(':')
+                .is_some_and(|(key, _)| key == FIELD_ID || key == FIELD_NAME)
+        })
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let heading = format!("{} {id}: {name}", "#".repeat(anchor.level));
+
+    let mut updated = content.to_string();
+    updated.replace_range(anchor.block, &block);
+    updated.replace_range(anchor.heading, &heading);
+    Some(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    use crate::schema::builtin;
+
+    const CHAPTER: &str = "---\nid: SWARCH-001\ntype: software_architecture\n\
+        name: UART driver\n---\n# Software requirements\n\nIntro.\n\n\
+        ## SWREQ-010: Baud rate\n\n```sara\ntype: software_requirement\n\
+        specification: The UART SHALL run at 115200 baud.\n\
+        derives_from: [SYSREQ-001]\n```\n\nThe UART SHALL run at 115200 baud.\n\n\
+        ### Rationale\n\nLegacy peers.\n\n\
+        ## Notes: not an item\n\n```yaml\ntype: x\n```\n\n\
+        ## SWREQ-011: Parity\n```sara\ntype: software_requirement\n\
+        specification: The UART SHALL use even parity.\n```\n\
+        Even parity, see [[SWREQ-010]].\n\n# Appendix\n";
+
+    fn parse(content: &str) -> Result<Vec<Item>, SaraError> {
+        parse_anchored_items(
+            content,
+            &PathBuf::from("srs.md"),
+            &PathBuf::from("/repo"),
+            &ParseContext::default(),
+        )
+    }
+
+    #[test]
+    fn test_find_anchors() {
+        let anchors = find_anchors(CHAPTER);
+        let found: Vec<_> = anchors
+            .iter()
+            .map(|a| (a.id, a.name, a.level, a.lines.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("SWREQ-010", "Baud rate", 2, 10..24),
+                ("SWREQ-011", "Parity", 2, 30..37)
+            ]
+        );
+        assert_eq!(
+            &CHAPTER[anchors[0].heading.clone()],
+            "## SWREQ-010: Baud rate"
+        );
+        assert!(CHAPTER[anchors[0].body.clone()].ends_with("Legacy peers.\n\n"));
+        assert_eq!(
+            &CHAPTER[anchors[1].body.clone()],
+            "Even parity, see [[SWREQ-010]].\n\n"
+        );
+        assert!(find_anchors("## SWREQ-010: Baud rate\n\nNo block.\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_anchored_items() {
+        let items = parse(CHAPTER).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id.as_str(), "SWREQ-010");
+        assert_eq!(items[0].name, "Baud rate");
+        assert_eq!(items[0].item_type, builtin::SOFTWARE_REQUIREMENT);
+        assert_eq!(items[0].relationship_ids(builtin::DERIVES_FROM).count(), 1);
+        assert_eq!(items[0].source.lines, Some(10..24));
+        assert_eq!(items[1].mentions.len(), 1);
+
+        let mismatch = "## SWREQ-010: Baud rate\n```sara\nid: SWREQ-011\n\
+                        type: software_requirement\nspecification: Fast.\n```\n";
+        assert!(matches!(
+            parse(mismatch),
+            Err(SaraError::InvalidFrontmatter { reason, .. }) if reason.starts_with("item SWREQ-010 (line 1)")
+        ));
+    }
+
+    #[test]
+    fn test_update_anchored_item() {
+        let metadata = "id: \"SWREQ-011\"\ntype: software_requirement\nname: \"Odd parity\"\n\
+                        specification: The UART SHALL use even parity.\n\
+                        derives_from:\n  - \"SYSREQ-002\"\n";
+        let updated = update_anchored_item(CHAPTER, "SWREQ-011", "Odd parity", metadata).unwrap();
+        assert!(updated.contains(
+            "## SWREQ-011: Odd parity\n```sara\ntype: software_requirement\n\
+             specification: The UART SHALL use even parity.\nderives_from:\n  - \"SYSREQ-002\"\n```\nEven parity"
+        ));
+        assert_eq!(
+            updated
+                .replace("Odd parity", "Parity")
+                .replace("derives_from:\n  - \"SYSREQ-002\"\n", ""),
+            CHAPTER
+        );
+        assert!(update_anchored_item(CHAPTER, "SWREQ-099", "Gone", metadata).is_none());
+    }
+}
@@ -0,0 +1,202 @@
+//! AsciiDoc document header and section title parsing.
+//!
+//! Item metadata lives in the attribute entries of the document header, the
+//! contiguous lines following the optional `= Title` line:
+//!
+//! ```text
+//! = Response time
+//! :id: SYSREQ-001
+//! :type: system_requirement
+//! :name: Response time
+//! :derives_from: SCEN-001, SCEN-002
+//! ```
+
+use crate::parser::fieldlist::{FieldBlock, FieldEntry, parse_field_line};
+use crate::parser::markdown::Heading;
+
+/// Delimiters of the blocks whose content is never a section title.
+const BLOCK_DELIMITERS: &[&str] = &["----", "....", "````", "```", "////", "++++"];
+
+/// Returns true if a line is a single-line comment.
+fn is_comment(line: &str) -> bool {
+    line.starts_with("//") && !line.starts_with("////")
+}
+
+/// Reads the attribute entries of the document header.
+///
+/// The header starts at the first line that is neither blank nor a comment,
+/// when that line is the document title or an attribute entry, and runs to
+/// the next blank line. A value ending with ` \` continues on the next line.
+pub(super) fn read_header(content: &str) -> FieldBlock {
+    let mut block = FieldBlock::default();
+    let mut offset = 0;
+    let mut started = false;
+    let mut open: Option<FieldEntry> = None;
+
+    for line in content.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let text = line.trim_end_matches(['\n', '\r']);
+
+        if let Some(entry) = open.as_mut() {
+            let continued = text.trim();
+            entry.lines.end = offset;
+            match continued.strip_suffix('\\') {
+                Some(more) => push_word(&mut entry.value, more.trim_end()),
+                None => {
+                    push_word(&mut entry.value, continued);
+                    block.entries.extend(open.take());
+                }
+            }
+            continue;
+        }
+
+        if text.trim().is_empty() {
+            if started {
+                break;
+            }
+            continue;
+        }
+        if is_comment(text) {
+            block.body_start = offset;
+            block.insert_at = offset;
+            continue;
+        }
+        if !started && !text.starts_with("= ") && parse_field_line(text).is_none() {
+            break;
+        }
+        started = true;
+        block.body_start = offset;
+        block.insert_at = offset;
+
+        if let Some((key, value)) = parse_field_line(text) {
+            let entry = FieldEntry {
+                key: key.to_string(),
+                value: value.to_string(),
+                lines: start..offset,
+            };
+            match value.strip_suffix('\\') {
+                Some(first) => {
+                    open = Some(FieldEntry {
+                        value: first.trim_end().to_string(),
+                        ..entry
+                    });
+                }
+                None => block.entries.push(entry),
+            }
+        }
+    }
+
+    if let Some(entry) = open {
+        block.body_start = entry.lines.end;
+        block.entries.push(entry);
+    } else if let Some(last) = block.entries.last() {
+        block.body_start = block.body_start.max(last.lines.end);
+    }
+    block.insert_at = block.insert_at.max(block.body_start);
+    block
+}
+
+/// Appends a word to a value, separated by a space.
+fn push_word(value: &mut String, word: &str) {
+    if !value.is_empty() && !word.is_empty() {
+        value.push(' ');
+    }
+    value.push_str(word);
+}
+
+/// Returns the document title, the text of a leading `= Title` line.
+pub(super) fn title(content: &str) -> Option<String> {
+    content
+        .lines()
+        .find(|line| !line.trim().is_empty() && !is_comment(line))?
+        .strip_prefix("= ")
+        .map(|title| title.trim().to_string())
+}
+
+/// Finds the section titles (`== Title`) of an AsciiDoc body, ignoring
+/// delimited blocks.
+pub(super) fn headings<'a>(lines: &[&'a str]) -> Vec<Heading<'a>> {
+    let mut headings = Vec::new();
+    let mut delimiter: Option<&str> = None;
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_end();
+        if let Some(marker) = BLOCK_DELIMITERS.iter().find(|m| trimmed == **m) {
+            delimiter = match delimiter {
+                Some(open) if open == *marker => None,
+                None => Some(marker),
+                other => other,
+            };
+            continue;
+        }
+        if delimiter.is_some() {
+            continue;
+        }
+        let marks = trimmed.chars().take_while(|c| *c == '=').count();
+        let rest = &trimmed[marks..];
+        if (1..=6).contains(&marks) && rest.starts_with(' ') && !rest.trim().is_empty() {
+            headings.push(Heading {
+                line: index,
+                level: marks,
+                text: rest.trim(),
+                content_start: index + 1,
+            });
+        }
+    }
+    headings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_header() {
+        let content = "// generated\n= Hub\nJane Doe\n:toc:\n:id: SOL-001\n\
+                       :description: First line \\\n  second line\n\n:name: Body\n";
+        let block = read_header(content);
+        let entries: Vec<_> = block
+            .entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.value.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("toc", ""),
+                ("id", "SOL-001"),
+                ("description", "First line second line")
+            ]
+        );
+        assert_eq!(&content[block.body_start..], "\n:name: Body\n");
+        assert_eq!(
+            &content[block.entries[2].lines.clone()],
+            ":description: First line \\\n  second line\n"
+        );
+
+        let block = read_header("Intro paragraph.\n:id: SOL-001\n");
+        assert!(block.entries.is_empty());
+        assert_eq!(block.insert_at, 0);
+    }
+
+    #[test]
+    fn test_title_and_headings() {
+        assert_eq!(title("\n= Hub\n:id: SOL-001\n"), Some("Hub".to_string()));
+        assert_eq!(title("Intro\n= Hub\n"), None);
+
+        let lines = [
+            "== Rationale",
+            "text",
+            "----",
+            "== Not a title",
+            "----",
+            "=== Details",
+            "==Nope",
+        ];
+        let found: Vec<_> = headings(&lines)
+            .into_iter()
+            .map(|h| (h.line, h.level, h.text))
+            .collect();
+        assert_eq!(found, [(0, 2, "Rationale"), (5, 3, "Details")]);
+    }
+}
@@ -0,0 +1,57 @@
+//! Context documents are parsed in.
+
+use std::sync::Arc;
+
+use super::MentionPattern;
+use crate::schema::{self, Schema};
+
+/// What documents are read against.
+///
+/// Carries the schema that resolves item types, fields and relations, and
+/// the pattern mentions are written with, so a caller parses documents of
+/// another model (a schema at a git ref, say) by handing the parser another
+/// context. Cloning is cheap: the schema is shared.
+#[derive(Debug, Clone)]
+pub struct ParseContext {
+    schema: Arc<Schema>,
+    mention_pattern: MentionPattern,
+}
+
+impl ParseContext {
+    /// Creates a context reading documents against `schema`, with the
+    /// default mention pattern.
+    #[must_use]
+    pub fn new(schema: Arc<Schema>) -> Self {
+        Self {
+            schema,
+            mention_pattern: MentionPattern::default(),
+        }
+    }
+
+    /// Sets the pattern mentions are found with in document bodies.
+    #[must_use]
+    pub fn with_mention_pattern(mut self, pattern: MentionPattern) -> Self {
+        self.mention_pattern = pattern;
+        self
+    }
+
+    /// Returns the schema documents are read against.
+    #[must_use]
+    pub fn schema(&self) -> &Arc<Schema> {
+        &self.schema
+    }
+
+    /// Returns the pattern mentions are found with.
+    #[must_use]
+    pub fn mention_pattern(&self) -> &MentionPattern {
+        &self.mention_pattern
+    }
+}
+
+impl Default for ParseContext {
+    /// Reads documents against the active schema (see [`schema::active`])
+    /// with the default mention pattern.
+    fn default() -> Self {
+        Self::new(schema::shared())
+    }
+}
@@ -0,0 +1,330 @@
+//! Field-list metadata shared by AsciiDoc and reStructuredText documents.
+//!
+//! Both formats carry item metadata as `:key: value` entries in the document
+//! header: attribute entries in AsciiDoc, the docinfo field list in
+//! reStructuredText. Entries map onto the same [`RawFrontmatter`] as YAML
+//! frontmatter: relation values and declared list fields are comma-separated
+//! identifiers, every other value is a plain string.
+
+use std::ops::Range;
+use std::path::Path;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::error::SaraError;
+use crate::model::{FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE, Item, RelationshipType};
+use crate::parser::markdown::{HeadingFinder, build_item, markdown_headings};
+use crate::parser::yaml::RawFrontmatter;
+use crate::parser::{InputFormat, ParseContext, asciidoc, body_start, rst};
+use crate::schema::{self, FieldType, Schema};
+
+/// One `:key: value` entry of a document header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FieldEntry {
+    /// Entry name.
+    pub key: String,
+    /// Entry value, continuation lines joined with a space.
+    pub value: String,
+    /// Byte range of the entry lines, continuation lines included.
+    pub lines: Range<usize>,
+}
+
+/// The field entries of a document header and where the body starts.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FieldBlock {
+    /// Header entries, in document order.
+    pub entries: Vec<FieldEntry>,
+    /// Byte offset at which metadata goes when the header holds none.
+    pub insert_at: usize,
+    /// Byte offset at which the body starts.
+    pub body_start: usize,
+}
+
+impl FieldBlock {
+    /// Returns true if the block holds an item identifier.
+    fn has_id(&self) -> bool {
+        self.entries.iter().any(|e| e.key == FIELD_ID)
+    }
+}
+
+/// Reads the header field entries of a document.
+pub(crate) fn read_block(content: &str, format: InputFormat) -> FieldBlock {
+    match format {
+        InputFormat::AsciiDoc => asciidoc::read_header(content),
+        InputFormat::Rst => rst::read_header(content),
+        InputFormat::Markdown | InputFormat::Yaml | InputFormat::Toml => FieldBlock {
+            body_start: body_start(content, format),
+            ..FieldBlock::default()
+        },
+    }
+}
+
+/// Parses a `:key: value` line into its key and trimmed value.
+///
+/// AsciiDoc unset entries (`:key!:`) are not values and yield `None`.
+pub(super) fn parse_field_line(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix(':')?;
+    let end = rest.find(':')?;
+    let (key, value) = (&rest[..end], &rest[end + 1..]);
+    let valid_key = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-'));
+    if !valid_key || !(value.is_empty() || value.starts_with(char::is_whitespace)) {
+        return None;
+    }
+    Some((key, value.trim()))
+}
+
+/// Returns true if the content has a header field list holding an `id`.
+pub(super) fn has_item_fields(content: &str, format: InputFormat) -> bool {
+    read_block(content, format).has_id()
+}
+
+/// Parses an AsciiDoc or reStructuredText document and extracts the item.
+pub(super) fn parse_field_list_file(
+    content: &str,
+    file_path: &Path,
+    repository: &Path,
+    format: InputFormat,
+    ctx: &ParseContext,
+) -> Result<Item, SaraError> {
+    let block = read_block(content, format);
+    if !block.has_id() {
+        return Err(SaraError::MissingFrontmatter {
+            file: file_path.to_path_buf(),
+        });
+    }
+
+    let frontmatter = to_frontmatter(&block.entries, file_path, ctx.schema())?;
+    let body = &content[block.body_start..];
+    build_item(
+        &frontmatter,
+        body,
+        headings(format),
+        file_path,
+        repository,
+        ctx,
+    )
+}
+
+/// Returns the heading finder of a format.
+fn headings(format: InputFormat) -> HeadingFinder {
+    match format {
+        InputFormat::AsciiDoc => asciidoc::headings,
+        InputFormat::Rst => rst::headings,
+        InputFormat::Markdown | InputFormat::Yaml | InputFormat::Toml => markdown_headings,
+    }
+}
+
+/// Converts header entries to the frontmatter structure used by all formats.
+fn to_frontmatter(
+    entries: &[FieldEntry],
+    file: &Path,
+    schema: &Schema,
+) -> Result<RawFrontmatter, SaraError> {
+    let type_def = entries
+        .iter()
+        .find(|e| e.key == FIELD_TYPE)
+        .and_then(|e| schema.item_type_by_name(&e.value));
+
+    let mut mapping = Mapping::new();
+    for entry in entries {
+        let is_list = schema.relation_by_name(&entry.key).is_some()
+            || type_def
+                .and_then(|def| def.field_by_name(&entry.key))
+                .is_some_and(|f| matches!(f.field_type, FieldType::List(_)));
+        let value = if is_list {
+            Value::Sequence(
+                entry
+                    .value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(|id| Value::String(id.to_string()))
+                    .collect(),
+            )
+        } else {
+            Value::String(entry.value.clone())
+        };
+        mapping.insert(Value::String(entry.key.clone()), value);
+    }
+
+    RawFrontmatter::deserialize_in(Value::Mapping(mapping), schema).map_err(|e| {
+        SaraError::InvalidFrontmatter {
+            file: file.to_path_buf(),
+            reason: e.to_string(),
+        }
+    })
+}
+
+/// Returns true if a header entry is item metadata managed by sara, under
+/// its canonical name or an alias.
+fn is_item_key(key: &str) -> bool {
+    [FIELD_ID, FIELD_TYPE, FIELD_NAME, FIELD_DESCRIPTION].contains(&key)
+        || RelationshipType::from_name(key).is_some()
+        || schema::active()
+            .item_types
+            .iter()
+            .any(|t| t.field_by_name(key).is_some())
+}
+
+/// Replaces the item entries of a header with a generated block.
+///
+/// Entries unknown to the schema stay in place; the block takes the place of
+/// the first item entry, or goes to the insertion point of a header without
+/// any.
+pub(super) fn update_fields(content: &str, metadata: &str, format: InputFormat) -> String {
+    let block = read_block(content, format);
+    let metadata = metadata.trim_end_matches('\n');
+    let owned: Vec<&FieldEntry> = block
+        .entries
+        .iter()
+        .filter(|e| is_item_key(&e.key))
+        .collect();
+
+    let Some(first) = owned.first() else {
+        let (head, tail) = content.split_at(block.insert_at);
+        let mut updated = head.to_string();
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push_str(metadata);
+        updated.push('\n');
+        if !(tail.is_empty() || tail.starts_with('\n')) {
+            updated.push('\n');
+        }
+        updated.push_str(tail);
+        return updated;
+    };
+
+    let mut updated = content[..first.lines.start].to_string();
+    updated.push_str(metadata);
+    updated.push('\n');
+    let mut cursor = first.lines.end;
+    for entry in &owned[1..] {
+        updated.push_str(&content[cursor..entry.lines.start]);
+        cursor = entry.lines.end;
+    }
+    updated.push_str(&content[cursor..]);
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    use crate::model::FieldValue;
+    use crate::schema::builtin;
+
+    const REQUIREMENT_ADOC: &str = "= Response time\n\
+        :toc:\n\
+        :id: SYSREQ-001\n\
+        :type: system_requirement\n\
+        :name: Response time\n\
+        :specification: The hub SHALL respond \\\n  within 100ms.\n\
+        :derives_from: SCEN-001, SCEN-002\n\
+        \n\
+        == Rationale\n\
+        \n\
+        Users notice delays, see [[SCEN-003]].\n";
+
+    const REQUIREMENT_RST: &str = "Response time\n\
+        =============\n\
+        \n\
+        :id: SYSREQ-001\n\
+        :type: system_requirement\n\
+        :name: Response time\n\
+        :specification: The hub SHALL respond\n    within 100ms.\n\
+        :derives_from: SCEN-001, SCEN-002\n\
+        :author: Jane\n\
+        \n\
+        Body text.\n";
+
+    fn parse(content: &str, format: InputFormat) -> Result<Item, SaraError> {
+        parse_field_list_file(
+            content,
+            &PathBuf::from("SYSREQ-001"),
+            &PathBuf::from("/repo"),
+            format,
+            &ParseContext::default(),
+        )
+    }
+
+    #[test]
+    fn test_parse_field_line() {
+        assert_eq!(parse_field_line(":id: SOL-001"), Some(("id", "SOL-001")));
+        assert_eq!(parse_field_line(":toc:"), Some(("toc", "")));
+        assert_eq!(parse_field_line(":toc!:"), None);
+        assert_eq!(parse_field_line(":id:SOL-001"), None);
+        assert_eq!(parse_field_line("id: SOL-001"), None);
+    }
+
+    #[test]
+    fn test_parse_asciidoc_and_rst_metadata() {
+        for (content, format) in [
+            (REQUIREMENT_ADOC, InputFormat::AsciiDoc),
+            (REQUIREMENT_RST, InputFormat::Rst),
+        ] {
+            let item = parse(content, format).unwrap();
+            assert_eq!(item.id.as_str(), "SYSREQ-001");
+            assert_eq!(item.item_type, builtin::SYSTEM_REQUIREMENT);
+            assert_eq!(item.name, "Response time");
+            assert_eq!(
+                item.attributes.get("specification"),
+                Some(&FieldValue::text("The hub SHALL respond within 100ms."))
+            );
+            let derives_from: Vec<_> = item
+                .relationship_ids(builtin::DERIVES_FROM)
+                .map(|id| id.as_str())
+                .collect();
+            assert_eq!(derives_from, ["SCEN-001", "SCEN-002"]);
+        }
+    }
+
+    #[test]
+    fn test_documents_without_id_are_not_items() {
+        let content = "= User guide\n:toc:\n\nPlain documentation.\n";
+        assert!(!has_item_fields(content, InputFormat::AsciiDoc));
+        assert!(matches!(
+            parse(content, InputFormat::AsciiDoc),
+            Err(SaraError::MissingFrontmatter { .. })
+        ));
+        assert!(has_item_fields(REQUIREMENT_ADOC, InputFormat::AsciiDoc));
+        assert!(has_item_fields(REQUIREMENT_RST, InputFormat::Rst));
+    }
+
+    #[test]
+    fn test_update_fields_keeps_foreign_entries() {
+        let metadata = ":id: SYSREQ-001\n:type: system_requirement\n:name: Latency\n";
+
+        let updated = update_fields(REQUIREMENT_ADOC, metadata, InputFormat::AsciiDoc);
+        assert!(updated.starts_with("= Response time\n:toc:\n:id: SYSREQ-001\n"));
+        assert!(updated.contains(":name: Latency\n\n== Rationale"));
+        assert!(!updated.contains("derives_from"));
+
+        let updated = update_fields(REQUIREMENT_RST, metadata, InputFormat::Rst);
+        assert!(updated.contains("=============\n\n:id: SYSREQ-001\n"));
+        assert!(updated.contains(":name: Latency\n:author: Jane\n\nBody text.\n"));
+        assert!(!updated.contains("within 100ms"));
+    }
+
+    #[test]
+    fn test_update_fields_inserts_after_title() {
+        let metadata = ":id: SOL-001\n";
+        assert_eq!(
+            update_fields("= Hub\n\nBody.\n", metadata, InputFormat::AsciiDoc),
+            "= Hub\n:id: SOL-001\n\nBody.\n"
+        );
+        assert_eq!(
+            update_fields("Hub\n===\n\nBody.\n", metadata, InputFormat::Rst),
+            "Hub\n===\n\n:id: SOL-001\n\nBody.\n"
+        );
+        assert_eq!(
+            update_fields("Body.\n", metadata, InputFormat::Rst),
+            ":id: SOL-001\n\nBody.\n"
+        );
+    }
+}
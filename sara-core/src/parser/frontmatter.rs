@@ -1,27 +1,49 @@
-//! YAML frontmatter extraction from Markdown files.
+//! Frontmatter extraction from Markdown files.
+//!
+//! Frontmatter is YAML between `---` lines or, as in Hugo and Zola, TOML
+//! between `+++` lines.
 
 use std::path::Path;
 
 use crate::error::SaraError;
 
-/// Line that opens and closes a frontmatter block.
+/// Line that opens and closes a YAML frontmatter block.
 const DELIMITER: &str = "---";
 
+/// Line that opens and closes a TOML frontmatter block.
+const TOML_DELIMITER: &str = "+++";
+
+/// Returns the delimiter opening a frontmatter block on the first line, if
+/// any.
+fn opening_delimiter(first_line: Option<&str>) -> Option<&'static str> {
+    let first_line = first_line?.trim();
+    [DELIMITER, TOML_DELIMITER]
+        .into_iter()
+        .find(|delimiter| first_line == *delimiter)
+}
+
 /// Returns the index of the closing delimiter line, searching after the
 /// opening delimiter at index 0. Returns `None` when the block is unclosed.
 fn closing_delimiter_index(lines: &[&str]) -> Option<usize> {
+    let delimiter = opening_delimiter(lines.first().copied())?;
     lines
         .iter()
         .enumerate()
         .skip(1)
-        .find(|(_, line)| line.trim() == DELIMITER)
+        .find(|(_, line)| line.trim() == delimiter)
         .map(|(i, _)| i)
 }
 
-/// Extracts YAML frontmatter from Markdown content.
+/// Returns true if the content opens with a TOML (`+++`) frontmatter block.
+pub fn has_toml_frontmatter(content: &str) -> bool {
+    opening_delimiter(content.lines().next()) == Some(TOML_DELIMITER)
+}
+
+/// Extracts the frontmatter from Markdown content.
 ///
-/// Frontmatter must be at the start of the file, enclosed by `---` delimiters.
-/// Returns the raw YAML string between the delimiters.
+/// Frontmatter must be at the start of the file, enclosed by `---` (YAML) or
+/// `+++` (TOML) delimiters. Returns the raw text between the delimiters; use
+/// [`has_toml_frontmatter`] to tell the two apart.
 ///
 /// # Example
 /// ```text
@@ -35,23 +57,24 @@ fn closing_delimiter_index(lines: &[&str]) -> Option<usize> {
 pub fn extract_frontmatter(content: &str, file: &Path) -> Result<String, SaraError> {
     let lines: Vec<&str> = content.lines().collect();
 
-    if lines.first().map(|line| line.trim()) != Some(DELIMITER) {
+    let Some(delimiter) = opening_delimiter(lines.first().copied()) else {
         return Err(SaraError::MissingFrontmatter {
             file: file.to_path_buf(),
         });
-    }
+    };
 
     let end_idx = closing_delimiter_index(&lines).ok_or_else(|| SaraError::InvalidFrontmatter {
         file: file.to_path_buf(),
-        reason: "Missing closing `---` delimiter".to_string(),
+        reason: format!("Missing closing `{delimiter}` delimiter"),
     })?;
 
     Ok(lines[1..end_idx].join("\n"))
 }
 
-/// Checks if content has frontmatter (starts with `---`).
+/// Checks if content has frontmatter (starts with `---` or `+++`).
 pub fn has_frontmatter(content: &str) -> bool {
-    content.trim_start().starts_with(DELIMITER)
+    let content = content.trim_start();
+    content.starts_with(DELIMITER) || content.starts_with(TOML_DELIMITER)
 }
 
 /// Extracts just the body content after the frontmatter (FR-064).
@@ -61,10 +84,6 @@ pub fn has_frontmatter(content: &str) -> bool {
 pub fn extract_body(content: &str) -> String {
     let lines: Vec<&str> = content.lines().collect();
 
-    if lines.first().map(|line| line.trim()) != Some(DELIMITER) {
-        return content.to_string();
-    }
-
     match closing_delimiter_index(&lines) {
         Some(end_idx) => lines[end_idx + 1..].join("\n"),
         // No closing delimiter found, return original
@@ -72,6 +91,24 @@ pub fn extract_body(content: &str) -> String {
     }
 }
 
+/// Returns the byte offset at which the body starts, after the closing
+/// frontmatter delimiter line; 0 when there is no complete frontmatter.
+pub(crate) fn body_offset(content: &str) -> usize {
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next();
+    let Some(delimiter) = opening_delimiter(first) else {
+        return 0;
+    };
+    let mut offset = first.map_or(0, str::len);
+    for line in lines {
+        offset += line.len();
+        if line.trim() == delimiter {
+            return offset;
+        }
+    }
+    0
+}
+
 /// Updates the YAML frontmatter in content while preserving the body (FR-064).
 ///
 /// The `new_frontmatter` must include the `---` delimiters.
@@ -210,4 +247,12 @@ name: "New Name"
 
         assert_eq!(updated, "---\nid: test\nname: Updated\n---\n");
     }
+
+    #[test]
+    fn test_body_offset() {
+        let content = "---\r\nid: test\r\n---\r\n# Body\r\n";
+        assert_eq!(&content[body_offset(content)..], "# Body\r\n");
+        assert_eq!(body_offset("# No frontmatter"), 0);
+        assert_eq!(body_offset("---\nid: test\n"), 0);
+    }
 }
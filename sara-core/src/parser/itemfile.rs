@@ -0,0 +1,323 @@
+//! Standalone item files without a document body.
+//!
+//! Items without prose, such as interface parameters or test cases, can be
+//! kept in `.sara.yaml` and `.sara.toml` files. A file holds either a single
+//! item, written exactly like frontmatter, or a list of items: a YAML
+//! sequence, or a TOML `[[items]]` array of tables.
+//!
+//! ```text
+//! - id: PARAM-001
+//!   type: software_requirement
+//!   name: Baud rate
+//!   specification: The UART SHALL run at 115200 baud.
+//! - id: PARAM-002
+//!   ...
+//! ```
+
+use std::path::Path;
+
+use serde_yaml::Value;
+
+use crate::error::SaraError;
+use crate::model::{FIELD_ID, Item};
+use crate::parser::markdown::{build_item, markdown_headings};
+use crate::parser::yaml::{RawFrontmatter, toml_to_yaml};
+use crate::parser::{InputFormat, ParseContext};
+
+/// Top-level TOML key holding the items of a list file.
+const TOML_ITEMS_KEY: &str = "items";
+
+/// Parses a standalone item file and extracts all its items.
+pub(super) fn parse_item_file(
+    content: &str,
+    file_path: &Path,
+    repository: &Path,
+    format: InputFormat,
+    ctx: &ParseContext,
+) -> Result<Vec<Item>, SaraError> {
+    let entries = match format {
+        InputFormat::Toml => toml_entries(content, file_path)?,
+        _ => yaml_entries(content, file_path)?,
+    };
+
+    let invalid = |reason: String| match format {
+        InputFormat::Toml => SaraError::InvalidToml {
+            file: file_path.to_path_buf(),
+            reason,
+        },
+        _ => SaraError::InvalidYaml {
+            file: file_path.to_path_buf(),
+            reason,
+        },
+    };
+    let listed = entries.len() > 1;
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let frontmatter = RawFrontmatter::deserialize_in(entry, ctx.schema()).map_err(|e| {
+                invalid(if listed {
+                    format!("item {}: {e}", index + 1)
+                } else {
+                    e.to_string()
+                })
+            })?;
+            build_item(
+                &frontmatter,
+                "",
+                markdown_headings,
+                file_path,
+                repository,
+                ctx,
+            )
+        })
+        .collect()
+}
+
+/// Returns the item mappings of a YAML item file.
+fn yaml_entries(content: &str, file: &Path) -> Result<Vec<Value>, SaraError> {
+    let value: Value = serde_yaml::from_str(content).map_err(|e| SaraError::InvalidYaml {
+        file: file.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+    match value {
+        Value::Sequence(entries) => Ok(entries),
+        Value::Mapping(_) => Ok(vec![value]),
+        Value::Null => Ok(Vec::new()),
+        _ => Err(SaraError::InvalidYaml {
+            file: file.to_path_buf(),
+            reason: "expected an item mapping or a list of items".to_string(),
+        }),
+    }
+}
+
+/// Returns the item tables of a TOML item file, converted to YAML values.
+fn toml_entries(content: &str, file: &Path) -> Result<Vec<Value>, SaraError> {
+    let mut table: toml::Table = toml::from_str(content).map_err(|e| SaraError::InvalidToml {
+        file: file.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+    if table.len() == 1
+        && let Some(toml::Value::Array(entries)) = table.get_mut(TOML_ITEMS_KEY)
+    {
+        return Ok(entries.drain(..).map(toml_to_yaml).collect());
+    }
+    if table.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(vec![toml_to_yaml(toml::Value::Table(table))])
+}
+
+/// Rewrites the entry of one item in a list file, leaving the others as
+/// written.
+///
+/// `metadata` is the item mapping or table as generated for a single-item
+/// file. An entry starts at a top-level `- ` marker in YAML or an
+/// `[[items]]` header in TOML and runs up to the next one; comments and
+/// blank lines closing it stay in place. Returns `None` when the content is
+/// not such a list or no single entry holds the item.
+pub(crate) fn update_listed_item(
+    content: &str,
+    id: &str,
+    metadata: &str,
+    format: InputFormat,
+) -> Option<String> {
+    let starts_entry = |line: &str| match format {
+        InputFormat::Toml => line.trim() == format!("[[{TOML_ITEMS_KEY}]]"),
+        _ => line.starts_with("- ") || line.trim_end() == "-",
+    };
+
+    let mut starts = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if starts_entry(line) {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+    let ends = starts.iter().skip(1).copied().chain([content.len()]);
+    let mut matching = starts
+        .iter()
+        .zip(ends)
+        .map(|(&start, end)| start..start + entry_len(&content[start..end]))
+        .filter(|range| entry_id(&content[range.clone()], format).as_deref() == Some(id));
+    let range = matching.next()?;
+    if matching.next().is_some() {
+        return None;
+    }
+
+    let entry = match format {
+        InputFormat::Toml => format!("[[{TOML_ITEMS_KEY}]]\n{metadata}"),
+        _ => metadata
+            .lines()
+            .enumerate()
+            .map(|(index, line)| match (index, line) {
+                (0, line) => format!("- {line}\n"),
+                (_, "") => "\n".to_string(),
+                (_, line) => format!("  {line}\n"),
+            })
+            .collect(),
+    };
+    let mut updated = content.to_string();
+    updated.replace_range(range, &entry);
+    Some(updated)
+}
+
+/// Returns the length of an entry without the comments and blank lines
+/// closing it.
+fn entry_len(entry: &str) -> usize {
+    let mut len = entry.len();
+    for line in entry.split_inclusive('\n').rev() {
+        let trimmed = line.trim();
+        if !(trimmed.is_empty() || trimmed.starts_with('#')) {
+            break;
+        }
+        len -= line.len();
+    }
+    len
+}
+
+/// Returns the `id` written in a single list entry.
+fn entry_id(entry: &str, format: InputFormat) -> Option<String> {
+    let value = match format {
+        InputFormat::Toml => {
+            let mut table: toml::Table = toml::from_str(entry).ok()?;
+            match table.remove(TOML_ITEMS_KEY)? {
+                toml::Value::Array(mut entries) if entries.len() == 1 => {
+                    toml_to_yaml(entries.remove(0))
+                }
+                _ => return None,
+            }
+        }
+        _ => match serde_yaml::from_str(entry).ok()? {
+            Value::Sequence(mut entries) if entries.len() == 1 => entries.remove(0),
+            _ => return None,
+        },
+    };
+    value.get(FIELD_ID)?.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    use crate::model::FieldValue;
+    use crate::schema::builtin;
+
+    fn parse(content: &str, format: InputFormat) -> Result<Vec<Item>, SaraError> {
+        parse_item_file(
+            content,
+            &PathBuf::from("params.sara"),
+            &PathBuf::from("/repo"),
+            format,
+            &ParseContext::default(),
+        )
+    }
+
+    const PARAMS_YAML: &str = r#"- id: SWREQ-001
+  type: software_requirement
+  name: Baud rate
+  specification: The UART SHALL run at 115200 baud.
+  derives_from: [SYSREQ-001]
+- id: SWREQ-002
+  type: software_requirement
+  name: Parity
+  specification: The UART SHALL use even parity.
+"#;
+
+    const PARAMS_TOML: &str = r#"[[items]]
+id = "SWREQ-001"
+type = "software_requirement"
+name = "Baud rate"
+specification = "The UART SHALL run at 115200 baud."
+derives_from = ["SYSREQ-001"]
+
+[[items]]
+id = "SWREQ-002"
+type = "software_requirement"
+name = "Parity"
+specification = "The UART SHALL use even parity."
+"#;
+
+    #[test]
+    fn test_parse_item_lists() {
+        for (content, format) in [
+            (PARAMS_YAML, InputFormat::Yaml),
+            (PARAMS_TOML, InputFormat::Toml),
+        ] {
+            let items = parse(content, format).unwrap();
+            assert_eq!(items.len(), 2);
+            assert_eq!(items[0].id.as_str(), "SWREQ-001");
+            assert_eq!(items[0].item_type, builtin::SOFTWARE_REQUIREMENT);
+            assert_eq!(
+                items[0].attributes.get("specification"),
+                Some(&FieldValue::text("The UART SHALL run at 115200 baud."))
+            );
+            assert_eq!(items[0].relationship_ids(builtin::DERIVES_FROM).count(), 1);
+            assert_eq!(items[1].name, "Parity");
+        }
+    }
+
+    #[test]
+    fn test_parse_single_items() {
+        let yaml = "id: SOL-001\ntype: solution\nname: Hub\n";
+        let toml = "id = \"SOL-001\"\ntype = \"solution\"\nname = \"Hub\"\n";
+        for (content, format) in [(yaml, InputFormat::Yaml), (toml, InputFormat::Toml)] {
+            let items = parse(content, format).unwrap();
+            assert_eq!(items.len(), 1);
+            assert_eq!(items[0].id.as_str(), "SOL-001");
+        }
+    }
+
+    #[test]
+    fn test_update_listed_item_needs_a_single_entry_of_the_item() {
+        let metadata = "id: SWREQ-002\ntype: software_requirement\nname: Stop bits\n\
+                        specification: The UART SHALL use one stop bit.\n";
+        let second = PARAMS_YAML.find("- id: SWREQ-002").unwrap();
+        let updated =
+            update_listed_item(PARAMS_YAML, "SWREQ-002", metadata, InputFormat::Yaml).unwrap();
+        assert_eq!(
+            parse(&updated, InputFormat::Yaml).unwrap()[1].name,
+            "Stop bits"
+        );
+        assert!(updated.starts_with(&PARAMS_YAML[..second]));
+
+        assert_eq!(
+            update_listed_item(PARAMS_YAML, "SWREQ-003", metadata, InputFormat::Yaml),
+            None
+        );
+        let twice = format!("{PARAMS_YAML}{}", &PARAMS_YAML[second..]);
+        assert_eq!(
+            update_listed_item(&twice, "SWREQ-002", metadata, InputFormat::Yaml),
+            None
+        );
+        let single = "id: SWREQ-002\ntype: software_requirement\nname: Parity\n";
+        assert_eq!(
+            update_listed_item(single, "SWREQ-002", metadata, InputFormat::Yaml),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_entries() {
+        let err = parse(
+            "- id: SOL-001\n  type: solution\n  name: Hub\n- id: SOL-002\n",
+            InputFormat::Yaml,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(&err, SaraError::InvalidYaml { reason, .. } if reason.starts_with("item 2:"))
+        );
+
+        assert!(matches!(
+            parse("id = ", InputFormat::Toml),
+            Err(SaraError::InvalidToml { .. })
+        ));
+        assert!(matches!(
+            parse("just text", InputFormat::Yaml),
+            Err(SaraError::InvalidYaml { .. })
+        ));
+    }
+}
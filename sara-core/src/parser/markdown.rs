@@ -3,23 +3,61 @@
 use std::path::Path;
 
 use crate::error::SaraError;
-use crate::model::{Item, ItemBuilder, ItemId, SourceLocation};
-use crate::parser::frontmatter::extract_frontmatter;
-use crate::parser::yaml::parse_yaml_frontmatter;
-use crate::schema;
+use crate::model::{FieldValue, Item, ItemBuilder, ItemId, SourceLocation};
+use crate::parser::anchored::find_anchors;
+use crate::parser::frontmatter::{
+    body_offset, extract_body, extract_frontmatter, has_toml_frontmatter,
+};
+use crate::parser::yaml::{RawFrontmatter, parse_toml_frontmatter, parse_yaml_frontmatter};
+use crate::parser::{MentionPattern, ParseContext};
+use crate::schema::SectionDef;
 
 /// Parses a Markdown file and extracts the item.
 ///
-/// The file must contain YAML frontmatter delimited by `---`.
+/// The file must contain YAML frontmatter delimited by `---`, or TOML
+/// frontmatter delimited by `+++`. The body of the item stops at the first
+/// item anchored on a heading, if any.
 pub fn parse_markdown_file(
     content: &str,
     file_path: &Path,
     repository: &Path,
+    ctx: &ParseContext,
 ) -> Result<Item, SaraError> {
-    let yaml = extract_frontmatter(content, file_path)?;
+    let raw = extract_frontmatter(content, file_path)?;
+
+    let frontmatter = if has_toml_frontmatter(content) {
+        parse_toml_frontmatter(&raw, file_path, ctx.schema())?
+    } else {
+        parse_yaml_frontmatter(&raw, file_path, ctx.schema())?
+    };
+    let mut body = extract_body(content);
+    if let Some(anchor) = find_anchors(content).first() {
+        body = content[body_offset(content)..anchor.heading.start].to_string();
+    }
 
-    let frontmatter = parse_yaml_frontmatter(&yaml, file_path)?;
+    build_item(
+        &frontmatter,
+        &body,
+        markdown_headings,
+        file_path,
+        repository,
+        ctx,
+    )
+}
 
+/// Builds an item from its parsed metadata and document body.
+///
+/// Shared by every input format: `headings` locates the headings of the
+/// format so that declared body sections can be read.
+pub(super) fn build_item(
+    frontmatter: &RawFrontmatter,
+    body: &str,
+    headings: HeadingFinder,
+    file_path: &Path,
+    repository: &Path,
+    ctx: &ParseContext,
+) -> Result<Item, SaraError> {
+    let schema = ctx.schema();
     // Validate item ID format
     let item_id = ItemId::new(&frontmatter.id).map_err(|e| SaraError::InvalidFrontmatter {
         file: file_path.to_path_buf(),
@@ -28,6 +66,14 @@ pub fn parse_markdown_file(
 
     // Create source location
     let source = SourceLocation::new(repository, file_path);
+    let mentions = extract_mentions(body, &item_id, ctx.mention_pattern());
+    let relationships =
+        frontmatter
+            .to_relationships(schema)
+            .map_err(|reason| SaraError::InvalidFrontmatter {
+                file: file_path.to_path_buf(),
+                reason,
+            })?;
 
     // Build the item
     let mut builder = ItemBuilder::new()
@@ -35,14 +81,16 @@ pub fn parse_markdown_file(
         .item_type(frontmatter.item_type)
         .name(&frontmatter.name)
         .source(source)
-        .relationships(frontmatter.to_relationships());
+        .relationships(relationships)
+        .mentions(mentions)
+        .aliases(frontmatter.aliases.clone());
 
     if let Some(desc) = &frontmatter.description {
         builder = builder.description(desc);
     }
 
     // Populate the attributes the schema declares for this type.
-    if let Some(def) = schema::item_type_def(frontmatter.item_type.as_str()) {
+    if let Some(def) = schema.item_type(frontmatter.item_type.as_str()) {
         for field in &def.fields {
             match frontmatter.declared_field_value(field) {
                 Ok(Some(value)) => builder = builder.attribute(field.name.clone(), value),
@@ -55,12 +103,127 @@ pub fn parse_markdown_file(
                 }
             }
         }
+
+        for (field, text) in extract_sections(body, &def.sections, headings) {
+            builder = builder.attribute(field, FieldValue::Text(text));
+        }
     }
 
-    builder.build().map_err(|e| SaraError::InvalidFrontmatter {
-        file: file_path.to_path_buf(),
-        reason: e.to_string(),
-    })
+    builder
+        .build_in(schema)
+        .map_err(|e| SaraError::InvalidFrontmatter {
+            file: file_path.to_path_buf(),
+            reason: e.to_string(),
+        })
+}
+
+/// Returns the items a body mentions through `pattern`, without duplicates
+/// nor the item itself.
+fn extract_mentions(body: &str, own_id: &ItemId, pattern: &MentionPattern) -> Vec<ItemId> {
+    let mut mentions: Vec<ItemId> = Vec::new();
+    for mention in pattern.find(body) {
+        let id = ItemId::new_unchecked(mention.id);
+        if id != *own_id && !mentions.contains(&id) {
+            mentions.push(id);
+        }
+    }
+    mentions
+}
+
+/// A heading found in a document body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Heading<'a> {
+    /// Index of the heading's first line.
+    pub line: usize,
+    /// Nesting level; a lower level encloses a higher one.
+    pub level: usize,
+    /// Heading text.
+    pub text: &'a str,
+    /// Index of the first content line after the heading.
+    pub content_start: usize,
+}
+
+/// Finds the headings of a body split into lines, for one format.
+pub(super) type HeadingFinder = for<'a> fn(&[&'a str]) -> Vec<Heading<'a>>;
+
+/// Extracts the content of the declared sections from a document body.
+///
+/// A section runs from its heading to the next heading of the same or a
+/// higher level. Returns the field name and trimmed content of each section
+/// found with content, the first matching heading winning.
+fn extract_sections(
+    body: &str,
+    sections: &[SectionDef],
+    headings: HeadingFinder,
+) -> Vec<(String, String)> {
+    if sections.is_empty() {
+        return Vec::new();
+    }
+
+    let lines: Vec<&str> = body.lines().collect();
+    let headings = headings(&lines);
+
+    sections
+        .iter()
+        .filter_map(|section| {
+            let wanted = section.heading.trim_start_matches(['#', '=']).trim();
+            let position = headings
+                .iter()
+                .position(|h| h.text.eq_ignore_ascii_case(wanted))?;
+            let heading = &headings[position];
+            let end = headings[position + 1..]
+                .iter()
+                .find(|other| other.level <= heading.level)
+                .map_or(lines.len(), |other| other.line);
+            let text = lines[heading.content_start.min(end)..end]
+                .join("\n")
+                .trim()
+                .to_string();
+            (!text.is_empty()).then(|| (section.field.clone(), text))
+        })
+        .collect()
+}
+
+/// Finds the ATX headings of a Markdown body, ignoring fenced code blocks.
+pub(super) fn markdown_headings<'a>(lines: &[&'a str]) -> Vec<Heading<'a>> {
+    let mut headings = Vec::new();
+    let mut fence: Option<&str> = None;
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = match fence {
+                Some(open) if open == marker => None,
+                None => Some(marker),
+                other => other,
+            };
+            continue;
+        }
+        if fence.is_none()
+            && let Some((level, text)) = parse_heading(line)
+        {
+            headings.push(Heading {
+                line: index,
+                level,
+                text,
+                content_start: index + 1,
+            });
+        }
+    }
+    headings
+}
+
+/// Parses an ATX heading (`## Title`) into its level and text.
+pub(super) fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    let rest = &trimmed[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end()))
 }
 
 /// Extracts a name from a markdown file's first heading.
@@ -115,6 +278,7 @@ is_satisfied_by:
             SOLUTION_MD,
             &PathBuf::from("SOL-001.md"),
             &PathBuf::from("/repo"),
+            &ParseContext::default(),
         )
         .unwrap();
 
@@ -133,6 +297,7 @@ is_satisfied_by:
             REQUIREMENT_MD,
             &PathBuf::from("SYSREQ-001.md"),
             &PathBuf::from("/repo"),
+            &ParseContext::default(),
         )
         .unwrap();
 
@@ -156,8 +321,12 @@ type: solution
 name: "Test"
 ---
 "#;
-        let result =
-            parse_markdown_file(content, &PathBuf::from("test.md"), &PathBuf::from("/repo"));
+        let result = parse_markdown_file(
+            content,
+            &PathBuf::from("test.md"),
+            &PathBuf::from("/repo"),
+            &ParseContext::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -168,8 +337,12 @@ id: "SOL-001"
 name: "Test"
 ---
 "#;
-        let result =
-            parse_markdown_file(content, &PathBuf::from("test.md"), &PathBuf::from("/repo"));
+        let result = parse_markdown_file(
+            content,
+            &PathBuf::from("test.md"),
+            &PathBuf::from("/repo"),
+            &ParseContext::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -205,6 +378,7 @@ Chosen option: Microservices, because it provides better scalability.
             ADR_MD,
             &PathBuf::from("ADR-001.md"),
             &PathBuf::from("/repo"),
+            &ParseContext::default(),
         )
         .unwrap();
 
@@ -240,8 +414,12 @@ name: "Test Decision"
 status: proposed
 ---
 "#;
-        let result =
-            parse_markdown_file(content, &PathBuf::from("test.md"), &PathBuf::from("/repo"));
+        let result = parse_markdown_file(
+            content,
+            &PathBuf::from("test.md"),
+            &PathBuf::from("/repo"),
+            &ParseContext::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -255,8 +433,12 @@ deciders:
   - "Alice"
 ---
 "#;
-        let result =
-            parse_markdown_file(content, &PathBuf::from("test.md"), &PathBuf::from("/repo"));
+        let result = parse_markdown_file(
+            content,
+            &PathBuf::from("test.md"),
+            &PathBuf::from("/repo"),
+            &ParseContext::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -280,6 +462,7 @@ supersedes:
             content,
             &PathBuf::from("ADR-005.md"),
             &PathBuf::from("/repo"),
+            &ParseContext::default(),
         )
         .unwrap();
 
@@ -303,4 +486,80 @@ supersedes:
         let content_no_heading = "No heading here";
         assert_eq!(extract_name_from_content(content_no_heading), None);
     }
+
+    #[test]
+    fn test_parse_body_mentions() {
+        let content = "---\nid: \"UC-001\"\ntype: use_case\nname: \"Pairing\"\n---\n\
+                       # Pairing\n\nSee [[SOL-001]], [[UC-001]] and [[SOL-001]] again.\n";
+        let item = parse_markdown_file(
+            content,
+            &PathBuf::from("UC-001.md"),
+            &PathBuf::from("/repo"),
+            &ParseContext::default(),
+        )
+        .unwrap();
+
+        assert_eq!(item.mentions, [ItemId::new_unchecked("SOL-001")]);
+        assert!(item.relationships.is_empty());
+    }
+
+    #[test]
+    fn test_parse_body_mentions_with_the_context_pattern() {
+        let content = "---\nid: \"UC-001\"\ntype: use_case\nname: \"Pairing\"\n---\n\
+                       # Pairing\n\nSee @SOL-001, not [[SOL-002]].\n";
+        let ctx =
+            ParseContext::default().with_mention_pattern(MentionPattern::parse("@{id}").unwrap());
+        let item = parse_markdown_file(
+            content,
+            &PathBuf::from("UC-001.md"),
+            &PathBuf::from("/repo"),
+            &ctx,
+        )
+        .unwrap();
+
+        assert_eq!(item.mentions, [ItemId::new_unchecked("SOL-001")]);
+    }
+
+    fn section(heading: &str, field: &str) -> SectionDef {
+        SectionDef {
+            heading: heading.to_string(),
+            field: field.to_string(),
+            required: false,
+        }
+    }
+
+    #[test]
+    fn test_extract_sections() {
+        let body = "# Title\n\n## Rationale\n\nBecause latency matters.\n\n\
+                    ### Details\n\nMeasured on target.\n\n```md\n## Verification\n```\n\n\
+                    ## verification ##\n\nRun the bench.\n\n## Notes\n\n## Empty\n";
+        let sections = [
+            section("## Rationale", "rationale"),
+            section("Verification", "verification"),
+            section("Empty", "empty"),
+            section("Missing", "missing"),
+        ];
+
+        let extracted = extract_sections(body, &sections, markdown_headings);
+        assert_eq!(extracted.len(), 2);
+        assert_eq!(extracted[0].0, "rationale");
+        assert_eq!(
+            extracted[0].1,
+            "Because latency matters.\n\n### Details\n\nMeasured on target.\n\n\
+             ```md\n## Verification\n```"
+        );
+        assert_eq!(
+            extracted[1],
+            ("verification".to_string(), "Run the bench.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_heading() {
+        assert_eq!(parse_heading("## Rationale"), Some((2, "Rationale")));
+        assert_eq!(parse_heading("   # Title #"), Some((1, "Title")));
+        assert_eq!(parse_heading("#Title"), None);
+        assert_eq!(parse_heading("    # Code"), None);
+        assert_eq!(parse_heading("####### Seven"), None);
+    }
 }
@@ -0,0 +1,177 @@
+//! Inline item mentions in document bodies.
+//!
+//! A mention is an item identifier written in prose through a pattern such as
+//! `[[SYSREQ-002]]`: literal text around a single `{id}` placeholder. The
+//! pattern travels with the [`super::ParseContext`] documents are parsed in
+//! (typically built by the CLI from `Config`); without one, the default
+//! `[[{id}]]` applies.
+
+use std::ops::Range;
+
+/// Placeholder standing for the identifier in a mention pattern.
+const ID_PLACEHOLDER: &str = "{id}";
+
+/// Pattern used when none is configured.
+pub const DEFAULT_MENTION_PATTERN: &str = "[[{id}]]";
+
+/// One mention found in a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    /// Mentioned identifier, as written.
+    pub id: String,
+    /// Byte range of the whole mention, delimiters included.
+    pub range: Range<usize>,
+}
+
+/// Compiled mention pattern: the literal text around the `{id}` placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionPattern {
+    prefix: String,
+    suffix: String,
+}
+
+impl MentionPattern {
+    /// Compiles a pattern such as `[[{id}]]` or `@{id}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem when the pattern does not hold
+    /// exactly one `{id}` placeholder or has no literal text before it, which
+    /// would make every word of a body a mention.
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let Some((prefix, suffix)) = pattern.split_once(ID_PLACEHOLDER) else {
+            return Err(format!("mention pattern `{pattern}` has no `{{id}}`"));
+        };
+        if suffix.contains(ID_PLACEHOLDER) {
+            return Err(format!(
+                "mention pattern `{pattern}` has more than one `{{id}}`"
+            ));
+        }
+        if prefix.is_empty() {
+            return Err(format!(
+                "mention pattern `{pattern}` needs literal text before `{{id}}`"
+            ));
+        }
+        Ok(Self {
+            prefix: prefix.to_string(),
+            suffix: suffix.to_string(),
+        })
+    }
+
+    /// Writes a mention of an identifier.
+    #[must_use]
+    pub fn render(&self, id: &str) -> String {
+        format!("{}{id}{}", self.prefix, self.suffix)
+    }
+
+    /// Finds the mentions of a Markdown body, ignoring fenced code blocks and
+    /// inline code spans.
+    #[must_use]
+    pub fn find(&self, body: &str) -> Vec<Mention> {
+        let mut mentions = Vec::new();
+        let mut fence: Option<&str> = None;
+        let mut offset = 0;
+        for line in body.split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+                fence = match fence {
+                    Some(open) if open == marker => None,
+                    None => Some(marker),
+                    other => other,
+                };
+            } else if fence.is_none() {
+                self.find_in_line(line, offset, &mut mentions);
+            }
+            offset += line.len();
+        }
+        mentions
+    }
+
+    /// Collects the mentions of one line outside inline code spans.
+    fn find_in_line(&self, line: &str, offset: usize, mentions: &mut Vec<Mention>) {
+        let mut start = 0;
+        for (index, span) in line.split('`').enumerate() {
+            // Odd segments sit between backticks.
+            if index % 2 == 0 {
+                self.find_in_span(span, offset + start, mentions);
+            }
+            start += span.len() + 1;
+        }
+    }
+
+    /// Collects the mentions of a span of prose.
+    fn find_in_span(&self, span: &str, offset: usize, mentions: &mut Vec<Mention>) {
+        let mut cursor = 0;
+        while let Some(found) = span[cursor..].find(&self.prefix) {
+            let id_start = cursor + found + self.prefix.len();
+            let rest = &span[id_start..];
+            let mut id_len = rest
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | ':')))
+                .unwrap_or(rest.len());
+            if self.suffix.is_empty() {
+                // Punctuation closing a sentence is not part of the id.
+                id_len = rest[..id_len].trim_end_matches(['-', '_', ':']).len();
+            }
+            let end = id_start + id_len;
+            if id_len > 0 && span[end..].starts_with(&self.suffix) {
+                let mention_end = end + self.suffix.len();
+                mentions.push(Mention {
+                    id: span[id_start..end].to_string(),
+                    range: offset + cursor + found..offset + mention_end,
+                });
+                cursor = mention_end;
+            } else {
+                cursor = id_start;
+            }
+        }
+    }
+}
+
+impl Default for MentionPattern {
+    fn default() -> Self {
+        Self::parse(DEFAULT_MENTION_PATTERN).expect("default mention pattern is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(pattern: &MentionPattern, body: &str) -> Vec<String> {
+        pattern.find(body).into_iter().map(|m| m.id).collect()
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_patterns() {
+        assert!(MentionPattern::parse("[[{id}]]").is_ok());
+        assert!(MentionPattern::parse("@{id}").is_ok());
+        assert!(MentionPattern::parse("[[id]]").is_err());
+        assert!(MentionPattern::parse("{id}").is_err());
+        assert!(MentionPattern::parse("[[{id}|{id}]]").is_err());
+    }
+
+    #[test]
+    fn test_find_default_pattern() {
+        let pattern = MentionPattern::default();
+        let body = "See [[SYSREQ-002]] and [[platform:SOL-001]].\n\
+                    Not [[ ]] nor [[bad id]] nor `[[SYSREQ-003]]`.\n\
+                    ```\n[[SYSREQ-004]]\n```\n[[SYSREQ-005]]";
+        assert_eq!(
+            ids(&pattern, body),
+            ["SYSREQ-002", "platform:SOL-001", "SYSREQ-005"]
+        );
+
+        let first = &pattern.find(body)[0];
+        assert_eq!(&body[first.range.clone()], "[[SYSREQ-002]]");
+    }
+
+    #[test]
+    fn test_find_pattern_without_suffix() {
+        let pattern = MentionPattern::parse("@{id}").unwrap();
+        assert_eq!(
+            ids(&pattern, "Ask @SYSREQ-002, then @UC-001-. Mail a@"),
+            ["SYSREQ-002", "UC-001"]
+        );
+        assert_eq!(pattern.render("UC-001"), "@UC-001");
+    }
+}
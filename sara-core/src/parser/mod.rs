@@ -5,17 +5,34 @@
 //!
 //! Use [`InputFormat`] with [`parse_metadata`] or [`parse_document`] to parse
 //! content without depending on format-specific functions.
+//!
+//! Item types, fields and relations are read against the schema of the
+//! [`ParseContext`] handed to the parser.
 
+mod anchored;
+mod asciidoc;
+mod context;
+mod fieldlist;
 mod frontmatter;
+mod itemfile;
 mod markdown;
+mod mention;
+mod rst;
 mod yaml;
 
 use std::path::Path;
 
+pub(crate) use anchored::{find_anchors, update_anchored_item};
+#[doc(inline)]
+pub use context::ParseContext;
+use frontmatter::body_offset;
 #[doc(inline)]
-pub use frontmatter::{has_frontmatter, update_frontmatter};
+pub use frontmatter::{has_frontmatter, has_toml_frontmatter, update_frontmatter};
+pub(crate) use itemfile::update_listed_item;
 #[doc(inline)]
 pub use markdown::extract_name_from_content;
+#[doc(inline)]
+pub use mention::{DEFAULT_MENTION_PATTERN, Mention, MentionPattern};
 
 use crate::error::SaraError;
 use crate::model::Item;
@@ -23,8 +40,42 @@ use crate::model::Item;
 /// Supported input formats for document parsing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputFormat {
-    /// Markdown with YAML frontmatter.
+    /// Markdown with YAML (`---`) or TOML (`+++`) frontmatter.
     Markdown,
+    /// AsciiDoc with metadata in the document header attributes.
+    AsciiDoc,
+    /// reStructuredText with metadata in a leading field list.
+    Rst,
+    /// Standalone `.sara.yaml` item file: one item or a list of items.
+    Yaml,
+    /// Standalone `.sara.toml` item file: one item or an `[[items]]` list.
+    Toml,
+}
+
+impl InputFormat {
+    /// Returns the format of a document from its file extension, or `None`
+    /// when the file is not a supported document.
+    ///
+    /// YAML and TOML files are items only with the `.sara.yaml`,
+    /// `.sara.yml` or `.sara.toml` double extension, so that other data and
+    /// configuration files are left alone.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if file_name.ends_with(".sara.yaml") || file_name.ends_with(".sara.yml") {
+            return Some(Self::Yaml);
+        }
+        if file_name.ends_with(".sara.toml") {
+            return Some(Self::Toml);
+        }
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "adoc" | "asciidoc" | "asc" => Some(Self::AsciiDoc),
+            "rst" => Some(Self::Rst),
+            _ => None,
+        }
+    }
 }
 
 /// Parses content and extracts an [`Item`].
@@ -33,15 +84,124 @@ pub enum InputFormat {
 ///
 /// # Errors
 ///
-/// Returns `SaraError` if the content cannot be parsed in the given format.
+/// Returns `SaraError` if the content cannot be parsed in the given format,
+/// or if it does not define exactly one item.
 pub fn parse_metadata(
     content: &str,
     file_path: &Path,
     repository: &Path,
     format: InputFormat,
+    ctx: &ParseContext,
 ) -> Result<Item, SaraError> {
     match format {
-        InputFormat::Markdown => markdown::parse_markdown_file(content, file_path, repository),
+        InputFormat::Markdown if !anchored::has_anchored_items(content) => {
+            markdown::parse_markdown_file(content, file_path, repository, ctx)
+        }
+        InputFormat::AsciiDoc | InputFormat::Rst => {
+            fieldlist::parse_field_list_file(content, file_path, repository, format, ctx)
+        }
+        InputFormat::Markdown | InputFormat::Yaml | InputFormat::Toml => {
+            let mut items = parse_document(content, file_path, repository, format, ctx)?;
+            if items.len() != 1 {
+                return Err(SaraError::InvalidFrontmatter {
+                    file: file_path.to_path_buf(),
+                    reason: format!("expected a single item, found {}", items.len()),
+                });
+            }
+            Ok(items.remove(0))
+        }
+    }
+}
+
+/// Parses content and extracts every [`Item`] it defines.
+///
+/// Documents define a single item, except standalone item files which may
+/// hold a list of items and Markdown documents which may anchor items on
+/// their headings, after the item of their frontmatter if any.
+///
+/// # Errors
+///
+/// Returns `SaraError` if the content cannot be parsed in the given format.
+pub fn parse_document(
+    content: &str,
+    file_path: &Path,
+    repository: &Path,
+    format: InputFormat,
+    ctx: &ParseContext,
+) -> Result<Vec<Item>, SaraError> {
+    match format {
+        InputFormat::Yaml | InputFormat::Toml => {
+            itemfile::parse_item_file(content, file_path, repository, format, ctx)
+        }
+        InputFormat::Markdown if anchored::has_anchored_items(content) => {
+            let mut items = Vec::new();
+            if has_frontmatter(content) {
+                items.push(markdown::parse_markdown_file(
+                    content, file_path, repository, ctx,
+                )?);
+            }
+            items.extend(anchored::parse_anchored_items(
+                content, file_path, repository, ctx,
+            )?);
+            Ok(items)
+        }
+        _ => parse_metadata(content, file_path, repository, format, ctx).map(|item| vec![item]),
+    }
+}
+
+/// Checks if content carries item metadata in the given format.
+///
+/// Markdown needs a frontmatter block or items anchored on headings;
+/// AsciiDoc and reStructuredText need a header field list holding an `id`,
+/// so ordinary documents without item metadata are told apart from
+/// malformed items. Standalone item files are
+/// all metadata and only need some content.
+#[must_use]
+pub fn has_metadata(content: &str, format: InputFormat) -> bool {
+    match format {
+        InputFormat::Markdown => has_frontmatter(content) || anchored::has_anchored_items(content),
+        InputFormat::AsciiDoc | InputFormat::Rst => fieldlist::has_item_fields(content, format),
+        InputFormat::Yaml | InputFormat::Toml => !content.trim().is_empty(),
+    }
+}
+
+/// Replaces the item metadata of content while preserving the rest.
+///
+/// `metadata` is a block produced by [`crate::generator::generate_metadata`]
+/// for the same format. In AsciiDoc and reStructuredText headers, entries
+/// unknown to the schema (such as `:toc:`) are kept; a document without
+/// metadata gets the block right after its title. A standalone item file is
+/// replaced as a whole.
+#[must_use]
+pub fn update_metadata(content: &str, metadata: &str, format: InputFormat) -> String {
+    match format {
+        InputFormat::Markdown => update_frontmatter(content, metadata),
+        InputFormat::AsciiDoc | InputFormat::Rst => {
+            fieldlist::update_fields(content, metadata, format)
+        }
+        InputFormat::Yaml | InputFormat::Toml => metadata.to_string(),
+    }
+}
+
+/// Extracts the document title, used as the default item name.
+#[must_use]
+pub fn extract_title(content: &str, format: InputFormat) -> Option<String> {
+    match format {
+        InputFormat::Markdown => extract_name_from_content(content),
+        InputFormat::AsciiDoc => asciidoc::title(content),
+        InputFormat::Rst => rst::title(content),
+        InputFormat::Yaml | InputFormat::Toml => None,
+    }
+}
+
+/// Returns the byte offset at which the body starts, after the metadata.
+pub(crate) fn body_start(content: &str, format: InputFormat) -> usize {
+    match format {
+        InputFormat::Markdown => body_offset(content),
+        InputFormat::AsciiDoc | InputFormat::Rst => {
+            fieldlist::read_block(content, format).body_start
+        }
+        InputFormat::Yaml | InputFormat::Toml => content.len(),
     }
 }
 
@@ -49,9 +209,11 @@ pub fn parse_metadata(
 mod tests {
     use std::path::PathBuf;
 
+    use std::sync::Arc;
+
     use super::*;
 
-    use crate::schema::builtin;
+    use crate::schema::{Schema, builtin};
 
     const SOLUTION_MD: &str = r#"---
 id: "SOL-001"
@@ -73,6 +235,7 @@ Body content.
             &PathBuf::from("SOL-001.md"),
             &PathBuf::from("/repo"),
             InputFormat::Markdown,
+            &ParseContext::default(),
         )
         .unwrap();
 
@@ -84,6 +247,47 @@ Body content.
         assert_eq!(is_refined_by[0].as_str(), "UC-001");
     }
 
+    #[test]
+    fn test_parse_metadata_reads_against_the_context_schema() {
+        let mut schema = Schema::builtin();
+        for def in &mut schema.item_types {
+            if def.id == "solution" {
+                def.aliases.push("offer".to_string());
+            }
+        }
+        let ctx = ParseContext::new(Arc::new(schema));
+        let content = SOLUTION_MD.replace("type: solution", "type: offer");
+        let parse = |ctx: &ParseContext| {
+            parse_metadata(
+                &content,
+                &PathBuf::from("SOL-001.md"),
+                &PathBuf::from("/repo"),
+                InputFormat::Markdown,
+                ctx,
+            )
+        };
+
+        assert_eq!(parse(&ctx).unwrap().item_type, builtin::SOLUTION);
+        assert!(parse(&ParseContext::default()).is_err());
+    }
+
+    #[test]
+    fn test_input_format_from_path() {
+        let format = |name: &str| InputFormat::from_path(Path::new(name));
+        assert_eq!(format("docs/SOL-001.md"), Some(InputFormat::Markdown));
+        assert_eq!(format("SOL-001.markdown"), Some(InputFormat::Markdown));
+        assert_eq!(format("SOL-001.adoc"), Some(InputFormat::AsciiDoc));
+        assert_eq!(format("SOL-001.ASCIIDOC"), Some(InputFormat::AsciiDoc));
+        assert_eq!(format("SOL-001.rst"), Some(InputFormat::Rst));
+        assert_eq!(format("params.sara.yaml"), Some(InputFormat::Yaml));
+        assert_eq!(format("params.SARA.yml"), Some(InputFormat::Yaml));
+        assert_eq!(format("params.sara.toml"), Some(InputFormat::Toml));
+        assert_eq!(format("sara.toml"), None);
+        assert_eq!(format("data.yaml"), None);
+        assert_eq!(format("SOL-001.txt"), None);
+        assert_eq!(format("README"), None);
+    }
+
     #[test]
     fn test_input_format_debug() {
         assert_eq!(format!("{:?}", InputFormat::Markdown), "Markdown");
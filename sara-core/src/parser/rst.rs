@@ -0,0 +1,236 @@
+//! reStructuredText document header and section title parsing.
+//!
+//! Item metadata lives in the field list opening the document, right after
+//! the optional title, like a docinfo block:
+//!
+//! ```text
+//! Response time
+//! =============
+//!
+//! :id: SYSREQ-001
+//! :type: system_requirement
+//! :name: Response time
+//! :derives_from: SCEN-001, SCEN-002
+//! ```
+
+use crate::parser::fieldlist::{FieldBlock, FieldEntry, parse_field_line};
+use crate::parser::markdown::Heading;
+
+/// Returns the adornment character of a line made of one repeated
+/// punctuation character, e.g. `=====`.
+fn adornment(line: &str) -> Option<char> {
+    let line = line.trim_end();
+    let first = line.chars().next()?;
+    (first.is_ascii_punctuation() && line.chars().all(|c| c == first)).then_some(first)
+}
+
+/// A section title and the lines it spans.
+struct Title<'a> {
+    text: &'a str,
+    /// Adornment character and whether the title is overlined too.
+    style: (char, bool),
+    /// Number of lines spanned, adornments included.
+    span: usize,
+}
+
+/// Recognizes a section title starting at `lines[index]`.
+fn title_at<'a>(lines: &[&'a str], index: usize) -> Option<Title<'a>> {
+    let line = |i: usize| lines.get(i).map(|l| l.trim_end());
+    let is_text = |text: &str| {
+        !text.is_empty() && !text.starts_with(char::is_whitespace) && adornment(text).is_none()
+    };
+
+    let first = line(index)?;
+    if let Some(mark) = adornment(first) {
+        // Overlined title: adornment, text, same adornment.
+        let text = line(index + 1)?;
+        let under = line(index + 2)?;
+        let width = text.trim().chars().count();
+        return (!text.trim().is_empty()
+            && adornment(under) == Some(mark)
+            && under.chars().count() >= width)
+            .then(|| Title {
+                text: text.trim(),
+                style: (mark, true),
+                span: 3,
+            });
+    }
+
+    let under = line(index + 1)?;
+    let mark = adornment(under)?;
+    (is_text(first) && under.chars().count() >= first.chars().count()).then_some(Title {
+        text: first,
+        style: (mark, false),
+        span: 2,
+    })
+}
+
+/// Reads the field list opening the document.
+///
+/// Leading blank lines and the document title are skipped; the field list
+/// must come next. Indented lines continue the value of the field above.
+pub(super) fn read_header(content: &str) -> FieldBlock {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let offsets: Vec<usize> = lines
+        .iter()
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some(start)
+        })
+        .chain([content.len()])
+        .collect();
+    let text = |i: usize| lines[i].trim_end_matches(['\n', '\r']);
+    let skip_blank = |mut i: usize| {
+        while i < lines.len() && text(i).trim().is_empty() {
+            i += 1;
+        }
+        i
+    };
+    let trimmed: Vec<&str> = (0..lines.len()).map(text).collect();
+
+    let mut index = skip_blank(0);
+    if let Some(title) = title_at(&trimmed, index) {
+        index = skip_blank(index + title.span);
+    }
+
+    let mut block = FieldBlock {
+        insert_at: if index == skip_blank(0) {
+            0
+        } else {
+            offsets[index]
+        },
+        ..FieldBlock::default()
+    };
+    while index < lines.len() {
+        let Some((key, value)) = parse_field_line(text(index)) else {
+            break;
+        };
+        let start = index;
+        let mut value = value.to_string();
+        index += 1;
+        while index < lines.len()
+            && text(index).starts_with(char::is_whitespace)
+            && !text(index).trim().is_empty()
+        {
+            value.push(' ');
+            value.push_str(text(index).trim());
+            index += 1;
+        }
+        block.entries.push(FieldEntry {
+            key: key.to_string(),
+            value,
+            lines: offsets[start]..offsets[index],
+        });
+    }
+    block.body_start = block
+        .entries
+        .last()
+        .map_or(block.insert_at, |e| e.lines.end);
+    block
+}
+
+/// Returns the document title, the first section title of the document.
+pub(super) fn title(content: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    headings(&lines).first().map(|h| h.text.to_string())
+}
+
+/// Finds the section titles of a reStructuredText body.
+///
+/// Levels follow the order in which adornment styles are first met, as in
+/// reStructuredText itself.
+pub(super) fn headings<'a>(lines: &[&'a str]) -> Vec<Heading<'a>> {
+    let mut styles: Vec<(char, bool)> = Vec::new();
+    let mut headings = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let follows_blank = index == 0 || lines[index - 1].trim().is_empty();
+        match title_at(lines, index).filter(|_| follows_blank) {
+            Some(title) => {
+                let level = match styles.iter().position(|s| *s == title.style) {
+                    Some(position) => position + 1,
+                    None => {
+                        styles.push(title.style);
+                        styles.len()
+                    }
+                };
+                headings.push(Heading {
+                    line: index,
+                    level,
+                    text: title.text,
+                    content_start: index + title.span,
+                });
+                index += title.span;
+            }
+            None => index += 1,
+        }
+    }
+    headings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_header() {
+        let content = "\nHub\n===\n\n:id: SOL-001\n:description: First line\n   second line\n\
+                       :toc:\n\n:name: Body\n";
+        let block = read_header(content);
+        let entries: Vec<_> = block
+            .entries
+            .iter()
+            .map(|e| (e.key.as_str(), e.value.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                ("id", "SOL-001"),
+                ("description", "First line second line"),
+                ("toc", "")
+            ]
+        );
+        assert_eq!(&content[block.body_start..], "\n:name: Body\n");
+
+        let block = read_header("Hub\n===\n\nBody.\n");
+        assert!(block.entries.is_empty());
+        assert_eq!(block.insert_at, "Hub\n===\n\n".len());
+    }
+
+    #[test]
+    fn test_title_and_headings() {
+        assert_eq!(
+            title("=====\n Hub \n=====\n\n:id: SOL-001\n"),
+            Some("Hub".to_string())
+        );
+        assert_eq!(title("Just a paragraph.\n"), None);
+
+        let lines = [
+            "Rationale",
+            "---------",
+            "",
+            "Because.",
+            "",
+            "Details",
+            "~~~~~~~",
+            "",
+            "Verification",
+            "------------",
+            "Too long",
+            "---",
+        ];
+        let found: Vec<_> = headings(&lines)
+            .into_iter()
+            .map(|h| (h.line, h.level, h.text))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (0, 1, "Rationale"),
+                (5, 2, "Details"),
+                (8, 1, "Verification")
+            ]
+        );
+    }
+}
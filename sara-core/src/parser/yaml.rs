@@ -3,51 +3,128 @@
 //! Provides the input adapter for YAML frontmatter. Deserializes raw YAML
 //! strings into `RawFrontmatter` and converts them to core model types
 //! (`Relationship`, `ItemId`, etc.) by resolving every field and relation
-//! name against the active schema.
+//! name against the schema of the parse. TOML metadata is converted to the YAML
+//! value model first, so both languages resolve identically.
 
 use std::path::Path;
 
 use indexmap::IndexMap;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 
 use crate::error::SaraError;
-use crate::model::{FieldValue, ItemId, ItemType, Relationship, RelationshipType};
-use crate::schema::{self, FieldDef, FieldType};
+use crate::model::{
+    AliasUse, FIELD_ID, FieldValue, ItemAttributes, ItemId, ItemType, Relationship,
+    RelationshipType,
+};
+use crate::schema::{FieldDef, FieldType, Schema};
 
 /// Raw frontmatter structure for deserialization.
 ///
 /// Represents the YAML frontmatter as it appears in Markdown files. Only the
 /// core identity fields have a dedicated member; every schema-declared field
 /// or relation is captured by name in [`Self::extra`] and resolved against
-/// the active schema, so custom types and relations parse exactly like the
-/// built-in ones.
+/// the schema of the parse, so custom types and relations parse exactly like the
+/// built-in ones. Former names declared as schema aliases are resolved to
+/// their canonical names while reading, and recorded in [`Self::aliases`].
 ///
 /// The serde member names must mirror the canonical core field names
 /// (`crate::model::{FIELD_ID, FIELD_TYPE, FIELD_NAME, FIELD_DESCRIPTION}`) —
 /// serde attributes cannot reference constants, so a guard test pins the
 /// correspondence.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct RawFrontmatter {
     /// Unique identifier (required).
     pub id: String,
 
     /// Item type (required).
-    #[serde(rename = "type")]
     pub item_type: ItemType,
 
     /// Human-readable name (required).
     pub name: String,
 
     /// Description (optional).
-    #[serde(default)]
     pub description: Option<String>,
 
     /// Remaining frontmatter entries, keyed by field or relation name.
-    #[serde(flatten)]
     pub extra: IndexMap<String, serde_yaml::Value>,
+
+    /// Aliases the frontmatter used instead of canonical names.
+    pub aliases: Vec<AliasUse>,
+}
+
+/// Frontmatter entries as written, before aliases are resolved.
+#[derive(Deserialize)]
+struct WrittenFrontmatter {
+    id: String,
+    #[serde(rename = "type")]
+    item_type: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(flatten)]
+    extra: IndexMap<String, serde_yaml::Value>,
 }
 
 impl RawFrontmatter {
+    /// Deserializes frontmatter, resolving its names against `schema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error when an entry is malformed, the type
+    /// is unknown to the schema, or an entry is set twice through an alias.
+    pub fn deserialize_in<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        schema: &Schema,
+    ) -> Result<Self, D::Error> {
+        let written = WrittenFrontmatter::deserialize(deserializer)?;
+        Self::resolve(written, schema).map_err(serde::de::Error::custom)
+    }
+
+    /// Resolves the written type, field and relation names to canonical
+    /// ones.
+    fn resolve(written: WrittenFrontmatter, schema: &Schema) -> Result<Self, String> {
+        let mut aliases = Vec::new();
+        let mut resolve = |name: String, canonical: Option<&str>| match canonical {
+            Some(canonical) if canonical != name => {
+                aliases.push(AliasUse {
+                    alias: name,
+                    canonical: canonical.to_string(),
+                });
+                canonical.to_string()
+            }
+            _ => name,
+        };
+
+        let def = schema
+            .item_type_by_name(&written.item_type)
+            .ok_or_else(|| format!("unknown item type `{}`", written.item_type))?;
+        let item_type = ItemType::from_id_in(&def.id, schema)
+            .ok_or_else(|| format!("unknown item type `{}`", written.item_type))?;
+        resolve(written.item_type, Some(&def.id));
+
+        let mut extra = IndexMap::with_capacity(written.extra.len());
+        for (key, value) in written.extra {
+            let canonical = def
+                .field_by_name(&key)
+                .map(|f| f.name.as_str())
+                .or_else(|| schema.relation_by_name(&key).map(|r| r.id.as_str()));
+            let key = resolve(key, canonical);
+            if extra.contains_key(&key) {
+                return Err(format!("`{key}` is set twice, once through an alias"));
+            }
+            extra.insert(key, value);
+        }
+
+        Ok(Self {
+            id: written.id,
+            item_type,
+            name: written.name,
+            description: written.description,
+            extra,
+            aliases,
+        })
+    }
+
     /// Returns the value of a schema-declared field as a typed [`FieldValue`].
     ///
     /// Absent fields and empty lists yield `Ok(None)`.
@@ -66,46 +143,138 @@ impl RawFrontmatter {
 
     /// Converts all relation entries to a Vec of Relationships.
     ///
-    /// Each relation of the active schema is read from the frontmatter entry
-    /// carrying its id; both a single id string and a sequence of ids are
-    /// accepted. Entries that match no relation of the schema are ignored.
-    #[must_use]
-    pub fn to_relationships(&self) -> Vec<Relationship> {
+    /// Each relation of `schema` is read from the frontmatter entry
+    /// carrying its id; a single id string, a sequence of ids, and links
+    /// written as `{id: ..., attribute: value}` mappings are accepted.
+    /// Entries that match no relation of the schema are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable reason when a link mapping has no `id`, or
+    /// carries an attribute the schema does not declare for the relation or
+    /// a value that does not match its declared type.
+    pub fn to_relationships(&self, schema: &Schema) -> Result<Vec<Relationship>, String> {
         let mut rels = Vec::new();
 
-        for def in &schema::active().relations {
+        for def in &schema.relations {
             let Some(value) = self.extra.get(&def.id) else {
                 continue;
             };
-            let Some(rel_type) = RelationshipType::from_id(&def.id) else {
+            let Some(rel_type) = RelationshipType::from_id_in(&def.id, schema) else {
                 continue;
             };
-            match value {
-                serde_yaml::Value::String(id) => {
-                    rels.push(Relationship::new(ItemId::new_unchecked(id), rel_type));
-                }
-                serde_yaml::Value::Sequence(ids) => {
-                    rels.extend(ids.iter().filter_map(|entry| {
-                        let id = entry.as_str()?;
-                        Some(Relationship::new(ItemId::new_unchecked(id), rel_type))
-                    }));
-                }
-                _ => {}
+            let entries = match value {
+                serde_yaml::Value::Sequence(entries) => entries.as_slice(),
+                entry => std::slice::from_ref(entry),
+            };
+            for entry in entries {
+                let link = link_from_yaml(entry, rel_type, schema.link_attributes(&def.id))
+                    .map_err(|reason| format!("relation `{}`: {reason}", def.id))?;
+                rels.extend(link);
             }
         }
 
-        rels
+        Ok(rels)
+    }
+}
+
+/// Converts one entry of a relation to a [`Relationship`].
+///
+/// An id string is a plain link; a mapping holds the target `id` and the
+/// link attributes. Entries of any other shape are ignored.
+fn link_from_yaml(
+    entry: &serde_yaml::Value,
+    rel_type: RelationshipType,
+    attributes: &[FieldDef],
+) -> Result<Option<Relationship>, String> {
+    let mapping = match entry {
+        serde_yaml::Value::String(id) => {
+            return Ok(Some(Relationship::new(ItemId::new_unchecked(id), rel_type)));
+        }
+        serde_yaml::Value::Mapping(mapping) => mapping,
+        _ => return Ok(None),
+    };
+
+    let mut id = None;
+    let mut values = ItemAttributes::new();
+    for (key, value) in mapping {
+        let key = key.as_str().unwrap_or_default();
+        if key == FIELD_ID {
+            id = value.as_str();
+            continue;
+        }
+        let attribute = attributes
+            .iter()
+            .find(|a| a.name == key)
+            .ok_or_else(|| format!("unknown link attribute `{key}`"))?;
+        if let Some(value) = field_value_from_yaml(value, &attribute.field_type)
+            .map_err(|reason| format!("link attribute `{key}`: {reason}"))?
+        {
+            values.insert(key, value);
+        }
     }
+    let id = id.ok_or_else(|| "link without an `id`".to_string())?;
+    Ok(Some(
+        Relationship::new(ItemId::new_unchecked(id), rel_type).with_attributes(values),
+    ))
 }
 
-/// Parses a raw YAML string into a `RawFrontmatter`.
-pub fn parse_yaml_frontmatter(yaml: &str, file: &Path) -> Result<RawFrontmatter, SaraError> {
-    serde_yaml::from_str(yaml).map_err(|e| SaraError::InvalidYaml {
+/// Parses a raw YAML string into a `RawFrontmatter`, read against `schema`.
+pub fn parse_yaml_frontmatter(
+    yaml: &str,
+    file: &Path,
+    schema: &Schema,
+) -> Result<RawFrontmatter, SaraError> {
+    RawFrontmatter::deserialize_in(serde_yaml::Deserializer::from_str(yaml), schema).map_err(|e| {
+        SaraError::InvalidYaml {
+            file: file.to_path_buf(),
+            reason: e.to_string(),
+        }
+    })
+}
+
+/// Parses a raw TOML string into a `RawFrontmatter`, read against `schema`.
+pub fn parse_toml_frontmatter(
+    toml: &str,
+    file: &Path,
+    schema: &Schema,
+) -> Result<RawFrontmatter, SaraError> {
+    let table: toml::Table = toml::from_str(toml).map_err(|e| SaraError::InvalidToml {
         file: file.to_path_buf(),
         reason: e.to_string(),
+    })?;
+    RawFrontmatter::deserialize_in(toml_to_yaml(toml::Value::Table(table)), schema).map_err(|e| {
+        SaraError::InvalidToml {
+            file: file.to_path_buf(),
+            reason: e.to_string(),
+        }
     })
 }
 
+/// Converts a TOML value to the equivalent YAML value.
+///
+/// Dates and times become strings, as written in the document.
+pub(super) fn toml_to_yaml(value: toml::Value) -> serde_yaml::Value {
+    use serde_yaml::Value;
+
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::Number(i.into()),
+        toml::Value::Float(f) => Value::Number(f.into()),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(values) => {
+            Value::Sequence(values.into_iter().map(toml_to_yaml).collect())
+        }
+        toml::Value::Table(table) => Value::Mapping(
+            table
+                .into_iter()
+                .map(|(key, value)| (Value::String(key), toml_to_yaml(value)))
+                .collect(),
+        ),
+    }
+}
+
 /// Collects values into a list field value, mapping an empty list to `None`.
 fn non_empty_list(values: impl Iterator<Item = FieldValue>) -> Option<FieldValue> {
     let list: Vec<FieldValue> = values.collect();
@@ -119,27 +288,19 @@ fn non_empty_list(values: impl Iterator<Item = FieldValue>) -> Option<FieldValue
 /// Converts a raw YAML value to a [`FieldValue`] of the declared type.
 ///
 /// Values whose shape does not match the declared type yield `Ok(None)`,
-/// mirroring the historical tolerance of the parser; only enum values outside
-/// the allowed set are reported as errors.
+/// mirroring the historical tolerance of the parser. Values that break a
+/// constraint of the type — an enum value outside the allowed set, a number
+/// out of range, text not matching the pattern, a malformed URL — are
+/// reported as errors. Numbers, integers and booleans are also accepted as
+/// strings, as written in field lists.
 fn field_value_from_yaml(
     value: &serde_yaml::Value,
     field_type: &FieldType,
 ) -> Result<Option<FieldValue>, String> {
-    match field_type {
-        FieldType::Text => Ok(value.as_str().map(|s| FieldValue::Text(s.to_string()))),
-        FieldType::Date => Ok(value.as_str().map(|s| FieldValue::Date(s.to_string()))),
-        FieldType::ItemRef => Ok(value
-            .as_str()
-            .map(|s| FieldValue::ItemRef(ItemId::new_unchecked(s)))),
-        FieldType::Enum { values } => match value.as_str() {
-            Some(s) if values.iter().any(|v| v == s) => Ok(Some(FieldValue::Enum(s.to_string()))),
-            Some(s) => Err(format!(
-                "invalid value `{s}`, expected one of: {}",
-                values.join(", ")
-            )),
-            None => Ok(None),
-        },
-        FieldType::List(inner) => {
+    use serde_yaml::Value;
+
+    match (field_type, value) {
+        (FieldType::List(inner), value) => {
             let Some(sequence) = value.as_sequence() else {
                 return Ok(None);
             };
@@ -151,6 +312,15 @@ fn field_value_from_yaml(
             }
             Ok(non_empty_list(list.into_iter()))
         }
+        (
+            FieldType::Number { .. } | FieldType::Integer { .. } | FieldType::Boolean,
+            Value::Number(_) | Value::Bool(_),
+        ) => {
+            let text = serde_yaml::to_string(value).map_err(|e| e.to_string())?;
+            field_type.parse_value(&text).map(Some)
+        }
+        (_, Value::String(s)) => field_type.parse_value(s).map(Some),
+        _ => Ok(None),
     }
 }
 
@@ -158,7 +328,7 @@ fn field_value_from_yaml(
 mod tests {
     use super::*;
 
-    use crate::schema::builtin;
+    use crate::schema::{self, builtin};
 
     #[test]
     fn test_parse_yaml_frontmatter_solution() {
@@ -168,7 +338,7 @@ type: solution
 name: "Test Solution"
 description: "A test solution"
 "#;
-        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md")).unwrap();
+        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md"), schema::active()).unwrap();
         assert_eq!(fm.id, "SOL-001");
         assert_eq!(fm.item_type, builtin::SOLUTION);
         assert_eq!(fm.name, "Test Solution");
@@ -184,8 +354,8 @@ name: "Login"
 refines:
   - "SOL-001"
 "#;
-        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md")).unwrap();
-        let rels = fm.to_relationships();
+        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md"), schema::active()).unwrap();
+        let rels = fm.to_relationships(schema::active()).unwrap();
         assert_eq!(rels.len(), 1);
         assert_eq!(rels[0].to.as_str(), "SOL-001");
         assert_eq!(rels[0].relationship_type, builtin::REFINES);
@@ -199,13 +369,47 @@ type: use_case
 name: "Login"
 refines: "SOL-001"
 "#;
-        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md")).unwrap();
-        let rels = fm.to_relationships();
+        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md"), schema::active()).unwrap();
+        let rels = fm.to_relationships(schema::active()).unwrap();
         assert_eq!(rels.len(), 1);
         assert_eq!(rels[0].to.as_str(), "SOL-001");
         assert_eq!(rels[0].relationship_type, builtin::REFINES);
     }
 
+    #[test]
+    fn test_parse_yaml_frontmatter_link_mappings() {
+        let yaml = r#"
+id: "SYSARCH-001"
+type: system_architecture
+name: "Platform"
+satisfies:
+  - id: "SYSREQ-001"
+  - "SYSREQ-002"
+"#;
+        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md"), schema::active()).unwrap();
+        let rels = fm.to_relationships(schema::active()).unwrap();
+        assert_eq!(rels.len(), 2);
+        assert_eq!(rels[0].to.as_str(), "SYSREQ-001");
+        assert!(rels[0].attributes.is_empty());
+
+        // The built-in relations declare no link attributes.
+        let unknown = yaml.replace(
+            "  - id: \"SYSREQ-001\"",
+            "  - id: \"SYSREQ-001\"\n    coverage: partial",
+        );
+        let fm = parse_yaml_frontmatter(&unknown, Path::new("test.md"), schema::active()).unwrap();
+        let reason = fm.to_relationships(schema::active()).unwrap_err();
+        assert!(
+            reason.contains("unknown link attribute `coverage`"),
+            "{reason}"
+        );
+
+        let missing_id = yaml.replace("  - id: \"SYSREQ-001\"", "  - {}");
+        let fm =
+            parse_yaml_frontmatter(&missing_id, Path::new("test.md"), schema::active()).unwrap();
+        assert!(fm.to_relationships(schema::active()).is_err());
+    }
+
     #[test]
     fn test_core_field_names_match_model_constants() {
         use crate::model::{FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE};
@@ -213,7 +417,7 @@ refines: "SOL-001"
         let yaml = format!(
             "{FIELD_ID}: \"SOL-001\"\n{FIELD_TYPE}: solution\n{FIELD_NAME}: \"Named\"\n{FIELD_DESCRIPTION}: \"Described\"\n"
         );
-        let fm = parse_yaml_frontmatter(&yaml, Path::new("test.md")).unwrap();
+        let fm = parse_yaml_frontmatter(&yaml, Path::new("test.md"), schema::active()).unwrap();
         assert_eq!(fm.id, "SOL-001");
         assert_eq!(fm.item_type, builtin::SOLUTION);
         assert_eq!(fm.name, "Named");
@@ -227,7 +431,7 @@ refines: "SOL-001"
     #[test]
     fn test_parse_yaml_frontmatter_invalid() {
         let yaml = "not: valid: yaml: [";
-        let result = parse_yaml_frontmatter(yaml, Path::new("test.md"));
+        let result = parse_yaml_frontmatter(yaml, Path::new("test.md"), schema::active());
         assert!(result.is_err());
     }
 
@@ -245,7 +449,7 @@ justifies:
 supersedes:
   - "ADR-000"
 "#;
-        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md")).unwrap();
+        let fm = parse_yaml_frontmatter(yaml, Path::new("test.md"), schema::active()).unwrap();
 
         let fields = builtin::ARCHITECTURE_DECISION_RECORD.declared_fields();
         let status_field = fields.iter().find(|f| f.name == "status").unwrap();
@@ -261,7 +465,7 @@ supersedes:
             )]))
         );
 
-        let rels = fm.to_relationships();
+        let rels = fm.to_relationships(schema::active()).unwrap();
         assert_eq!(rels.len(), 2);
         assert_eq!(rels[0].relationship_type, builtin::JUSTIFIES);
         assert_eq!(rels[1].relationship_type, builtin::SUPERSEDES);
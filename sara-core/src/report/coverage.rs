@@ -4,6 +4,7 @@ use serde::Serialize;
 
 use crate::graph::KnowledgeGraph;
 use crate::model::ItemType;
+use crate::schema::Schema;
 
 /// Coverage statistics for a single item type.
 #[derive(Debug, Clone, Serialize)]
@@ -58,8 +59,10 @@ impl CoverageReport {
         let mut total_items = 0;
         let mut complete_items = 0;
 
+        let schema = graph.schema();
+
         // Calculate coverage for each item type
-        for item_type in ItemType::all() {
+        for item_type in ItemType::all_in(schema) {
             let items = graph.items_by_type(item_type);
             let total = items.len();
 
@@ -89,7 +92,7 @@ impl CoverageReport {
 
             by_type.push(TypeCoverage {
                 item_type,
-                type_name: item_type.display_name().to_string(),
+                type_name: item_type.display_name_in(schema).to_string(),
                 total,
                 complete: type_complete,
                 incomplete: type_incomplete,
@@ -118,22 +121,23 @@ impl CoverageReport {
     /// Checks if an item has complete traceability.
     fn check_item_complete(item: &crate::model::Item, graph: &KnowledgeGraph) -> bool {
         // Solutions are complete if they have downstream items (use graph to find children)
-        if item.item_type.is_root() {
+        if item.item_type.is_root_in(graph.schema()) {
             return !graph.children(&item.id).is_empty();
         }
 
         // All other items are complete if they have upstream items
-        item.has_upstream()
+        item.has_upstream_in(graph.schema())
     }
 
     /// Creates an IncompleteItem from an item.
     fn create_incomplete_item(item: &crate::model::Item, graph: &KnowledgeGraph) -> IncompleteItem {
-        let reason = if item.item_type.is_root() && graph.children(&item.id).is_empty() {
+        let schema = graph.schema();
+        let reason = if item.item_type.is_root_in(schema) && graph.children(&item.id).is_empty() {
             "No downstream items defined".to_string()
-        } else if !item.has_upstream() {
+        } else if !item.has_upstream_in(schema) {
             format!(
                 "Missing parent {}",
-                Self::expected_parent_type(item.item_type)
+                Self::expected_parent_type(item.item_type, schema)
             )
         } else {
             "Incomplete traceability".to_string()
@@ -142,15 +146,15 @@ impl CoverageReport {
         IncompleteItem {
             id: item.id.as_str().to_string(),
             name: item.name.clone(),
-            item_type: item.item_type.display_name().to_string(),
+            item_type: item.item_type.display_name_in(schema).to_string(),
             reason,
         }
     }
 
     /// Returns the expected parent type for an item type.
-    fn expected_parent_type(item_type: ItemType) -> &'static str {
-        match item_type.required_parent_type() {
-            Some(parent) => parent.display_name(),
+    fn expected_parent_type(item_type: ItemType, schema: &Schema) -> &str {
+        match item_type.required_parent_type_in(schema) {
+            Some(parent) => parent.display_name_in(schema),
             None => "N/A (root)",
         }
     }
@@ -2,10 +2,12 @@
 
 use std::collections::HashMap;
 
+use indexmap::IndexMap;
 use serde::Serialize;
 
 use crate::graph::KnowledgeGraph;
-use crate::model::{ItemType, RelationshipType};
+use crate::model::{ItemType, Relationship, RelationshipType};
+use crate::schema::Schema;
 
 /// A row in the traceability matrix.
 #[derive(Debug, Clone, Serialize)]
@@ -31,6 +33,22 @@ pub struct MatrixTarget {
     pub target_type: String,
     /// Relationship type.
     pub relationship: String,
+    /// Attributes of the link (coverage, rationale...), as displayed.
+    #[serde(skip_serializing_if = "IndexMap::is_empty")]
+    pub attributes: IndexMap<String, String>,
+}
+
+impl MatrixTarget {
+    /// Describes the link attributes as `name: value` pairs, or an empty
+    /// string when the link carries none.
+    #[must_use]
+    pub fn attributes_summary(&self) -> String {
+        self.attributes
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
 
 /// The complete traceability matrix.
@@ -47,7 +65,7 @@ pub struct TraceabilityMatrix {
 impl TraceabilityMatrix {
     /// Generates a traceability matrix from a knowledge graph.
     pub fn generate(graph: &KnowledgeGraph) -> Self {
-        let relation_types = RelationshipType::all();
+        let relation_types = RelationshipType::all_in(graph.schema());
         let mut rows: Vec<MatrixRow> = graph
             .items()
             .map(|item| Self::build_row(item, graph, &relation_types))
@@ -55,9 +73,9 @@ impl TraceabilityMatrix {
 
         let total_relationships = rows.iter().map(|r| r.targets.len()).sum();
 
-        Self::sort_rows(&mut rows);
+        Self::sort_rows(&mut rows, graph.schema());
 
-        let columns = Self::build_columns();
+        let columns = Self::build_columns(graph.schema());
 
         Self {
             rows,
@@ -79,60 +97,72 @@ impl TraceabilityMatrix {
             graph,
             relation_types,
             &mut targets,
-            RelationshipType::is_upstream,
+            RelationshipType::is_upstream_in,
         );
         Self::collect_targets_by_direction(
             item,
             graph,
             relation_types,
             &mut targets,
-            RelationshipType::is_downstream,
+            RelationshipType::is_downstream_in,
         );
 
         MatrixRow {
             source_id: item.id.as_str().to_string(),
             source_name: item.name.clone(),
-            source_type: item.item_type.display_name().to_string(),
+            source_type: item.item_type.display_name_in(graph.schema()).to_string(),
             targets,
         }
     }
 
-    /// Collects targets of every active-schema relation matching a direction.
+    /// Collects targets of every graph-schema relation matching a direction.
     fn collect_targets_by_direction(
         item: &crate::model::Item,
         graph: &KnowledgeGraph,
         relation_types: &[RelationshipType],
         targets: &mut Vec<MatrixTarget>,
-        matches_direction: fn(&RelationshipType) -> bool,
+        matches_direction: fn(&RelationshipType, &Schema) -> bool,
     ) {
-        for rel_type in relation_types.iter().copied().filter(matches_direction) {
-            let ids: Vec<_> = item.relationship_ids(rel_type).cloned().collect();
-            Self::add_targets(&ids, rel_type.as_str(), graph, targets);
+        for rel_type in relation_types
+            .iter()
+            .copied()
+            .filter(|rel_type| matches_direction(rel_type, graph.schema()))
+        {
+            let links: Vec<_> = item
+                .relationships
+                .iter()
+                .filter(|rel| rel.relationship_type == rel_type)
+                .collect();
+            Self::add_targets(&links, graph, targets);
         }
     }
 
-    /// Adds targets for a list of reference IDs.
+    /// Adds targets for a list of links.
     fn add_targets(
-        ref_ids: &[crate::model::ItemId],
-        relationship: &str,
+        links: &[&Relationship],
         graph: &KnowledgeGraph,
         targets: &mut Vec<MatrixTarget>,
     ) {
-        for ref_id in ref_ids {
-            if let Some(target) = graph.get(ref_id) {
+        for link in links {
+            if let Some(target) = graph.get(&link.to) {
                 targets.push(MatrixTarget {
-                    id: ref_id.as_str().to_string(),
+                    id: link.to.as_str().to_string(),
                     name: target.name.clone(),
-                    target_type: target.item_type.display_name().to_string(),
-                    relationship: relationship.to_string(),
+                    target_type: target.item_type.display_name_in(graph.schema()).to_string(),
+                    relationship: link.relationship_type.as_str().to_string(),
+                    attributes: link
+                        .attributes
+                        .iter()
+                        .map(|(name, value)| (name.clone(), value.to_string()))
+                        .collect(),
                 });
             }
         }
     }
 
-    /// Sorts rows by the active schema's type declaration order, then by ID.
-    fn sort_rows(rows: &mut [MatrixRow]) {
-        let order = Self::build_columns();
+    /// Sorts rows by the schema's type declaration order, then by ID.
+    fn sort_rows(rows: &mut [MatrixRow], schema: &Schema) {
+        let order = Self::build_columns(schema);
         let mut positions: HashMap<&str, usize> = HashMap::with_capacity(order.len());
         for (index, name) in order.iter().enumerate() {
             positions.entry(name.as_str()).or_insert(index);
@@ -145,11 +175,11 @@ impl TraceabilityMatrix {
         });
     }
 
-    /// Builds column headers from item types.
-    fn build_columns() -> Vec<String> {
-        ItemType::all()
+    /// Builds column headers from the item types of `schema`.
+    fn build_columns(schema: &Schema) -> Vec<String> {
+        ItemType::all_in(schema)
             .iter()
-            .map(|t| t.display_name().to_string())
+            .map(|t| t.display_name_in(schema).to_string())
             .collect()
     }
 
@@ -159,14 +189,14 @@ impl TraceabilityMatrix {
 
         // Header
         csv.push_str(
-            "Source ID,Source Name,Source Type,Target ID,Target Name,Target Type,Relationship\n",
+            "Source ID,Source Name,Source Type,Target ID,Target Name,Target Type,Relationship,Attributes\n",
         );
 
         // Rows
         for row in &self.rows {
             if row.targets.is_empty() {
                 csv.push_str(&format!(
-                    "{},{},{},,,, \n",
+                    "{},{},{},,,,, \n",
                     Self::escape_csv(&row.source_id),
                     Self::escape_csv(&row.source_name),
                     Self::escape_csv(&row.source_type),
@@ -174,7 +204,7 @@ impl TraceabilityMatrix {
             } else {
                 for target in &row.targets {
                     csv.push_str(&format!(
-                        "{},{},{},{},{},{},{}\n",
+                        "{},{},{},{},{},{},{},{}\n",
                         Self::escape_csv(&row.source_id),
                         Self::escape_csv(&row.source_name),
                         Self::escape_csv(&row.source_type),
@@ -182,6 +212,7 @@ impl TraceabilityMatrix {
                         Self::escape_csv(&target.name),
                         Self::escape_csv(&target.target_type),
                         Self::escape_csv(&target.relationship),
+                        Self::escape_csv(&target.attributes_summary()),
                     ));
                 }
             }
@@ -1,18 +1,21 @@
 //! Git repository integration using gitoxide (`gix`).
 //!
 //! Provides read-only access to commits and tree contents for parsing
-//! Markdown items at arbitrary Git references. Pure Rust — no libgit2 or
+//! document items at arbitrary Git references. Pure Rust — no libgit2 or
 //! OpenSSL dependency.
 
 use std::path::{Component, Path, PathBuf};
 
 use gix::bstr::ByteSlice;
+use gix::index::entry::{Mode, Stage};
 use gix::object::tree::EntryKind;
-use gix::{Commit, Repository, Tree};
+use gix::{Commit, ObjectId, Repository, Tree};
 
+use super::ScanWarning;
+use super::source::{Document, DocumentSource, Documents};
 use crate::error::SaraError;
 use crate::model::Item;
-use crate::parser::InputFormat;
+use crate::parser::{InputFormat, ParseContext};
 
 /// Wraps a gix error into [`SaraError::Gix`].
 fn gix_err<E>(e: E) -> SaraError
@@ -30,7 +33,7 @@ where
 const REVSPEC_CHARS: &[char] = &['~', '^', '@', ':'];
 
 /// Represents a Git reference that can be used to read files.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum GitRef {
     /// HEAD of the repository.
     Head,
@@ -191,7 +194,21 @@ impl GitReader {
             .map_err(|e| SaraError::Git(format!("Invalid UTF-8 in file: {e}")))
     }
 
-    /// Lists the Markdown files under `scope` in a commit's tree.
+    /// Reads a file at a reference, or `None` when the commit has no file at
+    /// `path` (relative to the repository root).
+    ///
+    /// # Errors
+    /// Propagates errors from [`Self::resolve_ref`] and [`Self::read_file`].
+    pub fn read_file_at(&self, git_ref: &GitRef, path: &Path) -> Result<Option<String>, SaraError> {
+        let commit = self.resolve_ref(git_ref)?;
+        let tree = commit.tree().map_err(gix_err)?;
+        if tree.lookup_entry_by_path(path).map_err(gix_err)?.is_none() {
+            return Ok(None);
+        }
+        self.read_file(&commit, path).map(Some)
+    }
+
+    /// Lists the document files under `scope` in a commit's tree.
     ///
     /// `scope` is a path relative to the repository root; an empty path or
     /// `.` selects the whole tree. Returned paths are always relative to the
@@ -201,7 +218,7 @@ impl GitReader {
     /// # Errors
     /// Returns [`SaraError::Gix`] if tree traversal fails, or
     /// [`SaraError::Git`] for non-UTF-8 file names.
-    pub fn list_markdown_files(
+    pub fn list_document_files(
         &self,
         commit: &Commit<'_>,
         scope: &Path,
@@ -251,7 +268,7 @@ impl GitReader {
         Ok(Some((subtree, scope)))
     }
 
-    /// Recursively walks a tree to find Markdown files.
+    /// Recursively walks a tree to find document files.
     fn walk_tree(
         &self,
         tree: &Tree<'_>,
@@ -273,7 +290,7 @@ impl GitReader {
 
             match entry.mode().kind() {
                 EntryKind::Blob | EntryKind::BlobExecutable
-                    if name.ends_with(".md") || name.ends_with(".markdown") =>
+                    if InputFormat::from_path(Path::new(name)).is_some() =>
                 {
                     files.push(path);
                 }
@@ -292,7 +309,7 @@ impl GitReader {
         Ok(())
     }
 
-    /// Parses the Markdown files under `scope` at a specific commit.
+    /// Parses the document files under `scope` at a specific commit.
     ///
     /// `scope` is a path relative to the repository root; an empty path or
     /// `.` selects the whole tree. A scope that is absent from the commit
@@ -304,46 +321,337 @@ impl GitReader {
     /// error only when no items could be parsed at all; otherwise individual
     /// parse failures are logged via `tracing` and the successful items are
     /// returned.
-    pub fn parse_commit(&self, git_ref: &GitRef, scope: &Path) -> Result<Vec<Item>, SaraError> {
-        let commit = self.resolve_ref(git_ref)?;
-        let files = self.list_markdown_files(&commit, scope)?;
+    pub fn parse_commit(
+        &self,
+        git_ref: &GitRef,
+        scope: &Path,
+        ctx: &ParseContext,
+    ) -> Result<Vec<Item>, SaraError> {
+        self.parse_documents(
+            GitSource::new(self, git_ref.clone(), scope).documents()?,
+            ctx,
+        )
+    }
 
-        let mut items = Vec::new();
-        let mut parse_errors = Vec::new();
+    /// Parses the document files under `scope` as staged in the index:
+    /// HEAD with the staged changes, ignoring unstaged ones.
+    ///
+    /// Unlike [`Self::parse_commit`], documents that cannot be read or
+    /// parsed are returned with their error, so that staged documents can
+    /// be held to account for it.
+    ///
+    /// # Errors
+    /// Returns [`SaraError::Gix`] when the index cannot be read.
+    pub fn parse_index(&self, scope: &Path, ctx: &ParseContext) -> Result<ParsedItems, SaraError> {
+        let mut parsed = ParsedItems::default();
+        let mut documents = Vec::new();
+        for (path, id) in self.staged_entries(scope)? {
+            match self.read_blob(id, &path) {
+                Ok(content) => documents.push(Document { path, content }),
+                Err(e) => parsed.failures.push((path, e)),
+            }
+        }
+        self.parse_into(documents, &mut parsed, ctx);
+        Ok(parsed)
+    }
 
-        for file_path in files {
-            let content = match self.read_file(&commit, &file_path) {
-                Ok(c) => c,
-                Err(e) => {
-                    tracing::warn!("Failed to read {}: {}", file_path.display(), e);
-                    continue;
-                }
-            };
+    /// Parses the given document files at a reference, skipping the ones
+    /// the commit does not have. Paths are relative to the repository root.
+    ///
+    /// Documents that cannot be parsed are returned with their error.
+    ///
+    /// # Errors
+    /// Propagates errors from [`Self::read_file_at`].
+    pub fn parse_files_at(
+        &self,
+        git_ref: &GitRef,
+        paths: &[PathBuf],
+        ctx: &ParseContext,
+    ) -> Result<ParsedItems, SaraError> {
+        let mut documents = Vec::new();
+        for path in paths {
+            if let Some(content) = self.read_file_at(git_ref, path)? {
+                documents.push(Document {
+                    path: path.clone(),
+                    content,
+                });
+            }
+        }
+        let mut parsed = ParsedItems::default();
+        self.parse_into(documents, &mut parsed, ctx);
+        Ok(parsed)
+    }
+
+    /// Parses documents read from the repository.
+    ///
+    /// Returns the first parse error only when no items could be parsed at
+    /// all; otherwise individual parse failures are logged via `tracing`.
+    fn parse_documents(&self, read: Documents, ctx: &ParseContext) -> Result<Vec<Item>, SaraError> {
+        for warning in &read.warnings {
+            tracing::warn!(
+                "Failed to read {}: {}",
+                warning.path.display(),
+                warning.reason
+            );
+        }
 
-            // Skip files without frontmatter
-            if !crate::parser::has_frontmatter(&content) {
+        let mut parsed = ParsedItems::default();
+        self.parse_into(read.documents, &mut parsed, ctx);
+        for (path, e) in &parsed.failures {
+            tracing::warn!("Failed to parse {}: {}", path.display(), e);
+        }
+
+        if parsed.items.is_empty() && !parsed.failures.is_empty() {
+            return Err(parsed.failures.remove(0).1);
+        }
+
+        Ok(parsed.items)
+    }
+
+    /// Parses documents into `parsed`, skipping the ones without item
+    /// metadata.
+    fn parse_into(&self, documents: Vec<Document>, parsed: &mut ParsedItems, ctx: &ParseContext) {
+        for document in documents {
+            let format = InputFormat::from_path(&document.path).unwrap_or(InputFormat::Markdown);
+            if !crate::parser::has_metadata(&document.content, format) {
                 continue;
             }
 
-            match crate::parser::parse_metadata(
-                &content,
-                &file_path,
+            match crate::parser::parse_document(
+                &document.content,
+                &document.path,
                 &self.repo_path,
-                InputFormat::Markdown,
+                format,
+                ctx,
             ) {
-                Ok(item) => items.push(item),
-                Err(e) => {
-                    tracing::warn!("Failed to parse {}: {}", file_path.display(), e);
-                    parse_errors.push(e);
-                }
+                Ok(items) => parsed.items.extend(items),
+                Err(e) => parsed.failures.push((document.path, e)),
+            }
+        }
+    }
+
+    /// Tells whether HEAD points to a commit, which it does not in a
+    /// repository without commits yet.
+    pub fn has_commits(&self) -> bool {
+        self.repo.head_id().is_ok()
+    }
+
+    /// Lists the local and remote-tracking branches, sorted by name, with
+    /// the commit each one points to.
+    ///
+    /// Branches are named in their short form (`main`, `origin/main`).
+    /// Symbolic references such as `origin/HEAD` are skipped, since they
+    /// only alias another branch.
+    ///
+    /// # Errors
+    /// Returns [`SaraError::Gix`] if the references cannot be read.
+    pub fn list_branches(&self) -> Result<Vec<(String, GitRef)>, SaraError> {
+        let references = self.repo.references().map_err(gix_err)?;
+        let local = references.local_branches().map_err(gix_err)?;
+        let remote = references.remote_branches().map_err(gix_err)?;
+
+        let mut branches = Vec::new();
+        for reference in local.chain(remote) {
+            let mut reference = reference.map_err(SaraError::Gix)?;
+            if matches!(reference.target(), gix::refs::TargetRef::Symbolic(_)) {
+                continue;
+            }
+            let name = reference.name().shorten().to_str_lossy().into_owned();
+            let id = reference.peel_to_id().map_err(gix_err)?;
+            branches.push((name, GitRef::Commit(id.to_string())));
+        }
+        branches.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(branches)
+    }
+
+    /// Returns the best common ancestor of two references, or `None` when
+    /// their histories are unrelated.
+    ///
+    /// # Errors
+    /// Propagates errors from [`Self::resolve_ref`], or returns
+    /// [`SaraError::Gix`] if the commit graph cannot be walked.
+    pub fn merge_base(&self, one: &GitRef, two: &GitRef) -> Result<Option<GitRef>, SaraError> {
+        let one = self.resolve_ref(one)?.id;
+        let two = self.resolve_ref(two)?.id;
+        match self.repo.merge_base(one, two) {
+            Ok(base) => Ok(Some(GitRef::Commit(base.to_string()))),
+            Err(gix::repository::merge_base::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(gix_err(e)),
+        }
+    }
+
+    /// Returns the directory Git runs hooks from.
+    pub fn hooks_dir(&self) -> PathBuf {
+        self.repo.common_dir().join("hooks")
+    }
+
+    /// Lists the document files under `scope` whose staged content differs
+    /// from HEAD: added, modified or deleted. Paths are relative to the
+    /// repository root.
+    ///
+    /// # Errors
+    /// Returns [`SaraError::Gix`] if the index or HEAD cannot be read, or
+    /// [`SaraError::Git`] for non-UTF-8 file names.
+    pub fn list_staged_files(&self, scope: &Path) -> Result<Vec<PathBuf>, SaraError> {
+        let head = if self.has_commits() {
+            self.repo.head_tree().map_err(gix_err)?
+        } else {
+            self.repo.empty_tree()
+        };
+
+        let staged = self.staged_entries(scope)?;
+        let mut changed = Vec::new();
+        for (path, id) in &staged {
+            let committed = head.lookup_entry_by_path(path).map_err(gix_err)?;
+            if committed.map(|entry| entry.object_id()) != Some(*id) {
+                changed.push(path.clone());
+            }
+        }
+
+        let mut committed = Vec::new();
+        if let Some((tree, prefix)) = self.scoped_tree(head, scope)? {
+            self.walk_tree(&tree, prefix, &mut committed)?;
+        }
+        changed.extend(
+            committed
+                .into_iter()
+                .filter(|path| !staged.iter().any(|(staged, _)| staged == path)),
+        );
+
+        changed.sort();
+        Ok(changed)
+    }
+
+    /// Returns the path and blob of the document files under `scope` in the
+    /// index, skipping conflicted entries and hidden paths like
+    /// [`Self::list_document_files`] does.
+    fn staged_entries(&self, scope: &Path) -> Result<Vec<(PathBuf, ObjectId)>, SaraError> {
+        let scope: PathBuf = scope
+            .components()
+            .filter(|c| !matches!(c, Component::CurDir))
+            .collect();
+        let index = self.repo.index_or_empty().map_err(gix_err)?;
+
+        let mut entries = Vec::new();
+        for entry in index.entries() {
+            if entry.stage() != Stage::Unconflicted
+                || !matches!(entry.mode, Mode::FILE | Mode::FILE_EXECUTABLE)
+            {
+                continue;
+            }
+            let path = entry
+                .path(&index)
+                .to_str()
+                .map_err(|_| SaraError::Git("Invalid file name".to_string()))?;
+            let path = PathBuf::from(path);
+            let hidden = path
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+            if path.starts_with(&scope) && !hidden && InputFormat::from_path(&path).is_some() {
+                entries.push((path, entry.id));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Reads a blob as UTF-8 text.
+    fn read_blob(&self, id: ObjectId, path: &Path) -> Result<String, SaraError> {
+        let mut blob = self.repo.find_blob(id).map_err(gix_err)?;
+        String::from_utf8(blob.take_data())
+            .map_err(|e| SaraError::Git(format!("Invalid UTF-8 in file {}: {e}", path.display())))
+    }
+}
+
+/// Items parsed from repository documents, along with the documents that
+/// could not be read or parsed.
+#[derive(Debug, Default)]
+pub struct ParsedItems {
+    /// Items parsed successfully.
+    pub items: Vec<Item>,
+    /// Path, relative to the repository root, and error of each document
+    /// that could not be read or parsed.
+    pub failures: Vec<(PathBuf, SaraError)>,
+}
+
+/// The document files under a scope of a Git commit.
+///
+/// Document paths are relative to the repository root. See
+/// [`GitReader::list_document_files`] for the meaning of the scope.
+pub struct GitSource<'a> {
+    reader: &'a GitReader,
+    git_ref: GitRef,
+    scope: PathBuf,
+}
+
+impl<'a> GitSource<'a> {
+    /// Creates a source over the documents under `scope` at `git_ref`.
+    pub fn new(reader: &'a GitReader, git_ref: GitRef, scope: impl Into<PathBuf>) -> Self {
+        Self {
+            reader,
+            git_ref,
+            scope: scope.into(),
+        }
+    }
+}
+
+impl DocumentSource for GitSource<'_> {
+    fn root(&self) -> &Path {
+        self.reader.repo_path()
+    }
+
+    fn documents(&self) -> Result<Documents, SaraError> {
+        let commit = self.reader.resolve_ref(&self.git_ref)?;
+        let mut documents = Documents::default();
+        for path in self.reader.list_document_files(&commit, &self.scope)? {
+            match self.reader.read_file(&commit, &path) {
+                Ok(content) => documents.documents.push(Document { path, content }),
+                Err(e) => documents.warnings.push(ScanWarning {
+                    path,
+                    reason: e.to_string(),
+                }),
             }
         }
+        Ok(documents)
+    }
+}
+
+/// The document files under a scope of the index, as staged for the next
+/// commit.
+///
+/// Document paths are relative to the repository root. See
+/// [`GitReader::list_document_files`] for the meaning of the scope.
+pub struct IndexSource<'a> {
+    reader: &'a GitReader,
+    scope: PathBuf,
+}
 
-        if !parse_errors.is_empty() && items.is_empty() {
-            return Err(parse_errors.remove(0));
+impl<'a> IndexSource<'a> {
+    /// Creates a source over the staged documents under `scope`.
+    pub fn new(reader: &'a GitReader, scope: impl Into<PathBuf>) -> Self {
+        Self {
+            reader,
+            scope: scope.into(),
         }
+    }
+}
 
-        Ok(items)
+impl DocumentSource for IndexSource<'_> {
+    fn root(&self) -> &Path {
+        self.reader.repo_path()
+    }
+
+    fn documents(&self) -> Result<Documents, SaraError> {
+        let mut documents = Documents::default();
+        for (path, id) in self.reader.staged_entries(&self.scope)? {
+            match self.reader.read_blob(id, &path) {
+                Ok(content) => documents.documents.push(Document { path, content }),
+                Err(e) => documents.warnings.push(ScanWarning {
+                    path,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        Ok(documents)
     }
 }
 
@@ -431,7 +739,7 @@ name: "Root"
         let reader = GitReader::discover(repo.path()).unwrap();
 
         let items = reader
-            .parse_commit(&GitRef::Head, Path::new("docs"))
+            .parse_commit(&GitRef::Head, Path::new("docs"), &ParseContext::default())
             .unwrap();
 
         assert_eq!(items.len(), 1);
@@ -443,7 +751,9 @@ name: "Root"
         let repo = scoped_repo();
         let reader = GitReader::discover(repo.path()).unwrap();
 
-        let items = reader.parse_commit(&GitRef::Head, Path::new(".")).unwrap();
+        let items = reader
+            .parse_commit(&GitRef::Head, Path::new("."), &ParseContext::default())
+            .unwrap();
 
         assert_eq!(items.len(), 2);
     }
@@ -454,25 +764,145 @@ name: "Root"
         let reader = GitReader::discover(repo.path()).unwrap();
 
         let items = reader
-            .parse_commit(&GitRef::Head, Path::new("no-such-dir"))
+            .parse_commit(
+                &GitRef::Head,
+                Path::new("no-such-dir"),
+                &ParseContext::default(),
+            )
             .unwrap();
 
         assert!(items.is_empty());
     }
 
     #[test]
-    fn test_list_markdown_files_keeps_repo_relative_paths() {
+    fn test_parse_files_at_skips_missing_files() {
+        let repo = scoped_repo();
+        let reader = GitReader::discover(repo.path()).unwrap();
+
+        let parsed = reader
+            .parse_files_at(
+                &GitRef::Head,
+                &[PathBuf::from("docs/SOL-001.md"), PathBuf::from("new.md")],
+                &ParseContext::default(),
+            )
+            .unwrap();
+
+        assert_eq!(parsed.items.len(), 1);
+        assert_eq!(parsed.items[0].id.as_str(), "SOL-001");
+        assert!(parsed.failures.is_empty());
+    }
+
+    #[test]
+    fn test_list_document_files_keeps_repo_relative_paths() {
         let repo = scoped_repo();
         let reader = GitReader::discover(repo.path()).unwrap();
         let commit = reader.resolve_ref(&GitRef::Head).unwrap();
 
         let files = reader
-            .list_markdown_files(&commit, Path::new("docs"))
+            .list_document_files(&commit, Path::new("docs"))
             .unwrap();
 
         assert_eq!(files, vec![PathBuf::from("docs/SOL-001.md")]);
     }
 
+    #[test]
+    fn test_list_staged_files_reports_added_modified_and_deleted() {
+        let repo = scoped_repo();
+        let path = repo.path();
+        fs::write(
+            path.join("docs/SOL-003.md"),
+            ROOT_ITEM.replace("002", "003"),
+        )
+        .unwrap();
+        fs::write(
+            path.join("docs/SOL-001.md"),
+            SCOPED_ITEM.replace("Scoped", "Renamed"),
+        )
+        .unwrap();
+        run_git(path, &["add", "docs"]);
+        run_git(path, &["rm", "--quiet", "SOL-002.md"]);
+        // Unstaged changes are not staged files.
+        fs::write(
+            path.join("docs/SOL-004.md"),
+            ROOT_ITEM.replace("002", "004"),
+        )
+        .unwrap();
+
+        let reader = GitReader::discover(path).unwrap();
+        assert_eq!(
+            reader.list_staged_files(Path::new("")).unwrap(),
+            vec![
+                PathBuf::from("SOL-002.md"),
+                PathBuf::from("docs/SOL-001.md"),
+                PathBuf::from("docs/SOL-003.md"),
+            ]
+        );
+        assert_eq!(
+            reader.list_staged_files(Path::new("docs")).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_parse_index_reads_staged_content() {
+        let repo = scoped_repo();
+        let path = repo.path();
+        fs::write(
+            path.join("docs/SOL-001.md"),
+            SCOPED_ITEM.replace("Scoped", "Staged"),
+        )
+        .unwrap();
+        run_git(path, &["add", "docs"]);
+        fs::write(
+            path.join("docs/SOL-001.md"),
+            SCOPED_ITEM.replace("Scoped", "Unstaged"),
+        )
+        .unwrap();
+
+        let reader = GitReader::discover(path).unwrap();
+        let parsed = reader
+            .parse_index(Path::new("docs"), &ParseContext::default())
+            .unwrap();
+        assert_eq!(parsed.items.len(), 1);
+        assert_eq!(parsed.items[0].name, "Staged");
+    }
+
+    #[test]
+    fn test_parse_index_returns_unparsable_documents() {
+        let repo = scoped_repo();
+        let path = repo.path();
+        fs::write(
+            path.join("docs/SOL-003.md"),
+            "---\nid: \"SOL-003\ntype: solution\n---\n# Broken\n",
+        )
+        .unwrap();
+        run_git(path, &["add", "docs"]);
+
+        let reader = GitReader::discover(path).unwrap();
+        let parsed = reader
+            .parse_index(Path::new("docs"), &ParseContext::default())
+            .unwrap();
+        assert_eq!(parsed.items.len(), 1);
+        assert_eq!(parsed.failures.len(), 1);
+        assert_eq!(parsed.failures[0].0, PathBuf::from("docs/SOL-003.md"));
+    }
+
+    #[test]
+    fn test_list_staged_files_without_commits() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        run_git(path, &["init"]);
+        fs::write(path.join("SOL-001.md"), SCOPED_ITEM).unwrap();
+        run_git(path, &["add", "."]);
+
+        let reader = GitReader::discover(path).unwrap();
+        assert!(!reader.has_commits());
+        assert_eq!(
+            reader.list_staged_files(Path::new("")).unwrap(),
+            vec![PathBuf::from("SOL-001.md")]
+        );
+    }
+
     #[test]
     fn test_git_ref_parse_head() {
         assert!(matches!(GitRef::parse("HEAD"), GitRef::Head));
@@ -506,6 +936,67 @@ name: "Root"
         assert!(matches!(GitRef::parse("main~2"), GitRef::Revspec(_)));
     }
 
+    #[test]
+    fn test_list_branches_includes_remote_tracking_refs() {
+        let repo = scoped_repo();
+        run_git(repo.path(), &["branch", "feature"]);
+        run_git(
+            repo.path(),
+            &["update-ref", "refs/remotes/origin/shared", "HEAD"],
+        );
+        run_git(
+            repo.path(),
+            &[
+                "symbolic-ref",
+                "refs/remotes/origin/HEAD",
+                "refs/remotes/origin/shared",
+            ],
+        );
+        let reader = GitReader::discover(repo.path()).unwrap();
+
+        let branches = reader.list_branches().unwrap();
+
+        let names: Vec<&str> = branches.iter().map(|(name, _)| name.as_str()).collect();
+        assert!(names.contains(&"feature"));
+        assert!(names.contains(&"origin/shared"));
+        assert!(!names.contains(&"origin/HEAD"));
+        assert!(names.is_sorted());
+        assert!(
+            branches
+                .iter()
+                .all(|(_, commit)| matches!(commit, GitRef::Commit(_)))
+        );
+    }
+
+    #[test]
+    fn test_merge_base_of_diverged_branches() {
+        let repo = scoped_repo();
+        let base = reader_head(repo.path());
+        run_git(repo.path(), &["checkout", "-q", "-b", "feature"]);
+        run_git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "feature"],
+        );
+        run_git(repo.path(), &["checkout", "-q", "-"]);
+        run_git(
+            repo.path(),
+            &["commit", "-q", "--allow-empty", "-m", "main"],
+        );
+        let reader = GitReader::discover(repo.path()).unwrap();
+
+        let merge_base = reader
+            .merge_base(&GitRef::Head, &GitRef::Branch("feature".to_string()))
+            .unwrap();
+
+        assert_eq!(merge_base, Some(GitRef::Commit(base)));
+    }
+
+    /// Returns the commit HEAD points to in `repo`.
+    fn reader_head(repo: &Path) -> String {
+        let reader = GitReader::discover(repo).unwrap();
+        reader.resolve_ref(&GitRef::Head).unwrap().id.to_string()
+    }
+
     #[test]
     fn test_is_git_repo() {
         let current_dir = std::env::current_dir().unwrap();
@@ -1,7 +1,15 @@
 //! Repository scanning and file operations.
 
+#[cfg(feature = "git")]
 pub mod git;
+pub mod namespace;
 mod scanner;
+mod source;
 
-pub use git::{GitReader, GitRef, get_repo_root, is_git_repo};
-pub use scanner::{ScanResult, ScanWarning, parse_directory, parse_repositories};
+#[cfg(feature = "git")]
+pub use git::{GitReader, GitRef, GitSource, IndexSource, ParsedItems, get_repo_root, is_git_repo};
+pub use namespace::{apply_namespaces, qualify_items, resolve_references};
+pub use scanner::{
+    ScanResult, ScanWarning, parse_directory, parse_repositories, parse_source, scan_directory,
+};
+pub use source::{DirectorySource, Document, DocumentSource, Documents, MemorySource};
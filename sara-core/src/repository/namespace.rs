@@ -0,0 +1,232 @@
+//! Repository namespaces for identifiers reused across repositories.
+//!
+//! Items of a namespaced repository are identified as `namespace:LOCAL-ID`.
+//! References keep their frontmatter spelling until [`resolve_references`]
+//! binds them: unqualified IDs resolve within the referencing item's own
+//! repository first.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::config::RepositoryConfig;
+use crate::model::{Item, ItemId};
+
+/// Qualifies the identifiers of items read from a namespaced repository.
+///
+/// Identifiers already qualified are left untouched.
+pub fn qualify_items(items: &mut [Item], namespace: &str) {
+    for item in items {
+        if item.id.namespace().is_none() {
+            item.id = ItemId::qualified(namespace, item.id.as_str());
+        }
+    }
+}
+
+/// Applies the configured namespaces to items scanned from the filesystem,
+/// then resolves their references.
+///
+/// Each item is matched to its namespace through
+/// [`SourceLocation::repository`](crate::model::SourceLocation), the
+/// repository path it was scanned from. Does nothing when no namespace is
+/// configured.
+pub fn apply_namespaces(items: &mut [Item], config: &RepositoryConfig) {
+    if config.namespaces.is_empty() {
+        return;
+    }
+
+    let mut namespaces: HashMap<PathBuf, Option<String>> = HashMap::new();
+    for item in items.iter_mut() {
+        let namespace = namespaces
+            .entry(item.source.repository.clone())
+            .or_insert_with(|| {
+                config
+                    .namespace_of(&item.source.repository)
+                    .map(str::to_string)
+            });
+        if let Some(namespace) = namespace {
+            qualify_items(std::slice::from_mut(item), namespace);
+        }
+    }
+
+    resolve_references(items);
+}
+
+/// Binds unqualified relationship targets and body mentions to the
+/// identifiers of `items`.
+///
+/// An unqualified target resolves, in order, to the item of the same local
+/// ID in the referencing item's namespace, to an item without namespace,
+/// then to the only namespaced item with that local ID. Qualified targets
+/// and targets that match nothing (or several namespaces) are left as
+/// written, so the broken-reference rule reports them.
+pub fn resolve_references(items: &mut [Item]) {
+    if items.iter().all(|item| item.id.namespace().is_none()) {
+        return;
+    }
+
+    let known: HashSet<String> = items
+        .iter()
+        .map(|item| item.id.as_str().to_string())
+        .collect();
+    let mut by_local: HashMap<String, Vec<ItemId>> = HashMap::new();
+    for item in items.iter().filter(|item| item.id.namespace().is_some()) {
+        by_local
+            .entry(item.id.local().to_string())
+            .or_default()
+            .push(item.id.clone());
+    }
+
+    for item in items.iter_mut() {
+        let namespace = item.id.namespace().map(str::to_string);
+        for relationship in &mut item.relationships {
+            if let Some(resolved) =
+                resolve_reference(&relationship.to, namespace.as_deref(), &known, &by_local)
+            {
+                relationship.to = resolved;
+            }
+        }
+        for mention in &mut item.mentions {
+            if let Some(resolved) =
+                resolve_reference(mention, namespace.as_deref(), &known, &by_local)
+            {
+                *mention = resolved;
+            }
+        }
+    }
+}
+
+/// Resolves one unqualified reference, or returns `None` to keep it as is.
+fn resolve_reference(
+    target: &ItemId,
+    namespace: Option<&str>,
+    known: &HashSet<String>,
+    by_local: &HashMap<String, Vec<ItemId>>,
+) -> Option<ItemId> {
+    if target.namespace().is_some() {
+        return None;
+    }
+
+    if let Some(namespace) = namespace {
+        let same_repository = ItemId::qualified(namespace, target.as_str());
+        if known.contains(same_repository.as_str()) {
+            return Some(same_repository);
+        }
+    }
+
+    if known.contains(target.as_str()) {
+        return None;
+    }
+
+    match by_local.get(target.as_str()).map(Vec::as_slice) {
+        Some([only]) => Some(only.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::Relationship;
+    use crate::schema::builtin;
+    use crate::test_utils::{create_test_item, create_test_item_with_relationships};
+
+    fn refines(id: &str, target: &str) -> Item {
+        create_test_item_with_relationships(
+            id,
+            builtin::USE_CASE,
+            vec![Relationship::new(
+                ItemId::new_unchecked(target),
+                builtin::REFINES,
+            )],
+        )
+    }
+
+    fn target_of(item: &Item) -> &str {
+        item.relationships[0].to.as_str()
+    }
+
+    #[test]
+    fn test_qualify_items() {
+        let mut items = vec![
+            create_test_item("SOL-001", builtin::SOLUTION),
+            create_test_item("platform:SOL-002", builtin::SOLUTION),
+        ];
+
+        qualify_items(&mut items, "product");
+
+        assert_eq!(items[0].id.as_str(), "product:SOL-001");
+        assert_eq!(items[1].id.as_str(), "platform:SOL-002");
+    }
+
+    #[test]
+    fn test_unqualified_reference_prefers_own_namespace() {
+        let mut platform = vec![
+            create_test_item("SOL-001", builtin::SOLUTION),
+            refines("UC-001", "SOL-001"),
+        ];
+        qualify_items(&mut platform, "platform");
+        let mut product = vec![create_test_item("SOL-001", builtin::SOLUTION)];
+        qualify_items(&mut product, "product");
+
+        let mut items: Vec<Item> = platform.into_iter().chain(product).collect();
+        resolve_references(&mut items);
+
+        assert_eq!(target_of(&items[1]), "platform:SOL-001");
+    }
+
+    #[test]
+    fn test_unqualified_reference_falls_back_to_unique_namespace() {
+        let mut platform = vec![create_test_item("SOL-001", builtin::SOLUTION)];
+        qualify_items(&mut platform, "platform");
+
+        let mut items: Vec<Item> = platform
+            .into_iter()
+            .chain([refines("UC-001", "SOL-001")])
+            .collect();
+        resolve_references(&mut items);
+
+        assert_eq!(target_of(&items[1]), "platform:SOL-001");
+    }
+
+    #[test]
+    fn test_ambiguous_and_qualified_references_are_kept() {
+        let mut platform = vec![create_test_item("SOL-001", builtin::SOLUTION)];
+        qualify_items(&mut platform, "platform");
+        let mut product = vec![create_test_item("SOL-001", builtin::SOLUTION)];
+        qualify_items(&mut product, "product");
+
+        let mut items: Vec<Item> = platform
+            .into_iter()
+            .chain(product)
+            .chain([
+                refines("UC-001", "SOL-001"),
+                refines("UC-002", "product:SOL-001"),
+            ])
+            .collect();
+        resolve_references(&mut items);
+
+        assert_eq!(target_of(&items[2]), "SOL-001");
+        assert_eq!(target_of(&items[3]), "product:SOL-001");
+    }
+
+    #[test]
+    fn test_apply_namespaces_by_repository() {
+        let mut config = RepositoryConfig::default();
+        config
+            .namespaces
+            .insert("platform".to_string(), PathBuf::from("/platform"));
+
+        let mut platform_item = create_test_item("SOL-001", builtin::SOLUTION);
+        platform_item.source.repository = PathBuf::from("/platform");
+        let mut product_item = refines("UC-001", "SOL-001");
+        product_item.source.repository = PathBuf::from("/product");
+
+        let mut items = vec![platform_item, product_item];
+        apply_namespaces(&mut items, &config);
+
+        assert_eq!(items[0].id.as_str(), "platform:SOL-001");
+        assert_eq!(items[1].id.as_str(), "UC-001");
+        assert_eq!(target_of(&items[1]), "platform:SOL-001");
+    }
+}
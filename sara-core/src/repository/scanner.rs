@@ -1,16 +1,20 @@
-//! File scanner for discovering Markdown files.
+//! File scanner for discovering Markdown, AsciiDoc and reStructuredText files.
 
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use super::source::{DirectorySource, Document, DocumentSource};
 use crate::error::SaraError;
 use crate::model::Item;
-use crate::parser::{InputFormat, has_frontmatter, parse_metadata};
+use crate::parser::{InputFormat, ParseContext, has_metadata, parse_document};
 
-/// Scans a directory for Markdown files and returns their paths.
+/// Scans a directory for document files and returns their paths.
+///
+/// A file is a document when its extension maps to an [`InputFormat`].
 pub fn scan_directory(path: &Path) -> Result<Vec<PathBuf>, SaraError> {
     let mut files = Vec::new();
     scan_directory_recursive(path, &mut files)?;
@@ -35,13 +39,8 @@ fn scan_directory_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(),
                 continue;
             }
             scan_directory_recursive(&path, files)?;
-        } else if path.is_file() {
-            // Check for Markdown extension
-            if let Some(ext) = path.extension()
-                && (ext == "md" || ext == "markdown")
-            {
-                files.push(path);
-            }
+        } else if path.is_file() && InputFormat::from_path(&path).is_some() {
+            files.push(path);
         }
     }
 
@@ -75,76 +74,74 @@ pub struct ScanResult {
     pub warnings: Vec<ScanWarning>,
 }
 
-/// Result of parsing a single file.
+/// Result of parsing a single document.
 #[derive(Debug)]
 enum ParseResult {
-    /// Successfully parsed item.
-    Item(Box<Item>),
-    /// File had no frontmatter, skip it.
+    /// Successfully parsed items; standalone item files may hold several.
+    Items(Vec<Item>),
+    /// Document had no item metadata, skip it.
     Skipped,
-    /// Error reading file.
-    ReadError(std::io::Error),
-    /// Error parsing file.
+    /// Error parsing document.
     ParseError(SaraError),
 }
 
-/// Parses all Markdown files in a directory.
+/// Parses all document files in a directory.
 ///
-/// Files are parsed in parallel with rayon, significantly improving
-/// performance on large document sets. Target: 500 documents in <1 second
-/// (SC-001). Files that cannot be read or parsed are skipped and reported
-/// in [`ScanResult::warnings`].
-pub fn parse_directory(repository_path: &Path) -> Result<ScanResult, SaraError> {
-    let files = scan_directory(repository_path)?;
-
-    // Parse files in parallel
-    let results: Vec<ParseResult> = files
-        .par_iter()
-        .map(|file_path| {
-            // Read file content
-            let content = match fs::read_to_string(file_path) {
-                Ok(c) => c,
-                Err(e) => return ParseResult::ReadError(e),
-            };
-
-            // Skip files without frontmatter
-            if !has_frontmatter(&content) {
-                return ParseResult::Skipped;
-            }
+/// Files that cannot be read or parsed are skipped and reported in
+/// [`ScanResult::warnings`]. See [`parse_source`].
+pub fn parse_directory(
+    repository_path: &Path,
+    ctx: &ParseContext,
+) -> Result<ScanResult, SaraError> {
+    parse_source(&DirectorySource::new(repository_path), ctx)
+}
 
-            // Calculate relative path from repository root
-            let relative_path = file_path
-                .strip_prefix(repository_path)
-                .unwrap_or(file_path)
-                .to_path_buf();
-
-            // Parse the markdown file
-            match parse_metadata(
-                &content,
-                &relative_path,
-                repository_path,
-                InputFormat::Markdown,
-            ) {
-                Ok(item) => ParseResult::Item(Box::new(item)),
-                Err(e) => ParseResult::ParseError(e),
-            }
-        })
-        .collect();
+/// Parses every document of a source.
+///
+/// With the `parallel` feature, documents are parsed in parallel with rayon,
+/// significantly improving performance on large document sets. Target: 500
+/// documents in <1 second (SC-001). Documents that cannot be read or parsed
+/// are skipped and reported in [`ScanResult::warnings`], under their path
+/// joined to the source root.
+///
+/// # Errors
+///
+/// Returns a [`SaraError`] when the source cannot be listed.
+pub fn parse_source(
+    source: &dyn DocumentSource,
+    ctx: &ParseContext,
+) -> Result<ScanResult, SaraError> {
+    let repository = source.root();
+    let read = source.documents()?;
+
+    let parse = |document: &Document| {
+        // Skip documents without item metadata
+        let format = InputFormat::from_path(&document.path).unwrap_or(InputFormat::Markdown);
+        if !has_metadata(&document.content, format) {
+            return ParseResult::Skipped;
+        }
 
-    let mut scan = ScanResult::default();
-    for (file_path, result) in files.iter().zip(results) {
+        match parse_document(&document.content, &document.path, repository, format, ctx) {
+            Ok(items) => ParseResult::Items(items),
+            Err(e) => ParseResult::ParseError(e),
+        }
+    };
+    #[cfg(feature = "parallel")]
+    let results: Vec<ParseResult> = read.documents.par_iter().map(parse).collect();
+    #[cfg(not(feature = "parallel"))]
+    let results: Vec<ParseResult> = read.documents.iter().map(parse).collect();
+
+    let mut scan = ScanResult {
+        items: Vec::new(),
+        warnings: read.warnings,
+    };
+    for (document, result) in read.documents.iter().zip(results) {
         match result {
-            ParseResult::Item(item) => scan.items.push(*item),
+            ParseResult::Items(items) => scan.items.extend(items),
             ParseResult::Skipped => {}
-            ParseResult::ReadError(e) => {
-                scan.warnings.push(ScanWarning {
-                    path: file_path.clone(),
-                    reason: e.to_string(),
-                });
-            }
             ParseResult::ParseError(e) => {
                 scan.warnings.push(ScanWarning {
-                    path: file_path.clone(),
+                    path: repository.join(&document.path),
                     reason: e.to_string(),
                 });
             }
@@ -159,7 +156,7 @@ pub fn parse_directory(repository_path: &Path) -> Result<ScanResult, SaraError>
 /// Repository paths that do not exist or cannot be scanned are skipped and
 /// reported in [`ScanResult::warnings`], together with the warnings from
 /// every scanned repository.
-pub fn parse_repositories(paths: &[PathBuf]) -> ScanResult {
+pub fn parse_repositories(paths: &[PathBuf], ctx: &ParseContext) -> ScanResult {
     let mut scan = ScanResult::default();
 
     let mut valid_paths = Vec::new();
@@ -176,10 +173,11 @@ pub fn parse_repositories(paths: &[PathBuf]) -> ScanResult {
 
     // For small number of repositories, parallelism at file level is more efficient
     // For larger numbers, we could parallelize at the repository level too
-    let results: Vec<Result<ScanResult, SaraError>> = valid_paths
-        .par_iter()
-        .map(|path| parse_directory(path))
-        .collect();
+    let parse = |path: &&PathBuf| parse_directory(path, ctx);
+    #[cfg(feature = "parallel")]
+    let results: Vec<Result<ScanResult, SaraError>> = valid_paths.par_iter().map(parse).collect();
+    #[cfg(not(feature = "parallel"))]
+    let results: Vec<Result<ScanResult, SaraError>> = valid_paths.iter().map(parse).collect();
 
     // Combine results
     for (path, result) in valid_paths.iter().zip(results) {
@@ -238,7 +236,7 @@ mod tests {
         )
         .unwrap();
 
-        let scan = parse_directory(temp_dir.path()).unwrap();
+        let scan = parse_directory(temp_dir.path(), &ParseContext::default()).unwrap();
 
         assert_eq!(scan.items.len(), 1);
         assert_eq!(scan.items[0].id.as_str(), "SOL-001");
@@ -247,11 +245,90 @@ mod tests {
         assert!(scan.warnings[0].to_string().starts_with("skipped "));
     }
 
+    #[test]
+    fn test_parse_directory_dispatches_on_extension() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, content: &str| {
+            std::fs::write(temp_dir.path().join(name), content).unwrap();
+        };
+        write(
+            "SOL-001.adoc",
+            "= Hub\n:id: SOL-001\n:type: solution\n:name: Hub\n\nBody.\n",
+        );
+        write(
+            "UC-001.rst",
+            "Pairing\n=======\n\n:id: UC-001\n:type: use_case\n:name: Pairing\n\
+             :refines: SOL-001\n",
+        );
+        write("guide.adoc", "= User guide\n:toc:\n\nNot an item.\n");
+        write("notes.txt", ":id: SOL-009\n");
+
+        let scan = parse_directory(temp_dir.path(), &ParseContext::default()).unwrap();
+
+        let mut ids: Vec<_> = scan.items.iter().map(|i| i.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["SOL-001", "UC-001"]);
+        assert!(scan.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_directory_reads_item_files_and_toml_frontmatter() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, content: &str| {
+            std::fs::write(temp_dir.path().join(name), content).unwrap();
+        };
+        write(
+            "SOL-001.md",
+            "+++\nid = \"SOL-001\"\ntype = \"solution\"\nname = \"Hub\"\n+++\n# Hub\n",
+        );
+        write(
+            "use-cases.sara.yaml",
+            "- id: UC-001\n  type: use_case\n  name: Pairing\n  refines: [SOL-001]\n\
+             - id: UC-002\n  type: use_case\n  name: Reset\n  refines: [SOL-001]\n",
+        );
+        write(
+            "scenarios.sara.toml",
+            "[[items]]\nid = \"SCEN-001\"\ntype = \"scenario\"\nname = \"Pair\"\n\
+             refines = [\"UC-001\"]\n",
+        );
+        write("config.yaml", "id: SOL-009\n");
+
+        let scan = parse_directory(temp_dir.path(), &ParseContext::default()).unwrap();
+
+        let mut ids: Vec<_> = scan.items.iter().map(|i| i.id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["SCEN-001", "SOL-001", "UC-001", "UC-002"]);
+        assert!(scan.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_directory_reads_items_anchored_on_headings() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("srs.md"),
+            "# Chapter 3\n\n## SYSREQ-001: Latency\n\n```sara\ntype: system_requirement\n\
+             specification: The hub SHALL answer within 100ms.\n```\n\nBody.\n\n\
+             ## SYSREQ-002: Throughput\n\n```sara\ntype: system_requirement\n\
+             specification: The hub SHALL route 1000 messages per second.\n```\n",
+        )
+        .unwrap();
+
+        let scan = parse_directory(temp_dir.path(), &ParseContext::default()).unwrap();
+
+        assert!(scan.warnings.is_empty());
+        let located: Vec<_> = scan
+            .items
+            .iter()
+            .map(|i| format!("{} {}", i.id, i.source))
+            .collect();
+        assert_eq!(located, ["SYSREQ-001 srs.md:3", "SYSREQ-002 srs.md:12"]);
+    }
+
     #[test]
     fn test_parse_repositories_reports_missing_path() {
         let missing = PathBuf::from("/nonexistent/sara-repository");
 
-        let scan = parse_repositories(std::slice::from_ref(&missing));
+        let scan = parse_repositories(std::slice::from_ref(&missing), &ParseContext::default());
 
         assert!(scan.items.is_empty());
         assert_eq!(scan.warnings.len(), 1);
@@ -0,0 +1,150 @@
+//! Sources of documents to read items from.
+//!
+//! A [`DocumentSource`] lists documents and their content, wherever they
+//! live: a directory on disk ([`DirectorySource`]), a Git commit
+//! ([`super::GitSource`], with the `git` feature) or memory
+//! ([`MemorySource`]), so embedders can validate documents without touching
+//! the filesystem.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ScanWarning, scan_directory};
+use crate::error::SaraError;
+
+/// A document read from a source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    /// Path of the document, relative to the source root. Its extension
+    /// selects the input format.
+    pub path: PathBuf,
+    /// Content of the document.
+    pub content: String,
+}
+
+/// Documents read from a source, along with the ones that could not be read.
+#[derive(Debug, Default)]
+pub struct Documents {
+    /// Documents read successfully.
+    pub documents: Vec<Document>,
+    /// One warning per document that could not be read.
+    pub warnings: Vec<ScanWarning>,
+}
+
+/// A set of documents to read items from.
+pub trait DocumentSource {
+    /// Returns the root the document paths are relative to, recorded as the
+    /// repository of the items read.
+    fn root(&self) -> &Path;
+
+    /// Reads every document of the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SaraError`] when the source cannot be listed at all;
+    /// documents that cannot be read are reported in
+    /// [`Documents::warnings`] instead.
+    fn documents(&self) -> Result<Documents, SaraError>;
+}
+
+/// The documents of a directory tree on disk.
+#[derive(Debug, Clone)]
+pub struct DirectorySource {
+    root: PathBuf,
+}
+
+impl DirectorySource {
+    /// Creates a source over the documents under `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl DocumentSource for DirectorySource {
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn documents(&self) -> Result<Documents, SaraError> {
+        let mut documents = Documents::default();
+        for file in scan_directory(&self.root)? {
+            match fs::read_to_string(&file) {
+                Ok(content) => documents.documents.push(Document {
+                    path: file.strip_prefix(&self.root).unwrap_or(&file).to_path_buf(),
+                    content,
+                }),
+                Err(e) => documents.warnings.push(ScanWarning {
+                    path: file,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        Ok(documents)
+    }
+}
+
+/// Documents held in memory, as `(path, content)` pairs.
+///
+/// ```
+/// use sara_core::repository::MemorySource;
+///
+/// let source: MemorySource = [(
+///     "SOL-001.md",
+///     "---\nid: SOL-001\ntype: solution\nname: Platform\n---\n",
+/// )]
+/// .into_iter()
+/// .collect();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemorySource {
+    root: PathBuf,
+    documents: Vec<Document>,
+}
+
+impl MemorySource {
+    /// Creates an empty source whose items record `root` as their
+    /// repository.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            documents: Vec::new(),
+        }
+    }
+
+    /// Adds a document.
+    pub fn with_document(mut self, path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        self.add_document(path, content);
+        self
+    }
+
+    /// Adds a document.
+    pub fn add_document(&mut self, path: impl Into<PathBuf>, content: impl Into<String>) {
+        self.documents.push(Document {
+            path: path.into(),
+            content: content.into(),
+        });
+    }
+}
+
+impl<P: Into<PathBuf>, C: Into<String>> FromIterator<(P, C)> for MemorySource {
+    fn from_iter<I: IntoIterator<Item = (P, C)>>(iter: I) -> Self {
+        let mut source = Self::default();
+        for (path, content) in iter {
+            source.add_document(path, content);
+        }
+        source
+    }
+}
+
+impl DocumentSource for MemorySource {
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn documents(&self) -> Result<Documents, SaraError> {
+        Ok(Documents {
+            documents: self.documents.clone(),
+            warnings: Vec::new(),
+        })
+    }
+}
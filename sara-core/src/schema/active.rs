@@ -1,33 +1,52 @@
-//! Process-wide active [`Schema`] used by domain methods.
+//! Process-wide default [`Schema`] and the identifiers of loaded schemas.
 //!
 //! Domain types ([`crate::model::ItemType`], [`crate::model::RelationshipType`])
-//! resolve their metadata against the active schema rather than hard-coded
-//! tables. Callers therefore see the active schema's prefixes, display names,
-//! parent links and relationship matrix.
+//! resolve their metadata against a schema rather than hard-coded tables.
+//! Their `_in` methods take the schema to resolve against; this is how the
+//! parser, a [`crate::graph::KnowledgeGraph`] and the validator work against
+//! the schema they were handed. Their plain methods are shorthands resolving
+//! against [`active`]: the schema installed once at startup (typically by
+//! the CLI after loading `Config`) through [`install`], otherwise the
+//! built-in default.
 //!
-//! The active schema is installed once per process at startup (typically by
-//! the CLI after loading `Config`). If no schema is installed, the active
-//! schema is the built-in default. An installed schema replaces the built-in
-//! model entirely: types and relations it does not define do not exist.
+//! An installed schema replaces the built-in model entirely: types and
+//! relations it does not define do not exist. A schema file extending the
+//! built-in model is installed fully resolved.
 
-use std::sync::OnceLock;
+use std::collections::BTreeSet;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
 
 use super::{ItemTypeDef, RelationDef, Schema};
 
 /// Holds the installed schema, if any. Lazily defaults to [`Schema::builtin`].
-static ACTIVE: OnceLock<Schema> = OnceLock::new();
+static ACTIVE: OnceLock<Arc<Schema>> = OnceLock::new();
 
-/// Returns the active schema, initializing it to the built-in default on first
-/// access if no schema has been installed.
+/// Identifiers of the types and relations of every schema resolved so far.
+static SYMBOLS: RwLock<BTreeSet<&'static str>> = RwLock::new(BTreeSet::new());
+
+/// Returns the installed schema, initializing it to the built-in default on
+/// first access if no schema has been installed.
 ///
 /// The returned reference has `'static` lifetime so domain methods can keep
 /// returning `&'static str` for prefixes and display names.
 #[must_use]
 pub fn active() -> &'static Schema {
-    ACTIVE.get_or_init(Schema::builtin)
+    installed()
+}
+
+/// Returns a shared handle on the installed schema (see [`active`]), to
+/// hand to the parser or a graph.
+#[must_use]
+pub fn shared() -> Arc<Schema> {
+    Arc::clone(installed())
 }
 
-/// Installs the process-wide active schema.
+/// Returns the installed schema, defaulting to the built-in one.
+fn installed() -> &'static Arc<Schema> {
+    ACTIVE.get_or_init(|| Arc::new(Schema::builtin()))
+}
+
+/// Installs the process-wide default schema.
 ///
 /// Intended to be called once at startup, before any domain method is invoked.
 /// Subsequent calls return the supplied schema back as `Err`, mirroring the
@@ -37,17 +56,64 @@ pub fn active() -> &'static Schema {
 ///
 /// Returns the supplied schema unchanged if another schema is already active.
 pub fn install(schema: Schema) -> Result<(), Schema> {
-    ACTIVE.set(schema)
+    if ACTIVE.get().is_some() {
+        return Err(schema);
+    }
+    ACTIVE.set(Arc::new(schema)).map_err(Arc::unwrap_or_clone)
 }
 
-/// Returns the definition for an item type id in the active schema.
+/// Returns the `'static` copy of a type or relation identifier.
+///
+/// Domain types are `Copy` handles over their identifier, so identifiers
+/// read from a schema other than the installed one are kept for the rest of
+/// the process. The table grows with distinct identifiers only: loading the
+/// same model again (one diff after another, say, or a reloaded schema)
+/// allocates nothing.
+pub(crate) fn symbol(id: &str) -> &'static str {
+    if let Some(symbol) = SYMBOLS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(id)
+    {
+        return symbol;
+    }
+    let mut symbols = SYMBOLS.write().unwrap_or_else(PoisonError::into_inner);
+    if let Some(symbol) = symbols.get(id) {
+        return symbol;
+    }
+    let symbol: &'static str = id.to_string().leak();
+    symbols.insert(symbol);
+    symbol
+}
+
+/// Returns the definition for an item type id in the installed schema.
 #[must_use]
 pub(crate) fn item_type_def(id: &str) -> Option<&'static ItemTypeDef> {
     active().item_type(id)
 }
 
-/// Returns the relation definition for a relation id in the active schema.
+/// Returns the relation definition for a relation id in the installed
+/// schema.
 #[must_use]
 pub(crate) fn relation_def(id: &str) -> Option<&'static RelationDef> {
     active().relation(id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols_are_shared_by_equal_ids() {
+        let first = symbol(&String::from("test_case"));
+        let second = symbol(&String::from("test_case"));
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(first, "test_case");
+        assert!(!std::ptr::eq(first, symbol("test_plan")));
+    }
+
+    #[test]
+    fn test_shared_handle_is_the_active_schema() {
+        assert!(std::ptr::eq(active(), &*shared()));
+    }
+}
@@ -75,6 +75,11 @@ fn relation(
         inverse: inverse.as_str().to_string(),
         direction,
         primary,
+        aliases: Vec::new(),
+        acyclic: true,
+        transitive: false,
+        unique_target: false,
+        attributes: Vec::new(),
     }
 }
 
@@ -83,6 +88,8 @@ fn allowed(relation: RelationshipType, targets: &[ItemType]) -> AllowedTarget {
     AllowedTarget {
         relation: relation.as_str().to_string(),
         targets: targets.iter().map(|t| t.as_str().to_string()).collect(),
+        min: None,
+        max: None,
     }
 }
 
@@ -91,9 +98,10 @@ fn specification_field() -> FieldDef {
     FieldDef {
         name: "specification".to_string(),
         display_name: "Specification".to_string(),
-        field_type: FieldType::Text,
+        field_type: FieldType::TEXT,
         required: true,
         placeholder: Some("The system SHALL <describe the requirement>.".to_string()),
+        aliases: Vec::new(),
     }
 }
 
@@ -202,6 +210,8 @@ impl Schema {
                 parent_types: vec![],
                 fields: vec![],
                 allowed_targets: vec![],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: USE_CASE.as_str().to_string(),
@@ -211,6 +221,8 @@ impl Schema {
                 parent_types: vec![SOLUTION.as_str().to_string()],
                 fields: vec![],
                 allowed_targets: vec![allowed(REFINES, &[SOLUTION])],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: SCENARIO.as_str().to_string(),
@@ -220,6 +232,8 @@ impl Schema {
                 parent_types: vec![USE_CASE.as_str().to_string()],
                 fields: vec![],
                 allowed_targets: vec![allowed(REFINES, &[USE_CASE])],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: SYSTEM_REQUIREMENT.as_str().to_string(),
@@ -232,6 +246,8 @@ impl Schema {
                     allowed(DERIVES_FROM, &[SCENARIO]),
                     allowed(DEPENDS_ON, &[SYSTEM_REQUIREMENT]),
                 ],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: SYSTEM_ARCHITECTURE.as_str().to_string(),
@@ -242,11 +258,14 @@ impl Schema {
                 fields: vec![FieldDef {
                     name: "platform".to_string(),
                     display_name: "Platform".to_string(),
-                    field_type: FieldType::Text,
+                    field_type: FieldType::TEXT,
                     required: false,
                     placeholder: None,
+                    aliases: Vec::new(),
                 }],
                 allowed_targets: vec![allowed(SATISFIES, &[SYSTEM_REQUIREMENT])],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: HARDWARE_REQUIREMENT.as_str().to_string(),
@@ -259,6 +278,8 @@ impl Schema {
                     allowed(DERIVES_FROM, &[SYSTEM_ARCHITECTURE]),
                     allowed(DEPENDS_ON, &[HARDWARE_REQUIREMENT]),
                 ],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: SOFTWARE_REQUIREMENT.as_str().to_string(),
@@ -271,6 +292,8 @@ impl Schema {
                     allowed(DERIVES_FROM, &[SYSTEM_ARCHITECTURE]),
                     allowed(DEPENDS_ON, &[SOFTWARE_REQUIREMENT]),
                 ],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: HARDWARE_DETAILED_DESIGN.as_str().to_string(),
@@ -280,6 +303,8 @@ impl Schema {
                 parent_types: vec![HARDWARE_REQUIREMENT.as_str().to_string()],
                 fields: vec![],
                 allowed_targets: vec![allowed(SATISFIES, &[HARDWARE_REQUIREMENT])],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: SOFTWARE_DETAILED_DESIGN.as_str().to_string(),
@@ -289,6 +314,8 @@ impl Schema {
                 parent_types: vec![SOFTWARE_REQUIREMENT.as_str().to_string()],
                 fields: vec![],
                 allowed_targets: vec![allowed(SATISFIES, &[SOFTWARE_REQUIREMENT])],
+                sections: vec![],
+                aliases: Vec::new(),
             },
             ItemTypeDef {
                 id: ARCHITECTURE_DECISION_RECORD.as_str().to_string(),
@@ -310,13 +337,15 @@ impl Schema {
                         },
                         required: true,
                         placeholder: Some("proposed".to_string()),
+                        aliases: Vec::new(),
                     },
                     FieldDef {
                         name: "deciders".to_string(),
                         display_name: "Deciders".to_string(),
-                        field_type: FieldType::List(Box::new(FieldType::Text)),
+                        field_type: FieldType::List(Box::new(FieldType::TEXT)),
                         required: true,
                         placeholder: Some("TBD".to_string()),
+                        aliases: Vec::new(),
                     },
                 ],
                 allowed_targets: vec![
@@ -330,6 +359,8 @@ impl Schema {
                     ),
                     allowed(SUPERSEDES, &[ARCHITECTURE_DECISION_RECORD]),
                 ],
+                sections: vec![],
+                aliases: Vec::new(),
             },
         ];
 
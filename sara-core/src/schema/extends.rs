@@ -0,0 +1,465 @@
+//! Schema inheritance: extending a base schema instead of replacing it.
+//!
+//! A schema file with an `extends` key is an overlay on a base schema, either
+//! the built-in model (`extends: builtin`) or another schema file, itself
+//! possibly an overlay. The overlay only lists what changes:
+//!
+//! ```yaml
+//! extends: builtin
+//! item_types:
+//! - id: test_case              # unknown to the base: a complete new type
+//!   display_name: Test Case
+//!   prefix: TC
+//!   id_format: "{prefix}-{seq:03}"
+//!   allowed_targets:
+//!   - relation: verifies
+//!     targets: [system_requirement]
+//! - id: system_requirement     # known to the base: merged into it
+//!   fields:
+//!   - name: asil
+//!     display_name: ASIL
+//!     field_type: text
+//! relations:
+//! - id: verifies
+//!   # ...
+//! remove:
+//!   item_types: [hardware_detailed_design]
+//!   fields: [architecture_decision_record.deciders]
+//! ```
+//!
+//! Removals apply first, then additions, so an entry can be removed and
+//! declared again to replace it wholesale.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::{AllowedTarget, FieldDef, ItemTypeDef, ReadFile, RelationDef, Schema, SectionDef};
+use crate::error::SaraError;
+
+/// `extends` value naming the built-in model.
+const BUILTIN_BASE: &str = "builtin";
+
+/// A schema file extending a base schema.
+#[derive(Debug, Deserialize)]
+pub(super) struct SchemaOverlay {
+    /// `builtin`, or the path of the base schema file, relative to the
+    /// overlay file.
+    pub extends: String,
+    /// Types to add, or to merge into the base types of the same id.
+    #[serde(default)]
+    item_types: Vec<ItemTypePatch>,
+    /// Relations to add, or to replace the base relations of the same id.
+    #[serde(default)]
+    relations: Vec<RelationDef>,
+    /// Base entries to remove.
+    #[serde(default)]
+    remove: Removals,
+}
+
+/// Additions to one item type. Every key is optional for a type of the base
+/// schema; a new type needs `display_name`, `prefix` and `id_format`.
+#[derive(Debug, Deserialize)]
+struct ItemTypePatch {
+    id: String,
+    display_name: Option<String>,
+    prefix: Option<String>,
+    id_format: Option<String>,
+    /// Parent types added to the base ones.
+    #[serde(default)]
+    parent_types: Vec<String>,
+    /// Fields added; a field of the same name replaces the base one.
+    #[serde(default)]
+    fields: Vec<FieldDef>,
+    /// Targets added to the relation of the same id, or new relations; a
+    /// cardinality bound replaces the base one.
+    #[serde(default)]
+    allowed_targets: Vec<AllowedTarget>,
+    /// Sections added; a section of the same field replaces the base one.
+    #[serde(default)]
+    sections: Vec<SectionDef>,
+    /// Aliases added to the base ones.
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+/// Base entries removed by an overlay.
+///
+/// Members of a type are written `type.member`; `type.relation.target`
+/// removes a single target of an allowed relation.
+#[derive(Debug, Default, Deserialize)]
+struct Removals {
+    /// Types removed, with every reference to them.
+    #[serde(default)]
+    item_types: Vec<String>,
+    /// Relations removed together with their inverse and every declaration.
+    #[serde(default)]
+    relations: Vec<String>,
+    /// `type.parent` parent links.
+    #[serde(default)]
+    parent_types: Vec<String>,
+    /// `type.field` fields.
+    #[serde(default)]
+    fields: Vec<String>,
+    /// `type.field` body sections.
+    #[serde(default)]
+    sections: Vec<String>,
+    /// `type.relation` or `type.relation.target` allowed targets.
+    #[serde(default)]
+    allowed_targets: Vec<String>,
+}
+
+impl SchemaOverlay {
+    /// Loads the base schema and applies the overlay to it.
+    ///
+    /// `chain` holds the files being resolved, to report inheritance cycles,
+    /// and `read` reads the base file.
+    pub(super) fn resolve(
+        self,
+        source: &Path,
+        chain: &mut Vec<PathBuf>,
+        read: &ReadFile<'_>,
+    ) -> Result<Schema, SaraError> {
+        let mut schema = if self.extends == BUILTIN_BASE {
+            Schema::builtin()
+        } else {
+            let base = source.parent().unwrap_or(Path::new("")).join(&self.extends);
+            Schema::load(&base, chain, read)?
+        };
+        let invalid = |reason: String| SaraError::InvalidConfig {
+            path: source.to_path_buf(),
+            reason,
+        };
+
+        self.remove.apply(&mut schema).map_err(invalid)?;
+        for relation in self.relations {
+            match schema.relations.iter_mut().find(|r| r.id == relation.id) {
+                Some(existing) => *existing = relation,
+                None => schema.relations.push(relation),
+            }
+        }
+        for patch in self.item_types {
+            match schema.item_types.iter_mut().find(|t| t.id == patch.id) {
+                Some(existing) => patch.merge_into(existing),
+                None => schema
+                    .item_types
+                    .push(patch.into_new_type().map_err(invalid)?),
+            }
+        }
+        Ok(schema)
+    }
+}
+
+impl ItemTypePatch {
+    /// Merges the patch into a type of the base schema.
+    fn merge_into(self, def: &mut ItemTypeDef) {
+        if let Some(display_name) = self.display_name {
+            def.display_name = display_name;
+        }
+        if let Some(prefix) = self.prefix {
+            def.prefix = prefix;
+        }
+        if let Some(id_format) = self.id_format {
+            def.id_format = id_format;
+        }
+        for parent in self.parent_types {
+            if !def.parent_types.contains(&parent) {
+                def.parent_types.push(parent);
+            }
+        }
+        for field in self.fields {
+            match def.fields.iter_mut().find(|f| f.name == field.name) {
+                Some(existing) => *existing = field,
+                None => def.fields.push(field),
+            }
+        }
+        for target in self.allowed_targets {
+            match def
+                .allowed_targets
+                .iter_mut()
+                .find(|t| t.relation == target.relation)
+            {
+                Some(existing) => {
+                    for id in target.targets {
+                        if !existing.targets.contains(&id) {
+                            existing.targets.push(id);
+                        }
+                    }
+                    existing.min = target.min.or(existing.min);
+                    existing.max = target.max.or(existing.max);
+                }
+                None => def.allowed_targets.push(target),
+            }
+        }
+        for section in self.sections {
+            match def.sections.iter_mut().find(|s| s.field == section.field) {
+                Some(existing) => *existing = section,
+                None => def.sections.push(section),
+            }
+        }
+        for alias in self.aliases {
+            if !def.aliases.contains(&alias) {
+                def.aliases.push(alias);
+            }
+        }
+    }
+
+    /// Turns the patch of a type unknown to the base into a new type.
+    fn into_new_type(self) -> Result<ItemTypeDef, String> {
+        let missing: Vec<&str> = [
+            ("display_name", self.display_name.is_none()),
+            ("prefix", self.prefix.is_none()),
+            ("id_format", self.id_format.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(key, missing)| missing.then_some(key))
+        .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "type '{}' is not in the base schema and lacks {}",
+                self.id,
+                missing.join(", ")
+            ));
+        }
+        Ok(ItemTypeDef {
+            id: self.id,
+            display_name: self.display_name.unwrap_or_default(),
+            prefix: self.prefix.unwrap_or_default(),
+            id_format: self.id_format.unwrap_or_default(),
+            parent_types: self.parent_types,
+            fields: self.fields,
+            allowed_targets: self.allowed_targets,
+            sections: self.sections,
+            aliases: self.aliases,
+        })
+    }
+}
+
+impl Removals {
+    /// Removes the listed entries from the base schema.
+    ///
+    /// Removing a type or a relation also drops every reference to it, so the
+    /// result stays consistent. Naming an entry the base does not have is an
+    /// error, as it usually is a typo.
+    fn apply(&self, schema: &mut Schema) -> Result<(), String> {
+        for id in &self.item_types {
+            let before = schema.item_types.len();
+            schema.item_types.retain(|t| t.id != *id);
+            if schema.item_types.len() == before {
+                return Err(format!("cannot remove unknown type '{id}'"));
+            }
+            for def in &mut schema.item_types {
+                def.parent_types.retain(|p| p != id);
+                for target in &mut def.allowed_targets {
+                    target.targets.retain(|t| t != id);
+                }
+                def.allowed_targets.retain(|t| !t.targets.is_empty());
+            }
+        }
+
+        for id in &self.relations {
+            let inverse = schema
+                .relation(id)
+                .map(|r| r.inverse.clone())
+                .ok_or_else(|| format!("cannot remove unknown relation '{id}'"))?;
+            schema.relations.retain(|r| r.id != *id && r.id != inverse);
+            for def in &mut schema.item_types {
+                def.allowed_targets
+                    .retain(|t| t.relation != *id && t.relation != inverse);
+            }
+        }
+
+        for path in &self.parent_types {
+            let (def, parent) = member(schema, path)?;
+            let before = def.parent_types.len();
+            def.parent_types.retain(|p| p != parent);
+            if def.parent_types.len() == before {
+                return Err(format!("cannot remove unknown parent type '{path}'"));
+            }
+        }
+
+        for path in &self.fields {
+            let (def, name) = member(schema, path)?;
+            let before = def.fields.len();
+            def.fields.retain(|f| f.name != name);
+            if def.fields.len() == before {
+                return Err(format!("cannot remove unknown field '{path}'"));
+            }
+        }
+
+        for path in &self.sections {
+            let (def, field) = member(schema, path)?;
+            let before = def.sections.len();
+            def.sections.retain(|s| s.field != field);
+            if def.sections.len() == before {
+                return Err(format!("cannot remove unknown section '{path}'"));
+            }
+        }
+
+        for path in &self.allowed_targets {
+            let (def, rest) = member(schema, path)?;
+            let (relation, target) = match rest.split_once('.') {
+                Some((relation, target)) => (relation, Some(target)),
+                None => (rest, None),
+            };
+            let Some(index) = def
+                .allowed_targets
+                .iter()
+                .position(|t| t.relation == relation)
+            else {
+                return Err(format!("cannot remove unknown allowed target '{path}'"));
+            };
+            match target {
+                Some(target) => {
+                    let targets = &mut def.allowed_targets[index].targets;
+                    let before = targets.len();
+                    targets.retain(|t| t != target);
+                    if targets.len() == before {
+                        return Err(format!("cannot remove unknown allowed target '{path}'"));
+                    }
+                    if targets.is_empty() {
+                        def.allowed_targets.remove(index);
+                    }
+                }
+                None => {
+                    def.allowed_targets.remove(index);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves a `type.member` path to the type definition and the member.
+fn member<'a, 'p>(
+    schema: &'a mut Schema,
+    path: &'p str,
+) -> Result<(&'a mut ItemTypeDef, &'p str), String> {
+    let (type_id, rest) = path
+        .split_once('.')
+        .ok_or_else(|| format!("'{path}' must be written type.member"))?;
+    let def = schema
+        .item_types
+        .iter_mut()
+        .find(|t| t.id == type_id)
+        .ok_or_else(|| format!("'{path}' names unknown type '{type_id}'"))?;
+    Ok((def, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const TEST_CASE: &str = r#"extends: builtin
+item_types:
+- id: test_case
+  display_name: Test Case
+  prefix: TC
+  id_format: "{prefix}-{seq:03}"
+  parent_types: [system_requirement]
+  allowed_targets:
+  - relation: verifies
+    targets: [system_requirement]
+- id: system_requirement
+  fields:
+  - name: asil
+    display_name: ASIL
+    field_type: text
+relations:
+- id: verifies
+  display_name: Verifies
+  inverse: is_verified_by
+  direction: upstream
+  primary: true
+- id: is_verified_by
+  display_name: Is verified by
+  inverse: verifies
+  direction: downstream
+  primary: false
+"#;
+
+    fn load(yaml: &str) -> Result<Schema, SaraError> {
+        Schema::from_yaml_str(yaml, Path::new("model.yaml"))
+    }
+
+    #[test]
+    fn test_extends_builtin_adds_types_fields_and_relations() {
+        let schema = load(TEST_CASE).unwrap();
+        let base = Schema::builtin();
+
+        assert_eq!(schema.item_types.len(), base.item_types.len() + 1);
+        assert_eq!(schema.relations.len(), base.relations.len() + 2);
+        assert!(schema.is_valid_relationship("test_case", "system_requirement", "verifies"));
+        assert!(schema.is_valid_relationship("system_requirement", "test_case", "is_verified_by"));
+
+        let sysreq = schema.item_type("system_requirement").unwrap();
+        let base_sysreq = base.item_type("system_requirement").unwrap();
+        assert_eq!(sysreq.fields.len(), base_sysreq.fields.len() + 1);
+        assert_eq!(sysreq.fields.last().unwrap().name, "asil");
+        assert_eq!(sysreq.prefix, base_sysreq.prefix);
+    }
+
+    #[test]
+    fn test_removals_cascade() {
+        let yaml = "extends: builtin\nremove:\n  item_types: [hardware_detailed_design]\n  \
+                    relations: [supersedes]\n  fields: [architecture_decision_record.deciders]\n  \
+                    allowed_targets: [software_requirement.derives_from.system_architecture]\n";
+        let schema = load(yaml).unwrap();
+
+        assert!(schema.item_type("hardware_detailed_design").is_none());
+        assert!(schema.relation("supersedes").is_none());
+        assert!(schema.relation("is_superseded_by").is_none());
+        let adr = schema.item_type("architecture_decision_record").unwrap();
+        assert!(adr.fields.iter().all(|f| f.name != "deciders"));
+        assert!(
+            adr.allowed_targets
+                .iter()
+                .all(|t| t.relation != "supersedes")
+        );
+        assert!(schema.item_types.iter().all(|t| {
+            t.allowed_targets
+                .iter()
+                .all(|a| !a.targets.iter().any(|id| id == "hardware_detailed_design"))
+        }));
+        assert!(!schema.is_valid_relationship(
+            "software_requirement",
+            "system_architecture",
+            "derives_from"
+        ));
+
+        for unknown in [
+            "remove:\n  item_types: [nope]",
+            "remove:\n  fields: [solution.nope]",
+            "remove:\n  fields: [nope]",
+            "item_types:\n- id: test_case\n  prefix: TC",
+        ] {
+            let err = load(&format!("extends: builtin\n{unknown}\n")).unwrap_err();
+            assert!(
+                matches!(err, SaraError::InvalidConfig { .. }),
+                "{unknown}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_extends_a_schema_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("base.yaml"), TEST_CASE).unwrap();
+        let overlay = dir.path().join("model.yaml");
+        std::fs::write(
+            &overlay,
+            "extends: base.yaml\nitem_types:\n- id: test_case\n  display_name: Test\n",
+        )
+        .unwrap();
+
+        let schema = Schema::from_path(&overlay).unwrap();
+        assert_eq!(schema.item_type("test_case").unwrap().display_name, "Test");
+
+        std::fs::write(dir.path().join("base.yaml"), "extends: model.yaml\n").unwrap();
+        let err = Schema::from_path(&overlay).unwrap_err();
+        assert!(err.to_string().contains("inheritance cycle"), "{err}");
+    }
+}
@@ -0,0 +1,564 @@
+//! Human-readable documentation of the model.
+//!
+//! Renders a model guide from the schema: an entity-relationship diagram of
+//! the item types and their relations, a reference of each type (ID format,
+//! fields, relations), the relation catalog and the validity matrix derived
+//! by [`Schema::is_valid_relationship`]. The guide is built once as a list of
+//! blocks, then written as Markdown or as a standalone HTML page, so both
+//! stay in sync with the schema instead of being maintained by hand.
+
+use super::{FieldType, ItemTypeDef, RelationDirection, Schema};
+use crate::model::{FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME};
+
+/// Script rendering the `mermaid` blocks of the HTML guide.
+const MERMAID_SCRIPT: &str = r#"<script type="module">
+import mermaid from "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs";
+mermaid.initialize({ startOnLoad: true });
+</script>"#;
+
+impl Schema {
+    /// Returns a Mermaid entity-relationship diagram of the item types and
+    /// the relations they declare.
+    ///
+    /// Upstream relations are drawn from the target (parent) to the source,
+    /// labelled with the inverse relation; peer relations from the source,
+    /// labelled with the relation itself.
+    #[must_use]
+    pub fn to_mermaid(&self) -> String {
+        let mut lines = vec!["erDiagram".to_string()];
+
+        for def in &self.item_types {
+            lines.push(format!("    {} {{", entity_name(def)));
+            lines.push(format!("        string {FIELD_ID} PK"));
+            lines.push(format!("        string {FIELD_NAME}"));
+            lines.push(format!("        string {FIELD_DESCRIPTION}"));
+            for field in &def.fields {
+                lines.push(format!(
+                    "        {} {}",
+                    mermaid_type(&field.field_type),
+                    field.name
+                ));
+            }
+            for allowed in &def.allowed_targets {
+                lines.push(format!("        string[] {} FK", allowed.relation));
+            }
+            lines.push("    }".to_string());
+            lines.push(String::new());
+        }
+
+        for def in &self.item_types {
+            for allowed in &def.allowed_targets {
+                let Some(relation) = self.relation(&allowed.relation) else {
+                    continue;
+                };
+                // The side of the target type reflects the declared cardinality.
+                let target_side = match (allowed.min.unwrap_or(0) > 0, allowed.max == Some(1)) {
+                    (true, true) => "||",
+                    (false, true) => "|o",
+                    (true, false) => "}|",
+                    (false, false) => "}o",
+                };
+                for target in allowed.targets.iter().filter_map(|t| self.item_type(t)) {
+                    let line = if relation.direction == RelationDirection::Peer {
+                        format!(
+                            "    {} }}o--o{{ {} : \"{}\"",
+                            entity_name(def),
+                            entity_name(target),
+                            relation.id
+                        )
+                    } else {
+                        format!(
+                            "    {} {target_side}--o{{ {} : \"{}\"",
+                            entity_name(target),
+                            entity_name(def),
+                            relation.inverse
+                        )
+                    };
+                    lines.push(line);
+                }
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Returns the model guide as a Markdown document.
+    #[must_use]
+    pub fn to_markdown_guide(&self) -> String {
+        self.guide()
+            .iter()
+            .map(Block::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the model guide as a standalone HTML page. The diagram is
+    /// rendered by Mermaid, loaded from a CDN.
+    #[must_use]
+    pub fn to_html_guide(&self) -> String {
+        let body: String = self.guide().iter().map(Block::to_html).collect();
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<title>Model Guide</title>
+<style>
+body {{ font-family: system-ui, sans-serif; margin: 2rem; }}
+table {{ border-collapse: collapse; margin-bottom: 1rem; }}
+th, td {{ border: 1px solid #ddd; padding: 8px; text-align: left; vertical-align: top; }}
+th {{ background-color: #f4f4f4; }}
+</style>
+</head>
+<body>
+{body}{MERMAID_SCRIPT}
+</body>
+</html>
+"#
+        )
+    }
+
+    /// Builds the blocks of the model guide.
+    fn guide(&self) -> Vec<Block<'_>> {
+        let mut blocks = vec![
+            Block::Heading(1, "Model Guide".to_string()),
+            Block::Heading(2, "Diagram".to_string()),
+            Block::Diagram(self.to_mermaid()),
+            Block::Heading(2, "Item Types".to_string()),
+        ];
+        for def in &self.item_types {
+            blocks.extend(self.item_type_blocks(def));
+        }
+        blocks.push(Block::Heading(2, "Relations".to_string()));
+        blocks.push(self.relations_table());
+        blocks.push(Block::Heading(2, "Validity Matrix".to_string()));
+        blocks.push(Block::Paragraph(vec![Inline::plain(
+            "Relations an item of the row type may declare toward an item of the column type.",
+        )]));
+        blocks.push(self.validity_matrix());
+        blocks
+    }
+
+    /// Builds the reference of an item type: its facts, fields and relations.
+    fn item_type_blocks<'a>(&'a self, def: &'a ItemTypeDef) -> Vec<Block<'a>> {
+        let mut facts = vec![
+            (
+                "Type",
+                with_aliases(vec![Inline::code(&def.id)], &def.aliases),
+            ),
+            (
+                "ID format",
+                vec![
+                    Inline::code(&def.id_format),
+                    Inline::plain(" with prefix "),
+                    Inline::code(&def.prefix),
+                ],
+            ),
+        ];
+        let parents: Vec<String> = def
+            .parent_types
+            .iter()
+            .map(|id| self.display_name(id))
+            .collect();
+        facts.push((
+            "Parents",
+            vec![Inline::plain(if parents.is_empty() {
+                "none (hierarchy root)".to_string()
+            } else {
+                parents.join(", ")
+            })],
+        ));
+
+        let mut fields = vec![
+            vec![
+                vec![Inline::code(FIELD_ID)],
+                vec![Inline::plain("text")],
+                vec![Inline::plain("yes")],
+                vec![Inline::plain("Unique identifier")],
+            ],
+            vec![
+                vec![Inline::code(FIELD_NAME)],
+                vec![Inline::plain("text")],
+                vec![Inline::plain("yes")],
+                vec![Inline::plain("Human-readable name")],
+            ],
+            vec![
+                vec![Inline::code(FIELD_DESCRIPTION)],
+                vec![Inline::plain("text")],
+                vec![Inline::plain("no")],
+                vec![Inline::plain("Free-form description")],
+            ],
+        ];
+        for field in &def.fields {
+            let mut notes = vec![Inline::plain(&field.display_name)];
+            if let Some(section) = def.sections.iter().find(|s| s.field == field.name) {
+                notes.push(Inline::plain(", read from the "));
+                notes.push(Inline::code(&section.heading));
+                notes.push(Inline::plain(" section"));
+            }
+            fields.push(vec![
+                with_aliases(vec![Inline::code(&field.name)], &field.aliases),
+                vec![Inline::plain(field.field_type.describe())],
+                vec![Inline::plain(yes_no(field.required))],
+                notes,
+            ]);
+        }
+
+        let mut blocks = vec![
+            Block::Heading(3, def.display_name.clone()),
+            Block::Facts(facts),
+            Block::Table {
+                headers: vec!["Field", "Type", "Required", "Notes"],
+                rows: fields,
+            },
+        ];
+
+        if !def.allowed_targets.is_empty() {
+            let rows = def
+                .allowed_targets
+                .iter()
+                .map(|allowed| {
+                    let targets: Vec<String> = allowed
+                        .targets
+                        .iter()
+                        .map(|id| self.display_name(id))
+                        .collect();
+                    vec![
+                        vec![Inline::code(&allowed.relation)],
+                        vec![Inline::plain(targets.join(", "))],
+                        vec![Inline::plain(
+                            allowed.cardinality().unwrap_or_else(|| "any".to_string()),
+                        )],
+                    ]
+                })
+                .collect();
+            blocks.push(Block::Table {
+                headers: vec!["Relation", "Targets", "Cardinality"],
+                rows,
+            });
+        }
+        blocks
+    }
+
+    /// Builds the table of the relation catalog.
+    fn relations_table(&self) -> Block<'_> {
+        let rows = self
+            .relations
+            .iter()
+            .map(|relation| {
+                let direction = match relation.direction {
+                    RelationDirection::Upstream => "upstream",
+                    RelationDirection::Downstream => "downstream",
+                    RelationDirection::Peer => "peer",
+                };
+                // Properties and attributes are read on the primary relation.
+                let primary = if relation.primary {
+                    Some(relation)
+                } else {
+                    self.relation(&relation.inverse)
+                };
+                let properties: Vec<&str> = primary
+                    .map(|p| {
+                        [
+                            (p.acyclic, "acyclic"),
+                            (p.transitive, "transitive"),
+                            (p.unique_target, "unique target"),
+                        ]
+                        .into_iter()
+                        .filter_map(|(set, name)| set.then_some(name))
+                        .collect()
+                    })
+                    .unwrap_or_default();
+                let attributes: Vec<Inline> = self
+                    .link_attributes(&relation.id)
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, attribute)| {
+                        let separator = (i > 0).then(|| Inline::plain(", "));
+                        separator.into_iter().chain([
+                            Inline::code(&attribute.name),
+                            Inline::plain(format!(" ({})", attribute.field_type.describe())),
+                        ])
+                    })
+                    .collect();
+                vec![
+                    with_aliases(vec![Inline::code(&relation.id)], &relation.aliases),
+                    vec![Inline::plain(&relation.display_name)],
+                    vec![Inline::code(&relation.inverse)],
+                    vec![Inline::plain(direction)],
+                    vec![Inline::plain(properties.join(", "))],
+                    attributes,
+                ]
+            })
+            .collect();
+        Block::Table {
+            headers: vec![
+                "Relation",
+                "Name",
+                "Inverse",
+                "Direction",
+                "Properties",
+                "Link attributes",
+            ],
+            rows,
+        }
+    }
+
+    /// Builds the validity matrix: the relations valid from each type (rows)
+    /// to each type (columns).
+    fn validity_matrix(&self) -> Block<'_> {
+        let mut headers = vec!["From \\ To"];
+        headers.extend(self.item_types.iter().map(|def| def.display_name.as_str()));
+        let rows = self
+            .item_types
+            .iter()
+            .map(|from| {
+                let mut row = vec![vec![Inline::plain(&from.display_name)]];
+                for to in &self.item_types {
+                    let mut cell = Vec::new();
+                    for relation in &self.relations {
+                        if self.is_valid_relationship(&from.id, &to.id, &relation.id) {
+                            if !cell.is_empty() {
+                                cell.push(Inline::plain(", "));
+                            }
+                            cell.push(Inline::code(&relation.id));
+                        }
+                    }
+                    row.push(cell);
+                }
+                row
+            })
+            .collect();
+        Block::Table { headers, rows }
+    }
+
+    /// Returns the display name of an item type, or its id when unknown.
+    fn display_name(&self, id: &str) -> String {
+        self.item_type(id)
+            .map_or_else(|| id.to_string(), |def| def.display_name.clone())
+    }
+}
+
+/// A block of the model guide.
+enum Block<'a> {
+    /// A heading of the given level.
+    Heading(u8, String),
+    /// A paragraph.
+    Paragraph(Vec<Inline>),
+    /// A list of labelled facts.
+    Facts(Vec<(&'a str, Vec<Inline>)>),
+    /// A table; each cell is a run of inlines.
+    Table {
+        headers: Vec<&'a str>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    /// A Mermaid diagram.
+    Diagram(String),
+}
+
+impl Block<'_> {
+    /// Renders the block as Markdown, followed by a blank line.
+    fn to_markdown(&self) -> String {
+        match self {
+            Self::Heading(level, text) => {
+                format!("{} {text}\n", "#".repeat(usize::from(*level)))
+            }
+            Self::Paragraph(inlines) => format!("{}\n", markdown_inlines(inlines)),
+            Self::Facts(facts) => facts
+                .iter()
+                .map(|(label, value)| format!("- **{label}:** {}\n", markdown_inlines(value)))
+                .collect(),
+            Self::Table { headers, rows } => {
+                let mut table = format!("| {} |\n", headers.join(" | "));
+                table.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+                for row in rows {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|cell| markdown_inlines(cell).replace('|', "\\|"))
+                        .collect();
+                    table.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                table
+            }
+            Self::Diagram(diagram) => format!("```mermaid\n{diagram}```\n"),
+        }
+    }
+
+    /// Renders the block as HTML.
+    fn to_html(&self) -> String {
+        match self {
+            Self::Heading(level, text) => format!("<h{level}>{}</h{level}>\n", escape_html(text)),
+            Self::Paragraph(inlines) => format!("<p>{}</p>\n", html_inlines(inlines)),
+            Self::Facts(facts) => {
+                let items: String = facts
+                    .iter()
+                    .map(|(label, value)| {
+                        format!(
+                            "<li><strong>{}:</strong> {}</li>\n",
+                            escape_html(label),
+                            html_inlines(value)
+                        )
+                    })
+                    .collect();
+                format!("<ul>\n{items}</ul>\n")
+            }
+            Self::Table { headers, rows } => {
+                let headers: String = headers
+                    .iter()
+                    .map(|header| format!("<th>{}</th>", escape_html(header)))
+                    .collect();
+                let rows: String = rows
+                    .iter()
+                    .map(|row| {
+                        let cells: String = row
+                            .iter()
+                            .map(|cell| format!("<td>{}</td>", html_inlines(cell)))
+                            .collect();
+                        format!("<tr>{cells}</tr>\n")
+                    })
+                    .collect();
+                format!(
+                    "<table>\n<thead>\n<tr>{headers}</tr>\n</thead>\n<tbody>\n{rows}</tbody>\n</table>\n"
+                )
+            }
+            Self::Diagram(diagram) => {
+                format!("<pre class=\"mermaid\">\n{}</pre>\n", escape_html(diagram))
+            }
+        }
+    }
+}
+
+/// A run of text within a block.
+enum Inline {
+    /// Plain text.
+    Plain(String),
+    /// Code: an id, a key or a format.
+    Code(String),
+}
+
+impl Inline {
+    fn plain(text: impl Into<String>) -> Self {
+        Self::Plain(text.into())
+    }
+
+    fn code(text: impl Into<String>) -> Self {
+        Self::Code(text.into())
+    }
+}
+
+/// Renders inlines as Markdown.
+fn markdown_inlines(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Plain(text) => text.clone(),
+            Inline::Code(text) => format!("`{text}`"),
+        })
+        .collect()
+}
+
+/// Renders inlines as HTML.
+fn html_inlines(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| match inline {
+            Inline::Plain(text) => escape_html(text),
+            Inline::Code(text) => format!("<code>{}</code>", escape_html(text)),
+        })
+        .collect()
+}
+
+/// Escapes the HTML special characters of a text.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Appends the former names of a type, field or relation.
+fn with_aliases(mut inlines: Vec<Inline>, aliases: &[String]) -> Vec<Inline> {
+    for (i, alias) in aliases.iter().enumerate() {
+        inlines.push(Inline::plain(if i == 0 { " (formerly " } else { ", " }));
+        inlines.push(Inline::code(alias));
+    }
+    if !aliases.is_empty() {
+        inlines.push(Inline::plain(")"));
+    }
+    inlines
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Returns the diagram entity of an item type: its display name, stripped of
+/// the characters Mermaid does not accept in entity names.
+fn entity_name(def: &ItemTypeDef) -> String {
+    let name: String = def
+        .display_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    if name.is_empty() {
+        def.id.clone()
+    } else {
+        name
+    }
+}
+
+/// Returns the diagram attribute type of a field type.
+fn mermaid_type(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Text { .. } | FieldType::ItemRef | FieldType::Url => "string".to_string(),
+        FieldType::Enum { .. } => "enum".to_string(),
+        FieldType::List(inner) => format!("{}[]", mermaid_type(inner)),
+        FieldType::Date => "date".to_string(),
+        FieldType::Number { .. } => "number".to_string(),
+        FieldType::Integer { .. } => "integer".to_string(),
+        FieldType::Boolean => "boolean".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mermaid_diagram() {
+        let diagram = Schema::builtin().to_mermaid();
+        assert!(diagram.starts_with("erDiagram\n    Solution {\n"));
+        assert!(diagram.contains("        string[] deciders\n"));
+        assert!(diagram.contains("        string[] justifies FK\n"));
+        assert!(diagram.contains("    Solution }o--o{ UseCase : \"is_refined_by\"\n"));
+        assert!(
+            diagram.contains("    SystemRequirement }o--o{ SystemRequirement : \"depends_on\"\n")
+        );
+    }
+
+    #[test]
+    fn test_markdown_guide() {
+        let guide = Schema::builtin().to_markdown_guide();
+        assert!(guide.starts_with("# Model Guide\n"));
+        assert!(guide.contains("### Architecture Decision Record\n"));
+        assert!(guide.contains("- **ID format:** `{prefix}-{seq:03}` with prefix `ADR`\n"));
+        assert!(guide.contains(
+            "| `status` | one of: proposed, accepted, deprecated, superseded | yes | Status |\n"
+        ));
+        assert!(guide.contains("| `justifies` | System Architecture, Software Detailed Design, Hardware Detailed Design | any |\n"));
+        // The matrix row of use cases: refines solutions, is refined by scenarios.
+        assert!(
+            guide.contains("| Use Case | `refines` |  | `is_refined_by` |  |  |  |  |  |  |  |\n")
+        );
+    }
+
+    #[test]
+    fn test_html_guide_escapes_text() {
+        let mut schema = Schema::builtin();
+        schema.item_types[0].display_name = "Solution <Top>".to_string();
+        let html = schema.to_html_guide();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h3>Solution &lt;Top&gt;</h3>"));
+        assert!(html.contains("<td><code>refines</code></td>"));
+        assert!(html.contains("<pre class=\"mermaid\">\nerDiagram\n"));
+    }
+}
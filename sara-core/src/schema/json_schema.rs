@@ -0,0 +1,299 @@
+//! JSON Schema generation for editor validation of item metadata.
+//!
+//! Derives a JSON Schema (draft 2020-12) from the model: one schema per item
+//! type describing its core fields, declared fields and the relations it may
+//! use, and a combined schema dispatching on `type`. Editors wired to it
+//! (e.g. through yaml-language-server) complete and check frontmatter without
+//! Sara being installed. Keys the schema does not describe stay allowed, as
+//! Sara ignores them.
+
+use serde_json::{Map, Value, json};
+
+use super::{FieldDef, FieldType, ItemTypeDef, Schema};
+use crate::model::{FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE};
+
+/// JSON Schema dialect of the generated documents.
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl Schema {
+    /// Returns the JSON Schema of the metadata of every item type, dispatched
+    /// on the `type` entry. Each type's schema lives under `$defs`, keyed by
+    /// the type id.
+    #[must_use]
+    pub fn to_json_schema(&self) -> Value {
+        let type_names: Vec<&str> = self.item_types.iter().flat_map(type_names).collect();
+        let dispatch: Vec<Value> = self
+            .item_types
+            .iter()
+            .map(|def| {
+                json!({
+                    "if": {
+                        "properties": { FIELD_TYPE: { "enum": type_names_of(def) } },
+                        "required": [FIELD_TYPE],
+                    },
+                    "then": { "$ref": format!("#/$defs/{}", def.id) },
+                })
+            })
+            .collect();
+        let defs: Map<String, Value> = self
+            .item_types
+            .iter()
+            .map(|def| (def.id.clone(), self.item_type_schema(def)))
+            .collect();
+
+        json!({
+            "$schema": DIALECT,
+            "title": "Sara item metadata",
+            "type": "object",
+            "properties": { FIELD_TYPE: { "enum": type_names } },
+            "required": [FIELD_TYPE],
+            "allOf": dispatch,
+            "$defs": defs,
+        })
+    }
+
+    /// Returns the standalone JSON Schema of the metadata of an item type,
+    /// or `None` when the schema declares no such type.
+    #[must_use]
+    pub fn item_type_json_schema(&self, type_id: &str) -> Option<Value> {
+        let mut schema = self.item_type_schema(self.item_type(type_id)?);
+        if let Value::Object(object) = &mut schema {
+            object.insert("$schema".to_string(), json!(DIALECT));
+        }
+        Some(schema)
+    }
+
+    /// Builds the schema of an item type, without the dialect.
+    fn item_type_schema(&self, def: &ItemTypeDef) -> Value {
+        let mut properties = Map::new();
+        properties.insert(
+            FIELD_ID.to_string(),
+            json!({
+                "type": "string",
+                "minLength": 1,
+                "description": format!("Unique identifier, formatted as `{}`", def.id_format),
+            }),
+        );
+        properties.insert(
+            FIELD_TYPE.to_string(),
+            json!({ "enum": type_names_of(def) }),
+        );
+        properties.insert(FIELD_NAME.to_string(), json!({ "type": "string" }));
+        properties.insert(FIELD_DESCRIPTION.to_string(), json!({ "type": "string" }));
+
+        let mut required = vec![FIELD_ID, FIELD_TYPE, FIELD_NAME];
+        // Fields read from a body section are not written in the metadata.
+        let from_section = |field: &FieldDef| def.sections.iter().any(|s| s.field == field.name);
+        for field in &def.fields {
+            properties.insert(field.name.clone(), field_schema(field));
+            for alias in &field.aliases {
+                properties.insert(alias.clone(), deprecated(field_schema(field)));
+            }
+            if field.required && !from_section(field) {
+                required.push(&field.name);
+            }
+        }
+
+        for relation in &self.relations {
+            let targets: Vec<&str> = self
+                .item_types
+                .iter()
+                .filter(|target| self.is_valid_relationship(&def.id, &target.id, &relation.id))
+                .map(|target| target.id.as_str())
+                .collect();
+            if targets.is_empty() {
+                continue;
+            }
+            let schema = self.relation_schema(&relation.id, &relation.display_name, &targets);
+            properties.insert(relation.id.clone(), schema.clone());
+            for alias in &relation.aliases {
+                properties.insert(alias.clone(), deprecated(schema.clone()));
+            }
+        }
+
+        json!({
+            "title": def.display_name,
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+
+    /// Builds the schema of a relation entry: a target id, or a list of
+    /// target ids and links carrying attributes.
+    fn relation_schema(&self, relation: &str, display_name: &str, targets: &[&str]) -> Value {
+        let attributes = self.link_attributes(relation);
+        let link = if attributes.is_empty() {
+            json!({ "type": "string" })
+        } else {
+            let mut properties = Map::new();
+            properties.insert(FIELD_ID.to_string(), json!({ "type": "string" }));
+            let mut required = vec![FIELD_ID];
+            for attribute in attributes {
+                properties.insert(attribute.name.clone(), field_schema(attribute));
+                if attribute.required {
+                    required.push(&attribute.name);
+                }
+            }
+            json!({
+                "anyOf": [
+                    { "type": "string" },
+                    {
+                        "type": "object",
+                        "properties": properties,
+                        "required": required,
+                        "additionalProperties": false,
+                    },
+                ],
+            })
+        };
+
+        json!({
+            "title": display_name,
+            "description": format!("Targets of type {}", targets.join(", ")),
+            "anyOf": [link, { "type": "array", "items": link }],
+        })
+    }
+}
+
+/// Returns the id and aliases of an item type.
+fn type_names(def: &ItemTypeDef) -> impl Iterator<Item = &str> {
+    std::iter::once(def.id.as_str()).chain(def.aliases.iter().map(String::as_str))
+}
+
+/// Returns the id and aliases of an item type, as a list.
+fn type_names_of(def: &ItemTypeDef) -> Vec<&str> {
+    type_names(def).collect()
+}
+
+/// Builds the schema of a declared field.
+fn field_schema(field: &FieldDef) -> Value {
+    let mut schema = value_schema(&field.field_type);
+    if let Value::Object(object) = &mut schema {
+        object.insert("title".to_string(), json!(field.display_name));
+        if field.field_type != FieldType::TEXT {
+            object.insert(
+                "description".to_string(),
+                json!(field.field_type.describe()),
+            );
+        }
+    }
+    schema
+}
+
+/// Builds the schema of the values of a field type.
+fn value_schema(field_type: &FieldType) -> Value {
+    match field_type {
+        FieldType::Text { pattern: None } | FieldType::ItemRef => json!({ "type": "string" }),
+        FieldType::Text {
+            pattern: Some(pattern),
+        } => json!({ "type": "string", "pattern": format!("^(?:{pattern})$") }),
+        FieldType::Enum { values } => json!({ "enum": values }),
+        FieldType::List(inner) => json!({ "type": "array", "items": value_schema(inner) }),
+        FieldType::Date => json!({ "type": "string", "format": "date" }),
+        FieldType::Number { min, max, unit } => {
+            let mut number = json!({ "type": "number" });
+            bound(&mut number, *min, *max);
+            match unit {
+                // The unit may follow the number, as in `500 ms`.
+                Some(unit) => json!({
+                    "anyOf": [
+                        number,
+                        {
+                            "type": "string",
+                            "pattern": format!(
+                                r"^\s*[-+]?[0-9.]+(?:[eE][-+]?[0-9]+)?\s*(?:{})?\s*$",
+                                regex::escape(unit)
+                            ),
+                        },
+                    ],
+                }),
+                None => number,
+            }
+        }
+        FieldType::Integer { min, max } => {
+            let mut integer = json!({ "type": "integer" });
+            bound(&mut integer, *min, *max);
+            integer
+        }
+        FieldType::Boolean => json!({ "type": "boolean" }),
+        FieldType::Url => json!({ "type": "string", "format": "uri" }),
+    }
+}
+
+/// Adds the `minimum` and `maximum` keywords of a numeric schema.
+fn bound<T: Into<Value>>(schema: &mut Value, min: Option<T>, max: Option<T>) {
+    if let Value::Object(object) = schema {
+        if let Some(min) = min {
+            object.insert("minimum".to_string(), min.into());
+        }
+        if let Some(max) = max {
+            object.insert("maximum".to_string(), max.into());
+        }
+    }
+}
+
+/// Marks the schema of a former name as deprecated.
+fn deprecated(mut schema: Value) -> Value {
+    if let Value::Object(object) = &mut schema {
+        object.insert("deprecated".to_string(), json!(true));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_type_json_schema() {
+        let schema = Schema::builtin();
+        let adr = schema
+            .item_type_json_schema("architecture_decision_record")
+            .unwrap();
+        assert_eq!(adr["$schema"], DIALECT);
+        assert_eq!(
+            adr["properties"]["type"]["enum"][0],
+            "architecture_decision_record"
+        );
+        assert!(adr["properties"]["status"]["enum"].is_array());
+        assert!(adr["properties"]["justifies"].is_object());
+        assert!(adr["properties"]["supersedes"].is_object());
+        // Relations the type cannot use are not offered.
+        assert!(adr["properties"]["refines"].is_null());
+        let required = adr["required"].as_array().unwrap();
+        assert!(required.contains(&json!("id")));
+
+        let requirement = schema.item_type_json_schema("system_requirement").unwrap();
+        // The inverse of a relation targeting the type may be declared too.
+        assert!(requirement["properties"]["is_satisfied_by"].is_object());
+        assert!(schema.item_type_json_schema("unknown").is_none());
+    }
+
+    #[test]
+    fn test_combined_json_schema_dispatches_on_type() {
+        let schema = Schema::builtin();
+        let combined = schema.to_json_schema();
+        let dispatch = combined["allOf"].as_array().unwrap();
+        assert_eq!(dispatch.len(), schema.item_types.len());
+        assert_eq!(dispatch[0]["then"]["$ref"], "#/$defs/solution");
+        assert_eq!(combined["$defs"]["solution"]["title"], "Solution");
+    }
+
+    #[test]
+    fn test_field_type_schemas() {
+        let latency = value_schema(&FieldType::Number {
+            min: Some(0.0),
+            max: None,
+            unit: Some("ms".to_string()),
+        });
+        assert_eq!(latency["anyOf"][0]["minimum"], 0.0);
+        let pattern = latency["anyOf"][1]["pattern"].as_str().unwrap();
+        assert!(regex::Regex::new(pattern).unwrap().is_match("500 ms"));
+
+        assert_eq!(
+            value_schema(&FieldType::List(Box::new(FieldType::Url))),
+            json!({ "type": "array", "items": { "type": "string", "format": "uri" } })
+        );
+    }
+}
@@ -0,0 +1,569 @@
+//! Complete checking of a schema file.
+//!
+//! Loading a schema stops at its first inconsistency. Checking collects
+//! every inconsistency as an error, types whose identifiers can be mistaken
+//! for one another included, plus warnings about a model that loads but is
+//! unlikely to be what its author meant: types taking part in no relation,
+//! relations no type declares and types no chain of parents connects to a
+//! root. Each issue points at the line of the schema file declaring the
+//! entry it concerns.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_yaml::Value;
+
+use super::Schema;
+use super::extends::SchemaOverlay;
+use super::id_format::IdFormat;
+use super::yaml::read_file;
+use crate::error::SaraError;
+use crate::validation::Severity;
+
+/// A problem found in a schema file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaIssue {
+    /// Severity of the issue.
+    pub severity: Severity,
+    /// Line of the schema file the issue points at (1-based), when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Every problem found in a schema file, in file order within each check.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SchemaReport {
+    /// Issues found.
+    pub issues: Vec<SchemaIssue>,
+}
+
+impl SchemaReport {
+    /// Returns the number of errors.
+    #[must_use]
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    /// Returns the number of warnings.
+    #[must_use]
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    /// Tells whether the schema passes the check: it has no error and, in
+    /// strict mode, no warning either.
+    #[must_use]
+    pub fn passes(&self, strict: bool) -> bool {
+        self.error_count() == 0 && (!strict || self.warning_count() == 0)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+
+    fn push(&mut self, severity: Severity, line: Option<usize>, message: String) {
+        self.issues.push(SchemaIssue {
+            severity,
+            line,
+            message,
+        });
+    }
+}
+
+/// The top-level list of a schema file an entry is declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    ItemTypes,
+    Relations,
+}
+
+impl Section {
+    fn key(self) -> &'static str {
+        match self {
+            Self::ItemTypes => "item_types",
+            Self::Relations => "relations",
+        }
+    }
+}
+
+/// The schema entry a problem concerns, used to locate it in the file.
+#[derive(Debug, Clone)]
+pub(super) struct Subject {
+    section: Section,
+    /// Id of the type or relation.
+    id: String,
+    /// Values within the entry the problem is about (a field name, a
+    /// relation then one of its targets...), each looked up after the
+    /// previous one, pointing at their line rather than at the entry.
+    detail: Vec<String>,
+}
+
+impl Subject {
+    pub(super) fn item_type(id: &str, detail: &[&str]) -> Self {
+        Self {
+            section: Section::ItemTypes,
+            id: id.to_string(),
+            detail: detail.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    pub(super) fn relation(id: &str, detail: &[&str]) -> Self {
+        Self {
+            section: Section::Relations,
+            id: id.to_string(),
+            detail: detail.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+}
+
+/// A problem of a loaded schema, with the entry it concerns.
+#[derive(Debug, Clone)]
+pub(super) struct Problem {
+    pub(super) subject: Subject,
+    pub(super) reason: String,
+}
+
+impl Problem {
+    pub(super) fn new(subject: Subject, reason: String) -> Self {
+        Self { subject, reason }
+    }
+}
+
+impl Schema {
+    /// Checks a schema file, collecting every problem instead of stopping at
+    /// the first one.
+    ///
+    /// Errors are the inconsistencies that prevent loading the schema (see
+    /// [`Schema::validate`]); warnings point at a model that loads but
+    /// cannot work as intended (see the module documentation).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SaraError::ConfigRead`] if the file cannot be read.
+    pub fn check_path(path: &Path) -> Result<SchemaReport, SaraError> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| SaraError::ConfigRead {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        Ok(Self::check(&yaml, path, vec![key]))
+    }
+
+    /// Checks a schema given as a YAML string; `source` locates the base
+    /// schema of a file with an `extends` key (see [`Schema::check_path`]).
+    #[must_use]
+    pub fn check_yaml(yaml: &str, source: &Path) -> SchemaReport {
+        Self::check(yaml, source, Vec::new())
+    }
+
+    fn check(yaml: &str, source: &Path, mut chain: Vec<PathBuf>) -> SchemaReport {
+        let mut report = SchemaReport::default();
+        let location = |e: &serde_yaml::Error| e.location().map(|location| location.line());
+
+        let value: Value = match serde_yaml::from_str(yaml) {
+            Ok(value) => value,
+            Err(e) => {
+                report.push(Severity::Error, location(&e), e.to_string());
+                return report;
+            }
+        };
+        // Deserialized from the text rather than the value, so that errors
+        // carry their location.
+        let schema = if value.get("extends").is_some() {
+            serde_yaml::from_str::<SchemaOverlay>(yaml)
+                .map(|overlay| overlay.resolve(source, &mut chain, &read_file))
+        } else {
+            serde_yaml::from_str::<Self>(yaml).map(Ok)
+        };
+        let schema = match schema {
+            Ok(Ok(schema)) => schema,
+            Ok(Err(e)) => {
+                report.push(Severity::Error, None, e.to_string());
+                return report;
+            }
+            Err(e) => {
+                report.push(Severity::Error, location(&e), e.to_string());
+                return report;
+            }
+        };
+
+        for (severity, problems) in [
+            (Severity::Error, schema.problems()),
+            (Severity::Warning, schema.lints()),
+        ] {
+            for problem in problems {
+                report.push(severity, locate(yaml, &problem.subject), problem.reason);
+            }
+        }
+        report
+    }
+
+    /// Collects the warnings about a consistent schema.
+    fn lints(&self) -> Vec<Problem> {
+        let mut lints = Vec::new();
+        let linked = |from: &str, to: &str| {
+            self.relations
+                .iter()
+                .any(|r| self.is_valid_relationship(from, to, &r.id))
+        };
+
+        if self.item_types.len() > 1 {
+            for def in &self.item_types {
+                let reachable = self
+                    .item_types
+                    .iter()
+                    .any(|other| linked(&def.id, &other.id) || linked(&other.id, &def.id));
+                if !reachable {
+                    lints.push(Problem::new(
+                        Subject::item_type(&def.id, &[]),
+                        format!(
+                            "type '{}' takes part in no relation, so its items cannot be linked",
+                            def.id
+                        ),
+                    ));
+                }
+            }
+        }
+
+        for rel in &self.relations {
+            let declared = self
+                .item_types
+                .iter()
+                .flat_map(|def| &def.allowed_targets)
+                .any(|t| t.relation == rel.id || t.relation == rel.inverse);
+            // Reported once per pair, on its primary relation.
+            let reported = rel.primary || !self.relation(&rel.inverse).is_some_and(|i| i.primary);
+            if !declared && reported {
+                lints.push(Problem::new(
+                    Subject::relation(&rel.id, &[]),
+                    format!("relation '{}' is not declared by any type", rel.id),
+                ));
+            }
+        }
+
+        // A type reaches a root through parents it can link to.
+        for def in &self.item_types {
+            for parent in &def.parent_types {
+                if self.item_type(parent).is_some() && !linked(&def.id, parent) {
+                    lints.push(Problem::new(
+                        Subject::item_type(&def.id, &[parent]),
+                        format!(
+                            "type '{}' has parent type '{parent}' but no relation links them",
+                            def.id
+                        ),
+                    ));
+                }
+            }
+        }
+        let mut rooted: HashSet<&str> = self
+            .item_types
+            .iter()
+            .filter(|def| def.parent_types.is_empty())
+            .map(|def| def.id.as_str())
+            .collect();
+        loop {
+            let before = rooted.len();
+            for def in &self.item_types {
+                if def
+                    .parent_types
+                    .iter()
+                    .any(|p| rooted.contains(p.as_str()) && linked(&def.id, p))
+                {
+                    rooted.insert(&def.id);
+                }
+            }
+            if rooted.len() == before {
+                break;
+            }
+        }
+        for def in &self.item_types {
+            if !rooted.contains(def.id.as_str()) {
+                lints.push(Problem::new(
+                    Subject::item_type(&def.id, &[]),
+                    format!(
+                        "type '{}' is not connected to a root type through its parent types",
+                        def.id
+                    ),
+                ));
+            }
+        }
+
+        lints
+    }
+
+    /// Collects the pairs of types whose identifiers can be mistaken for
+    /// one another: types sharing a prefix, or whose `id_format` matches
+    /// identifiers of the other type.
+    ///
+    /// Either makes resolving the type of an identifier ambiguous, so these
+    /// are errors (see [`Schema::validate`]). A format is tried against
+    /// identifiers of the other type rendered with sequences of several
+    /// lengths, so a literal overlapping the digits of a sequence is caught.
+    pub(super) fn id_collisions(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        for (index, def) in self.item_types.iter().enumerate() {
+            for other in &self.item_types[..index] {
+                if other.prefix == def.prefix {
+                    problems.push(Problem::new(
+                        Subject::item_type(&def.id, &[&def.prefix]),
+                        format!(
+                            "type '{}' uses prefix '{}' already used by type '{}'",
+                            def.id, def.prefix, other.id
+                        ),
+                    ));
+                    continue;
+                }
+                let (Ok(format), Ok(other_format)) = (
+                    IdFormat::parse(&def.id_format),
+                    IdFormat::parse(&other.id_format),
+                ) else {
+                    continue;
+                };
+                let shared = SAMPLE_SEQUENCES
+                    .iter()
+                    .flat_map(|&seq| {
+                        [
+                            format.render(&def.prefix, &def.id, seq),
+                            other_format.render(&other.prefix, &other.id, seq),
+                        ]
+                    })
+                    .find(|id| {
+                        format.matches(id, &def.prefix, &def.id)
+                            && other_format.matches(id, &other.prefix, &other.id)
+                    });
+                if let Some(id) = shared {
+                    problems.push(Problem::new(
+                        Subject::item_type(&def.id, &[&def.id_format]),
+                        format!(
+                            "type '{}' id_format is ambiguous with type '{}': '{id}' matches both",
+                            def.id, other.id
+                        ),
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// Sequences rendered when looking for identifiers two formats both match.
+const SAMPLE_SEQUENCES: [u32; 5] = [1, 10, 100, 1_000, 10_000];
+
+/// Finds the line declaring the entry of a problem in a schema file: the
+/// line holding its details within the entry, or the `id` line of the entry.
+/// Returns `None` for entries the file does not declare, such as those
+/// inherited from a base schema.
+fn locate(yaml: &str, subject: &Subject) -> Option<usize> {
+    let lines: Vec<&str> = yaml.lines().collect();
+    let header = format!("{}:", subject.section.key());
+    let start = lines.iter().position(|l| l.trim_end() == header)? + 1;
+    let end = lines[start..]
+        .iter()
+        .position(|l| l.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_'))
+        .map_or(lines.len(), |offset| start + offset);
+
+    let id_line = (start..end).find(|&i| key_value(lines[i]) == Some(("id", &subject.id)))?;
+    let key_column = lines[id_line].find("id")?;
+    let dash_column = key_column.saturating_sub(2);
+    let is_entry = |line: &str| {
+        let text = line.trim_start();
+        line.len() - text.len() == dash_column && text.starts_with("- ")
+    };
+    let first = (start..=id_line)
+        .rev()
+        .find(|&i| is_entry(lines[i]))
+        .unwrap_or(id_line);
+    // The entry spans the lines indented past its dash.
+    let inside = |line: &str| {
+        let text = line.trim_start();
+        text.is_empty() || text.starts_with('#') || line.len() - text.len() > dash_column
+    };
+    let last = (id_line + 1..end)
+        .find(|&i| !inside(lines[i]))
+        .unwrap_or(end);
+
+    let mut line = id_line;
+    let mut from = first;
+    for detail in &subject.detail {
+        match (from..last).find(|&i| mentions(lines[i], detail)) {
+            Some(found) => {
+                line = found;
+                from = found + 1;
+            }
+            None => break,
+        }
+    }
+    Some(line + 1)
+}
+
+/// Splits a `key: value` line, ignoring a list dash, quotes and comments.
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    let line = line.strip_prefix("- ").unwrap_or(line);
+    let (key, value) = line.split_once(':')?;
+    let value = value.split(" #").next().unwrap_or_default().trim();
+    let value = value.trim_matches(|c| c == '"' || c == '\'');
+    Some((key.trim(), value))
+}
+
+/// Tells whether a line mentions a value: as a whole word for names, as a
+/// substring for formats and other values with punctuation.
+fn mentions(line: &str, value: &str) -> bool {
+    let is_name = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !is_name {
+        return line.contains(value);
+    }
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+        .any(|word| word == value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"item_types:
+- id: requirement
+  display_name: Requirement
+  prefix: REQ
+  id_format: "{prefix}-{seq:03}"
+  parent_types: [goal]
+  fields:
+  - name: level
+    display_name: Level
+    field_type: !enum { values: [] }
+  allowed_targets:
+  - relation: refines
+    targets: [goal, unknown]
+- id: goal
+  display_name: Goal
+  prefix: REQ
+  id_format: "{prefix}-{seq:03}"
+- id: note
+  display_name: Note
+  prefix: NOTE
+  id_format: "{prefix}-{seq:04}"
+relations:
+- id: refines
+  display_name: Refines
+  inverse: is_refined_by
+  direction: upstream
+  primary: true
+- id: is_refined_by
+  display_name: Is refined by
+  inverse: refines
+  direction: downstream
+  primary: false
+- id: relates_to
+  display_name: Relates to
+  inverse: missing
+  direction: peer
+  primary: true
+"#;
+
+    fn issue(report: &SchemaReport, needle: &str) -> SchemaIssue {
+        report
+            .issues
+            .iter()
+            .find(|i| i.message.contains(needle))
+            .unwrap_or_else(|| panic!("no issue about {needle}: {:?}", report.issues))
+            .clone()
+    }
+
+    #[test]
+    fn test_check_reports_every_error_with_its_line() {
+        let report = Schema::check_yaml(SCHEMA, Path::new("model.yaml"));
+        assert_eq!(report.error_count(), 4, "{:?}", report.issues);
+
+        let inverse = issue(&report, "unknown inverse 'missing'");
+        assert_eq!(inverse.severity, Severity::Error);
+        assert_eq!(inverse.line, Some(35));
+        assert_eq!(issue(&report, "enum with no values").line, Some(8));
+        assert_eq!(
+            issue(&report, "unknown target type 'unknown'").line,
+            Some(13)
+        );
+        let prefix = issue(&report, "prefix 'REQ' already used by type 'requirement'");
+        assert_eq!(prefix.severity, Severity::Error);
+        assert_eq!(prefix.line, Some(16));
+        assert!(!report.passes(false));
+    }
+
+    #[test]
+    fn test_check_warns_about_the_model() {
+        let report = Schema::check_yaml(SCHEMA, Path::new("model.yaml"));
+        let warning = issue(&report, "type 'note' takes part in no relation");
+        assert_eq!(warning.severity, Severity::Warning);
+        assert_eq!(warning.line, Some(18));
+        assert_eq!(
+            issue(&report, "relation 'relates_to' is not declared").line,
+            Some(33)
+        );
+    }
+
+    #[test]
+    fn test_check_reports_unrooted_types_and_rejects_ambiguous_formats() {
+        let yaml = r#"item_types:
+- id: a
+  display_name: A
+  prefix: A
+  id_format: "{prefix}-{seq:03}"
+  parent_types: [b]
+  allowed_targets:
+  - relation: peer
+    targets: [b]
+- id: b
+  display_name: B
+  prefix: B
+  id_format: "A-{seq:03}"
+  parent_types: [a]
+relations:
+- id: peer
+  display_name: Peer
+  inverse: peer_of
+  direction: peer
+  primary: true
+- id: peer_of
+  display_name: Peer of
+  inverse: peer
+  direction: peer
+  primary: false
+"#;
+        let report = Schema::check_yaml(yaml, Path::new("model.yaml"));
+        assert_eq!(report.error_count(), 1, "{:?}", report.issues);
+        issue(&report, "type 'a' is not connected to a root type");
+        assert_eq!(
+            issue(&report, "type 'b' has parent type 'a' but no relation").line,
+            Some(14)
+        );
+        let ambiguous = issue(&report, "'A-001' matches both");
+        assert_eq!(ambiguous.severity, Severity::Error);
+        assert_eq!(ambiguous.line, Some(13));
+        assert!(!report.passes(false));
+    }
+
+    #[test]
+    fn test_check_reports_yaml_errors_with_their_line() {
+        let report = Schema::check_yaml(
+            "item_types:\n- id: a\n  display_name: A\nrelations: []\n",
+            Path::new("model.yaml"),
+        );
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.issues[0].message.contains("missing field `prefix`"));
+        assert_eq!(report.issues[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_builtin_schema_checks_clean() {
+        let yaml = Schema::builtin().to_yaml().unwrap();
+        let report = Schema::check_yaml(&yaml, Path::new("builtin.yaml"));
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+    }
+}
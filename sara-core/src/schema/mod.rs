@@ -4,14 +4,18 @@
 //! typed fields, the relation catalog and the allowed links between types.
 //! A default schema is provided (see [`Schema::builtin`]) that covers most
 //! needs out of the box; projects with domain-specific needs supply their own
-//! schema in YAML.
+//! schema in YAML, either complete or extending another one.
 //!
 //! Lives in the core with no dependency on `parser` or `generator`, preserving
 //! the hexagonal boundary.
 
 mod active;
 pub mod builtin;
+mod extends;
+mod guide;
 mod id_format;
+mod json_schema;
+mod lint;
 mod yaml;
 
 #[cfg(test)]
@@ -19,9 +23,15 @@ mod parity_tests;
 
 use serde::{Deserialize, Serialize};
 
-pub use active::{active, install};
-pub(crate) use active::{item_type_def, relation_def};
+use crate::model::{FieldValue, ItemId};
+
+pub use active::{active, install, shared};
+pub(crate) use active::{item_type_def, relation_def, symbol};
 pub(crate) use id_format::IdFormat;
+pub use lint::{SchemaIssue, SchemaReport};
+pub use yaml::ReadFile;
+#[cfg(feature = "git")]
+pub(crate) use yaml::normalize_lexically;
 
 /// The direction of a relation relative to the model hierarchy.
 ///
@@ -39,11 +49,21 @@ pub enum RelationDirection {
 }
 
 /// The declared type of a type-specific field.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+///
+/// Types without constraints are written as a plain name (`text`, `number`,
+/// `integer`, `boolean`, `url`, `item_ref`, `date`); constrained ones as a
+/// tagged mapping (`!enum { values: [...] }`, `!number { min: 0, unit: ms }`,
+/// `!text { pattern: "^v[0-9]+$" }`) and lists as `!list <inner type>`. A
+/// YAML node carries a single tag, so a list of a constrained type spells
+/// its inner type as a mapping: `!list { enum: { values: [...] } }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "serde_yaml::Value", into = "serde_yaml::Value")]
 pub enum FieldType {
-    /// Free-form text.
-    Text,
+    /// Free-form text, optionally constrained by a regular expression.
+    Text {
+        /// Regular expression the whole value must match.
+        pattern: Option<String>,
+    },
     /// One value among a closed set (e.g. ADR status).
     Enum {
         /// Allowed values, in declaration order. Must be non-empty.
@@ -55,10 +75,191 @@ pub enum FieldType {
     List(Box<FieldType>),
     /// An ISO-8601 date.
     Date,
+    /// A decimal number, optionally bounded and carrying a unit.
+    Number {
+        /// Smallest allowed value.
+        min: Option<f64>,
+        /// Largest allowed value.
+        max: Option<f64>,
+        /// Unit the value is expressed in (e.g. `ms`), for display.
+        unit: Option<String>,
+    },
+    /// A whole number, optionally bounded.
+    Integer {
+        /// Smallest allowed value.
+        min: Option<i64>,
+        /// Largest allowed value.
+        max: Option<i64>,
+    },
+    /// `true` or `false`.
+    Boolean,
+    /// An absolute URL (e.g. `https://example.com/spec`).
+    Url,
+}
+
+impl FieldType {
+    /// Free-form text without constraint.
+    pub const TEXT: Self = Self::Text { pattern: None };
+
+    /// Parses the textual form of a scalar value of this type, checking its
+    /// constraints. A list parses one of its entries.
+    ///
+    /// Surrounding whitespace is ignored for numbers, booleans and URLs, and
+    /// a number may carry the declared unit as a suffix (`500 ms`).
+    ///
+    /// # Errors
+    ///
+    /// Returns a human-readable reason when the text is not a valid value,
+    /// e.g. a number out of range or text not matching the pattern.
+    pub fn parse_value(&self, text: &str) -> Result<FieldValue, String> {
+        match self {
+            Self::Text { pattern: None } => Ok(FieldValue::text(text)),
+            Self::Text {
+                pattern: Some(pattern),
+            } => {
+                let regex = full_match(pattern).map_err(|e| e.to_string())?;
+                if regex.is_match(text) {
+                    Ok(FieldValue::text(text))
+                } else {
+                    Err(format!(
+                        "invalid value `{text}`, expected a match for `{pattern}`"
+                    ))
+                }
+            }
+            Self::Enum { values } => {
+                if values.iter().any(|v| v == text) {
+                    Ok(FieldValue::Enum(text.to_string()))
+                } else {
+                    Err(format!(
+                        "invalid value `{text}`, expected one of: {}",
+                        values.join(", ")
+                    ))
+                }
+            }
+            Self::ItemRef => Ok(FieldValue::ItemRef(ItemId::new_unchecked(text))),
+            Self::List(inner) => inner.parse_value(text),
+            Self::Date => Ok(FieldValue::Date(text.to_string())),
+            Self::Number { min, max, unit } => {
+                let text = text.trim();
+                let number = unit
+                    .as_deref()
+                    .and_then(|unit| text.strip_suffix(unit))
+                    .unwrap_or(text)
+                    .trim();
+                let value: f64 = number
+                    .parse()
+                    .ok()
+                    .filter(|v: &f64| v.is_finite())
+                    .ok_or_else(|| format!("invalid value `{text}`, expected a number"))?;
+                check_range(text, value, *min, *max)?;
+                Ok(FieldValue::Number(value))
+            }
+            Self::Integer { min, max } => {
+                let text = text.trim();
+                let value: i64 = text
+                    .parse()
+                    .map_err(|_| format!("invalid value `{text}`, expected an integer"))?;
+                check_range(text, value, *min, *max)?;
+                Ok(FieldValue::Integer(value))
+            }
+            Self::Boolean => match text.trim() {
+                "true" => Ok(FieldValue::Boolean(true)),
+                "false" => Ok(FieldValue::Boolean(false)),
+                _ => Err(format!("invalid value `{text}`, expected true or false")),
+            },
+            Self::Url => {
+                let text = text.trim();
+                if is_url(text) {
+                    Ok(FieldValue::Url(text.to_string()))
+                } else {
+                    Err(format!("invalid value `{text}`, expected an absolute URL"))
+                }
+            }
+        }
+    }
+
+    /// Short description of the expected values, for prompts and help
+    /// texts (e.g. `number in ms, 0 to 1000`).
+    #[must_use]
+    pub fn describe(&self) -> String {
+        fn bounds(min: Option<String>, max: Option<String>) -> Option<String> {
+            match (min, max) {
+                (Some(min), Some(max)) => Some(format!("{min} to {max}")),
+                (Some(min), None) => Some(format!("at least {min}")),
+                (None, Some(max)) => Some(format!("at most {max}")),
+                (None, None) => None,
+            }
+        }
+
+        match self {
+            Self::Text { pattern: None } => "text".to_string(),
+            Self::Text {
+                pattern: Some(pattern),
+            } => format!("text matching `{pattern}`"),
+            Self::Enum { values } => format!("one of: {}", values.join(", ")),
+            Self::ItemRef => "item id".to_string(),
+            Self::List(inner) => format!("list of {}", inner.describe()),
+            Self::Date => "date (YYYY-MM-DD)".to_string(),
+            Self::Number { min, max, unit } => {
+                let mut description = "number".to_string();
+                if let Some(unit) = unit {
+                    description.push_str(&format!(" in {unit}"));
+                }
+                if let Some(bounds) = bounds(min.map(|v| v.to_string()), max.map(|v| v.to_string()))
+                {
+                    description.push_str(&format!(", {bounds}"));
+                }
+                description
+            }
+            Self::Integer { min, max } => {
+                match bounds(min.map(|v| v.to_string()), max.map(|v| v.to_string())) {
+                    Some(bounds) => format!("integer, {bounds}"),
+                    None => "integer".to_string(),
+                }
+            }
+            Self::Boolean => "true or false".to_string(),
+            Self::Url => "URL".to_string(),
+        }
+    }
+}
+
+/// Compiles a field pattern so that it must match the whole value.
+pub(crate) fn full_match(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    regex::Regex::new(&format!("^(?:{pattern})$"))
+}
+
+/// Checks a parsed number against optional bounds.
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    text: &str,
+    value: T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), String> {
+    if let Some(min) = min.filter(|min| value < *min) {
+        return Err(format!("invalid value `{text}`, expected at least {min}"));
+    }
+    if let Some(max) = max.filter(|max| value > *max) {
+        return Err(format!("invalid value `{text}`, expected at most {max}"));
+    }
+    Ok(())
+}
+
+/// Tells whether a text is an absolute URL: a scheme followed by a
+/// non-empty, whitespace-free remainder (`https://host/path`, `mailto:x`).
+fn is_url(text: &str) -> bool {
+    let Some((scheme, rest)) = text.split_once(':') else {
+        return false;
+    };
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.trim_start_matches('/').is_empty()
+        && !rest.contains(char::is_whitespace)
 }
 
 /// Declaration of a single type-specific field.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FieldDef {
     /// Canonical snake_case name as it appears in YAML frontmatter.
     pub name: String,
@@ -73,6 +274,26 @@ pub struct FieldDef {
     /// (e.g. a specification statement skeleton).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub placeholder: Option<String>,
+    /// Former names still accepted in documents, resolved to `name`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+/// Declaration of a Markdown body section read as a text field.
+///
+/// The content under the section heading, up to the next heading of the same
+/// or a higher level, is stored in the item's attributes under `field`, so
+/// authors can write prose instead of long YAML strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionDef {
+    /// Heading text, matched case-insensitively at any heading level
+    /// (e.g. `Rationale` matches `## Rationale`).
+    pub heading: String,
+    /// Attribute name the section content is stored under.
+    pub field: String,
+    /// Whether the section must be present with non-empty content.
+    #[serde(default)]
+    pub required: bool,
 }
 
 /// A relation that types can declare a target as (e.g. `refines`).
@@ -80,7 +301,7 @@ pub struct FieldDef {
 /// Relations come in inverse pairs. The `primary` relation is the one a type
 /// declares in [`ItemTypeDef::allowed_targets`]; the validity of the inverse
 /// is *derived* from it (see [`Schema::is_valid_relationship`]).
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RelationDef {
     /// Canonical snake_case id (matches the legacy frontmatter field name).
     pub id: String,
@@ -95,6 +316,39 @@ pub struct RelationDef {
     /// Upstream relations and the primary peer relations (`depends_on`,
     /// `supersedes`) are primary; their inverses are derived.
     pub primary: bool,
+    /// Former ids still accepted in documents, resolved to `id`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Whether links of this relation must never form a cycle.
+    ///
+    /// Like the other properties, only read on the primary relation of the
+    /// pair. Defaults to `true`.
+    #[serde(default = "default_acyclic", skip_serializing_if = "is_acyclic")]
+    pub acyclic: bool,
+    /// Whether `a -> b -> c` implies `a -> c`, making a direct link already
+    /// implied by a chain of this relation redundant.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub transitive: bool,
+    /// Whether an item may be the target of this relation from a single item
+    /// only (e.g. an ADR is superseded by one newer ADR at most).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unique_target: bool,
+    /// Attributes a link of this relation may carry (coverage, rationale,
+    /// verification method...), written as `{id: ..., name: value}`
+    /// entries. Links declared through the inverse relation use the
+    /// attributes of the primary one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<FieldDef>,
+}
+
+/// Relations are acyclic unless stated otherwise.
+fn default_acyclic() -> bool {
+    true
+}
+
+/// Tells whether `acyclic` holds its default value.
+fn is_acyclic(acyclic: &bool) -> bool {
+    *acyclic
 }
 
 /// A relation a type may establish toward a set of target types.
@@ -103,11 +357,39 @@ pub struct AllowedTarget {
     /// Id of the relation (must reference a [`RelationDef::id`]).
     pub relation: String,
     /// Ids of the item types this relation may point to, in declared order.
+    #[serde(default)]
     pub targets: Vec<String>,
+    /// Fewest links of this relation an item of the type must have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    /// Most links of this relation an item of the type may have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+}
+
+impl AllowedTarget {
+    /// Describes the declared cardinality (`exactly 1`, `at least 2`...),
+    /// or `None` when unconstrained.
+    #[must_use]
+    pub fn cardinality(&self) -> Option<String> {
+        match (self.min.filter(|&min| min > 0), self.max) {
+            (Some(min), Some(max)) if min == max => Some(format!("exactly {min}")),
+            (Some(min), Some(max)) => Some(format!("{min} to {max}")),
+            (Some(min), None) => Some(format!("at least {min}")),
+            (None, Some(max)) => Some(format!("at most {max}")),
+            (None, None) => None,
+        }
+    }
+
+    /// Tells whether a number of links satisfies the declared cardinality.
+    #[must_use]
+    pub fn allows_count(&self, count: usize) -> bool {
+        self.min.is_none_or(|min| count >= min) && self.max.is_none_or(|max| count <= max)
+    }
 }
 
 /// Definition of one item type.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ItemTypeDef {
     /// Canonical snake_case id (matches the legacy `type:` value).
     pub id: String,
@@ -126,6 +408,24 @@ pub struct ItemTypeDef {
     /// Primary relations this type may establish, with their valid targets.
     #[serde(default)]
     pub allowed_targets: Vec<AllowedTarget>,
+    /// Body sections read as text fields, in declaration order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<SectionDef>,
+    /// Former ids still accepted as `type:` values, resolved to `id`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+impl ItemTypeDef {
+    /// Returns the field named `name`, or having it as an alias.
+    #[must_use]
+    pub fn field_by_name(&self, name: &str) -> Option<&FieldDef> {
+        self.fields.iter().find(|f| f.name == name).or_else(|| {
+            self.fields
+                .iter()
+                .find(|f| f.aliases.iter().any(|a| a == name))
+        })
+    }
 }
 
 /// A complete, runtime-loadable description of the domain model.
@@ -133,7 +433,7 @@ pub struct ItemTypeDef {
 /// Order is significant: `item_types` follows the hierarchy order used by
 /// `ItemType::all`, and `allowed_targets`/`fields` preserve declaration order
 /// so that derived structures match the legacy behavior exactly.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Schema {
     /// Item type definitions, in hierarchy order.
     pub item_types: Vec<ItemTypeDef>,
@@ -154,6 +454,41 @@ impl Schema {
         self.relations.iter().find(|r| r.id == id)
     }
 
+    /// Returns the attributes links of a relation may carry, as declared on
+    /// the primary relation of its pair.
+    #[must_use]
+    pub fn link_attributes(&self, relation: &str) -> &[FieldDef] {
+        let Some(def) = self.relation(relation) else {
+            return &[];
+        };
+        let primary = if def.primary {
+            Some(def)
+        } else {
+            self.relation(&def.inverse)
+        };
+        primary.map_or(&[], |def| def.attributes.as_slice())
+    }
+
+    /// Returns the item type definition with the given id or alias, if any.
+    #[must_use]
+    pub fn item_type_by_name(&self, name: &str) -> Option<&ItemTypeDef> {
+        self.item_type(name).or_else(|| {
+            self.item_types
+                .iter()
+                .find(|t| t.aliases.iter().any(|a| a == name))
+        })
+    }
+
+    /// Returns the relation definition with the given id or alias, if any.
+    #[must_use]
+    pub fn relation_by_name(&self, name: &str) -> Option<&RelationDef> {
+        self.relation(name).or_else(|| {
+            self.relations
+                .iter()
+                .find(|r| r.aliases.iter().any(|a| a == name))
+        })
+    }
+
     /// Checks whether a relation is valid from one item type to another.
     ///
     /// The full validity matrix is *derived* from what each type declares in
@@ -1,27 +1,82 @@
 //! YAML loading and structural validation for [`Schema`].
 
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
+use serde::Deserialize;
+use serde_yaml::Value;
+use serde_yaml::value::{Mapping, Tag, TaggedValue};
+
+use super::extends::SchemaOverlay;
 use super::id_format::IdFormat;
+use super::lint::{Problem, Subject};
 use super::{FieldType, Schema};
 use crate::error::SaraError;
+use crate::model::{FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE};
+
+/// Reads a schema file, given its path.
+pub type ReadFile<'a> = dyn Fn(&Path) -> Result<String, SaraError> + 'a;
+
+/// Reads a schema file from disk.
+pub(super) fn read_file(path: &Path) -> Result<String, SaraError> {
+    std::fs::read_to_string(path).map_err(|e| SaraError::ConfigRead {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })
+}
+
+/// Resolves the `.` and `..` components of a path without touching the
+/// file system.
+pub(crate) fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
 
 impl Schema {
     /// Parses a schema from a YAML string.
     ///
-    /// `source` is only used for error reporting (mirrors the convention of
-    /// `parser::yaml::parse_yaml_frontmatter`).
+    /// `source` is used for error reporting (mirrors the convention of
+    /// `parser::yaml::parse_yaml_frontmatter`) and to locate the base schema
+    /// of a file with an `extends` key, relative to its directory. Such a
+    /// file is resolved against its base (see the `extends` module).
     ///
     /// # Errors
     ///
     /// Returns [`SaraError::InvalidYaml`] if the YAML is syntactically
     /// invalid, or [`SaraError::InvalidConfig`] if the schema is internally
-    /// inconsistent (see [`Schema::validate`]).
+    /// inconsistent (see [`Schema::validate`]) or cannot be resolved against
+    /// its base.
     pub fn from_yaml_str(yaml: &str, source: &Path) -> Result<Self, SaraError> {
-        let schema: Schema = serde_yaml::from_str(yaml).map_err(|e| SaraError::InvalidYaml {
+        Self::parse(yaml, source, &mut Vec::new(), &read_file)
+    }
+
+    /// Parses a schema, resolving inheritance; `chain` holds the files
+    /// being resolved and `read` reads their bases.
+    fn parse(
+        yaml: &str,
+        source: &Path,
+        chain: &mut Vec<PathBuf>,
+        read: &ReadFile<'_>,
+    ) -> Result<Self, SaraError> {
+        let invalid_yaml = |e: serde_yaml::Error| SaraError::InvalidYaml {
             file: source.to_path_buf(),
             reason: e.to_string(),
-        })?;
+        };
+        let value: serde_yaml::Value = serde_yaml::from_str(yaml).map_err(invalid_yaml)?;
+        let schema = if value.get("extends").is_some() {
+            let overlay: SchemaOverlay = serde_yaml::from_value(value).map_err(invalid_yaml)?;
+            overlay.resolve(source, chain, read)?
+        } else {
+            serde_yaml::from_value(value).map_err(invalid_yaml)?
+        };
         schema.validate(source)?;
         Ok(schema)
     }
@@ -33,101 +88,486 @@ impl Schema {
     /// Returns [`SaraError::ConfigRead`] if the file cannot be read, plus any
     /// error from [`Schema::from_yaml_str`].
     pub fn from_path(path: &Path) -> Result<Self, SaraError> {
-        let yaml = std::fs::read_to_string(path).map_err(|e| SaraError::ConfigRead {
-            path: path.to_path_buf(),
-            reason: e.to_string(),
-        })?;
-        Self::from_yaml_str(&yaml, path)
+        Self::from_path_with(path, &read_file)
+    }
+
+    /// Loads and validates a schema file read through `read`, as are the
+    /// files it extends.
+    ///
+    /// `read` returns the contents of a file; reading every file of the
+    /// inheritance chain from one source (a Git commit, say) loads the
+    /// schema as that source has it.
+    ///
+    /// # Errors
+    ///
+    /// Propagates the errors of `read`, plus any error from
+    /// [`Schema::from_yaml_str`].
+    pub fn from_path_with(path: &Path, read: &ReadFile<'_>) -> Result<Self, SaraError> {
+        Self::load(path, &mut Vec::new(), read)
+    }
+
+    /// Loads a schema file as a link of an inheritance chain.
+    pub(super) fn load(
+        path: &Path,
+        chain: &mut Vec<PathBuf>,
+        read: &ReadFile<'_>,
+    ) -> Result<Self, SaraError> {
+        let key = path
+            .canonicalize()
+            .unwrap_or_else(|_| normalize_lexically(path));
+        if chain.contains(&key) {
+            return Err(SaraError::InvalidConfig {
+                path: path.to_path_buf(),
+                reason: "schema inheritance cycle".to_string(),
+            });
+        }
+        let yaml = read(path)?;
+        chain.push(key);
+        let schema = Self::parse(&yaml, path, chain, read);
+        chain.pop();
+        schema
     }
 
     /// Validates internal consistency of the schema.
     ///
     /// Checks: relation inverses are symmetric, every referenced relation,
     /// parent and target type id exists, `Enum` fields list at least one
-    /// value, and every `id_format` parses (see `IdFormat`).
+    /// value, `Text` patterns compile, field and cardinality bounds are
+    /// ordered, link attributes are declared once on primary relations,
+    /// every `id_format` parses (see `IdFormat`), every alias names a
+    /// single type, relation or field, and no identifier can belong to two
+    /// types, through a shared prefix or overlapping `id_format`s.
     ///
     /// # Errors
     ///
-    /// Returns [`SaraError::InvalidConfig`] describing the first inconsistency.
+    /// Returns [`SaraError::InvalidConfig`] describing the first
+    /// inconsistency; [`Schema::check_yaml`] reports all of them.
     pub fn validate(&self, source: &Path) -> Result<(), SaraError> {
-        let invalid = |reason: String| SaraError::InvalidConfig {
-            path: source.to_path_buf(),
-            reason,
-        };
+        match self.problems().into_iter().next() {
+            Some(problem) => Err(SaraError::InvalidConfig {
+                path: source.to_path_buf(),
+                reason: problem.reason,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Collects every internal inconsistency of the schema, in declaration
+    /// order (see [`Schema::validate`]).
+    pub(super) fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
 
         // An installed schema replaces the built-in model entirely, so every
-        // reference must resolve within the schema itself.
+        // reference must resolve within the schema itself, once resolved
+        // against the schema it extends.
         for rel in &self.relations {
-            let Some(inv) = self.relation(&rel.inverse) else {
-                return Err(invalid(format!(
-                    "relation '{}' has unknown inverse '{}'",
-                    rel.id, rel.inverse
-                )));
+            let mut problem = |detail: &[&str], reason: String| {
+                problems.push(Problem::new(Subject::relation(&rel.id, detail), reason));
             };
-            if inv.inverse != rel.id {
-                return Err(invalid(format!(
-                    "relation '{}' inverse '{}' is not symmetric",
-                    rel.id, rel.inverse
-                )));
+            match self.relation(&rel.inverse) {
+                None => problem(
+                    &[&rel.inverse],
+                    format!(
+                        "relation '{}' has unknown inverse '{}'",
+                        rel.id, rel.inverse
+                    ),
+                ),
+                Some(inv) if inv.inverse != rel.id => problem(
+                    &[&rel.inverse],
+                    format!(
+                        "relation '{}' inverse '{}' is not symmetric",
+                        rel.id, rel.inverse
+                    ),
+                ),
+                Some(_) => {}
+            }
+            if !rel.primary && !rel.attributes.is_empty() {
+                problem(
+                    &[],
+                    format!(
+                        "relation '{}' declares link attributes but is not primary",
+                        rel.id
+                    ),
+                );
+            }
+            for (index, attribute) in rel.attributes.iter().enumerate() {
+                let subject = format!("relation '{}' attribute '{}'", rel.id, attribute.name);
+                if attribute.name == FIELD_ID {
+                    problem(
+                        &[&attribute.name],
+                        format!("{subject} is reserved for the link target"),
+                    );
+                }
+                if rel.attributes[..index]
+                    .iter()
+                    .any(|a| a.name == attribute.name)
+                {
+                    problem(&[&attribute.name], format!("{subject} is declared twice"));
+                }
+                if let Err(reason) = Self::validate_field_type(&attribute.field_type, &subject) {
+                    problem(&[&attribute.name], reason);
+                }
+            }
+        }
+
+        // Each alias must resolve to a single entry.
+        let core = [FIELD_ID, FIELD_TYPE, FIELD_NAME, FIELD_DESCRIPTION];
+        for def in &self.item_types {
+            for alias in &def.aliases {
+                let taken = self
+                    .item_types
+                    .iter()
+                    .any(|t| t.id == *alias || (t.id != def.id && t.aliases.contains(alias)));
+                if taken {
+                    problems.push(Problem::new(
+                        Subject::item_type(&def.id, &[alias]),
+                        format!("type '{}' alias '{alias}' is already taken", def.id),
+                    ));
+                }
+            }
+        }
+        for rel in &self.relations {
+            for alias in &rel.aliases {
+                let taken = core.contains(&alias.as_str())
+                    || self
+                        .relations
+                        .iter()
+                        .any(|r| r.id == *alias || (r.id != rel.id && r.aliases.contains(alias)));
+                if taken {
+                    problems.push(Problem::new(
+                        Subject::relation(&rel.id, &[alias]),
+                        format!("relation '{}' alias '{alias}' is already taken", rel.id),
+                    ));
+                }
             }
         }
 
         for def in &self.item_types {
+            let mut problem = |detail: &[&str], reason: String| {
+                problems.push(Problem::new(Subject::item_type(&def.id, detail), reason));
+            };
             if let Err(reason) = IdFormat::parse(&def.id_format) {
-                return Err(invalid(format!(
-                    "type '{}' has an invalid id_format '{}': {reason}",
-                    def.id, def.id_format
-                )));
+                problem(
+                    &[&def.id_format],
+                    format!(
+                        "type '{}' has an invalid id_format '{}': {reason}",
+                        def.id, def.id_format
+                    ),
+                );
             }
             for parent in &def.parent_types {
                 if self.item_type(parent).is_none() {
-                    return Err(invalid(format!(
-                        "type '{}' references unknown parent type '{}'",
-                        def.id, parent
-                    )));
+                    problem(
+                        &[parent],
+                        format!(
+                            "type '{}' references unknown parent type '{}'",
+                            def.id, parent
+                        ),
+                    );
                 }
             }
             for target in &def.allowed_targets {
                 if self.relation(&target.relation).is_none() {
-                    return Err(invalid(format!(
-                        "type '{}' references unknown relation '{}'",
-                        def.id, target.relation
-                    )));
+                    problem(
+                        &[&target.relation],
+                        format!(
+                            "type '{}' references unknown relation '{}'",
+                            def.id, target.relation
+                        ),
+                    );
                 }
                 for t in &target.targets {
                     if self.item_type(t).is_none() {
-                        return Err(invalid(format!(
-                            "type '{}' relation '{}' references unknown target type '{}'",
-                            def.id, target.relation, t
-                        )));
+                        problem(
+                            &[&target.relation, t],
+                            format!(
+                                "type '{}' relation '{}' references unknown target type '{}'",
+                                def.id, target.relation, t
+                            ),
+                        );
                     }
                 }
+                if let (Some(min), Some(max)) = (target.min, target.max)
+                    && min > max
+                {
+                    problem(
+                        &[&target.relation],
+                        format!(
+                            "type '{}' relation '{}' has a minimum above its maximum",
+                            def.id, target.relation
+                        ),
+                    );
+                }
             }
             for field in &def.fields {
-                Self::validate_field_type(&field.field_type, &def.id, &field.name, &invalid)?;
+                let subject = format!("type '{}' field '{}'", def.id, field.name);
+                if let Err(reason) = Self::validate_field_type(&field.field_type, &subject) {
+                    problem(&[&field.name], reason);
+                }
+                for alias in &field.aliases {
+                    let taken = core.contains(&alias.as_str())
+                        || self.relation_by_name(alias).is_some()
+                        || def.fields.iter().any(|f| {
+                            f.name == *alias || (f.name != field.name && f.aliases.contains(alias))
+                        });
+                    if taken {
+                        problem(
+                            &[&field.name, alias],
+                            format!(
+                                "type '{}' field '{}' alias '{alias}' is already taken",
+                                def.id, field.name
+                            ),
+                        );
+                    }
+                }
+            }
+            for (index, section) in def.sections.iter().enumerate() {
+                if section.heading.trim_start_matches('#').trim().is_empty() {
+                    problem(
+                        &[&section.field],
+                        format!(
+                            "type '{}' section '{}' has an empty heading",
+                            def.id, section.field
+                        ),
+                    );
+                }
+                // Section content shares the attribute map with the fields.
+                let taken = core.contains(&section.field.as_str())
+                    || def.fields.iter().any(|f| f.name == section.field)
+                    || self.relation(&section.field).is_some()
+                    || def.sections[..index]
+                        .iter()
+                        .any(|s| s.field == section.field);
+                if taken {
+                    problem(
+                        &[&section.field],
+                        format!(
+                            "type '{}' section '{}' uses a field name already taken",
+                            def.id, section.field
+                        ),
+                    );
+                }
             }
         }
 
-        Ok(())
+        problems.extend(self.id_collisions());
+        problems
     }
 
-    /// Recursively validates a field type declaration.
-    fn validate_field_type(
-        field_type: &FieldType,
-        type_id: &str,
-        field_name: &str,
-        invalid: &impl Fn(String) -> SaraError,
-    ) -> Result<(), SaraError> {
+    /// Recursively validates a field type declaration; `subject` names the
+    /// field in the returned reason (`type 'x' field 'y'`).
+    fn validate_field_type(field_type: &FieldType, subject: &str) -> Result<(), String> {
         match field_type {
-            FieldType::Enum { values } if values.is_empty() => Err(invalid(format!(
-                "type '{type_id}' field '{field_name}' is an enum with no values"
-            ))),
-            FieldType::List(inner) => {
-                Self::validate_field_type(inner, type_id, field_name, invalid)
+            FieldType::Enum { values } if values.is_empty() => {
+                Err(format!("{subject} is an enum with no values"))
+            }
+            FieldType::List(inner) => Self::validate_field_type(inner, subject),
+            FieldType::Text {
+                pattern: Some(pattern),
+            } => super::full_match(pattern)
+                .map(drop)
+                .map_err(|e| format!("{subject} has an invalid pattern: {e}")),
+            FieldType::Number {
+                min: Some(min),
+                max: Some(max),
+                ..
+            } if min > max => Err(format!("{subject} has a minimum above its maximum")),
+            FieldType::Integer {
+                min: Some(min),
+                max: Some(max),
+            } if min > max => Err(format!("{subject} has a minimum above its maximum")),
+            FieldType::Text { .. }
+            | FieldType::Enum { .. }
+            | FieldType::ItemRef
+            | FieldType::Date
+            | FieldType::Number { .. }
+            | FieldType::Integer { .. }
+            | FieldType::Boolean
+            | FieldType::Url => Ok(()),
+        }
+    }
+}
+
+/// Constraints of a `!text` field type.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TextSpec {
+    #[serde(default)]
+    pattern: Option<String>,
+}
+
+/// Values of an `!enum` field type.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EnumSpec {
+    values: Vec<String>,
+}
+
+/// Constraints of a `!number` field type.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NumberSpec {
+    #[serde(default)]
+    min: Option<f64>,
+    #[serde(default)]
+    max: Option<f64>,
+    #[serde(default)]
+    unit: Option<String>,
+}
+
+/// Constraints of an `!integer` field type.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IntegerSpec {
+    #[serde(default)]
+    min: Option<i64>,
+    #[serde(default)]
+    max: Option<i64>,
+}
+
+impl TryFrom<Value> for FieldType {
+    type Error = String;
+
+    /// Reads a field type written as a plain name, a tagged value
+    /// (`!enum { values: [...] }`) or a single-entry mapping
+    /// (`enum: { values: [...] }`).
+    fn try_from(value: Value) -> Result<Self, String> {
+        let (name, spec) = match value {
+            Value::String(name) => (name, None),
+            Value::Tagged(tagged) => {
+                let name = tagged.tag.to_string().trim_start_matches('!').to_string();
+                (name, Some(tagged.value))
             }
-            FieldType::Text | FieldType::Enum { .. } | FieldType::ItemRef | FieldType::Date => {
-                Ok(())
+            Value::Mapping(mapping) if mapping.len() == 1 => {
+                let (name, spec) = mapping.into_iter().next().expect("one entry");
+                let name = name
+                    .as_str()
+                    .ok_or("a field type name must be a string")?
+                    .to_string();
+                (name, Some(spec))
+            }
+            _ => return Err("invalid field type".to_string()),
+        };
+        let read = |spec: Option<Value>| -> Result<Value, String> {
+            match spec {
+                Some(Value::Null) | None => Ok(Value::Mapping(Mapping::new())),
+                Some(spec) => Ok(spec),
+            }
+        };
+        let invalid = |e: serde_yaml::Error| format!("field type `{name}`: {e}");
+
+        match name.as_str() {
+            "text" => {
+                let spec: TextSpec = serde_yaml::from_value(read(spec)?).map_err(invalid)?;
+                Ok(Self::Text {
+                    pattern: spec.pattern,
+                })
+            }
+            "enum" => {
+                let spec: EnumSpec = serde_yaml::from_value(read(spec)?).map_err(invalid)?;
+                Ok(Self::Enum {
+                    values: spec.values,
+                })
+            }
+            "list" => {
+                let inner = spec.ok_or("field type `list` needs an inner type")?;
+                Ok(Self::List(Box::new(Self::try_from(inner)?)))
+            }
+            "number" => {
+                let spec: NumberSpec = serde_yaml::from_value(read(spec)?).map_err(invalid)?;
+                Ok(Self::Number {
+                    min: spec.min,
+                    max: spec.max,
+                    unit: spec.unit,
+                })
+            }
+            "integer" => {
+                let spec: IntegerSpec = serde_yaml::from_value(read(spec)?).map_err(invalid)?;
+                Ok(Self::Integer {
+                    min: spec.min,
+                    max: spec.max,
+                })
+            }
+            "item_ref" | "date" | "boolean" | "url" if spec.is_some() => {
+                Err(format!("field type `{name}` takes no parameter"))
+            }
+            "item_ref" => Ok(Self::ItemRef),
+            "date" => Ok(Self::Date),
+            "boolean" => Ok(Self::Boolean),
+            "url" => Ok(Self::Url),
+            _ => Err(format!("unknown field type `{name}`")),
+        }
+    }
+}
+
+impl From<FieldType> for Value {
+    /// Writes a field type in the form read back by `TryFrom<Value>`: a
+    /// plain name when it carries no parameter, a tagged value otherwise.
+    fn from(field_type: FieldType) -> Self {
+        fn tagged(tag: &str, value: Value) -> Value {
+            Value::Tagged(Box::new(TaggedValue {
+                tag: Tag::new(tag),
+                value,
+            }))
+        }
+        fn spec(entries: Vec<(&str, Option<Value>)>) -> Value {
+            Value::Mapping(
+                entries
+                    .into_iter()
+                    .filter_map(|(key, value)| Some((Value::from(key), value?)))
+                    .collect(),
+            )
+        }
+
+        match field_type {
+            FieldType::Text { pattern: None } => Value::from("text"),
+            FieldType::Text { pattern } => {
+                tagged("text", spec(vec![("pattern", pattern.map(Value::from))]))
             }
+            FieldType::Enum { values } => tagged(
+                "enum",
+                spec(vec![(
+                    "values",
+                    Some(values.into_iter().map(Value::from).collect()),
+                )]),
+            ),
+            FieldType::ItemRef => Value::from("item_ref"),
+            FieldType::List(inner) => match Value::from(*inner) {
+                // A YAML node carries a single tag: a tagged inner type is
+                // written as a single-entry mapping.
+                Value::Tagged(inner) => {
+                    let name = inner.tag.to_string().trim_start_matches('!').to_string();
+                    tagged("list", spec(vec![(&name, Some(inner.value))]))
+                }
+                inner => tagged("list", inner),
+            },
+            FieldType::Date => Value::from("date"),
+            FieldType::Number {
+                min: None,
+                max: None,
+                unit: None,
+            } => Value::from("number"),
+            FieldType::Number { min, max, unit } => tagged(
+                "number",
+                spec(vec![
+                    ("min", min.map(Value::from)),
+                    ("max", max.map(Value::from)),
+                    ("unit", unit.map(Value::from)),
+                ]),
+            ),
+            FieldType::Integer {
+                min: None,
+                max: None,
+            } => Value::from("integer"),
+            FieldType::Integer { min, max } => tagged(
+                "integer",
+                spec(vec![
+                    ("min", min.map(Value::from)),
+                    ("max", max.map(Value::from)),
+                ]),
+            ),
+            FieldType::Boolean => Value::from("boolean"),
+            FieldType::Url => Value::from("url"),
         }
     }
 }
@@ -211,4 +651,101 @@ relations:
             "got: {err}"
         );
     }
+
+    #[test]
+    fn test_sections_load_and_must_not_reuse_field_names() {
+        let with_sections = |field: &str| {
+            STANDALONE_SCHEMA.replace(
+                "  allowed_targets:\n  - relation: refines",
+                &format!(
+                    "  sections:\n  - heading: Verification\n    field: {field}\n    required: true\n  allowed_targets:\n  - relation: refines"
+                ),
+            )
+        };
+
+        let schema = Schema::from_yaml_str(&with_sections("verification"), Path::new("<test>"))
+            .expect("sections must load");
+        let sections = &schema
+            .item_type("stakeholder_requirement")
+            .unwrap()
+            .sections;
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].heading, "Verification");
+        assert!(sections[0].required);
+
+        for taken in ["rationale", "name", "refines"] {
+            let err =
+                Schema::from_yaml_str(&with_sections(taken), Path::new("<test>")).unwrap_err();
+            assert!(
+                err.to_string().contains("already taken"),
+                "{taken}: got {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_typed_field_types_load_and_round_trip() {
+        let typed = STANDALONE_SCHEMA.replace(
+            "    required: true\n",
+            "    required: true\n  - name: latency\n    display_name: Latency\n    field_type: !number { min: 0, max: 1000, unit: ms }\n  - name: priority\n    display_name: Priority\n    field_type: integer\n  - name: tracker\n    display_name: Tracker\n    field_type: !list { text: { pattern: \"[A-Z]+-[0-9]+\" } }\n",
+        );
+        let schema = Schema::from_yaml_str(&typed, Path::new("<test>")).expect("typed fields");
+        let def = schema.item_type("stakeholder_requirement").unwrap();
+        let latency = &def.field_by_name("latency").unwrap().field_type;
+        assert_eq!(
+            *latency,
+            FieldType::Number {
+                min: Some(0.0),
+                max: Some(1000.0),
+                unit: Some("ms".to_string()),
+            }
+        );
+        assert_eq!(
+            def.field_by_name("priority").unwrap().field_type,
+            FieldType::Integer {
+                min: None,
+                max: None
+            }
+        );
+        let reloaded = Schema::from_yaml_str(&schema.to_yaml().unwrap(), Path::new("<test>"))
+            .expect("serialized schema reloads");
+        assert_eq!(reloaded, schema);
+
+        let tracker = &def.field_by_name("tracker").unwrap().field_type;
+        assert!(tracker.parse_value("JIRA-12").is_ok());
+        assert!(tracker.parse_value("jira-12").is_err());
+        assert_eq!(
+            latency.parse_value("250 ms"),
+            Ok(crate::model::FieldValue::Number(250.0))
+        );
+        assert!(latency.parse_value("1500").is_err());
+
+        let invalid = typed.replace("[A-Z]+-[0-9]+", "[A-Z");
+        let err = Schema::from_yaml_str(&invalid, Path::new("<test>")).unwrap_err();
+        assert!(err.to_string().contains("invalid pattern"), "got {err}");
+    }
+
+    #[test]
+    fn test_link_attributes_load_on_primary_relations_only() {
+        let attributed = STANDALONE_SCHEMA.replace(
+            "  primary: true\n",
+            "  primary: true\n  attributes:\n  - name: coverage\n    display_name: Coverage\n    field_type: !enum { values: [full, partial] }\n",
+        );
+        let schema =
+            Schema::from_yaml_str(&attributed, Path::new("<test>")).expect("link attributes");
+        assert_eq!(schema.link_attributes("refines")[0].name, "coverage");
+        // Links declared through the inverse use the primary's attributes.
+        assert_eq!(schema.link_attributes("is_refined_by").len(), 1);
+
+        let reserved = attributed.replace("name: coverage", "name: id");
+        let err = Schema::from_yaml_str(&reserved, Path::new("<test>")).unwrap_err();
+        assert!(err.to_string().contains("reserved"), "got {err}");
+
+        let inverse = attributed.replace(
+            "  primary: false\n",
+            "  primary: false\n  attributes:\n  - name: note\n    display_name: Note\n    field_type: text\n",
+        );
+        let err = Schema::from_yaml_str(&inverse, Path::new("<test>")).unwrap_err();
+        assert!(err.to_string().contains("not primary"), "got {err}");
+    }
 }
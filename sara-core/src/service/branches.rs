@@ -0,0 +1,364 @@
+//! Branch scanning service.
+//!
+//! Parses the items defined on every local and remote-tracking branch of a
+//! repository, so that new identifiers stay free across branches and
+//! identifiers introduced independently on several branches are reported
+//! before the branches are merged.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+
+use crate::error::SaraError;
+use crate::model::{Item, ItemId, ItemType};
+use crate::parser::ParseContext;
+use crate::repository::{GitReader, GitRef};
+
+/// Items defined on a branch.
+#[derive(Debug, Clone)]
+pub struct BranchItems {
+    /// Short branch name (`main`, `origin/main`).
+    pub branch: String,
+    /// Commit the branch points to.
+    pub commit: GitRef,
+    /// Items parsed under the scanned scope.
+    pub items: Vec<Item>,
+}
+
+/// Parses the items under `scope` on every local and remote-tracking branch.
+///
+/// Branches pointing to the same commit share a single parse. A branch whose
+/// documents cannot be parsed is skipped with a warning logged via
+/// `tracing`.
+///
+/// # Errors
+///
+/// Returns [`SaraError::Gix`] if the branches cannot be listed.
+pub fn parse_branches(
+    reader: &GitReader,
+    scope: &Path,
+    ctx: &ParseContext,
+) -> Result<Vec<BranchItems>, SaraError> {
+    let mut parsed: HashMap<GitRef, Vec<Item>> = HashMap::new();
+    let mut branches = Vec::new();
+
+    for (branch, commit) in reader.list_branches()? {
+        let items = match parsed.get(&commit) {
+            Some(items) => items.clone(),
+            None => match reader.parse_commit(&commit, scope, ctx) {
+                Ok(items) => {
+                    parsed.insert(commit.clone(), items.clone());
+                    items
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to parse branch {branch}: {e}");
+                    continue;
+                }
+            },
+        };
+        branches.push(BranchItems {
+            branch,
+            commit,
+            items,
+        });
+    }
+    Ok(branches)
+}
+
+/// One definition of a colliding identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchDefinition {
+    /// Branches defining the identifier this way.
+    pub branches: Vec<String>,
+    /// Item type of the definition.
+    pub item_type: ItemType,
+    /// Item name of the definition.
+    pub name: String,
+    /// Defining file, relative to the repository root.
+    pub file: PathBuf,
+}
+
+/// An identifier defined differently on several branches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdCollision {
+    /// The colliding identifier.
+    pub id: ItemId,
+    /// The conflicting definitions, in branch order.
+    pub definitions: Vec<BranchDefinition>,
+}
+
+impl From<IdCollision> for SaraError {
+    fn from(collision: IdCollision) -> Self {
+        let definitions = collision
+            .definitions
+            .iter()
+            .map(|def| {
+                format!(
+                    "{}: \"{}\" in {}",
+                    def.branches.join(", "),
+                    def.name,
+                    def.file.display()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        SaraError::BranchCollision {
+            id: collision.id,
+            definitions,
+        }
+    }
+}
+
+/// Finds the identifiers that `branches`, parsed under `scope` with
+/// [`parse_branches`] with `ctx`, define differently.
+///
+/// Two definitions collide when they differ in type, name or file and the
+/// common ancestor of their branches does not define the identifier: each
+/// branch then introduced it on its own, a duplicate that validation would
+/// only catch once the branches are merged. An item edited on one branch
+/// after the branches diverged is not a collision.
+///
+/// # Errors
+///
+/// Returns [`SaraError::Gix`] if the common ancestors of the branches
+/// cannot be computed, or the parse error of an ancestor whose documents
+/// cannot be parsed.
+pub fn find_id_collisions(
+    reader: &GitReader,
+    scope: &Path,
+    branches: &[BranchItems],
+    ctx: &ParseContext,
+) -> Result<Vec<IdCollision>, SaraError> {
+    // Definitions of each identifier, each with the branches defining it.
+    let mut by_id: IndexMap<&ItemId, Vec<(BranchDefinition, Vec<&GitRef>)>> = IndexMap::new();
+    for branch in branches {
+        for item in &branch.items {
+            let definitions = by_id.entry(&item.id).or_default();
+            let definition = BranchDefinition {
+                branches: Vec::new(),
+                item_type: item.item_type,
+                name: item.name.clone(),
+                file: item.source.file_path.clone(),
+            };
+            let index = match definitions.iter().position(|(def, _)| *def == definition) {
+                Some(index) => index,
+                None => {
+                    definitions.push((definition, Vec::new()));
+                    definitions.len() - 1
+                }
+            };
+            let (definition, commits) = &mut definitions[index];
+            definition.branches.push(branch.branch.clone());
+            commits.push(&branch.commit);
+        }
+    }
+
+    let mut ancestry = Ancestry::new(reader, scope, ctx);
+    let mut collisions = Vec::new();
+    for (id, definitions) in by_id {
+        let mut colliding = vec![false; definitions.len()];
+        for (i, (_, ours)) in definitions.iter().enumerate() {
+            for (j, (_, theirs)) in definitions.iter().enumerate().skip(i + 1) {
+                if ancestry.introduced_apart(ours, theirs, id)? {
+                    colliding[i] = true;
+                    colliding[j] = true;
+                }
+            }
+        }
+        if colliding.contains(&true) {
+            collisions.push(IdCollision {
+                id: id.clone(),
+                definitions: definitions
+                    .into_iter()
+                    .zip(colliding)
+                    .filter_map(|((def, _), collides)| collides.then_some(def))
+                    .collect(),
+            });
+        }
+    }
+    Ok(collisions)
+}
+
+/// Common ancestors of branch commits and the identifiers they define,
+/// cached across identifiers.
+struct Ancestry<'a> {
+    reader: &'a GitReader,
+    scope: &'a Path,
+    ctx: &'a ParseContext,
+    bases: HashMap<(GitRef, GitRef), Option<GitRef>>,
+    ids: HashMap<GitRef, HashSet<ItemId>>,
+}
+
+impl<'a> Ancestry<'a> {
+    fn new(reader: &'a GitReader, scope: &'a Path, ctx: &'a ParseContext) -> Self {
+        Self {
+            reader,
+            scope,
+            ctx,
+            bases: HashMap::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Tells whether some commit of `ours` and some commit of `theirs` do
+    /// not inherit `id` from their common ancestor.
+    fn introduced_apart(
+        &mut self,
+        ours: &[&GitRef],
+        theirs: &[&GitRef],
+        id: &ItemId,
+    ) -> Result<bool, SaraError> {
+        for one in ours {
+            for two in theirs {
+                let key = ((*one).clone(), (*two).clone());
+                let base = match self.bases.get(&key) {
+                    Some(base) => base.clone(),
+                    None => {
+                        let base = self.reader.merge_base(one, two)?;
+                        self.bases.insert(key, base.clone());
+                        base
+                    }
+                };
+                let Some(base) = base else {
+                    // Unrelated histories share no definition.
+                    return Ok(true);
+                };
+                if !self.defined_at(&base, id)? {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Tells whether `commit` defines `id` under the scanned scope.
+    fn defined_at(&mut self, commit: &GitRef, id: &ItemId) -> Result<bool, SaraError> {
+        if !self.ids.contains_key(commit) {
+            let ids = self
+                .reader
+                .parse_commit(commit, self.scope, self.ctx)?
+                .into_iter()
+                .map(|item| item.id)
+                .collect();
+            self.ids.insert(commit.clone(), ids);
+        }
+        Ok(self.ids[commit].contains(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::test_utils::run_git;
+
+    /// Writes a solution document to `repo` and commits it.
+    fn commit_solution(repo: &Path, file: &str, id: &str, name: &str) {
+        let content = format!(
+            "---\nid: \"{id}\"\ntype: solution\nname: \"{name}\"\n---\n# Solution: {name}\n"
+        );
+        fs::write(repo.join(file), content).unwrap();
+        run_git(repo, &["add", "."]);
+        run_git(repo, &["commit", "-q", "-m", name]);
+    }
+
+    /// Creates a repository with `SOL-001` committed on `master`.
+    fn repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        run_git(repo, &["init", "-q", "-b", "master"]);
+        run_git(repo, &["config", "user.name", "Sara Tests"]);
+        run_git(repo, &["config", "user.email", "tests@example.com"]);
+        commit_solution(repo, "SOL-001.md", "SOL-001", "Base");
+        temp_dir
+    }
+
+    #[test]
+    fn test_parse_branches_reads_every_branch() {
+        let repo = repo();
+        run_git(repo.path(), &["checkout", "-q", "-b", "feature"]);
+        commit_solution(repo.path(), "SOL-002.md", "SOL-002", "Feature");
+        let reader = GitReader::discover(repo.path()).unwrap();
+
+        let branches = parse_branches(&reader, Path::new(""), &ParseContext::default()).unwrap();
+
+        let counts: Vec<(&str, usize)> = branches
+            .iter()
+            .map(|b| (b.branch.as_str(), b.items.len()))
+            .collect();
+        assert_eq!(counts, [("feature", 2), ("master", 1)]);
+    }
+
+    #[test]
+    fn test_find_id_collisions_reports_ids_added_on_both_branches() {
+        let repo = repo();
+        run_git(repo.path(), &["checkout", "-q", "-b", "login"]);
+        commit_solution(repo.path(), "login.md", "SOL-002", "Login");
+        run_git(repo.path(), &["checkout", "-q", "master"]);
+        run_git(repo.path(), &["checkout", "-q", "-b", "cache"]);
+        commit_solution(repo.path(), "cache.md", "SOL-002", "Cache");
+        let reader = GitReader::discover(repo.path()).unwrap();
+        let branches = parse_branches(&reader, Path::new(""), &ParseContext::default()).unwrap();
+
+        let collisions =
+            find_id_collisions(&reader, Path::new(""), &branches, &ParseContext::default())
+                .unwrap();
+
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].id.as_str(), "SOL-002");
+        let names: Vec<&str> = collisions[0]
+            .definitions
+            .iter()
+            .map(|def| def.name.as_str())
+            .collect();
+        assert_eq!(names, ["Cache", "Login"]);
+        assert_eq!(collisions[0].definitions[0].branches, ["cache"]);
+    }
+
+    #[test]
+    fn test_find_id_collisions_ignores_items_edited_after_diverging() {
+        let repo = repo();
+        run_git(repo.path(), &["checkout", "-q", "-b", "rename"]);
+        commit_solution(repo.path(), "SOL-001.md", "SOL-001", "Renamed");
+        let reader = GitReader::discover(repo.path()).unwrap();
+        let branches = parse_branches(&reader, Path::new(""), &ParseContext::default()).unwrap();
+
+        let collisions =
+            find_id_collisions(&reader, Path::new(""), &branches, &ParseContext::default())
+                .unwrap();
+
+        assert!(collisions.is_empty());
+    }
+
+    #[test]
+    fn test_id_collision_converts_to_branch_collision_error() {
+        let collision = IdCollision {
+            id: ItemId::new_unchecked("SOL-002"),
+            definitions: vec![
+                BranchDefinition {
+                    branches: vec!["cache".to_string(), "origin/cache".to_string()],
+                    item_type: crate::schema::builtin::SOLUTION,
+                    name: "Cache".to_string(),
+                    file: PathBuf::from("cache.md"),
+                },
+                BranchDefinition {
+                    branches: vec!["login".to_string()],
+                    item_type: crate::schema::builtin::SOLUTION,
+                    name: "Login".to_string(),
+                    file: PathBuf::from("login.md"),
+                },
+            ],
+        };
+
+        assert_eq!(
+            SaraError::from(collision).to_string(),
+            "Branch collision: SOL-002 defined differently on \
+             cache, origin/cache: \"Cache\" in cache.md; login: \"Login\" in login.md"
+        );
+    }
+}
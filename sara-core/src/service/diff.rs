@@ -3,10 +3,17 @@
 //! Provides functionality to compute differences between two states of the
 //! requirements knowledge graph, supporting Git reference comparisons.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
+use indexmap::IndexMap;
+
+use crate::config::namespace_of;
+use crate::error::SaraError;
 use crate::graph::{GraphDiff, KnowledgeGraphBuilder};
-use crate::repository::{GitReader, GitRef};
+use crate::parser::ParseContext;
+use crate::repository::{GitReader, GitRef, qualify_items, resolve_references};
+use crate::schema::{self, Schema, normalize_lexically};
 
 /// Options for computing a diff between two graph states.
 #[derive(Debug, Clone)]
@@ -17,6 +24,11 @@ pub struct DiffOptions {
     pub ref2: String,
     /// Repository paths to compare.
     pub repositories: Vec<PathBuf>,
+    /// Namespaces of the repositories, keyed by namespace name.
+    pub namespaces: IndexMap<String, PathBuf>,
+    /// Model schema file, read at each reference (default: the active schema
+    /// at both).
+    pub model_schema: Option<PathBuf>,
     /// Show summary statistics only.
     pub stat: bool,
 }
@@ -28,6 +40,8 @@ impl DiffOptions {
             ref1: ref1.into(),
             ref2: ref2.into(),
             repositories: Vec::new(),
+            namespaces: IndexMap::new(),
+            model_schema: None,
             stat: false,
         }
     }
@@ -44,6 +58,18 @@ impl DiffOptions {
         self
     }
 
+    /// Sets the repository namespaces, keyed by namespace name.
+    pub fn with_namespaces(mut self, namespaces: IndexMap<String, PathBuf>) -> Self {
+        self.namespaces = namespaces;
+        self
+    }
+
+    /// Sets the model schema file, read at each reference.
+    pub fn with_model_schema(mut self, path: impl Into<PathBuf>) -> Self {
+        self.model_schema = Some(path.into());
+        self
+    }
+
     /// Sets whether to show only summary statistics.
     pub fn with_stat(mut self, stat: bool) -> Self {
         self.stat = stat;
@@ -58,6 +84,10 @@ pub enum DiffError {
     #[error("Failed to parse repository {path}: {reason}")]
     ParseError { path: String, reason: String },
 
+    /// The model schema could not be loaded at a reference.
+    #[error("Failed to load the model schema at {git_ref}: {reason}")]
+    SchemaError { git_ref: String, reason: String },
+
     /// Failed to build graph.
     #[error("Failed to build graph: {0}")]
     GraphBuildError(String),
@@ -84,6 +114,9 @@ pub struct DiffResult {
     pub ref1: String,
     /// The second reference used.
     pub ref2: String,
+    /// Problems that did not prevent the comparison, such as a reference
+    /// read with the built-in model for want of a schema file.
+    pub warnings: Vec<String>,
 }
 
 impl DiffResult {
@@ -110,12 +143,19 @@ impl DiffService {
     /// repositories are never parsed. The items compared at each reference
     /// are the union over all configured paths.
     ///
+    /// Each reference is read with the model schema it had: the
+    /// [`DiffOptions::model_schema`] file as committed at that reference,
+    /// along with the files it extends, or the built-in model, with a
+    /// warning, where the file is not committed. A schema file outside any
+    /// Git repository is read from disk for both references.
+    ///
     /// # Errors
     ///
     /// Returns [`DiffError::NoRepositories`] when no path is configured and
     /// [`DiffError::NotAGitRepository`] when a configured path lies outside
     /// any Git repository, since neither state can be compared at a
-    /// reference.
+    /// reference, and [`DiffError::SchemaError`] when the model schema of a
+    /// reference is invalid.
     pub fn diff(&self, opts: &DiffOptions) -> Result<DiffResult, DiffError> {
         if opts.repositories.is_empty() {
             return Err(DiffError::NoRepositories);
@@ -124,6 +164,19 @@ impl DiffService {
         // Parse Git references
         let git_ref1 = GitRef::parse(&opts.ref1);
         let git_ref2 = GitRef::parse(&opts.ref2);
+        let mut warnings = Vec::new();
+        let ctx1 = ParseContext::new(schema_at(
+            opts.model_schema.as_deref(),
+            &git_ref1,
+            &opts.ref1,
+            &mut warnings,
+        )?);
+        let ctx2 = ParseContext::new(schema_at(
+            opts.model_schema.as_deref(),
+            &git_ref2,
+            &opts.ref2,
+            &mut warnings,
+        )?);
 
         // Parse items at each reference, accumulated across all paths
         let mut items1 = Vec::new();
@@ -146,28 +199,40 @@ impl DiffService {
                         reason: e.to_string(),
                     })?;
 
-            items1.extend(git_reader.parse_commit(&git_ref1, &scope).map_err(|e| {
-                DiffError::ParseError {
+            let mut at_ref1 = git_reader
+                .parse_commit(&git_ref1, &scope, &ctx1)
+                .map_err(|e| DiffError::ParseError {
                     path: format!("{}@{}", repo_path.display(), opts.ref1),
                     reason: e.to_string(),
-                }
-            })?);
+                })?;
 
-            items2.extend(git_reader.parse_commit(&git_ref2, &scope).map_err(|e| {
-                DiffError::ParseError {
+            let mut at_ref2 = git_reader
+                .parse_commit(&git_ref2, &scope, &ctx2)
+                .map_err(|e| DiffError::ParseError {
                     path: format!("{}@{}", repo_path.display(), opts.ref2),
                     reason: e.to_string(),
-                }
-            })?);
+                })?;
+
+            if let Some(namespace) = namespace_of(&opts.namespaces, repo_path) {
+                qualify_items(&mut at_ref1, namespace);
+                qualify_items(&mut at_ref2, namespace);
+            }
+            items1.extend(at_ref1);
+            items2.extend(at_ref2);
         }
 
+        resolve_references(&mut items1);
+        resolve_references(&mut items2);
+
         // Build graphs from each reference
         let graph1 = KnowledgeGraphBuilder::new()
+            .schema(Arc::clone(ctx1.schema()))
             .add_items(items1)
             .build()
             .map_err(|e| DiffError::GraphBuildError(e.to_string()))?;
 
         let graph2 = KnowledgeGraphBuilder::new()
+            .schema(Arc::clone(ctx2.schema()))
             .add_items(items2)
             .build()
             .map_err(|e| DiffError::GraphBuildError(e.to_string()))?;
@@ -179,6 +244,7 @@ impl DiffService {
             diff,
             ref1: opts.ref1.clone(),
             ref2: opts.ref2.clone(),
+            warnings,
         })
     }
 
@@ -197,10 +263,70 @@ impl DiffService {
             diff,
             ref1: ref1.into(),
             ref2: ref2.into(),
+            warnings: Vec::new(),
         }
     }
 }
 
+/// Returns the model schema at a reference.
+///
+/// Without a schema file, the active schema is used. A schema file inside a
+/// Git repository is read as committed at the reference, the files it
+/// extends included; where it is not committed, the built-in model is used
+/// and a warning is pushed onto `warnings`. A schema file outside any
+/// repository is read from disk.
+fn schema_at(
+    path: Option<&Path>,
+    git_ref: &GitRef,
+    label: &str,
+    warnings: &mut Vec<String>,
+) -> Result<Arc<Schema>, DiffError> {
+    let Some(path) = path else {
+        return Ok(schema::shared());
+    };
+    let schema_error = |reason: String| DiffError::SchemaError {
+        git_ref: label.to_string(),
+        reason,
+    };
+
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Ok(reader) = GitReader::discover(dir) else {
+        let schema = Schema::from_path(path).map_err(|e| schema_error(e.to_string()))?;
+        return Ok(Arc::new(schema));
+    };
+    let file = reader
+        .scope_from_path(dir)
+        .map_err(|e| schema_error(e.to_string()))?
+        .join(path.file_name().unwrap_or_default());
+    if reader
+        .read_file_at(git_ref, &file)
+        .map_err(|e| schema_error(e.to_string()))?
+        .is_none()
+    {
+        warnings.push(format!(
+            "Model schema {} is not committed at {label}: reading it with the built-in model",
+            path.display()
+        ));
+        return Ok(Arc::new(Schema::builtin()));
+    }
+
+    // Paths are relative to the repository root, bases included.
+    let read = |file: &Path| {
+        let file = normalize_lexically(file);
+        reader
+            .read_file_at(git_ref, &file)?
+            .ok_or_else(|| SaraError::ConfigRead {
+                path: file,
+                reason: format!("not committed at {label}"),
+            })
+    };
+    let schema = Schema::from_path_with(&file, &read).map_err(|e| schema_error(e.to_string()))?;
+    Ok(Arc::new(schema))
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -339,6 +465,103 @@ name: "Test Solution"
         assert!(result.diff.modified_items.is_empty());
     }
 
+    #[test]
+    fn test_diff_reads_each_reference_with_its_schema() {
+        const TEST_CASE_TYPE: &str = r#"extends: builtin
+item_types:
+- id: test_case
+  display_name: Test Case
+  prefix: TC
+  id_format: "{prefix}-{seq:03}"
+"#;
+        const TEST_CASE_ITEM: &str = r#"---
+id: "TC-001"
+type: test_case
+name: "Login"
+---
+# Test Case: Login
+"#;
+
+        let repo = multi_path_repo();
+        let root = repo.path();
+        fs::write(root.join("model.yaml"), TEST_CASE_TYPE).unwrap();
+        create_test_file(&root.join("docs"), "TC-001.md", TEST_CASE_ITEM);
+        run_git(root, &["add", "."]);
+        run_git(root, &["commit", "-m", "add test cases"]);
+
+        let opts = DiffOptions::new("HEAD~1", "HEAD")
+            .with_repositories(vec![root.join("docs")])
+            .with_model_schema(root.join("model.yaml"));
+        let result = DiffService::new().diff(&opts).unwrap();
+
+        // The baseline predates the schema file and reads with the built-in
+        // model; the new type only exists at the target.
+        assert_eq!(result.diff.added_items.len(), 1);
+        assert_eq!(result.diff.added_items[0].id, "TC-001");
+        assert_eq!(result.diff.added_items[0].item_type, "Test Case");
+        assert!(schema::active().item_type("test_case").is_none());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("not committed at HEAD~1"));
+    }
+
+    #[test]
+    fn test_diff_reads_schema_bases_at_the_reference() {
+        let repo = multi_path_repo();
+        let root = repo.path();
+        fs::create_dir(root.join("model")).unwrap();
+        fs::write(
+            root.join("model/base.yaml"),
+            "extends: builtin\nitem_types:\n- id: test_case\n  display_name: Test Case\n  \
+             prefix: TC\n  id_format: \"{prefix}-{seq:03}\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("model.yaml"),
+            "extends: model/../model/base.yaml\n",
+        )
+        .unwrap();
+        create_test_file(
+            &root.join("docs"),
+            "TC-001.md",
+            "---\nid: \"TC-001\"\ntype: test_case\nname: \"Login\"\n---\n# Login\n",
+        );
+        run_git(root, &["add", "."]);
+        run_git(root, &["commit", "-m", "add test cases"]);
+        // Uncommitted edits of the base are not part of the reference.
+        fs::write(root.join("model/base.yaml"), "extends: builtin\n").unwrap();
+
+        let opts = DiffOptions::new("HEAD~1", "HEAD")
+            .with_repositories(vec![root.join("docs")])
+            .with_model_schema(root.join("model.yaml"));
+        let result = DiffService::new().diff(&opts).unwrap();
+
+        assert_eq!(result.diff.added_items.len(), 1);
+        assert_eq!(result.diff.added_items[0].item_type, "Test Case");
+    }
+
+    #[test]
+    fn test_diff_errors_when_a_schema_base_is_not_committed() {
+        let repo = multi_path_repo();
+        let root = repo.path();
+        fs::write(root.join("model.yaml"), "extends: base.yaml\n").unwrap();
+        run_git(root, &["add", "model.yaml"]);
+        run_git(root, &["commit", "-m", "add schema"]);
+        fs::write(root.join("base.yaml"), "extends: builtin\n").unwrap();
+
+        let opts = DiffOptions::new("HEAD~1", "HEAD")
+            .with_repositories(vec![root.join("docs")])
+            .with_model_schema(root.join("model.yaml"));
+        let error = DiffService::new().diff(&opts).unwrap_err();
+
+        let message = error.to_string();
+        assert!(
+            matches!(error, DiffError::SchemaError { .. }),
+            "got: {message}"
+        );
+        assert!(message.contains("base.yaml"), "got: {message}");
+        assert!(message.contains("not committed at HEAD"), "got: {message}");
+    }
+
     #[test]
     fn test_diff_in_git_repo() {
         // Use the current repository for testing Git comparison
@@ -14,8 +14,10 @@ use crate::model::{
     FieldChange, Item, ItemAttributes, ItemBuilder, ItemId, ItemType, RelationshipType,
     SourceLocation, TraceabilityLinks,
 };
-use crate::parser::update_frontmatter;
-use crate::schema::FieldType;
+use crate::parser::{
+    InputFormat, ParseContext, parse_document, update_anchored_item, update_listed_item,
+    update_metadata,
+};
 
 use super::FieldInput;
 use super::init::init_field_value;
@@ -246,15 +248,17 @@ impl EditService {
                     item_type.display_name()
                 )));
             };
-            if let FieldType::Enum { values } = &field.field_type
-                && let FieldInput::Text(value) = input
-                && !values.contains(value)
-            {
-                return Err(SaraError::EditFailed(format!(
-                    "invalid value `{value}` for --{}, expected one of: {}",
-                    name.replace('_', "-"),
-                    values.join(", ")
-                )));
+            let values = match input {
+                FieldInput::Text(value) => std::slice::from_ref(value),
+                FieldInput::List(values) => values.as_slice(),
+            };
+            for value in values {
+                if let Err(reason) = field.field_type.parse_value(value) {
+                    return Err(SaraError::EditFailed(format!(
+                        "--{}: {reason}",
+                        name.replace('_', "-")
+                    )));
+                }
             }
         }
 
@@ -364,6 +368,13 @@ impl EditService {
     }
 
     /// Applies changes to the file.
+    ///
+    /// The metadata is written in the format of the file, picked from its
+    /// extension; Markdown frontmatter keeps its YAML or TOML language. An
+    /// item anchored on a Markdown heading has only its heading and `sara`
+    /// block rewritten, and an item listed in a standalone item file has
+    /// only its entry rewritten. A namespace-qualified `item_id` is written
+    /// without its namespace.
     pub fn apply_changes(
         &self,
         item_id: &str,
@@ -373,8 +384,52 @@ impl EditService {
     ) -> Result<(), SaraError> {
         let content =
             fs::read_to_string(file_path).map_err(|e| SaraError::EditFailed(e.to_string()))?;
-        let new_yaml = self.build_frontmatter_yaml(item_id, item_type, new_values);
-        let updated_content = update_frontmatter(&content, &new_yaml);
+        // The frontmatter holds the local identifier: the namespace comes from
+        // the repository the file lives in.
+        let item_id = ItemId::new_unchecked(item_id);
+        let format = InputFormat::from_path(file_path).unwrap_or(InputFormat::Markdown);
+        let item = self.build_item_from_values(item_id.local(), item_type, new_values);
+        // An item anchored on a heading or listed in an item file only has
+        // its own heading and block, or its own entry, rewritten.
+        let in_place = match format {
+            InputFormat::Markdown => update_anchored_item(
+                &content,
+                item.id.as_str(),
+                &item.name,
+                &generator::generate_metadata(&item, OutputFormat::Yaml),
+            ),
+            InputFormat::Yaml | InputFormat::Toml => {
+                let metadata = generator::generate_metadata(
+                    &item,
+                    OutputFormat::from_path(file_path).unwrap_or(OutputFormat::Yaml),
+                );
+                let listed = update_listed_item(&content, item.id.as_str(), &metadata, format);
+                if listed.is_none()
+                    && parse_document(
+                        &content,
+                        file_path,
+                        Path::new(""),
+                        format,
+                        &ParseContext::default(),
+                    )?
+                    .len()
+                        > 1
+                {
+                    return Err(SaraError::EditFailed(format!(
+                        "{} lists several items but no single entry of {}; edit it directly",
+                        file_path.display(),
+                        item.id
+                    )));
+                }
+                listed
+            }
+            InputFormat::AsciiDoc | InputFormat::Rst => None,
+        };
+        let updated_content = in_place.unwrap_or_else(|| {
+            let metadata =
+                super::document_metadata(&item, file_path, &content, generator::generate_metadata);
+            update_metadata(&content, &metadata, format)
+        });
         fs::write(file_path, updated_content).map_err(|e| SaraError::EditFailed(e.to_string()))?;
         Ok(())
     }
@@ -405,6 +460,7 @@ impl EditService {
             repository: PathBuf::new(),
             file_path: PathBuf::from("edit.md"),
             git_ref: None,
+            lines: None,
         };
 
         let mut builder = ItemBuilder::new()
@@ -417,11 +473,7 @@ impl EditService {
             builder = builder.description(desc);
         }
 
-        let mut rels = Vec::new();
-        for (relation, ids) in values.traceability.iter() {
-            rels.extend(super::ids_to_relationships(ids, relation));
-        }
-        builder = builder.relationships(rels);
+        builder = builder.relationships(values.traceability.to_relationships());
 
         let mut attributes = values.attributes.clone();
         for field in item_type.declared_fields() {
@@ -823,6 +875,183 @@ mod tests {
         assert!(content.contains("Body to preserve."));
     }
 
+    #[test]
+    fn test_apply_changes_keeps_document_format() {
+        let service = EditService::new();
+
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("SOL-001.rst");
+        fs::write(
+            &file,
+            "Hub\n===\n\n:id: SOL-001\n:type: solution\n:name: Old Name\n:author: Jane\n\n\
+             Body to preserve.\n",
+        )
+        .unwrap();
+
+        service
+            .apply_changes(
+                "SOL-001",
+                builtin::SOLUTION,
+                &EditedValues::new("New Name"),
+                &file,
+            )
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "Hub\n===\n\n:id: SOL-001\n:type: solution\n:name: New Name\n:author: Jane\n\n\
+             Body to preserve.\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_keeps_toml_frontmatter() {
+        let service = EditService::new();
+
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("SOL-001.md");
+        fs::write(
+            &file,
+            "+++\nid = \"SOL-001\"\ntype = \"solution\"\nname = \"Old Name\"\n+++\n# Body\n",
+        )
+        .unwrap();
+        service
+            .apply_changes(
+                "SOL-001",
+                builtin::SOLUTION,
+                &EditedValues::new("New Name"),
+                &file,
+            )
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "+++\nid = \"SOL-001\"\ntype = \"solution\"\nname = \"New Name\"\n+++\n# Body"
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_rewrites_only_the_listed_item() {
+        let service = EditService::new();
+        let dir = TempDir::new().unwrap();
+
+        let yaml = dir.path().join("solutions.sara.yaml");
+        fs::write(
+            &yaml,
+            "# Platform solutions\n- id: SOL-001\n  type: solution\n  name: Hub\n\n\
+             # Kept as written\n- id: SOL-002\n  type: solution\n  name: \"Gateway\"\n",
+        )
+        .unwrap();
+        let toml = dir.path().join("solutions.sara.toml");
+        fs::write(
+            &toml,
+            "[[items]]\nid = \"SOL-001\"\ntype = \"solution\"\nname = \"Hub\"\n\n\
+             [[items]]\nid = \"SOL-002\"\ntype = \"solution\"\nname = \"Gateway\"\n",
+        )
+        .unwrap();
+        for file in [&yaml, &toml] {
+            service
+                .apply_changes(
+                    "SOL-002",
+                    builtin::SOLUTION,
+                    &EditedValues::new("New Gateway"),
+                    file,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            fs::read_to_string(&yaml).unwrap(),
+            "# Platform solutions\n- id: SOL-001\n  type: solution\n  name: Hub\n\n\
+             # Kept as written\n- id: \"SOL-002\"\n  type: solution\n  name: \"New Gateway\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&toml).unwrap(),
+            "[[items]]\nid = \"SOL-001\"\ntype = \"solution\"\nname = \"Hub\"\n\n\
+             [[items]]\nid = \"SOL-002\"\ntype = \"solution\"\nname = \"New Gateway\"\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_changes_refuses_item_lists_without_the_item() {
+        let service = EditService::new();
+
+        let dir = TempDir::new().unwrap();
+        let list = dir.path().join("solutions.sara.yaml");
+        let content = "- id: SOL-001\n  type: solution\n  name: Hub\n\
+                       - id: SOL-002\n  type: solution\n  name: Gateway\n";
+        fs::write(&list, content).unwrap();
+        let error = service
+            .apply_changes(
+                "SOL-003",
+                builtin::SOLUTION,
+                &EditedValues::new("New Name"),
+                &list,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&error, SaraError::EditFailed(reason) if reason.contains("solutions.sara.yaml"))
+        );
+        assert_eq!(fs::read_to_string(&list).unwrap(), content);
+    }
+
+    #[test]
+    fn test_apply_changes_rewrites_only_the_anchored_item() {
+        let service = EditService::new();
+
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("solutions.md");
+        let other = "## SOL-002: Gateway\n\n```sara\ntype: solution\n```\n\nGateway body.\n";
+        fs::write(
+            &file,
+            format!(
+                "# Solutions\n\n## SOL-001: Old Name\n\n```sara\ntype: solution\n```\n\n\
+                 Hub body.\n\n{other}"
+            ),
+        )
+        .unwrap();
+
+        service
+            .apply_changes(
+                "SOL-001",
+                builtin::SOLUTION,
+                &EditedValues::new("New Name"),
+                &file,
+            )
+            .unwrap();
+
+        let updated = fs::read_to_string(&file).unwrap();
+        assert!(updated.starts_with(
+            "# Solutions\n\n## SOL-001: New Name\n\n```sara\ntype: solution\n```\n\nHub body.\n"
+        ));
+        assert!(updated.ends_with(other));
+    }
+
+    #[test]
+    fn test_apply_changes_writes_local_id() {
+        let service = EditService::new();
+
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("SOL-001.md");
+        fs::write(
+            &file,
+            "---\nid: \"SOL-001\"\ntype: solution\nname: \"Old Name\"\n---\n",
+        )
+        .unwrap();
+
+        service
+            .apply_changes(
+                "platform:SOL-001",
+                builtin::SOLUTION,
+                &EditedValues::new("New Name"),
+                &file,
+            )
+            .unwrap();
+
+        let content = fs::read_to_string(&file).unwrap();
+        assert!(content.contains("id: \"SOL-001\""));
+        assert!(!content.contains("platform:"));
+    }
+
     #[test]
     fn test_apply_changes_missing_file() {
         let service = EditService::new();
@@ -0,0 +1,453 @@
+//! Import service.
+//!
+//! Writes items read from an interchange document (see
+//! [`interchange`](crate::interchange)) as Markdown documents, and applies
+//! the rows of an edited sheet to the repository.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::SaraError;
+use crate::generator::{self, OutputFormat};
+use crate::graph::{KnowledgeGraph, LookupResult};
+use crate::interchange::csv::CsvRecord;
+use crate::model::{FieldValue, Item, ItemType};
+use crate::parser::{InputFormat, ParseContext, parse_metadata};
+use crate::schema::FieldType;
+
+use super::{
+    EditOptions, EditResult, EditService, FieldInput, InitError, InitOptions, InitResult,
+    InitService, ItemContext, TypeConfig,
+};
+
+/// Result of writing imported items.
+#[derive(Debug, Clone, Default)]
+pub struct ImportResult {
+    /// Documents written.
+    pub written: Vec<PathBuf>,
+    /// Documents left untouched because they already exist.
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Result of applying the rows of a sheet.
+#[derive(Debug, Default)]
+pub struct RecordsResult {
+    /// Items created from rows naming no existing item.
+    pub created: Vec<InitResult>,
+    /// Items whose document was rewritten, with their changes.
+    pub updated: Vec<EditResult>,
+    /// Items whose row matches their current values.
+    pub unchanged: Vec<String>,
+    /// Rows that could not be applied, with their line and the reason.
+    pub failed: Vec<(usize, String)>,
+}
+
+/// Service for writing imported items.
+#[derive(Debug, Default)]
+pub struct ImportService;
+
+impl ImportService {
+    /// Creates a new import service.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Writes each item as a Markdown document at its source location.
+    ///
+    /// Existing documents are skipped unless `force` is set; missing parent
+    /// directories are created.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SaraError::Io`] if a document cannot be written.
+    pub fn write(&self, items: &[Item], force: bool) -> Result<ImportResult, SaraError> {
+        let mut result = ImportResult::default();
+        for item in items {
+            let path = item.source.full_path();
+            if path.exists() && !force {
+                result.skipped.push(path);
+                continue;
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(
+                &path,
+                generator::generate_document(item, OutputFormat::Markdown),
+            )?;
+            result.written.push(path);
+        }
+        Ok(result)
+    }
+
+    /// Applies the rows of a sheet of items of one type.
+    ///
+    /// A row naming an item of the graph updates it through
+    /// [`EditService`]; any other row creates a new item in `into` through
+    /// [`InitService`], generating its identifier when the row has none.
+    /// A row that fails is reported in [`RecordsResult::failed`] and does
+    /// not stop the others.
+    pub fn apply_records(
+        &self,
+        graph: &KnowledgeGraph,
+        item_type: ItemType,
+        records: &[CsvRecord],
+        into: &Path,
+    ) -> RecordsResult {
+        let mut result = RecordsResult::default();
+        let mut created: Vec<Item> = Vec::new();
+
+        for record in records {
+            let opts = edit_options(record, item_type);
+            if let Err(e) = EditService::new().validate_options(&opts, item_type) {
+                result.failed.push((record.line, e.to_string()));
+                continue;
+            }
+
+            let existing = match record.id.as_deref().map(|id| graph.lookup(id)) {
+                Some(LookupResult::Found(item)) => Some(item),
+                _ => None,
+            };
+            if let Some(item) = existing {
+                match self.update(item, item_type, opts) {
+                    Ok(Some(edit)) => result.updated.push(edit),
+                    Ok(None) => result.unchanged.push(item.id.as_str().to_string()),
+                    Err(e) => result.failed.push((record.line, e.to_string())),
+                }
+                continue;
+            }
+
+            // Generated identifiers continue the sequence of the items
+            // created by earlier rows, which explicit ones must not reuse.
+            let id = record
+                .id
+                .clone()
+                .unwrap_or_else(|| item_type.suggest_next_id_among(graph.items().chain(&created)));
+            if let Some(taken) = created.iter().find(|item| item.id.as_str() == id) {
+                let file = taken.source.full_path();
+                result
+                    .failed
+                    .push((record.line, InitError::DuplicateId { id, file }.to_string()));
+                continue;
+            }
+            let file = into.join(format!("{}.md", id));
+            let init = InitOptions::new(file, type_config(record, item_type))
+                .with_id(id)
+                .maybe_name(record.name.clone())
+                .maybe_description(record.description.clone());
+
+            match InitService::new().init(Some(graph), &init) {
+                Ok(init) => match read_created(&init.file, into) {
+                    Ok(item) => {
+                        created.push(item);
+                        result.created.push(init);
+                    }
+                    Err(e) => result.failed.push((
+                        record.line,
+                        format!(
+                            "{} was written but cannot be read back: {e}",
+                            init.file.display()
+                        ),
+                    )),
+                },
+                Err(e) => result.failed.push((record.line, e.to_string())),
+            }
+        }
+
+        result
+    }
+
+    /// Applies a row to an existing item; returns `None` when nothing changes.
+    fn update(
+        &self,
+        item: &Item,
+        item_type: ItemType,
+        opts: EditOptions,
+    ) -> Result<Option<EditResult>, SaraError> {
+        if item.item_type != item_type {
+            return Err(SaraError::EditFailed(format!(
+                "{} is a {}, not a {}",
+                item.id,
+                item.item_type.display_name(),
+                item_type.display_name()
+            )));
+        }
+
+        let service = EditService::new();
+        let context = as_read_from_sheet(service.get_item_context(item));
+        let values = service.merge_values(opts, &context);
+        let changes: Vec<_> = service
+            .build_change_summary(&context, &values)
+            .into_iter()
+            .filter(|c| c.is_changed())
+            .collect();
+        if changes.is_empty() {
+            return Ok(None);
+        }
+
+        service.apply_changes(&context.id, item_type, &values, &context.file_path)?;
+        Ok(Some(EditResult {
+            item_id: context.id,
+            file_path: context.file_path,
+            changes,
+        }))
+    }
+}
+
+/// Returns the values of an item as a sheet holds them.
+///
+/// Cells are trimmed on export and import, so comparing a row with the
+/// untrimmed values would report a change wherever a value ends with a line
+/// break, as folded YAML scalars do, even in a sheet nobody edited.
+fn as_read_from_sheet(mut context: ItemContext) -> ItemContext {
+    context.name = context.name.trim().to_string();
+    context.description = context.description.map(|d| d.trim().to_string());
+    let attributes: Vec<_> = context
+        .attributes
+        .iter()
+        .map(|(name, value)| (name.clone(), trimmed(value)))
+        .collect();
+    for (name, value) in attributes {
+        context.attributes.insert(name, value);
+    }
+    context
+}
+
+/// Trims the text of a field value, entry by entry for a list.
+fn trimmed(value: &FieldValue) -> FieldValue {
+    match value {
+        FieldValue::Text(text) => FieldValue::Text(text.trim().to_string()),
+        FieldValue::List(values) => FieldValue::List(values.iter().map(trimmed).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Reads back the item a row created in `file`.
+fn read_created(file: &Path, into: &Path) -> Result<Item, SaraError> {
+    let content = fs::read_to_string(file)?;
+    parse_metadata(
+        &content,
+        file,
+        into,
+        InputFormat::Markdown,
+        &ParseContext::default(),
+    )
+}
+
+/// Returns the input of each field of a row, by field name.
+fn field_inputs(
+    record: &CsvRecord,
+    item_type: ItemType,
+) -> impl Iterator<Item = (String, FieldInput)> + '_ {
+    record.fields.iter().map(move |(name, entries)| {
+        let is_list = item_type
+            .declared_field(name)
+            .is_some_and(|f| matches!(f.field_type, FieldType::List(_)));
+        let input = if is_list {
+            FieldInput::List(entries.clone())
+        } else {
+            FieldInput::Text(entries.join(" "))
+        };
+        (name.clone(), input)
+    })
+}
+
+/// Converts a row to the options editing its item.
+fn edit_options(record: &CsvRecord, item_type: ItemType) -> EditOptions {
+    let mut opts = EditOptions::new(record.id.clone().unwrap_or_default())
+        .maybe_name(record.name.clone())
+        .maybe_description(record.description.clone());
+    for (name, input) in field_inputs(record, item_type) {
+        opts = opts.with_field(name, input);
+    }
+    for (relation, targets) in &record.relations {
+        opts = opts.with_relation(*relation, targets.clone());
+    }
+    opts
+}
+
+/// Converts a row to the configuration initializing a new item.
+fn type_config(record: &CsvRecord, item_type: ItemType) -> TypeConfig {
+    let mut config = TypeConfig::new(item_type);
+    for (name, input) in field_inputs(record, item_type) {
+        config = config.field(name, input);
+    }
+    for (relation, targets) in &record.relations {
+        config = config.relation(relation.as_str(), targets.clone());
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    use crate::config::RepositoryConfig;
+    use crate::graph::KnowledgeGraphBuilder;
+    use crate::interchange::csv;
+    use crate::model::SourceLocation;
+    use crate::schema::builtin;
+    use crate::service::load_graph;
+    use crate::test_utils::create_test_item;
+
+    #[test]
+    fn test_write_skips_existing_documents_unless_forced() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut item = create_test_item("SOL-001", builtin::SOLUTION);
+        item.source = SourceLocation::new(temp_dir.path(), "imported/SOL-001.md");
+        let path = temp_dir.path().join("imported/SOL-001.md");
+
+        let result = ImportService::new()
+            .write(std::slice::from_ref(&item), false)
+            .unwrap();
+        assert_eq!(result.written, std::slice::from_ref(&path));
+        let content = fs::read_to_string(&path).unwrap();
+        let parsed = parse_metadata(
+            &content,
+            &path,
+            temp_dir.path(),
+            InputFormat::Markdown,
+            &ParseContext::default(),
+        )
+        .unwrap();
+        assert_eq!(parsed.name, item.name);
+
+        fs::write(&path, "edited").unwrap();
+        let result = ImportService::new()
+            .write(std::slice::from_ref(&item), false)
+            .unwrap();
+        assert_eq!(result.skipped, std::slice::from_ref(&path));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited");
+
+        ImportService::new()
+            .write(std::slice::from_ref(&item), true)
+            .unwrap();
+        assert_ne!(fs::read_to_string(&path).unwrap(), "edited");
+    }
+
+    fn write_adr(dir: &Path, id: &str, status: &str) {
+        fs::write(
+            dir.join(format!("{id}.md")),
+            format!(
+                "---\nid: \"{id}\"\ntype: architecture_decision_record\nname: \"Decision {id}\"\n\
+                 status: {status}\ndeciders:\n  - \"Alice\"\n---\n# Decision\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_apply_records_creates_updates_and_reports_rows() {
+        let temp_dir = TempDir::new().unwrap();
+        write_adr(temp_dir.path(), "ADR-001", "proposed");
+        write_adr(temp_dir.path(), "ADR-002", "accepted");
+        let repositories = RepositoryConfig {
+            paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        };
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+
+        let sheet = "id,name,status,deciders,supersedes\n\
+                     ADR-001,Decision ADR-001,accepted,Alice,\n\
+                     ADR-002,Decision ADR-002,accepted,Alice,\n\
+                     ,First new,proposed,Bob; Carol,ADR-001\n\
+                     ,Second new,,,\n\
+                     ADR-009,Bad status,rejected,,\n";
+        let records = csv::import(sheet, builtin::ARCHITECTURE_DECISION_RECORD).unwrap();
+        let result = ImportService::new().apply_records(
+            &graph,
+            builtin::ARCHITECTURE_DECISION_RECORD,
+            &records,
+            temp_dir.path(),
+        );
+
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].item_id, "ADR-001");
+        assert!(
+            fs::read_to_string(temp_dir.path().join("ADR-001.md"))
+                .unwrap()
+                .contains("status: accepted")
+        );
+        assert_eq!(result.unchanged, ["ADR-002"]);
+
+        let created: Vec<&str> = result.created.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(created, ["ADR-003", "ADR-004"]);
+        let content = fs::read_to_string(temp_dir.path().join("ADR-003.md")).unwrap();
+        let adr = parse_metadata(
+            &content,
+            Path::new("ADR-003.md"),
+            temp_dir.path(),
+            InputFormat::Markdown,
+            &ParseContext::default(),
+        )
+        .unwrap();
+        assert_eq!(adr.name, "First new");
+        assert_eq!(
+            adr.attributes.get("deciders"),
+            Some(&FieldValue::text_list(["Bob", "Carol"]))
+        );
+        assert_eq!(adr.relationships.len(), 1);
+
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, 6);
+        assert!(result.failed[0].1.contains("rejected"));
+        assert!(!temp_dir.path().join("ADR-009.md").exists());
+    }
+
+    #[test]
+    fn test_apply_records_of_an_exported_sheet_changes_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let document = "---\nid: \"ADR-001\"\ntype: architecture_decision_record\n\
+                        name: \"Decision\"\ndescription: >\n  Folded\n  text\n\
+                        status: proposed\ndeciders:\n  - \"Alice\"\n---\n# Decision\n";
+        fs::write(temp_dir.path().join("ADR-001.md"), document).unwrap();
+        let repositories = RepositoryConfig {
+            paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        };
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+
+        let sheet = csv::export(&graph, builtin::ARCHITECTURE_DECISION_RECORD);
+        let records = csv::import(&sheet, builtin::ARCHITECTURE_DECISION_RECORD).unwrap();
+        let result = ImportService::new().apply_records(
+            &graph,
+            builtin::ARCHITECTURE_DECISION_RECORD,
+            &records,
+            temp_dir.path(),
+        );
+
+        assert!(result.updated.is_empty());
+        assert!(result.failed.is_empty());
+        assert_eq!(result.unchanged, ["ADR-001"]);
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("ADR-001.md")).unwrap(),
+            document
+        );
+    }
+
+    #[test]
+    fn test_apply_records_rejects_an_id_created_by_an_earlier_row() {
+        let temp_dir = TempDir::new().unwrap();
+        let graph = KnowledgeGraphBuilder::new().build().unwrap();
+
+        let sheet = "id,name,status,deciders,supersedes\n\
+                     ADR-005,First,proposed,Alice,\n\
+                     ,Second,proposed,Alice,\n\
+                     ADR-005,Again,proposed,Alice,\n";
+        let records = csv::import(sheet, builtin::ARCHITECTURE_DECISION_RECORD).unwrap();
+        let result = ImportService::new().apply_records(
+            &graph,
+            builtin::ARCHITECTURE_DECISION_RECORD,
+            &records,
+            temp_dir.path(),
+        );
+
+        let created: Vec<&str> = result.created.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(created, ["ADR-005", "ADR-006"]);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0, 4);
+        assert!(result.failed[0].1.contains("ADR-005 is already used"));
+    }
+}
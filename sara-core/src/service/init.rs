@@ -11,8 +11,10 @@ use indexmap::IndexMap;
 use crate::generator::{self, OutputFormat};
 use crate::graph::KnowledgeGraph;
 use crate::model::{FieldValue, ItemBuilder, ItemId, ItemType, RelationshipType, SourceLocation};
-use crate::parser::{extract_name_from_content, has_frontmatter};
-use crate::schema::{self, FieldDef, FieldType, RelationDirection};
+use crate::parser::{
+    InputFormat, extract_title, has_metadata, has_toml_frontmatter, update_metadata,
+};
+use crate::schema::{self, FieldDef, FieldType, RelationDirection, Schema};
 
 /// Options for initializing a new item or adding frontmatter to an existing file.
 #[derive(Debug, Clone)]
@@ -95,7 +97,7 @@ impl InitOptions {
 /// Value provided for one declared field when initializing an item.
 #[derive(Debug, Clone)]
 pub enum FieldInput {
-    /// A single value (text, enum, date or item-reference fields).
+    /// A single value (any field type but lists).
     Text(String),
     /// A list of values (list fields).
     List(Vec<String>),
@@ -251,7 +253,7 @@ impl InitService {
         // Check for existing frontmatter
         if opts.file.exists() && !opts.force {
             let content = fs::read_to_string(&opts.file)?;
-            if has_frontmatter(&content) {
+            if has_metadata(&content, input_format(&opts.file)) {
                 return Err(InitError::FrontmatterExists(opts.file.clone()));
             }
         }
@@ -288,7 +290,7 @@ impl InitService {
         schema::item_type_def(type_config.item_type().as_str()).is_some_and(|def| {
             def.fields.iter().any(|field| {
                 field.required
-                    && matches!(field.field_type, FieldType::Text)
+                    && matches!(field.field_type, FieldType::Text { .. })
                     && !type_config.fields.contains_key(&field.name)
             })
         })
@@ -335,7 +337,7 @@ impl InitService {
 
         if opts.file.exists() {
             let content = fs::read_to_string(&opts.file)?;
-            if let Some(name) = extract_name_from_content(&content) {
+            if let Some(name) = extract_title(&content, input_format(&opts.file)) {
                 return Ok(name);
             }
         }
@@ -356,6 +358,7 @@ impl InitService {
             repository: PathBuf::new(),
             file_path: opts.file.clone(),
             git_ref: None,
+            lines: None,
         };
 
         let mut builder = ItemBuilder::new()
@@ -424,13 +427,22 @@ impl InitService {
         item: &crate::model::Item,
     ) -> Result<bool, InitError> {
         let content = fs::read_to_string(&opts.file)?;
-        let frontmatter = generator::generate_metadata(item, OutputFormat::Markdown);
-
-        let (new_content, replaced) = if has_frontmatter(&content) && opts.force {
-            let body = remove_frontmatter(&content);
-            (format!("{}\n{}", frontmatter, body), true)
-        } else {
-            (format!("{}\n{}", frontmatter, content), false)
+        let format = input_format(&opts.file);
+        let frontmatter =
+            super::document_metadata(item, &opts.file, &content, generator::generate_metadata);
+
+        let (new_content, replaced) = match format {
+            InputFormat::Markdown if has_metadata(&content, format) && opts.force => {
+                let body = remove_frontmatter(&content);
+                (format!("{}\n{}", frontmatter, body), true)
+            }
+            InputFormat::Markdown => (format!("{}\n{}", frontmatter, content), false),
+            // Header entries live after the title and next to foreign ones;
+            // item files are metadata only.
+            InputFormat::AsciiDoc | InputFormat::Rst | InputFormat::Yaml | InputFormat::Toml => (
+                update_metadata(&content, &frontmatter, format),
+                has_metadata(&content, format),
+            ),
         };
 
         fs::write(&opts.file, new_content)?;
@@ -443,7 +455,7 @@ impl InitService {
         opts: &InitOptions,
         item: &crate::model::Item,
     ) -> Result<(), InitError> {
-        let document = generator::generate_document(item, OutputFormat::Markdown);
+        let document = generator::generate_document(item, output_format(&opts.file));
 
         if let Some(parent) = opts.file.parent() {
             fs::create_dir_all(parent)?;
@@ -454,8 +466,23 @@ impl InitService {
     }
 }
 
-/// Removes YAML frontmatter delimited by `---` from `content`.
+/// Returns the input format of a document path, Markdown by default.
+fn input_format(path: &Path) -> InputFormat {
+    InputFormat::from_path(path).unwrap_or(InputFormat::Markdown)
+}
+
+/// Returns the output format of a document path, Markdown by default.
+fn output_format(path: &Path) -> OutputFormat {
+    OutputFormat::from_path(path).unwrap_or(OutputFormat::Markdown)
+}
+
+/// Removes frontmatter delimited by `---` (or `+++` for TOML) from `content`.
 fn remove_frontmatter(content: &str) -> &str {
+    let delimiter = if has_toml_frontmatter(content) {
+        "+++"
+    } else {
+        "---"
+    };
     let mut in_frontmatter = false;
     let mut byte_offset = 0;
 
@@ -463,7 +490,7 @@ fn remove_frontmatter(content: &str) -> &str {
         // Advance past the line and its newline delimiter.
         let line_end = byte_offset + line.len() + 1;
 
-        if line.trim() == "---" {
+        if line.trim() == delimiter {
             if !in_frontmatter {
                 in_frontmatter = true;
             } else {
@@ -538,12 +565,18 @@ pub(super) fn init_field_value(field: &FieldDef, input: Option<&FieldInput>) ->
 }
 
 /// Wraps a raw string as a value of the given scalar field type.
+///
+/// Typed values that do not parse (a placeholder for a number, say) are
+/// kept as text, for the author to fix.
 fn scalar_field_value(value: &str, field_type: &FieldType) -> FieldValue {
     match field_type {
         FieldType::ItemRef => FieldValue::ItemRef(ItemId::new_unchecked(value)),
         FieldType::Date => FieldValue::Date(value.to_string()),
         FieldType::Enum { .. } => FieldValue::Enum(value.to_string()),
-        FieldType::Text | FieldType::List(_) => FieldValue::Text(value.to_string()),
+        FieldType::Text { .. } | FieldType::List(_) => FieldValue::Text(value.to_string()),
+        typed => typed
+            .parse_value(value)
+            .unwrap_or_else(|_| FieldValue::Text(value.to_string())),
     }
 }
 
@@ -563,10 +596,18 @@ fn same_file(left: &Path, right: &Path) -> bool {
 /// type's id prefix in any case (`UC`), for every type the active schema
 /// knows.
 pub fn parse_item_type(type_str: &str) -> Option<ItemType> {
+    parse_item_type_in(type_str, schema::active())
+}
+
+/// Parses an item type string into an [`ItemType`] of `schema`, accepting
+/// the forms [`parse_item_type`] does.
+pub fn parse_item_type_in(type_str: &str, schema: &Schema) -> Option<ItemType> {
     let lower = type_str.to_lowercase();
-    ItemType::all().into_iter().find(|item_type| {
+    ItemType::all_in(schema).into_iter().find(|item_type| {
         let id = item_type.as_str();
-        id == lower || id.replace('_', "") == lower || item_type.prefix().to_lowercase() == lower
+        id == lower
+            || id.replace('_', "") == lower
+            || item_type.prefix_in(schema).to_lowercase() == lower
     })
 }
 
@@ -611,6 +652,7 @@ mod tests {
                 repository: file.parent().unwrap().to_path_buf(),
                 file_path: PathBuf::from(file.file_name().unwrap()),
                 git_ref: None,
+                lines: None,
             })
             .build()
             .unwrap();
@@ -721,6 +763,9 @@ mod tests {
         let content = "---\nid: test\n---\n# Body";
         let body = remove_frontmatter(content);
         assert_eq!(body.trim(), "# Body");
+
+        let content = "+++\nid = \"test\"\n+++\n# Body";
+        assert_eq!(remove_frontmatter(content).trim(), "# Body");
     }
 
     #[test]
@@ -769,6 +814,56 @@ mod tests {
         assert!(content.contains("# My Document"));
     }
 
+    #[test]
+    fn test_init_asciidoc_and_rst_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("existing.adoc");
+        fs::write(
+            &existing,
+            "= My Document
+:toc:
+
+Some content here.
+",
+        )
+        .unwrap();
+        let created = temp_dir.path().join("new.rst");
+
+        let service = InitService::new();
+        let result = service
+            .init(
+                None,
+                &InitOptions::new(existing.clone(), TypeConfig::new(builtin::SOLUTION))
+                    .with_id("SOL-001"),
+            )
+            .unwrap();
+        assert_eq!(result.name, "My Document");
+        assert!(!result.replaced_frontmatter);
+        assert_eq!(
+            fs::read_to_string(&existing).unwrap(),
+            "= My Document\n:toc:\n:id: SOL-001\n:type: solution\n:name: My Document\n\n\
+             Some content here.\n"
+        );
+
+        service
+            .init(
+                None,
+                &InitOptions::new(created.clone(), TypeConfig::new(builtin::SOLUTION))
+                    .with_id("SOL-002")
+                    .with_name("New"),
+            )
+            .unwrap();
+        let content = fs::read_to_string(&created).unwrap();
+        assert!(content.starts_with("New\n===\n\n:id: SOL-002\n"));
+
+        let rerun =
+            InitOptions::new(created, TypeConfig::new(builtin::SOLUTION)).with_id("SOL-003");
+        assert!(matches!(
+            service.init(None, &rerun),
+            Err(InitError::FrontmatterExists(_))
+        ));
+    }
+
     #[test]
     fn test_init_existing_file_with_frontmatter_no_force() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,572 @@
+//! Three-way merge of documents, for use as a Git merge driver.
+//!
+//! The metadata of the three versions is parsed and merged field by field:
+//! a field changed on one side only takes that change, and relation lists
+//! merge the links each side added or removed. The merged item is written
+//! back with the generator. Bodies, and documents that cannot be parsed as a
+//! single item, merge line by line. Conflict markers are left only where
+//! both sides changed the same thing differently.
+
+use std::path::Path;
+
+use crate::generator;
+use crate::model::{Item, ItemAttributes, Relationship};
+use crate::parser::{InputFormat, ParseContext, body_start, parse_metadata, update_metadata};
+
+/// Length of the conflict markers, as written by Git.
+const MARKER_SIZE: usize = 7;
+
+/// Outcome of a three-way merge of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeResult {
+    /// The merged document, with conflict markers around every conflict.
+    pub content: String,
+    /// What both sides changed differently: field or relation names,
+    /// `body`, or `document` when the document was merged line by line.
+    pub conflicts: Vec<String>,
+}
+
+impl MergeResult {
+    /// Returns true when the merge left no conflict.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merges the changes from `base` to `ours` and from `base` to `theirs`.
+///
+/// `path` is the path of the document, whose extension tells its format.
+/// Metadata is merged semantically when all three versions parse as a
+/// single item; the merged document keeps the layout of `ours`, with its
+/// metadata regenerated.
+#[must_use]
+pub fn merge_document(base: &str, ours: &str, theirs: &str, path: &Path) -> MergeResult {
+    let Some(format) = InputFormat::from_path(path) else {
+        return merge_text(base, ours, theirs);
+    };
+    let ctx = ParseContext::default();
+    let parse = |content: &str| parse_metadata(content, path, Path::new(""), format, &ctx).ok();
+    let (Some(base_item), Some(ours_item), Some(theirs_item)) =
+        (parse(base), parse(ours), parse(theirs))
+    else {
+        return merge_text(base, ours, theirs);
+    };
+
+    let mut merge = ItemMerge::new(&ours_item);
+    merge.merge_item(&base_item, &ours_item, &theirs_item);
+
+    let (body, body_clean) = diff3(
+        &base[body_start(base, format)..],
+        &ours[body_start(ours, format)..],
+        &theirs[body_start(theirs, format)..],
+    );
+    if !body_clean {
+        merge.conflicts.push("body".to_string());
+    }
+
+    let render = |item: &Item| {
+        let metadata = super::document_metadata(item, path, ours, generator::regenerate_metadata);
+        match format {
+            // Rewritten directly, so the body keeps its trailing newline.
+            InputFormat::Markdown => format!("{}\n{body}", metadata.trim_end()),
+            _ => {
+                let document = format!("{}{body}", &ours[..body_start(ours, format)]);
+                update_metadata(&document, &metadata, format)
+            }
+        }
+    };
+    let content = if merge.conflicts.is_empty() {
+        render(&merge.ours)
+    } else {
+        two_way(&render(&merge.ours), &render(&merge.theirs))
+    };
+
+    MergeResult {
+        content,
+        conflicts: merge.conflicts,
+    }
+}
+
+/// Merges three versions of a text line by line.
+fn merge_text(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let (content, clean) = diff3(base, ours, theirs);
+    MergeResult {
+        content,
+        conflicts: if clean {
+            Vec::new()
+        } else {
+            vec!["document".to_string()]
+        },
+    }
+}
+
+/// Result of merging one value changed on both sides.
+enum Merged<T> {
+    /// Both changes agree: the merged value.
+    Clean(T),
+    /// Both sides changed the value differently: ours, then theirs.
+    Conflict(T, T),
+}
+
+/// Three-way merge of a single value.
+fn merge3<T: PartialEq + Clone>(base: &T, ours: &T, theirs: &T) -> Merged<T> {
+    if ours == theirs || theirs == base {
+        Merged::Clean(ours.clone())
+    } else if ours == base {
+        Merged::Clean(theirs.clone())
+    } else {
+        Merged::Conflict(ours.clone(), theirs.clone())
+    }
+}
+
+/// An item being merged, as resolved toward each side.
+///
+/// Both resolutions are equal unless a conflict was found; the document is
+/// then rendered from each and the lines that differ marked as conflicting.
+struct ItemMerge {
+    ours: Item,
+    theirs: Item,
+    conflicts: Vec<String>,
+}
+
+impl ItemMerge {
+    fn new(ours: &Item) -> Self {
+        let mut item = ours.clone();
+        item.relationships.clear();
+        item.attributes = ItemAttributes::new();
+        Self {
+            ours: item.clone(),
+            theirs: item,
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Merges one value, recording `name` when it conflicts.
+    fn merge<T: PartialEq + Clone>(
+        &mut self,
+        name: &str,
+        base: &T,
+        ours: &T,
+        theirs: &T,
+        mut set: impl FnMut(&mut Item, T),
+    ) {
+        match merge3(base, ours, theirs) {
+            Merged::Clean(value) => {
+                set(&mut self.ours, value.clone());
+                set(&mut self.theirs, value);
+            }
+            Merged::Conflict(ours, theirs) => {
+                set(&mut self.ours, ours);
+                set(&mut self.theirs, theirs);
+                if !self.conflicts.iter().any(|conflict| conflict == name) {
+                    self.conflicts.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    /// Merges every part of the item written to its metadata.
+    fn merge_item(&mut self, base: &Item, ours: &Item, theirs: &Item) {
+        self.merge("id", &base.id, &ours.id, &theirs.id, |item, id| {
+            item.id = id
+        });
+        self.merge(
+            "type",
+            &base.item_type,
+            &ours.item_type,
+            &theirs.item_type,
+            |item, item_type| item.item_type = item_type,
+        );
+        self.merge(
+            "name",
+            &base.name,
+            &ours.name,
+            &theirs.name,
+            |item, name| {
+                item.name = name;
+            },
+        );
+        self.merge(
+            "description",
+            &base.description,
+            &ours.description,
+            &theirs.description,
+            |item, description| item.description = description,
+        );
+
+        let names = keys(
+            [&ours.attributes, &theirs.attributes, &base.attributes]
+                .map(|attributes| attributes.iter().map(|(name, _)| name.clone())),
+        );
+        for name in names {
+            let value = |item: &Item| item.attributes.get(&name).cloned();
+            self.merge(
+                &name,
+                &value(base),
+                &value(ours),
+                &value(theirs),
+                |item, value| {
+                    if let Some(value) = value {
+                        item.attributes.insert(name.clone(), value);
+                    }
+                },
+            );
+        }
+
+        // A link merges as its presence along with its attributes, so that
+        // the links each side added or removed merge independently.
+        let links = keys(
+            [
+                &ours.relationships,
+                &theirs.relationships,
+                &base.relationships,
+            ]
+            .map(|relationships| {
+                relationships
+                    .iter()
+                    .map(|link| (link.relationship_type, link.to.clone()))
+            }),
+        );
+        for (relationship_type, to) in links {
+            let attributes = |item: &Item| {
+                item.relationships
+                    .iter()
+                    .find(|link| link.relationship_type == relationship_type && link.to == to)
+                    .map(|link| link.attributes.clone())
+            };
+            self.merge(
+                relationship_type.as_str(),
+                &attributes(base),
+                &attributes(ours),
+                &attributes(theirs),
+                |item, attributes| {
+                    if let Some(attributes) = attributes {
+                        item.relationships.push(
+                            Relationship::new(to.clone(), relationship_type)
+                                .with_attributes(attributes),
+                        );
+                    }
+                },
+            );
+        }
+    }
+}
+
+/// Returns the distinct keys of several sequences, in order of first
+/// appearance.
+fn keys<K: PartialEq, const N: usize>(sequences: [impl Iterator<Item = K>; N]) -> Vec<K> {
+    let mut keys = Vec::new();
+    for key in sequences.into_iter().flatten() {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Merges three versions of a text line by line, as `git merge-file` does.
+///
+/// Returns the merged text and whether it is free of conflicts.
+fn diff3(base: &str, ours: &str, theirs: &str) -> (String, bool) {
+    let base: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs: Vec<&str> = theirs.split_inclusive('\n').collect();
+    let in_ours = matching_lines(&base, &ours);
+    let in_theirs = matching_lines(&base, &theirs);
+
+    let mut merged = String::new();
+    let mut clean = true;
+    let (mut o, mut a, mut b) = (0, 0, 0);
+    loop {
+        // Lines unchanged on both sides.
+        while o < base.len() && in_ours[o] == Some(a) && in_theirs[o] == Some(b) {
+            merged.push_str(base[o]);
+            (o, a, b) = (o + 1, a + 1, b + 1);
+        }
+        if o == base.len() && a == ours.len() && b == theirs.len() {
+            break;
+        }
+
+        // The changed chunk runs up to the next line both sides kept.
+        let (next_o, next_a, next_b) = (o..base.len())
+            .find_map(|k| Some((k, in_ours[k]?, in_theirs[k]?)))
+            .unwrap_or((base.len(), ours.len(), theirs.len()));
+        let (base_chunk, ours_chunk, theirs_chunk) =
+            (&base[o..next_o], &ours[a..next_a], &theirs[b..next_b]);
+        if ours_chunk == base_chunk {
+            merged.extend(theirs_chunk.iter().copied());
+        } else if theirs_chunk == base_chunk || ours_chunk == theirs_chunk {
+            merged.extend(ours_chunk.iter().copied());
+        } else {
+            push_conflict(&mut merged, ours_chunk, theirs_chunk);
+            clean = false;
+        }
+        (o, a, b) = (next_o, next_a, next_b);
+    }
+    (merged, clean)
+}
+
+/// Marks every difference between two texts as a conflict.
+fn two_way(ours: &str, theirs: &str) -> String {
+    let ours: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs: Vec<&str> = theirs.split_inclusive('\n').collect();
+    let in_theirs = matching_lines(&ours, &theirs);
+
+    let mut merged = String::new();
+    let (mut a, mut b) = (0, 0);
+    while a < ours.len() || b < theirs.len() {
+        let (next_a, next_b) = (a..ours.len())
+            .find_map(|k| Some((k, in_theirs[k]?)))
+            .unwrap_or((ours.len(), theirs.len()));
+        if next_a > a || next_b > b {
+            push_conflict(&mut merged, &ours[a..next_a], &theirs[b..next_b]);
+        }
+        if next_a < ours.len() {
+            merged.push_str(ours[next_a]);
+        }
+        (a, b) = (next_a + 1, next_b + 1);
+    }
+    merged
+}
+
+/// Appends a conflict between two chunks of lines, between Git's markers.
+fn push_conflict(merged: &mut String, ours: &[&str], theirs: &[&str]) {
+    let mut push_lines = |marker: char, label: &str, lines: &[&str]| {
+        merged.extend(std::iter::repeat_n(marker, MARKER_SIZE));
+        merged.push_str(label);
+        merged.push('\n');
+        for line in lines {
+            merged.push_str(line);
+        }
+        if !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+    };
+    push_lines('<', " ours", ours);
+    push_lines('=', "", theirs);
+    merged.extend(std::iter::repeat_n('>', MARKER_SIZE));
+    merged.push_str(" theirs\n");
+}
+
+/// Matches the lines of `from` to the lines of `to` along a longest common
+/// subsequence: entry `i` holds the index in `to` of line `i` of `from`, if
+/// kept.
+fn matching_lines(from: &[&str], to: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; from.len()];
+
+    // Lines shared at both ends match without searching.
+    let prefix = from.iter().zip(to).take_while(|(x, y)| x == y).count();
+    let suffix = from[prefix..]
+        .iter()
+        .rev()
+        .zip(to[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    for (i, slot) in matches.iter_mut().enumerate().take(prefix) {
+        *slot = Some(i);
+    }
+    for i in 0..suffix {
+        matches[from.len() - 1 - i] = Some(to.len() - 1 - i);
+    }
+
+    let from_middle = &from[prefix..from.len() - suffix];
+    let to_middle = &to[prefix..to.len() - suffix];
+    let (n, m) = (from_middle.len(), to_middle.len());
+    // lengths[i][j]: length of the longest common subsequence of the lines
+    // from `i` and from `j`.
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if from_middle[i] == to_middle[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if from_middle[i] == to_middle[j] {
+            matches[prefix + i] = Some(prefix + j);
+            (i, j) = (i + 1, j + 1);
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    const BASE: &str = r#"---
+id: "SYSREQ-001"
+type: system_requirement
+name: "Response Time"
+specification: "The system SHALL answer within 2 seconds."
+derives_from:
+  - "SCEN-001"
+is_satisfied_by:
+  - "SYSARCH-001"
+---
+# System Requirement: Response Time
+
+## Rationale
+
+Users leave slow pages.
+
+## Notes
+
+Measured at the gateway.
+"#;
+
+    fn path() -> PathBuf {
+        PathBuf::from("docs/SYSREQ-001.md")
+    }
+
+    fn item(content: &str) -> Item {
+        parse_metadata(
+            content,
+            &path(),
+            Path::new(""),
+            InputFormat::Markdown,
+            &ParseContext::default(),
+        )
+        .unwrap()
+    }
+
+    fn targets(item: &Item, relation: &str) -> Vec<String> {
+        item.relationships
+            .iter()
+            .filter(|link| link.relationship_type.as_str() == relation)
+            .map(|link| link.to.as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_links_added_on_both_sides_are_kept() {
+        let ours = BASE.replace(
+            "  - \"SYSARCH-001\"\n",
+            "  - \"SYSARCH-001\"\n  - \"SYSARCH-002\"\n",
+        );
+        let theirs = BASE.replace(
+            "  - \"SYSARCH-001\"\n",
+            "  - \"SYSARCH-001\"\n  - \"SYSARCH-003\"\n",
+        );
+
+        let result = merge_document(BASE, &ours, &theirs, &path());
+
+        assert!(result.is_clean(), "{}", result.content);
+        assert_eq!(
+            targets(&item(&result.content), "is_satisfied_by"),
+            ["SYSARCH-001", "SYSARCH-002", "SYSARCH-003"]
+        );
+    }
+
+    #[test]
+    fn test_link_removed_on_one_side_is_removed() {
+        let ours = BASE.replace(
+            "  - \"SYSARCH-001\"\n",
+            "  - \"SYSARCH-001\"\n  - \"SYSARCH-002\"\n",
+        );
+        let theirs = BASE.replace("is_satisfied_by:\n  - \"SYSARCH-001\"\n", "");
+
+        let result = merge_document(BASE, &ours, &theirs, &path());
+
+        assert!(result.is_clean(), "{}", result.content);
+        assert_eq!(
+            targets(&item(&result.content), "is_satisfied_by"),
+            ["SYSARCH-002"]
+        );
+    }
+
+    #[test]
+    fn test_independent_field_and_body_changes_merge() {
+        let ours = BASE
+            .replace("\"Response Time\"", "\"Authentication Response Time\"")
+            .replace("Users leave slow pages.", "Users abandon slow logins.");
+        let theirs = BASE
+            .replace("2 seconds", "1 second")
+            .replace("Measured at the gateway.", "Measured at the load balancer.");
+
+        let result = merge_document(BASE, &ours, &theirs, &path());
+
+        assert!(result.is_clean(), "{}", result.content);
+        let merged = item(&result.content);
+        assert_eq!(merged.name, "Authentication Response Time");
+        assert_eq!(
+            merged
+                .attributes
+                .get("specification")
+                .unwrap()
+                .as_text()
+                .unwrap(),
+            "The system SHALL answer within 1 second."
+        );
+        assert_eq!(targets(&merged, "derives_from"), ["SCEN-001"]);
+        assert!(result.content.contains("Users abandon slow logins."));
+        assert!(result.content.contains("Measured at the load balancer."));
+        assert!(result.content.ends_with("load balancer.\n"));
+    }
+
+    #[test]
+    fn test_field_changed_differently_on_both_sides_conflicts() {
+        let ours = BASE.replace("2 seconds", "1 second");
+        let theirs = BASE.replace("2 seconds", "3 seconds").replace(
+            "  - \"SYSARCH-001\"\n",
+            "  - \"SYSARCH-001\"\n  - \"SYSARCH-003\"\n",
+        );
+
+        let result = merge_document(BASE, &ours, &theirs, &path());
+
+        assert_eq!(result.conflicts, ["specification"]);
+        assert!(result.content.contains(
+            "<<<<<<< ours\n\
+             specification: \"The system SHALL answer within 1 second.\"\n\
+             =======\n\
+             specification: \"The system SHALL answer within 3 seconds.\"\n\
+             >>>>>>> theirs\n"
+        ));
+        assert_eq!(result.content.matches("<<<<<<<").count(), 1);
+        assert!(result.content.contains("\"SYSARCH-003\""));
+    }
+
+    #[test]
+    fn test_body_changed_differently_on_both_sides_conflicts() {
+        let ours = BASE.replace("Users leave slow pages.", "Users abandon slow logins.");
+        let theirs = BASE.replace("Users leave slow pages.", "Slow pages cost sales.");
+
+        let result = merge_document(BASE, &ours, &theirs, &path());
+
+        assert_eq!(result.conflicts, ["body"]);
+        assert!(result.content.contains(
+            "<<<<<<< ours\nUsers abandon slow logins.\n=======\nSlow pages cost sales.\n>>>>>>> theirs\n"
+        ));
+        assert!(result.content.contains("Measured at the gateway."));
+    }
+
+    #[test]
+    fn test_unparseable_document_merges_line_by_line() {
+        let base = "# Notes\n\nfirst\n\nsecond\n";
+        let ours = "# Notes\n\nfirst, edited\n\nsecond\n";
+        let theirs = "# Notes\n\nfirst\n\nsecond, edited\n";
+
+        let result = merge_document(base, ours, theirs, &path());
+
+        assert!(result.is_clean());
+        assert_eq!(
+            result.content,
+            "# Notes\n\nfirst, edited\n\nsecond, edited\n"
+        );
+
+        let theirs = "# Notes\n\nfirst, rewritten\n\nsecond\n";
+        let result = merge_document(base, ours, theirs, &path());
+        assert_eq!(result.conflicts, ["document"]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Line-level view of metadata blocks for in-place rewriting.
+//!
+//! Services that rewrite metadata (renaming an identifier, migrating to a
+//! new schema) edit the lines of a block in place, so comments, quoting and
+//! field order survive. The block may be YAML or TOML frontmatter, a `sara`
+//! block, AsciiDoc or reStructuredText `:key: value` entries, or a
+//! standalone item file holding a list of items.
+
+use std::ops::Range;
+
+use crate::model::FIELD_ID;
+
+/// One line of a metadata block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct MetadataLine<'a> {
+    /// The line, line ending included.
+    pub text: &'a str,
+    /// Key the value of the line belongs to: the key declared on the line,
+    /// or the key of the list the line continues.
+    pub key: Option<&'a str>,
+    /// Key of the entry a nested mapping belongs to, e.g. the relation of a
+    /// link written as `- id: ...` with attributes.
+    pub parent: Option<&'a str>,
+    /// Byte range of the key within the line, when the line declares it.
+    pub key_range: Option<Range<usize>>,
+    /// Offset of the value within the line, when the line holds one.
+    pub value_start: Option<usize>,
+    /// Whether the line starts a new item of a list of items (`- id: ...` at
+    /// the top level of a YAML list, `[[items]]` in TOML).
+    pub starts_item: bool,
+}
+
+/// Splits a metadata block into lines, tracking the key of each value.
+pub(super) fn metadata_lines(block: &str) -> Vec<MetadataLine<'_>> {
+    // Keys of the enclosing entries, with the column of each key.
+    let mut keys: Vec<(usize, &str)> = Vec::new();
+    block
+        .split_inclusive('\n')
+        .map(|text| {
+            let mut line = MetadataLine {
+                text,
+                key: None,
+                parent: None,
+                key_range: None,
+                value_start: None,
+                starts_item: false,
+            };
+            let trimmed = text.trim_start();
+            let indent = text.len() - trimmed.len();
+            if let Some(entry) = text.strip_prefix(':') {
+                // AsciiDoc and reStructuredText `:key: value` entries.
+                if let Some((name, _)) = entry.split_once(':') {
+                    keys.clear();
+                    keys.push((0, name.trim()));
+                    line.key_range = Some(1..1 + name.len());
+                    line.value_start = Some(name.len() + 2);
+                }
+            } else if let Some(entry) = trimmed.strip_prefix("- ") {
+                let entry_start = text.len() - entry.len();
+                match key_entry(entry) {
+                    // A mapping opening an entry of a YAML list.
+                    Some((name, value_start)) => {
+                        line.parent = enter(&mut keys, entry_start, Some(name));
+                        line.key_range = Some(entry_start..entry_start + name.len());
+                        line.value_start = Some(entry_start + value_start);
+                        line.starts_item = indent == 0;
+                    }
+                    None => {
+                        enter(&mut keys, entry_start, None);
+                        line.value_start = Some(entry_start);
+                    }
+                }
+            } else if let Some((name, value_start)) = key_entry(trimmed) {
+                line.parent = enter(&mut keys, indent, Some(name));
+                line.key_range = Some(indent..indent + name.len());
+                line.value_start = Some(indent + value_start);
+            } else if trimmed.starts_with(['"', '\'']) {
+                // An element of a multi-line TOML array.
+                line.value_start = Some(indent);
+            } else if trimmed.starts_with('[') {
+                line.starts_item = trimmed.starts_with("[[");
+                keys.clear();
+            }
+
+            if line.value_start.is_some() {
+                line.key = keys.last().map(|&(_, key)| key);
+            }
+            line
+        })
+        .collect()
+}
+
+/// Enters an entry declared at a column, leaving the entries it ends, and
+/// returns the key of the entry it is nested in.
+fn enter<'a>(
+    keys: &mut Vec<(usize, &'a str)>,
+    column: usize,
+    key: Option<&'a str>,
+) -> Option<&'a str> {
+    keys.retain(|&(c, _)| c < column);
+    let parent = keys.last().map(|&(_, key)| key);
+    keys.extend(key.map(|key| (column, key)));
+    parent
+}
+
+/// Splits a `key: value` (YAML) or `key = value` (TOML) entry, returning
+/// the key and the offset of its value.
+fn key_entry(entry: &str) -> Option<(&str, usize)> {
+    let name_end = entry
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-')))
+        .unwrap_or(entry.len());
+    let name = &entry[..name_end];
+    if name.is_empty() || !entry.starts_with(char::is_alphanumeric) {
+        return None;
+    }
+    let rest = &entry[name_end..];
+    let separator = rest.trim_start();
+    let value_start = if let Some(value) = separator.strip_prefix(':') {
+        if !(value.is_empty() || value.starts_with(char::is_whitespace)) {
+            return None;
+        }
+        entry.len() - value.len()
+    } else if let Some(value) = separator.strip_prefix('=') {
+        entry.len() - value.len()
+    } else {
+        return None;
+    };
+    Some((name, value_start))
+}
+
+/// Rewrites the value part of a line, leaving a trailing ` #` comment alone.
+pub(super) fn rewrite_value(
+    line: &MetadataLine<'_>,
+    rewrite: impl FnOnce(&str) -> String,
+) -> String {
+    let Some(value_start) = line.value_start else {
+        return line.text.to_string();
+    };
+    let (head, value) = line.text.split_at(value_start);
+    let (value, comment) = value.split_at(value.find(" #").unwrap_or(value.len()));
+    format!("{head}{}{comment}", rewrite(value))
+}
+
+/// Rewrites a scalar or an inline list of values, keeping quotes and
+/// spacing. Each value equal to the first element of a pair becomes the
+/// second one; in inline `{ id = ... }` link mappings, only the `id` is.
+pub(super) fn rewrite_values(value: &str, renames: &[(String, String)]) -> String {
+    value
+        .split_inclusive([',', '[', ']', '{', '}'])
+        .map(|part| {
+            let core = part.trim_end_matches([',', '[', ']', '{', '}']).trim();
+            let core = match core.strip_prefix(FIELD_ID).map(str::trim_start) {
+                Some(rest) if rest.starts_with([':', '=']) => rest[1..].trim(),
+                _ => core,
+            };
+            let unquoted = core.trim_matches(['"', '\'']);
+            match renames.iter().find(|(old, _)| old == unquoted) {
+                Some((_, new)) if !unquoted.is_empty() => part.replacen(unquoted, new, 1),
+                _ => part.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the unquoted scalar value of a line, if any.
+pub(super) fn scalar_value<'a>(line: &MetadataLine<'a>) -> Option<&'a str> {
+    let value = &line.text[line.value_start?..];
+    let value = &value[..value.find(" #").unwrap_or(value.len())];
+    let value = value.trim().trim_matches(['"', '\'']);
+    (!value.is_empty() && !value.starts_with('[')).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_lines_track_keys() {
+        let block = "- id: UC-001\n  refines:\n    - SOL-001 # main\n:derives_from: A, B\n\
+                     [[items]]\nrefines = [\n  \"SOL-002\",\n]\n";
+        let lines = metadata_lines(block);
+        let keys: Vec<_> = lines
+            .iter()
+            .map(|l| (l.key, l.starts_item, scalar_value(l)))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                (Some("id"), true, Some("UC-001")),
+                (Some("refines"), false, None),
+                (Some("refines"), false, Some("SOL-001")),
+                (Some("derives_from"), false, Some("A, B")),
+                (None, true, None),
+                (Some("refines"), false, None),
+                (Some("refines"), false, Some("SOL-002\",")),
+                (None, false, None),
+            ]
+        );
+        assert_eq!(
+            &block[2..4],
+            &lines[0].text[lines[0].key_range.clone().unwrap()]
+        );
+        assert_eq!(
+            rewrite_value(&lines[2], |v| rewrite_values(
+                v,
+                &[("SOL-001".to_string(), "SOL-010".to_string())]
+            )),
+            "    - SOL-010 # main\n"
+        );
+    }
+
+    #[test]
+    fn test_metadata_lines_track_link_mappings() {
+        let block = "satisfies:\n  - id: \"SYSREQ-001\"\n    coverage: partial\n  - SYSREQ-002\nstatus: draft\n";
+        let lines: Vec<_> = metadata_lines(block)
+            .iter()
+            .map(|l| (l.key, l.parent))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (Some("satisfies"), None),
+                (Some("id"), Some("satisfies")),
+                (Some("coverage"), Some("satisfies")),
+                (Some("satisfies"), None),
+                (Some("status"), None),
+            ]
+        );
+
+        let renames = [("SYSREQ-001".to_string(), "SYSREQ-010".to_string())];
+        assert_eq!(
+            rewrite_values(
+                " [{ id = \"SYSREQ-001\", note = \"SYSREQ-001\" }, \"SYSREQ-001\"]",
+                &renames
+            ),
+            " [{ id = \"SYSREQ-010\", note = \"SYSREQ-001\" }, \"SYSREQ-010\"]"
+        );
+    }
+}
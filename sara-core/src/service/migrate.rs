@@ -0,0 +1,1100 @@
+//! Migration service for moving documents from one schema to another.
+//!
+//! When a custom model evolves (a type, field or relation is renamed, enum
+//! values change), every document written against the previous schema turns
+//! invalid at once. The migration compares both schemas, completes the
+//! user's mapping with proposals for what it can match, and rewrites the
+//! metadata of every document in place. Proposals are only applied once
+//! accepted. Items it cannot carry over, such as an item whose type was
+//! dropped, are reported instead of guessed.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::SaraError;
+use crate::model::{FIELD_DESCRIPTION, FIELD_ID, FIELD_NAME, FIELD_TYPE};
+use crate::parser::{InputFormat, body_start, find_anchors};
+use crate::repository::scan_directory;
+use crate::schema::{FieldDef, FieldType, ItemTypeDef, RelationDef, Schema};
+use crate::service::metadata_lines::{
+    MetadataLine, metadata_lines, rewrite_value, rewrite_values, scalar_value,
+};
+
+/// Renames to apply when migrating documents, keyed by old names.
+///
+/// Read from the `--map` file:
+///
+/// ```yaml
+/// item_types:
+///   system_requirement: system_req
+/// relations:
+///   derives_from: traces_to
+/// fields:
+///   architecture_decision_record:   # old type
+///     status: state                 # old field: new field
+/// enum_values:
+///   architecture_decision_record:   # old type
+///     status:                       # old field
+///       superseded: replaced        # old value: new value
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MigrationMap {
+    /// Item type renames.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub item_types: IndexMap<String, String>,
+    /// Relation renames.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub relations: IndexMap<String, String>,
+    /// Field renames, per item type.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub fields: IndexMap<String, IndexMap<String, String>>,
+    /// Enum value remaps, per item type and field.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub enum_values: IndexMap<String, IndexMap<String, IndexMap<String, String>>>,
+}
+
+impl MigrationMap {
+    /// Loads a mapping from a YAML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be read, or
+    /// [`SaraError::InvalidConfig`] if it is not a valid mapping.
+    pub fn from_path(path: &Path) -> Result<Self, SaraError> {
+        let content = fs::read_to_string(path)?;
+        serde_yaml::from_str::<Option<Self>>(&content)
+            .map(Option::unwrap_or_default)
+            .map_err(|e| SaraError::InvalidConfig {
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Returns true if the mapping renames nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.item_types.is_empty()
+            && self.relations.is_empty()
+            && self.fields.is_empty()
+            && self.enum_values.is_empty()
+    }
+
+    /// Serializes the mapping to YAML, in the `--map` file format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SaraError::InvalidConfig`] if serialization fails.
+    pub fn to_yaml(&self) -> Result<String, SaraError> {
+        serde_yaml::to_string(self).map_err(|e| SaraError::InvalidConfig {
+            path: PathBuf::from("<migration map>"),
+            reason: e.to_string(),
+        })
+    }
+
+    /// New name of an item type.
+    fn item_type<'a>(&'a self, old: &'a str) -> &'a str {
+        self.item_types.get(old).map_or(old, String::as_str)
+    }
+
+    /// New name of a relation.
+    fn relation<'a>(&'a self, old: &'a str) -> &'a str {
+        self.relations.get(old).map_or(old, String::as_str)
+    }
+
+    /// New name of a field of an item type, both given by their old names.
+    fn field<'a>(&'a self, item_type: &str, old: &'a str) -> &'a str {
+        self.fields
+            .get(item_type)
+            .and_then(|fields| fields.get(old))
+            .map_or(old, String::as_str)
+    }
+
+    /// Enum value remaps of a field, both given by their old names.
+    fn enum_values(&self, item_type: &str, field: &str) -> Vec<(String, String)> {
+        self.enum_values
+            .get(item_type)
+            .and_then(|fields| fields.get(field))
+            .map(|values| {
+                values
+                    .iter()
+                    .map(|(old, new)| (old.clone(), new.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Adds the entries of `other` this mapping does not set yet.
+    fn complete_with(&mut self, other: &Self) {
+        for (old, new) in &other.item_types {
+            self.item_types.entry(old.clone()).or_insert(new.clone());
+        }
+        for (old, new) in &other.relations {
+            self.relations.entry(old.clone()).or_insert(new.clone());
+        }
+        for (item_type, fields) in &other.fields {
+            let entry = self.fields.entry(item_type.clone()).or_default();
+            for (old, new) in fields {
+                entry.entry(old.clone()).or_insert(new.clone());
+            }
+        }
+        for (item_type, fields) in &other.enum_values {
+            let entry = self.enum_values.entry(item_type.clone()).or_default();
+            for (field, values) in fields {
+                let entry = entry.entry(field.clone()).or_default();
+                for (old, new) in values {
+                    entry.entry(old.clone()).or_insert(new.clone());
+                }
+            }
+        }
+    }
+}
+
+/// A difference between two schemas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    /// An item type only the old schema defines.
+    ItemTypeRemoved(String),
+    /// An item type only the new schema defines.
+    ItemTypeAdded(String),
+    /// A relation only the old schema defines.
+    RelationRemoved(String),
+    /// A relation only the new schema defines.
+    RelationAdded(String),
+    /// A field only the old definition of a type declares.
+    FieldRemoved {
+        /// The item type.
+        item_type: String,
+        /// The field name.
+        field: String,
+    },
+    /// A field only the new definition of a type declares.
+    FieldAdded {
+        /// The item type.
+        item_type: String,
+        /// The field name.
+        field: String,
+    },
+    /// A field declared with another type in the new schema.
+    FieldTypeChanged {
+        /// The item type.
+        item_type: String,
+        /// The field name.
+        field: String,
+    },
+    /// An enum value only the old definition of a field allows.
+    EnumValueRemoved {
+        /// The item type.
+        item_type: String,
+        /// The field name.
+        field: String,
+        /// The value.
+        value: String,
+    },
+    /// An enum value only the new definition of a field allows.
+    EnumValueAdded {
+        /// The item type.
+        item_type: String,
+        /// The field name.
+        field: String,
+        /// The value.
+        value: String,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ItemTypeRemoved(id) => write!(f, "- item type {id}"),
+            Self::ItemTypeAdded(id) => write!(f, "+ item type {id}"),
+            Self::RelationRemoved(id) => write!(f, "- relation {id}"),
+            Self::RelationAdded(id) => write!(f, "+ relation {id}"),
+            Self::FieldRemoved { item_type, field } => write!(f, "- field {item_type}.{field}"),
+            Self::FieldAdded { item_type, field } => write!(f, "+ field {item_type}.{field}"),
+            Self::FieldTypeChanged { item_type, field } => {
+                write!(f, "~ field {item_type}.{field} changed type")
+            }
+            Self::EnumValueRemoved {
+                item_type,
+                field,
+                value,
+            } => write!(f, "- value {item_type}.{field} = {value}"),
+            Self::EnumValueAdded {
+                item_type,
+                field,
+                value,
+            } => write!(f, "+ value {item_type}.{field} = {value}"),
+        }
+    }
+}
+
+/// Lists the differences between two schemas.
+///
+/// Fields and enum values are compared for the types both schemas define.
+#[must_use]
+pub fn diff_schemas(old: &Schema, new: &Schema) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    for def in &old.item_types {
+        if new.item_type(&def.id).is_none() {
+            changes.push(SchemaChange::ItemTypeRemoved(def.id.clone()));
+        }
+    }
+    for def in &new.item_types {
+        if old.item_type(&def.id).is_none() {
+            changes.push(SchemaChange::ItemTypeAdded(def.id.clone()));
+        }
+    }
+    for def in &old.relations {
+        if new.relation(&def.id).is_none() {
+            changes.push(SchemaChange::RelationRemoved(def.id.clone()));
+        }
+    }
+    for def in &new.relations {
+        if old.relation(&def.id).is_none() {
+            changes.push(SchemaChange::RelationAdded(def.id.clone()));
+        }
+    }
+
+    for old_def in &old.item_types {
+        let Some(new_def) = new.item_type(&old_def.id) else {
+            continue;
+        };
+        let item_type = || old_def.id.clone();
+        for field in &old_def.fields {
+            match field_def(new_def, &field.name) {
+                None => changes.push(SchemaChange::FieldRemoved {
+                    item_type: item_type(),
+                    field: field.name.clone(),
+                }),
+                Some(new_field) if !same_kind(&field.field_type, &new_field.field_type) => {
+                    changes.push(SchemaChange::FieldTypeChanged {
+                        item_type: item_type(),
+                        field: field.name.clone(),
+                    });
+                }
+                Some(new_field) => {
+                    let (old_values, new_values) = (
+                        enum_values(&field.field_type),
+                        enum_values(&new_field.field_type),
+                    );
+                    for value in old_values.iter().filter(|v| !new_values.contains(v)) {
+                        changes.push(SchemaChange::EnumValueRemoved {
+                            item_type: item_type(),
+                            field: field.name.clone(),
+                            value: value.clone(),
+                        });
+                    }
+                    for value in new_values.iter().filter(|v| !old_values.contains(v)) {
+                        changes.push(SchemaChange::EnumValueAdded {
+                            item_type: item_type(),
+                            field: field.name.clone(),
+                            value: value.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        for field in &new_def.fields {
+            if field_def(old_def, &field.name).is_none() {
+                changes.push(SchemaChange::FieldAdded {
+                    item_type: item_type(),
+                    field: field.name.clone(),
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Proposes renames for the types, relations, fields and enum values that
+/// `map` leaves unmapped between two schemas.
+///
+/// A removed entry is matched with the only added one sharing its
+/// identifying traits: the prefix or display name of a type, the direction
+/// and display name of a relation, the kind and display name of a field, the
+/// spelling of an enum value. Entries with nothing in common are never
+/// paired, even when a single one was removed and a single one added.
+#[must_use]
+pub fn propose_renames(old: &Schema, new: &Schema, map: &MigrationMap) -> MigrationMap {
+    let mut proposed = MigrationMap::default();
+
+    let removed: Vec<&ItemTypeDef> = old
+        .item_types
+        .iter()
+        .filter(|d| new.item_type_by_name(&d.id).is_none() && !map.item_types.contains_key(&d.id))
+        .collect();
+    let added: Vec<&ItemTypeDef> = new
+        .item_types
+        .iter()
+        .filter(|d| old.item_type(&d.id).is_none() && !map.item_types.values().any(|v| *v == d.id))
+        .collect();
+    for (old_def, new_def) in pair_up(&removed, &added, |a, b| {
+        a.prefix == b.prefix || a.display_name.eq_ignore_ascii_case(&b.display_name)
+    }) {
+        proposed
+            .item_types
+            .insert(old_def.id.clone(), new_def.id.clone());
+    }
+
+    let removed: Vec<&RelationDef> = old
+        .relations
+        .iter()
+        .filter(|d| new.relation_by_name(&d.id).is_none() && !map.relations.contains_key(&d.id))
+        .collect();
+    let added: Vec<&RelationDef> = new
+        .relations
+        .iter()
+        .filter(|d| old.relation(&d.id).is_none() && !map.relations.values().any(|v| *v == d.id))
+        .collect();
+    let same_side =
+        |a: &RelationDef, b: &RelationDef| a.direction == b.direction && a.primary == b.primary;
+    for (old_def, new_def) in pair_up(&removed, &added, |a, b| {
+        same_side(a, b) && a.display_name.eq_ignore_ascii_case(&b.display_name)
+    }) {
+        proposed
+            .relations
+            .insert(old_def.id.clone(), new_def.id.clone());
+    }
+
+    let mut types = map.clone();
+    types.complete_with(&proposed);
+    for old_def in &old.item_types {
+        let Some(new_def) = new.item_type(types.item_type(&old_def.id)) else {
+            continue;
+        };
+        let explicit = map.fields.get(&old_def.id);
+        let removed: Vec<&FieldDef> = old_def
+            .fields
+            .iter()
+            .filter(|f| {
+                new_def.field_by_name(&f.name).is_none()
+                    && !explicit.is_some_and(|m| m.contains_key(&f.name))
+            })
+            .collect();
+        let added: Vec<&FieldDef> = new_def
+            .fields
+            .iter()
+            .filter(|f| {
+                field_def(old_def, &f.name).is_none()
+                    && !explicit.is_some_and(|m| m.values().any(|v| *v == f.name))
+            })
+            .collect();
+        for (old_field, new_field) in pair_up(&removed, &added, |a, b| {
+            same_kind(&a.field_type, &b.field_type)
+                && a.display_name.eq_ignore_ascii_case(&b.display_name)
+        }) {
+            proposed
+                .fields
+                .entry(old_def.id.clone())
+                .or_default()
+                .insert(old_field.name.clone(), new_field.name.clone());
+        }
+    }
+
+    types.complete_with(&proposed);
+    for old_def in &old.item_types {
+        let Some(new_def) = new.item_type(types.item_type(&old_def.id)) else {
+            continue;
+        };
+        for old_field in &old_def.fields {
+            let Some(new_field) = field_def(new_def, types.field(&old_def.id, &old_field.name))
+            else {
+                continue;
+            };
+            let explicit = map
+                .enum_values
+                .get(&old_def.id)
+                .and_then(|fields| fields.get(&old_field.name));
+            let (old_values, new_values) = (
+                enum_values(&old_field.field_type),
+                enum_values(&new_field.field_type),
+            );
+            let removed: Vec<&String> = old_values
+                .iter()
+                .filter(|v| {
+                    !new_values.contains(v) && !explicit.is_some_and(|m| m.contains_key(*v))
+                })
+                .collect();
+            let added: Vec<&String> = new_values
+                .iter()
+                .filter(|v| {
+                    !old_values.contains(v)
+                        && !explicit.is_some_and(|m| m.values().any(|n| n == *v))
+                })
+                .collect();
+            for (old_value, new_value) in
+                pair_up(&removed, &added, |a, b| normalize(a) == normalize(b))
+            {
+                proposed
+                    .enum_values
+                    .entry(old_def.id.clone())
+                    .or_default()
+                    .entry(old_field.name.clone())
+                    .or_default()
+                    .insert(old_value.to_string(), new_value.to_string());
+            }
+        }
+    }
+
+    proposed
+}
+
+/// Pairs each removed entry with the added entry it alone matches.
+fn pair_up<'a, T>(
+    removed: &[&'a T],
+    added: &[&'a T],
+    matches: impl Fn(&T, &T) -> bool,
+) -> Vec<(&'a T, &'a T)> {
+    removed
+        .iter()
+        .filter_map(|old| {
+            let mut candidates = added.iter().filter(|new| matches(old, new));
+            match (candidates.next(), candidates.next()) {
+                (Some(new), None) => Some((*old, *new)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Lowercases a value and drops its separators, for loose comparison.
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Returns the field of a type with the given name.
+fn field_def<'a>(def: &'a ItemTypeDef, name: &str) -> Option<&'a FieldDef> {
+    def.fields.iter().find(|f| f.name == name)
+}
+
+/// Returns true if two field types hold the same kind of values, enum
+/// values aside.
+fn same_kind(a: &FieldType, b: &FieldType) -> bool {
+    match (a, b) {
+        (FieldType::Enum { .. }, FieldType::Enum { .. }) => true,
+        (FieldType::List(a), FieldType::List(b)) => same_kind(a, b),
+        _ => a == b,
+    }
+}
+
+/// Returns the values an enum field, or a list of enum values, allows.
+fn enum_values(field_type: &FieldType) -> &[String] {
+    match field_type {
+        FieldType::Enum { values } => values,
+        FieldType::List(inner) => enum_values(inner),
+        _ => &[],
+    }
+}
+
+/// Options for migrating documents.
+#[derive(Debug, Clone)]
+pub struct MigrateOptions {
+    /// The schema the documents follow.
+    pub from: Schema,
+    /// The schema to migrate them to.
+    pub to: Schema,
+    /// Renames given by the user.
+    pub map: MigrationMap,
+    /// Whether to apply the proposed renames along with the user's ones.
+    pub accept_proposals: bool,
+    /// Whether to report the changes without writing them.
+    pub dry_run: bool,
+}
+
+impl MigrateOptions {
+    /// Creates options migrating from one schema to another.
+    pub fn new(from: Schema, to: Schema) -> Self {
+        Self {
+            from,
+            to,
+            map: MigrationMap::default(),
+            accept_proposals: false,
+            dry_run: false,
+        }
+    }
+
+    /// Sets the renames given by the user.
+    pub fn with_map(mut self, map: MigrationMap) -> Self {
+        self.map = map;
+        self
+    }
+
+    /// Sets whether the proposed renames are applied.
+    pub fn with_accept_proposals(mut self, accept_proposals: bool) -> Self {
+        self.accept_proposals = accept_proposals;
+        self
+    }
+
+    /// Sets whether the changes are only reported.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// An item the migration could not carry over to the new schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unmigrated {
+    /// The document holding the item.
+    pub file: PathBuf,
+    /// The item identifier, when the metadata gives one.
+    pub item_id: Option<String>,
+    /// Why the item needs manual attention.
+    pub reason: String,
+}
+
+impl fmt::Display for Unmigrated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.item_id {
+            Some(id) => write!(f, "{id} ({}): {}", self.file.display(), self.reason),
+            None => write!(f, "{}: {}", self.file.display(), self.reason),
+        }
+    }
+}
+
+/// Result of a migration.
+#[derive(Debug, Clone, Default)]
+pub struct MigrateResult {
+    /// Differences between the two schemas.
+    pub changes: Vec<SchemaChange>,
+    /// Renames proposed for what the user's mapping left unmapped, applied
+    /// along with it only when [`MigrateOptions::accept_proposals`] is set.
+    pub proposed: MigrationMap,
+    /// Documents rewritten, or to be rewritten on a dry run.
+    pub migrated: Vec<PathBuf>,
+    /// Items needing manual attention.
+    pub unmigrated: Vec<Unmigrated>,
+}
+
+/// Service for migrating documents between schemas.
+#[derive(Debug, Default)]
+pub struct MigrateService;
+
+impl MigrateService {
+    /// Creates a new migration service.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Migrates the documents found under `paths` to the new schema.
+    ///
+    /// Item types, relations and fields are renamed and enum values remapped
+    /// in the metadata of each document, following the user's mapping, and
+    /// the renames proposed by [`propose_renames`] once accepted. An item whose type, a relation or field of which,
+    /// or an enum value of which the new schema does not know, or that lacks
+    /// a field the new schema requires, is reported in
+    /// [`MigrateResult::unmigrated`]; the entries the migration could not
+    /// carry over are left as written. Names written through schema aliases
+    /// are migrated to the canonical names.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if a document cannot be read or written.
+    pub fn migrate(
+        &self,
+        paths: &[PathBuf],
+        opts: &MigrateOptions,
+    ) -> Result<MigrateResult, SaraError> {
+        let proposed = propose_renames(&opts.from, &opts.to, &opts.map);
+        let mut map = opts.map.clone();
+        if opts.accept_proposals {
+            map.complete_with(&proposed);
+        }
+        let migration = Migration {
+            from: &opts.from,
+            to: &opts.to,
+            map: &map,
+        };
+
+        let mut result = MigrateResult {
+            changes: diff_schemas(&opts.from, &opts.to),
+            proposed,
+            ..Default::default()
+        };
+        for root in paths {
+            let mut files = scan_directory(root)?;
+            files.sort();
+            for path in files {
+                let Some(format) = InputFormat::from_path(&path) else {
+                    continue;
+                };
+                let content = fs::read_to_string(&path)?;
+                let mut problems = Vec::new();
+                let updated = migration.document(&content, format, &mut problems);
+                result
+                    .unmigrated
+                    .extend(problems.into_iter().map(|(item_id, reason)| Unmigrated {
+                        file: path.clone(),
+                        item_id,
+                        reason,
+                    }));
+                if updated != content {
+                    if !opts.dry_run {
+                        fs::write(&path, &updated)?;
+                    }
+                    result.migrated.push(path);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Problems found in a document, as (item identifier, reason).
+type Problems = Vec<(Option<String>, String)>;
+
+/// How an entry of an item carries over to the new schema.
+struct EntryMigration<'a> {
+    /// New name of the entry.
+    name: String,
+    /// Enum value remaps.
+    remaps: Vec<(String, String)>,
+    /// Values the new definition allows; empty when it is not an enum.
+    allowed: &'a [String],
+}
+
+/// A migration between two schemas, with its complete mapping.
+struct Migration<'a> {
+    from: &'a Schema,
+    to: &'a Schema,
+    map: &'a MigrationMap,
+}
+
+impl Migration<'_> {
+    /// Migrates the metadata of a document.
+    fn document(&self, content: &str, format: InputFormat, problems: &mut Problems) -> String {
+        match format {
+            InputFormat::Markdown => {
+                let end = body_start(content, format);
+                let frontmatter = self.item(&metadata_lines(&content[..end]), None, problems);
+                let blocks: Vec<_> = find_anchors(content)
+                    .into_iter()
+                    .map(|anchor| {
+                        let lines = metadata_lines(&content[anchor.block.clone()]);
+                        (anchor.block, self.item(&lines, Some(anchor.id), problems))
+                    })
+                    .collect();
+                let mut updated = content.to_string();
+                for (range, block) in blocks.into_iter().rev() {
+                    updated.replace_range(range, &block);
+                }
+                updated.replace_range(..end, &frontmatter);
+                updated
+            }
+            InputFormat::AsciiDoc | InputFormat::Rst => {
+                let end = body_start(content, format);
+                let lines = metadata_lines(&content[..end]);
+                self.item(&lines, None, problems) + &content[end..]
+            }
+            InputFormat::Yaml | InputFormat::Toml => {
+                let lines = metadata_lines(content);
+                // A list of items starts with its first item; the lines of
+                // a single item are all its own.
+                let is_list = lines
+                    .iter()
+                    .find(|l| l.key_range.is_some() || l.starts_item)
+                    .is_some_and(|l| l.starts_item);
+                if !is_list {
+                    return self.item(&lines, None, problems);
+                }
+                let mut segments: Vec<&[MetadataLine<'_>]> = Vec::new();
+                let mut start = 0;
+                for (index, line) in lines.iter().enumerate() {
+                    if line.starts_item && index > 0 {
+                        segments.push(&lines[start..index]);
+                        start = index;
+                    }
+                }
+                segments.push(&lines[start..]);
+                segments
+                    .into_iter()
+                    .map(|segment| self.item(segment, None, problems))
+                    .collect()
+            }
+        }
+    }
+
+    /// Migrates the metadata lines of one item.
+    ///
+    /// `anchor_id` is the identifier of an item anchored on a heading, which
+    /// the block itself may omit.
+    fn item<'l>(
+        &self,
+        lines: &[MetadataLine<'l>],
+        anchor_id: Option<&str>,
+        problems: &mut Problems,
+    ) -> String {
+        let unchanged = || lines.iter().map(|l| l.text).collect::<String>();
+        let Some(indent) = lines
+            .iter()
+            .find_map(|l| l.key_range.as_ref().map(|r| r.start))
+        else {
+            return unchanged();
+        };
+        // Entries of the item itself, not of nested mappings.
+        let top_key = |line: &MetadataLine<'l>| -> Option<&'l str> {
+            line.key_range
+                .as_ref()
+                .filter(|r| r.start == indent)
+                .and(line.key)
+        };
+        let entry = |key: &str| {
+            lines
+                .iter()
+                .find(|l| top_key(l) == Some(key))
+                .and_then(scalar_value)
+        };
+        let item_id = entry(FIELD_ID).or(anchor_id).map(str::to_string);
+        let mut report = |reason: String| problems.push((item_id.clone(), reason));
+
+        let Some(old_type) = entry(FIELD_TYPE) else {
+            if item_id.is_some() {
+                report("no item type".to_string());
+            }
+            return unchanged();
+        };
+        // Names written through a schema alias resolve to canonical ones.
+        let old_def = self.from.item_type_by_name(old_type);
+        let canonical = old_def.map_or(old_type, |def| def.id.as_str());
+        let Some(new_def) = self.to.item_type_by_name(self.map.item_type(canonical)) else {
+            report(format!(
+                "item type '{old_type}' has no counterpart in the new schema"
+            ));
+            return unchanged();
+        };
+        let new_type = new_def.id.as_str();
+
+        let mut migrated = String::new();
+        let mut present: Vec<String> = Vec::new();
+        // The entry the current line belongs to.
+        let mut current: Option<EntryMigration<'_>> = None;
+        for line in lines {
+            if let Some(key) = top_key(line) {
+                current = self.entry(key, canonical, old_def, new_def, &mut report);
+                present.push(current.as_ref().map_or(key, |e| &e.name).to_string());
+            }
+
+            let mut text = line.text.to_string();
+            if top_key(line) == Some(FIELD_TYPE) && new_type != old_type {
+                let renames = [(old_type.to_string(), new_type.to_string())];
+                text = rewrite_value(line, |value| rewrite_values(value, &renames));
+            } else if let Some(EntryMigration {
+                name,
+                remaps,
+                allowed,
+            }) = &current
+            {
+                if !remaps.is_empty() {
+                    text = rewrite_value(line, |value| rewrite_values(value, remaps));
+                }
+                if !allowed.is_empty()
+                    && let Some(start) = line.value_start
+                {
+                    for value in text[start..]
+                        .split(" #")
+                        .next()
+                        .unwrap_or_default()
+                        .split([',', '[', ']'])
+                        .map(|v| v.trim().trim_matches(['"', '\'']))
+                        .filter(|v| !v.is_empty())
+                    {
+                        if !allowed.iter().any(|a| a == value) {
+                            report(format!("value '{value}' of field '{name}' is not allowed"));
+                        }
+                    }
+                }
+            }
+            if let Some(range) = line.key_range.as_ref().filter(|r| r.start == indent)
+                && let Some(entry) = &current
+                && entry.name != line.text[range.clone()]
+            {
+                text.replace_range(range.clone(), &entry.name);
+            }
+            migrated.push_str(&text);
+        }
+
+        for field in new_def.fields.iter().filter(|f| f.required) {
+            let from_section = new_def.sections.iter().any(|s| s.field == field.name);
+            if !from_section && !present.contains(&field.name) {
+                report(format!("required field '{}' is missing", field.name));
+            }
+        }
+        migrated
+    }
+
+    /// Resolves an entry of an item against the new schema.
+    ///
+    /// Returns `None` for the core entries and entries neither schema knows,
+    /// which are kept as written.
+    fn entry<'s>(
+        &'s self,
+        key: &str,
+        old_type: &str,
+        old_def: Option<&ItemTypeDef>,
+        new_def: &'s ItemTypeDef,
+        report: &mut impl FnMut(String),
+    ) -> Option<EntryMigration<'s>> {
+        if [FIELD_ID, FIELD_TYPE, FIELD_NAME, FIELD_DESCRIPTION].contains(&key) {
+            return None;
+        }
+        if let Some(relation) = self.from.relation_by_name(key) {
+            let Some(relation) = self.to.relation_by_name(self.map.relation(&relation.id)) else {
+                report(format!(
+                    "relation '{key}' has no counterpart in the new schema"
+                ));
+                return None;
+            };
+            return Some(EntryMigration {
+                name: relation.id.clone(),
+                remaps: Vec::new(),
+                allowed: &[],
+            });
+        }
+        if let Some(old_field) = old_def.and_then(|def| def.field_by_name(key)) {
+            let name = self.map.field(old_type, &old_field.name);
+            let Some(field) = new_def.field_by_name(name) else {
+                report(format!(
+                    "field '{key}' has no counterpart in type '{}'",
+                    new_def.id
+                ));
+                return None;
+            };
+            return Some(EntryMigration {
+                name: field.name.clone(),
+                remaps: self.map.enum_values(old_type, &old_field.name),
+                allowed: enum_values(&field.field_type),
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn schemas() -> (Schema, Schema) {
+        let old = Schema::builtin();
+        let mut new = old.clone();
+        // Rename a type, a relation pair, a field and an enum value.
+        let sysreq = new
+            .item_types
+            .iter_mut()
+            .find(|t| t.id == "system_requirement")
+            .unwrap();
+        sysreq.id = "system_req".to_string();
+        for def in &mut new.item_types {
+            for target in &mut def.allowed_targets {
+                for t in &mut target.targets {
+                    if t == "system_requirement" {
+                        *t = "system_req".to_string();
+                    }
+                }
+                if target.relation == "derives_from" {
+                    target.relation = "traces_to".to_string();
+                }
+            }
+            for parent in &mut def.parent_types {
+                if parent == "system_requirement" {
+                    *parent = "system_req".to_string();
+                }
+            }
+        }
+        for rel in &mut new.relations {
+            match rel.id.as_str() {
+                "derives_from" => rel.id = "traces_to".to_string(),
+                "is_source_of" => rel.id = "traced_by".to_string(),
+                _ => {}
+            }
+            match rel.inverse.as_str() {
+                "derives_from" => rel.inverse = "traces_to".to_string(),
+                "is_source_of" => rel.inverse = "traced_by".to_string(),
+                _ => {}
+            }
+        }
+        let adr = new
+            .item_types
+            .iter_mut()
+            .find(|t| t.id == "architecture_decision_record")
+            .unwrap();
+        let status = adr.fields.iter_mut().find(|f| f.name == "status").unwrap();
+        status.name = "state".to_string();
+        if let FieldType::Enum { values } = &mut status.field_type {
+            for value in values {
+                if value == "superseded" {
+                    *value = "Superseded".to_string();
+                }
+            }
+        }
+        (old, new)
+    }
+
+    #[test]
+    fn test_diff_and_proposals() {
+        let (old, new) = schemas();
+        let changes = diff_schemas(&old, &new);
+        assert!(changes.contains(&SchemaChange::ItemTypeRemoved(
+            "system_requirement".to_string()
+        )));
+        assert!(changes.contains(&SchemaChange::RelationAdded("traces_to".to_string())));
+        assert!(changes.contains(&SchemaChange::FieldRemoved {
+            item_type: "architecture_decision_record".to_string(),
+            field: "status".to_string(),
+        }));
+
+        let mut map = MigrationMap::default();
+        map.relations
+            .insert("is_source_of".to_string(), "traced_by".to_string());
+        let proposed = propose_renames(&old, &new, &map);
+        assert_eq!(proposed.item_types["system_requirement"], "system_req");
+        assert_eq!(proposed.relations["derives_from"], "traces_to");
+        assert!(!proposed.relations.contains_key("is_source_of"));
+        assert_eq!(
+            proposed.fields["architecture_decision_record"]["status"],
+            "state"
+        );
+        assert_eq!(
+            proposed.enum_values["architecture_decision_record"]["status"]["superseded"],
+            "Superseded"
+        );
+    }
+
+    #[test]
+    fn test_migrate_rewrites_documents_and_reports_leftovers() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(
+            dir.join("SYSREQ-001.md"),
+            "---\nid: SYSREQ-001\ntype: system_requirement # keep\nname: Speed\n\
+             specification: The system SHALL be fast.\nderives_from:\n  - SCEN-001\n---\n# Speed\n\n\
+             type: system_requirement stays in the body.\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("ADR-001.md"),
+            "---\nid: ADR-001\ntype: architecture_decision_record\nname: Bus\n\
+             status: superseded\ndeciders: [Ann]\n---\n# Bus\n\n\
+             ## SYSREQ-002: Latency\n\n```sara\ntype: system_requirement\n\
+             specification: Low.\nderives_from: [SCEN-001]\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("params.sara.yaml"),
+            "- id: SYSREQ-003\n  type: system_requirement\n  name: Size\n\
+             \x20 specification: Small.\n- id: ADR-002\n  type: architecture_decision_record\n\
+             \x20 name: Old\n  status: rejected\n  deciders: [Bob]\n",
+        )
+        .unwrap();
+
+        let (old, new) = schemas();
+        let opts = MigrateOptions::new(old, new).with_accept_proposals(true);
+        let result = MigrateService::new()
+            .migrate(&[dir.to_path_buf()], &opts)
+            .unwrap();
+
+        assert_eq!(result.migrated.len(), 3);
+        assert_eq!(
+            fs::read_to_string(dir.join("SYSREQ-001.md")).unwrap(),
+            "---\nid: SYSREQ-001\ntype: system_req # keep\nname: Speed\n\
+             specification: The system SHALL be fast.\ntraces_to:\n  - SCEN-001\n---\n# Speed\n\n\
+             type: system_requirement stays in the body.\n"
+        );
+        let adr = fs::read_to_string(dir.join("ADR-001.md")).unwrap();
+        assert!(adr.contains("state: Superseded\n"));
+        assert!(adr.contains(
+            "```sara\ntype: system_req\nspecification: Low.\ntraces_to: [SCEN-001]\n```"
+        ));
+        let params = fs::read_to_string(dir.join("params.sara.yaml")).unwrap();
+        assert!(params.starts_with("- id: SYSREQ-003\n  type: system_req\n"));
+        assert!(params.contains("  state: rejected\n"));
+
+        let unmigrated: Vec<String> = result
+            .unmigrated
+            .iter()
+            .map(|u| format!("{} {}", u.item_id.as_deref().unwrap_or("?"), u.reason))
+            .collect();
+        assert_eq!(
+            unmigrated,
+            ["ADR-002 value 'rejected' of field 'state' is not allowed"]
+        );
+
+        // Dropping the type leaves its items for manual migration.
+        let (_, mut new) = schemas();
+        new.item_types.retain(|t| t.id != "system_req");
+        let result = MigrateService::new()
+            .migrate(
+                &[dir.to_path_buf()],
+                &MigrateOptions::new(new.clone(), new).with_dry_run(true),
+            )
+            .unwrap();
+        let ids: Vec<_> = result
+            .unmigrated
+            .iter()
+            .filter(|u| {
+                u.reason
+                    .contains("item type 'system_req' has no counterpart")
+            })
+            .filter_map(|u| u.item_id.as_deref())
+            .collect();
+        assert_eq!(ids, ["SYSREQ-002", "SYSREQ-001", "SYSREQ-003"]);
+        assert!(result.migrated.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_leaves_proposals_unapplied_unless_accepted() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let document = "---\nid: SYSREQ-001\ntype: system_requirement\nname: Speed\n\
+                        specification: The system SHALL be fast.\n---\n# Speed\n";
+        fs::write(dir.join("SYSREQ-001.md"), document).unwrap();
+
+        let (old, new) = schemas();
+        let result = MigrateService::new()
+            .migrate(&[dir.to_path_buf()], &MigrateOptions::new(old, new))
+            .unwrap();
+
+        assert_eq!(
+            result.proposed.item_types["system_requirement"],
+            "system_req"
+        );
+        assert!(result.migrated.is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.join("SYSREQ-001.md")).unwrap(),
+            document
+        );
+        assert!(
+            result
+                .unmigrated
+                .iter()
+                .any(|u| u.item_id.as_deref() == Some("SYSREQ-001"))
+        );
+    }
+
+    #[test]
+    fn test_propose_renames_skips_an_unrelated_single_pair() {
+        let old = Schema::builtin();
+        let mut new = old.clone();
+        let scenario = new
+            .item_types
+            .iter_mut()
+            .find(|t| t.id == "scenario")
+            .unwrap();
+        scenario.id = "test_case".to_string();
+        scenario.prefix = "TC".to_string();
+        scenario.display_name = "Test Case".to_string();
+
+        let proposed = propose_renames(&old, &new, &MigrationMap::default());
+
+        assert!(proposed.item_types.is_empty());
+    }
+}
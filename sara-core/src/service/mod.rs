@@ -4,28 +4,101 @@
 //! with file I/O operations. These functions bridge the gap between the pure
 //! domain layer (`model/`) and the CLI/application layer.
 
-use std::path::PathBuf;
+use std::path::Path;
+use std::sync::Arc;
 
+use crate::config::RepositoryConfig;
 use crate::error::SaraError;
+use crate::generator::OutputFormat;
 use crate::graph::{KnowledgeGraph, KnowledgeGraphBuilder};
-use crate::model::{ItemId, Relationship, RelationshipType};
-use crate::repository::{ScanWarning, parse_repositories};
+use crate::model::{Item, ItemId, Relationship, RelationshipType};
+use crate::parser::{ParseContext, has_toml_frontmatter};
+use crate::repository::{
+    DocumentSource, ScanWarning, apply_namespaces, parse_repositories, parse_source,
+    resolve_references,
+};
 
+#[cfg(feature = "git")]
+pub mod branches;
+#[cfg(feature = "git")]
 pub mod diff;
 pub mod edit;
+pub mod import;
 pub mod init;
+pub mod merge;
+mod metadata_lines;
+pub mod migrate;
+pub mod rename;
 
-/// Parses the given repository paths and builds the knowledge graph from
-/// every item found.
+/// Parses the configured repository paths and builds the knowledge graph
+/// from every item found, both read against the schema of `ctx`.
 ///
-/// Warnings for paths skipped during the scan are returned alongside the
-/// graph so callers can report them.
-pub fn load_graph(paths: &[PathBuf]) -> Result<(KnowledgeGraph, Vec<ScanWarning>), SaraError> {
-    let scan = parse_repositories(paths);
-    let graph = KnowledgeGraphBuilder::new().add_items(scan.items).build()?;
+/// Items of namespaced repositories are qualified and references resolved
+/// before the graph is built. Warnings for paths skipped during the scan are
+/// returned alongside the graph so callers can report them.
+pub fn load_graph(
+    repositories: &RepositoryConfig,
+    ctx: &ParseContext,
+) -> Result<(KnowledgeGraph, Vec<ScanWarning>), SaraError> {
+    let mut scan = parse_repositories(&repositories.paths, ctx);
+    apply_namespaces(&mut scan.items, repositories);
+    let graph = KnowledgeGraphBuilder::new()
+        .schema(Arc::clone(ctx.schema()))
+        .add_items(scan.items)
+        .build()?;
     Ok((graph, scan.warnings))
 }
 
+/// Builds the knowledge graph from the items of document sources, such as
+/// in-memory documents (see [`crate::repository::MemorySource`]), both read
+/// against the schema of `ctx`.
+///
+/// Documents that cannot be read or parsed are skipped; their warnings are
+/// returned alongside the graph so callers can report them.
+///
+/// # Errors
+///
+/// Returns a [`SaraError`] when a source cannot be listed or the graph
+/// cannot be built.
+pub fn load_graph_from_sources(
+    sources: &[&dyn DocumentSource],
+    ctx: &ParseContext,
+) -> Result<(KnowledgeGraph, Vec<ScanWarning>), SaraError> {
+    let mut items = Vec::new();
+    let mut warnings = Vec::new();
+    for source in sources {
+        let scan = parse_source(*source, ctx)?;
+        items.extend(scan.items);
+        warnings.extend(scan.warnings);
+    }
+    resolve_references(&mut items);
+    let graph = KnowledgeGraphBuilder::new()
+        .schema(Arc::clone(ctx.schema()))
+        .add_items(items)
+        .build()?;
+    Ok((graph, warnings))
+}
+
+/// Renders the metadata block of an item for the document at `path`, with
+/// [`crate::generator::generate_metadata`] or
+/// [`crate::generator::regenerate_metadata`].
+///
+/// The format follows the file extension; a Markdown document whose
+/// `content` already opens with TOML frontmatter keeps TOML.
+fn document_metadata(
+    item: &Item,
+    path: &Path,
+    content: &str,
+    generate: fn(&Item, OutputFormat) -> String,
+) -> String {
+    match OutputFormat::from_path(path).unwrap_or(OutputFormat::Markdown) {
+        OutputFormat::Markdown if has_toml_frontmatter(content) => {
+            format!("+++\n{}+++", generate(item, OutputFormat::Toml))
+        }
+        format => generate(item, format),
+    }
+}
+
 /// Converts string IDs into [`Relationship`] values of the given type.
 fn ids_to_relationships(ids: &[String], rel_type: RelationshipType) -> Vec<Relationship> {
     ids.iter()
@@ -33,14 +106,32 @@ fn ids_to_relationships(ids: &[String], rel_type: RelationshipType) -> Vec<Relat
         .collect()
 }
 
+// Branch scanning exports
+#[cfg(feature = "git")]
+pub use branches::{
+    BranchDefinition, BranchItems, IdCollision, find_id_collisions, parse_branches,
+};
 // Diff service exports
+#[cfg(feature = "git")]
 pub use diff::{DiffError, DiffOptions, DiffResult, DiffService};
 // Edit service exports
 pub use edit::{EditOptions, EditResult, EditService, EditedValues, ItemContext};
+// Import service exports
+pub use import::{ImportResult, ImportService};
 // Init service exports
 pub use init::{
     FieldInput, InitError, InitOptions, InitResult, InitService, TypeConfig, parse_item_type,
+    parse_item_type_in,
+};
+// Merge service exports
+pub use merge::{MergeResult, merge_document};
+// Migrate service exports
+pub use migrate::{
+    MigrateOptions, MigrateResult, MigrateService, MigrationMap, SchemaChange, Unmigrated,
+    diff_schemas, propose_renames,
 };
+// Rename service exports
+pub use rename::{RenameOptions, RenameResult, RenameService};
 
 #[cfg(test)]
 mod tests {
@@ -65,9 +156,75 @@ name: "Test Solution"
         )
         .unwrap();
 
-        let (graph, warnings) = load_graph(&[temp_dir.path().to_path_buf()]).unwrap();
+        let repositories = RepositoryConfig {
+            paths: vec![temp_dir.path().to_path_buf()],
+            ..Default::default()
+        };
+        let (graph, warnings) = load_graph(&repositories, &ParseContext::default()).unwrap();
 
         assert_eq!(graph.item_count(), 1);
         assert!(warnings.is_empty());
     }
+
+    #[test]
+    fn test_load_graph_from_in_memory_sources() {
+        use crate::repository::MemorySource;
+
+        let solutions: MemorySource = [(
+            "SOL-001.md",
+            "---\nid: \"SOL-001\"\ntype: solution\nname: \"Platform\"\n---\n",
+        )]
+        .into_iter()
+        .collect();
+        let use_cases = MemorySource::new("use-cases")
+            .with_document(
+                "UC-001.sara.yaml",
+                "id: UC-001\ntype: use_case\nname: Pairing\nrefines: [SOL-001]\n",
+            )
+            .with_document("notes.md", "# No metadata\n")
+            .with_document("UC-002.md", "---\nid: \"UC-002\ntype: use_case\n---\n");
+
+        let (graph, warnings) =
+            load_graph_from_sources(&[&solutions, &use_cases], &ParseContext::default()).unwrap();
+
+        assert_eq!(graph.item_count(), 2);
+        assert_eq!(graph.relationship_count(), 1);
+        let use_case = graph.lookup_or_suggest("UC-001").unwrap();
+        assert_eq!(use_case.source.repository, Path::new("use-cases"));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, Path::new("use-cases/UC-002.md"));
+    }
+
+    #[test]
+    fn test_load_graph_with_colliding_ids_across_namespaces() {
+        let platform = TempDir::new().unwrap();
+        let product = TempDir::new().unwrap();
+        let solution =
+            |name: &str| format!("---\nid: \"SOL-001\"\ntype: solution\nname: \"{name}\"\n---\n");
+        fs::write(platform.path().join("sol.md"), solution("Platform")).unwrap();
+        fs::write(product.path().join("sol.md"), solution("Product")).unwrap();
+        fs::write(
+            product.path().join("uc.md"),
+            "---\nid: \"UC-001\"\ntype: use_case\nname: \"Use\"\nrefines:\n  - \"SOL-001\"\n---\n",
+        )
+        .unwrap();
+
+        let mut repositories = RepositoryConfig {
+            paths: vec![platform.path().to_path_buf(), product.path().to_path_buf()],
+            ..Default::default()
+        };
+        repositories
+            .namespaces
+            .insert("platform".to_string(), platform.path().to_path_buf());
+        repositories
+            .namespaces
+            .insert("product".to_string(), product.path().to_path_buf());
+
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+
+        assert_eq!(graph.item_count(), 3);
+        let use_case = graph.lookup_or_suggest("product:UC-001").unwrap();
+        assert_eq!(use_case.relationships[0].to.as_str(), "product:SOL-001");
+        assert_eq!(graph.parents(&use_case.id)[0].name, "Product");
+    }
 }
@@ -0,0 +1,470 @@
+//! Rename service for changing the identifier of an item.
+//!
+//! Rewrites the item's own `id`, every frontmatter reference to it and,
+//! unless disabled, the body mentions of it across the documents of the
+//! graph. Frontmatter lines, and the `sara` blocks of items anchored on
+//! Markdown headings, are rewritten in place, so comments, field order and
+//! downstream declarations survive the rename.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::error::SaraError;
+use crate::graph::KnowledgeGraph;
+use crate::model::{FIELD_ID, FieldValue, ItemId, ItemType, RelationshipType};
+use crate::parser::{InputFormat, MentionPattern, body_start, find_anchors};
+use crate::schema::FieldType;
+use crate::service::metadata_lines::{metadata_lines, rewrite_value, rewrite_values};
+
+/// Options for renaming an item.
+#[derive(Debug, Clone)]
+pub struct RenameOptions {
+    /// The item ID to rename.
+    pub item_id: String,
+    /// The new identifier, without namespace.
+    pub new_id: String,
+    /// Whether body mentions of the item are rewritten too.
+    pub update_mentions: bool,
+    /// Pattern mentions are written with.
+    pub mention_pattern: MentionPattern,
+}
+
+impl RenameOptions {
+    /// Creates options renaming an item, body mentions included.
+    pub fn new(item_id: impl Into<String>, new_id: impl Into<String>) -> Self {
+        Self {
+            item_id: item_id.into(),
+            new_id: new_id.into(),
+            update_mentions: true,
+            mention_pattern: MentionPattern::default(),
+        }
+    }
+
+    /// Sets whether body mentions of the item are rewritten.
+    pub fn with_mentions(mut self, update_mentions: bool) -> Self {
+        self.update_mentions = update_mentions;
+        self
+    }
+
+    /// Sets the pattern mentions are written with.
+    pub fn with_mention_pattern(mut self, pattern: MentionPattern) -> Self {
+        self.mention_pattern = pattern;
+        self
+    }
+}
+
+/// Result of a rename.
+#[derive(Debug, Clone)]
+pub struct RenameResult {
+    /// The previous identifier.
+    pub old_id: ItemId,
+    /// The new identifier.
+    pub new_id: ItemId,
+    /// The renamed item's document, moved when its name was the identifier.
+    pub file_path: PathBuf,
+    /// Other documents whose frontmatter references were rewritten.
+    pub references: Vec<PathBuf>,
+    /// Documents whose body mentions were rewritten.
+    pub mentions: Vec<PathBuf>,
+}
+
+/// Service for renaming items.
+#[derive(Debug, Default)]
+pub struct RenameService;
+
+impl RenameService {
+    /// Creates a new rename service.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Renames an item and rewrites the documents referring to it.
+    ///
+    /// The new identifier keeps the item's namespace. A document named after
+    /// the old identifier (e.g. `SYSREQ-001.md`) is moved to the new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SaraError::ItemNotFound`] if the item does not exist,
+    /// [`SaraError::InvalidId`] if the new identifier is malformed,
+    /// [`SaraError::EditFailed`] if it is already taken, or an I/O error if a
+    /// document cannot be rewritten.
+    pub fn rename(
+        &self,
+        graph: &KnowledgeGraph,
+        opts: &RenameOptions,
+    ) -> Result<RenameResult, SaraError> {
+        let item = graph.lookup_or_suggest(&opts.item_id)?;
+        let local = ItemId::new(opts.new_id.as_str())?;
+        let new_id = match item.id.namespace() {
+            Some(namespace) => ItemId::qualified(namespace, local.as_str()),
+            None => local,
+        };
+        if new_id == item.id || graph.contains(&new_id) {
+            return Err(SaraError::EditFailed(format!(
+                "an item with ID {new_id} already exists"
+            )));
+        }
+
+        let renames = [
+            (item.id.as_str().to_string(), new_id.as_str().to_string()),
+            (item.id.local().to_string(), new_id.local().to_string()),
+        ];
+        let mut result = RenameResult {
+            old_id: item.id.clone(),
+            new_id: new_id.clone(),
+            file_path: item.source.full_path(),
+            references: Vec::new(),
+            mentions: Vec::new(),
+        };
+
+        for doc in graph.items() {
+            let is_self = doc.id == item.id;
+            let references = doc.all_references().any(|id| *id == item.id)
+                || doc
+                    .attributes
+                    .iter()
+                    .any(|(_, value)| refers_to(value, &item.id));
+            let mentions = opts.update_mentions && doc.mentions.contains(&item.id);
+            if !(is_self || references || mentions) {
+                continue;
+            }
+
+            let path = doc.source.full_path();
+            let content = fs::read_to_string(&path)?;
+            let format = InputFormat::from_path(&path).unwrap_or(InputFormat::Markdown);
+            let (frontmatter, body) = content.split_at(body_start(&content, format));
+            let frontmatter = if is_self || references {
+                rewrite_frontmatter(frontmatter, doc.item_type, is_self, &renames)
+            } else {
+                frontmatter.to_string()
+            };
+            let body = if mentions {
+                rewrite_mentions(body, &renames, &opts.mention_pattern)
+            } else {
+                body.to_string()
+            };
+            let mut updated = frontmatter + &body;
+            if format == InputFormat::Markdown && (is_self || references) {
+                updated = rewrite_anchors(&updated, graph, &doc.id, &renames);
+            }
+            if updated == content {
+                continue;
+            }
+            fs::write(&path, &updated)?;
+
+            // Items anchored in the same document share its path.
+            if mentions && !is_self && !result.mentions.contains(&path) {
+                result.mentions.push(path.clone());
+            }
+            if references && !is_self && !result.references.contains(&path) {
+                result.references.push(path);
+            }
+        }
+
+        // A document named after its identifier follows the rename, e.g.
+        // `SOL-001.md` or `SOL-001.sara.yaml`, unless it anchors other items.
+        let old_path = item.source.full_path();
+        let file_name = old_path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        let suffix = file_name
+            .strip_prefix(item.id.local())
+            .filter(|rest| rest.is_empty() || rest.starts_with('.'))
+            .filter(|_| item.source.lines.is_none());
+        if let Some(suffix) = suffix {
+            let new_path = old_path.with_file_name(format!("{}{suffix}", new_id.local()));
+            if !new_path.exists() {
+                fs::rename(&old_path, &new_path)?;
+                result.file_path = new_path;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Returns true if an item reference field value names `id`.
+fn refers_to(value: &FieldValue, id: &ItemId) -> bool {
+    match value {
+        FieldValue::ItemRef(target) => target == id,
+        FieldValue::List(values) => values.iter().any(|v| refers_to(v, id)),
+        _ => false,
+    }
+}
+
+/// Rewrites the identifier values of a frontmatter block.
+///
+/// Only the `id` of the renamed item itself, relation lists (including the
+/// `id` of links carrying attributes) and item reference fields are
+/// rewritten; free text such as the name is left alone.
+fn rewrite_frontmatter(
+    frontmatter: &str,
+    item_type: ItemType,
+    is_self: bool,
+    renames: &[(String, String)],
+) -> String {
+    metadata_lines(frontmatter)
+        .iter()
+        .map(|line| {
+            let rewritable = line.key.is_some_and(|key| {
+                (is_self && key == FIELD_ID && line.parent.is_none())
+                    || RelationshipType::from_name(key).is_some()
+                    || (key == FIELD_ID
+                        && line
+                            .parent
+                            .is_some_and(|parent| RelationshipType::from_name(parent).is_some()))
+                    || item_type
+                        .declared_field(key)
+                        .is_some_and(|f| match &f.field_type {
+                            FieldType::ItemRef => true,
+                            FieldType::List(inner) => **inner == FieldType::ItemRef,
+                            _ => false,
+                        })
+            });
+            if rewritable {
+                rewrite_value(line, |value| rewrite_values(value, renames))
+            } else {
+                line.text.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Rewrites the headings and `sara` blocks of the items anchored on the
+/// headings of a Markdown document.
+///
+/// `doc` is one of the items of the document, giving the namespace of the
+/// anchored identifiers. The heading and `id` of an anchored item are only
+/// rewritten for the renamed item itself.
+fn rewrite_anchors(
+    content: &str,
+    graph: &KnowledgeGraph,
+    doc: &ItemId,
+    renames: &[(String, String)],
+) -> String {
+    let (old_local, new_local) = &renames[1];
+    let mut updated = content.to_string();
+    for anchor in find_anchors(content).into_iter().rev() {
+        let id = match doc.namespace() {
+            Some(namespace) => ItemId::qualified(namespace, anchor.id),
+            None => ItemId::new_unchecked(anchor.id),
+        };
+        let Some(anchored) = graph.get(&id) else {
+            continue;
+        };
+        let is_self = anchor.id == old_local.as_str();
+        let block = &content[anchor.block.clone()];
+        updated.replace_range(
+            anchor.block.clone(),
+            &rewrite_frontmatter(block, anchored.item_type, is_self, renames),
+        );
+        if is_self {
+            let heading = &content[anchor.heading.clone()];
+            updated.replace_range(
+                anchor.heading.clone(),
+                &heading.replacen(anchor.id, new_local, 1),
+            );
+        }
+    }
+    updated
+}
+
+/// Rewrites the mentions of a renamed item in a document body.
+fn rewrite_mentions(body: &str, renames: &[(String, String)], pattern: &MentionPattern) -> String {
+    let mut rewritten = body.to_string();
+    for mention in pattern.find(body).into_iter().rev() {
+        if let Some((_, new)) = renames.iter().find(|(old, _)| *old == mention.id) {
+            rewritten.replace_range(mention.range, &pattern.render(new));
+        }
+    }
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    use crate::config::RepositoryConfig;
+    use crate::parser::ParseContext;
+    use crate::service::load_graph;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn test_rename_rewrites_id_references_and_mentions() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(
+            dir,
+            "SOL-001.md",
+            "---\nid: \"SOL-001\"\ntype: solution\nname: \"Hub\"\n\
+             is_refined_by: [\"UC-001\"]\n---\n# Hub\n\nStarts with [[UC-001]].\n",
+        );
+        write(
+            dir,
+            "UC-001.md",
+            "---\nid: \"UC-001\"\ntype: use_case\nname: \"UC-001\"\n\
+             refines:\n  - \"SOL-001\"\n---\n# Use case\n",
+        );
+        write(
+            dir,
+            "SCEN-001.md",
+            "---\nid: SCEN-001\ntype: scenario\nname: \"Scenario\"\n\
+             refines:\n  - UC-001 # the main one\n---\n# Scenario\n\n\
+             See [[UC-001]], not `[[UC-001]]`.\n",
+        );
+        let repositories = RepositoryConfig {
+            paths: vec![dir.to_path_buf()],
+            ..Default::default()
+        };
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+
+        let result = RenameService::new()
+            .rename(&graph, &RenameOptions::new("UC-001", "UC-010"))
+            .unwrap();
+
+        assert_eq!(result.new_id.as_str(), "UC-010");
+        assert_eq!(result.file_path, dir.join("UC-010.md"));
+        assert!(!dir.join("UC-001.md").exists());
+        assert_eq!(result.references.len(), 2);
+        assert_eq!(result.mentions.len(), 2);
+
+        let renamed = fs::read_to_string(dir.join("UC-010.md")).unwrap();
+        assert!(renamed.contains("id: \"UC-010\""));
+        assert!(renamed.contains("name: \"UC-001\""));
+        let solution = fs::read_to_string(dir.join("SOL-001.md")).unwrap();
+        assert!(solution.contains("is_refined_by: [\"UC-010\"]"));
+        assert!(solution.contains("Starts with [[UC-010]]."));
+        let scenario = fs::read_to_string(dir.join("SCEN-001.md")).unwrap();
+        assert!(scenario.contains("  - UC-010 # the main one\n"));
+        assert!(scenario.contains("See [[UC-010]], not `[[UC-001]]`."));
+    }
+
+    #[test]
+    fn test_rename_can_keep_mentions_and_refuses_taken_ids() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(
+            dir,
+            "hub.md",
+            "---\nid: \"SOL-001\"\ntype: solution\nname: \"Hub\"\n---\n# Hub\n",
+        );
+        write(
+            dir,
+            "other.md",
+            "---\nid: \"SOL-002\"\ntype: solution\nname: \"Other\"\n---\nSee [[SOL-001]].\n",
+        );
+        let repositories = RepositoryConfig {
+            paths: vec![dir.to_path_buf()],
+            ..Default::default()
+        };
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+
+        assert!(matches!(
+            RenameService::new().rename(&graph, &RenameOptions::new("SOL-001", "SOL-002")),
+            Err(SaraError::EditFailed(_))
+        ));
+        assert!(matches!(
+            RenameService::new().rename(&graph, &RenameOptions::new("SOL-001", "SOL 3")),
+            Err(SaraError::InvalidId { .. })
+        ));
+
+        let result = RenameService::new()
+            .rename(
+                &graph,
+                &RenameOptions::new("SOL-001", "SOL-003").with_mentions(false),
+            )
+            .unwrap();
+        assert_eq!(result.file_path, dir.join("hub.md"));
+        assert!(result.mentions.is_empty());
+        assert!(
+            fs::read_to_string(dir.join("other.md"))
+                .unwrap()
+                .contains("[[SOL-001]]")
+        );
+    }
+
+    #[test]
+    fn test_rename_in_standalone_item_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(
+            dir,
+            "SOL-001.sara.yaml",
+            "id: SOL-001\ntype: solution\nname: Hub\n",
+        );
+        write(
+            dir,
+            "use-cases.sara.yaml",
+            "- id: UC-001\n  type: use_case\n  name: Pairing\n  refines: [SOL-001]\n\
+             - id: UC-002\n  type: use_case\n  name: Reset\n  refines:\n    - SOL-001\n",
+        );
+        write(
+            dir,
+            "scenarios.sara.toml",
+            "[[items]]\nid = \"SCEN-001\"\ntype = \"scenario\"\nname = \"Pair\"\n\
+             refines = [\n  \"UC-001\",\n]\n",
+        );
+        let repositories = RepositoryConfig {
+            paths: vec![dir.to_path_buf()],
+            ..Default::default()
+        };
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+
+        let result = RenameService::new()
+            .rename(&graph, &RenameOptions::new("SOL-001", "SOL-010"))
+            .unwrap();
+        assert_eq!(result.file_path, dir.join("SOL-010.sara.yaml"));
+        assert!(
+            fs::read_to_string(dir.join("SOL-010.sara.yaml"))
+                .unwrap()
+                .starts_with("id: SOL-010\n")
+        );
+        let use_cases = fs::read_to_string(dir.join("use-cases.sara.yaml")).unwrap();
+        assert!(use_cases.contains("  refines: [SOL-010]\n"));
+        assert!(use_cases.contains("    - SOL-010\n"));
+
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+        RenameService::new()
+            .rename(&graph, &RenameOptions::new("UC-001", "UC-010"))
+            .unwrap();
+        let use_cases = fs::read_to_string(dir.join("use-cases.sara.yaml")).unwrap();
+        assert!(use_cases.starts_with("- id: UC-010\n"));
+        assert!(use_cases.contains("- id: UC-002\n"));
+        let scenarios = fs::read_to_string(dir.join("scenarios.sara.toml")).unwrap();
+        assert!(scenarios.contains("  \"UC-010\",\n"));
+    }
+
+    #[test]
+    fn test_rename_item_anchored_on_a_heading() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(
+            dir,
+            "SOL-001.md",
+            "# Solutions\n\n## SOL-001: Hub\n\n```sara\ntype: solution\n```\n\n\
+             ## UC-001: Pairing\n\n```sara\ntype: use_case\nrefines: [SOL-001]\n```\n",
+        );
+        let repositories = RepositoryConfig {
+            paths: vec![dir.to_path_buf()],
+            ..Default::default()
+        };
+        let (graph, _) = load_graph(&repositories, &ParseContext::default()).unwrap();
+
+        let result = RenameService::new()
+            .rename(&graph, &RenameOptions::new("SOL-001", "SOL-010"))
+            .unwrap();
+
+        assert_eq!(result.file_path, dir.join("SOL-001.md"));
+        assert_eq!(
+            fs::read_to_string(dir.join("SOL-001.md")).unwrap(),
+            "# Solutions\n\n## SOL-010: Hub\n\n```sara\ntype: solution\n```\n\n\
+             ## UC-001: Pairing\n\n```sara\ntype: use_case\nrefines: [SOL-010]\n```\n"
+        );
+    }
+}
@@ -1,8 +1,10 @@
 //! Validation engine and rules for the knowledge graph.
 //!
 //! The validation system uses trait-based rules orchestrated by the [`Validator`].
-//! External code should use [`validate`] or [`pre_validate`] functions.
+//! External code should use [`validate`] or [`pre_validate`] functions, or a
+//! [`Validator`] to run the external plugins of a configuration as well.
 
+mod plugin;
 mod report;
 mod rule;
 mod rules;
@@ -10,4 +12,4 @@ mod validator;
 
 pub use report::{ValidationIssue, ValidationReport};
 pub use rule::Severity;
-pub use validator::{pre_validate, validate};
+pub use validator::{Validator, pre_validate, validate};
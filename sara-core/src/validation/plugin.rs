@@ -0,0 +1,266 @@
+//! External validation plugins.
+//!
+//! A plugin is an executable registered under `[[validation.plugins]]` in
+//! the configuration. It receives the graph as a JSON [`Request`] on stdin
+//! and answers a JSON [`Response`] on stdout:
+//!
+//! ```json
+//! {"issues": [{"severity": "warning", "item": "SYSREQ-001", "message": "..."}]}
+//! ```
+//!
+//! Issues are reported as [`SaraError::PluginIssue`]; a plugin that cannot be
+//! started, exits with a failure status or answers malformed output yields a
+//! single [`SaraError::PluginFailed`] error instead.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use super::report::ValidationIssue;
+use super::rule::Severity;
+use crate::config::PluginConfig;
+use crate::error::SaraError;
+use crate::graph::KnowledgeGraph;
+use crate::model::{Item, ItemId, RelationshipType};
+use crate::schema::Schema;
+
+/// Version of the protocol, sent with every request.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// The graph sent to a plugin.
+#[derive(Debug, Serialize)]
+struct Request<'a> {
+    /// Version of the protocol.
+    version: u32,
+    /// Every item of the graph.
+    items: Vec<&'a Item>,
+    /// Every relationship of the graph.
+    relationships: Vec<Edge<'a>>,
+    /// The schema the graph was read with.
+    schema: &'a Schema,
+}
+
+/// A relationship between two items.
+#[derive(Debug, Serialize)]
+struct Edge<'a> {
+    from: &'a ItemId,
+    to: &'a ItemId,
+    #[serde(rename = "type")]
+    relationship_type: RelationshipType,
+}
+
+/// The answer of a plugin.
+#[derive(Debug, Deserialize)]
+struct Response {
+    #[serde(default)]
+    issues: Vec<Issue>,
+}
+
+/// An issue found by a plugin.
+#[derive(Debug, Deserialize)]
+struct Issue {
+    severity: Severity,
+    #[serde(default)]
+    item: Option<String>,
+    message: String,
+}
+
+impl<'a> Request<'a> {
+    fn new(graph: &'a KnowledgeGraph) -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            items: graph.items().collect(),
+            relationships: graph
+                .relationships()
+                .map(|(from, to, relationship_type)| Edge {
+                    from,
+                    to,
+                    relationship_type,
+                })
+                .collect(),
+            schema: graph.schema(),
+        }
+    }
+}
+
+/// Runs a plugin against the graph and returns the issues it reports.
+pub(super) fn run(plugin: &PluginConfig, graph: &KnowledgeGraph) -> Vec<ValidationIssue> {
+    let failed = |reason: String| {
+        vec![ValidationIssue::error(SaraError::PluginFailed {
+            plugin: plugin.name.clone(),
+            reason,
+        })]
+    };
+
+    let request = match serde_json::to_vec(&Request::new(graph)) {
+        Ok(request) => request,
+        Err(e) => return failed(format!("cannot serialize the graph: {e}")),
+    };
+    let output = match exchange(plugin, &request) {
+        Ok(output) => output,
+        Err(reason) => return failed(reason),
+    };
+    match serde_json::from_slice::<Response>(&output) {
+        Ok(response) => response
+            .issues
+            .into_iter()
+            .map(|issue| {
+                let error = SaraError::PluginIssue {
+                    plugin: plugin.name.clone(),
+                    item: issue.item.map(ItemId::new_unchecked),
+                    message: issue.message,
+                };
+                match issue.severity {
+                    Severity::Error => ValidationIssue::error(error),
+                    Severity::Warning => ValidationIssue::warning(error),
+                }
+            })
+            .collect(),
+        Err(e) => failed(format!("invalid response: {e}")),
+    }
+}
+
+/// Starts the plugin, writes the request to its stdin and returns its
+/// stdout.
+fn exchange(plugin: &PluginConfig, request: &[u8]) -> Result<Vec<u8>, String> {
+    let mut child = Command::new(&plugin.command)
+        .args(&plugin.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot run `{}`: {e}", plugin.command))?;
+
+    // Write from another thread, so a plugin answering before it has read
+    // the whole request cannot deadlock on a full pipe.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let output = thread::scope(|scope| {
+        scope.spawn(move || {
+            // A plugin may ignore its input and close stdin early.
+            let _ = stdin.write_all(request);
+        });
+        child.wait_with_output()
+    })
+    .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.trim() {
+            "" => format!("exited with {}", output.status),
+            stderr => format!("exited with {}: {stderr}", output.status),
+        });
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::graph::KnowledgeGraphBuilder;
+    use crate::model::Relationship;
+    use crate::schema::builtin;
+    use crate::test_utils::{create_test_item, create_test_item_with_relationships};
+
+    fn graph() -> KnowledgeGraph {
+        KnowledgeGraphBuilder::new()
+            .add_item(create_test_item("SOL-001", builtin::SOLUTION))
+            .add_item(create_test_item_with_relationships(
+                "UC-001",
+                builtin::USE_CASE,
+                vec![Relationship::new(
+                    ItemId::new_unchecked("SOL-001"),
+                    builtin::REFINES,
+                )],
+            ))
+            .build()
+            .unwrap()
+    }
+
+    fn shell(name: &str, script: &str) -> PluginConfig {
+        PluginConfig {
+            name: name.to_string(),
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+        }
+    }
+
+    #[test]
+    fn test_request_holds_items_relationships_and_schema() {
+        let graph = graph();
+        let request = serde_json::to_value(Request::new(&graph)).unwrap();
+
+        assert_eq!(request["version"], PROTOCOL_VERSION);
+        assert_eq!(request["items"].as_array().unwrap().len(), 2);
+        assert!(
+            request["relationships"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|edge| edge["from"] == "UC-001"
+                    && edge["to"] == "SOL-001"
+                    && edge["type"] == "refines")
+        );
+        assert!(request["schema"]["item_types"].is_array());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_issues_keep_their_severity() {
+        let plugin = shell(
+            "naming",
+            r#"grep -q '"UC-001"' && echo '{"issues": [
+                {"severity": "error", "item": "UC-001", "message": "Name must be a verb phrase"},
+                {"severity": "warning", "message": "Glossary unavailable"}
+            ]}'"#,
+        );
+
+        let issues = run(&plugin, &graph());
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(
+            issues[0].error.to_string(),
+            "naming: UC-001: Name must be a verb phrase"
+        );
+        assert_eq!(issues[1].severity, Severity::Warning);
+        assert_eq!(issues[1].error.to_string(), "naming: Glossary unavailable");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_failing_plugin_is_reported_as_an_error() {
+        for (script, reason) in [
+            ("echo 'database down' >&2; exit 3", "database down"),
+            ("echo 'not json'", "invalid response"),
+        ] {
+            let issues = run(&shell("glossary", script), &graph());
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].severity, Severity::Error);
+            assert!(
+                matches!(&issues[0].error, SaraError::PluginFailed { plugin, reason: r }
+                    if plugin == "glossary" && r.contains(reason)),
+                "{}",
+                issues[0].error
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_executable_is_reported_as_an_error() {
+        let plugin = PluginConfig {
+            name: "missing".to_string(),
+            command: "sara-plugin-that-does-not-exist".to_string(),
+            args: Vec::new(),
+        };
+        let issues = run(&plugin, &graph());
+        assert!(matches!(
+            &issues[..],
+            [ValidationIssue {
+                severity: Severity::Error,
+                error: SaraError::PluginFailed { .. },
+            }]
+        ));
+    }
+}
@@ -1,14 +1,15 @@
 //! Validation rule trait definition.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::config::ValidationConfig;
 use crate::error::SaraError;
 use crate::graph::KnowledgeGraph;
 use crate::model::Item;
+use crate::schema::Schema;
 
 /// Severity level for validation issues.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// Validation error that blocks acceptance.
@@ -26,11 +27,17 @@ pub trait ValidationRule: Send + Sync {
     ///
     /// This enables fail-fast validation during parsing/loading. Rules that
     /// only need individual items (not relationships to other items) should
-    /// implement this method.
+    /// implement this method, resolving item types against `schema`: the
+    /// schema the items were parsed with.
     ///
     /// Rules that require the full graph context (e.g., cycle detection,
     /// broken references) should return an empty vector.
-    fn pre_validate(&self, _items: &[Item], _config: &ValidationConfig) -> Vec<SaraError> {
+    fn pre_validate(
+        &self,
+        _items: &[Item],
+        _schema: &Schema,
+        _config: &ValidationConfig,
+    ) -> Vec<SaraError> {
         Vec::new()
     }
 
@@ -0,0 +1,96 @@
+//! Deprecated alias validation rule.
+
+use crate::config::ValidationConfig;
+use crate::error::SaraError;
+use crate::graph::KnowledgeGraph;
+use crate::model::Item;
+use crate::validation::rule::{Severity, ValidationRule};
+
+/// Deprecated alias rule.
+///
+/// Reports every type, field or relation a document names through a schema
+/// alias. The document still parses; authors should switch to the canonical
+/// name before the alias is dropped from the schema.
+///
+/// Default severity is Warning, but in strict mode all warnings become errors.
+/// Only the graph is checked: warnings do not stop pre-validation, so
+/// reporting them from both hooks would list every alias use twice.
+pub struct AliasesRule;
+
+impl ValidationRule for AliasesRule {
+    fn validate(&self, graph: &KnowledgeGraph, _config: &ValidationConfig) -> Vec<SaraError> {
+        graph.items().flat_map(alias_warnings).collect()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+/// Returns a warning for each alias an item used.
+fn alias_warnings(item: &Item) -> Vec<SaraError> {
+    item.aliases
+        .iter()
+        .map(|alias| SaraError::DeprecatedAlias {
+            file: item.source.to_string(),
+            id: item.id.clone(),
+            alias: alias.alias.clone(),
+            canonical: alias.canonical.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::graph::KnowledgeGraphBuilder;
+    use crate::model::AliasUse;
+    use crate::schema::{self, builtin};
+    use crate::test_utils::create_test_item;
+    use crate::validation::{pre_validate, validate};
+
+    fn items_with_alias() -> Vec<Item> {
+        let mut item = create_test_item("SYSREQ-001", builtin::SYSTEM_REQUIREMENT);
+        item.aliases.push(AliasUse {
+            alias: "implements".to_string(),
+            canonical: "satisfies".to_string(),
+        });
+        vec![item, create_test_item("SOL-001", builtin::SOLUTION)]
+    }
+
+    #[test]
+    fn test_alias_use_is_reported() {
+        let graph = KnowledgeGraphBuilder::new()
+            .add_items(items_with_alias())
+            .build()
+            .unwrap();
+
+        let warnings = AliasesRule.validate(&graph, &ValidationConfig::default());
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            &warnings[0],
+            SaraError::DeprecatedAlias { id, alias, canonical, .. }
+                if id.as_str() == "SYSREQ-001" && alias == "implements" && canonical == "satisfies"
+        ));
+    }
+
+    #[test]
+    fn test_alias_use_is_reported_once_across_pre_validation_and_validation() {
+        let items = items_with_alias();
+        let pre_report = pre_validate(&items, schema::active(), false);
+        let graph = KnowledgeGraphBuilder::new()
+            .add_items(items)
+            .build()
+            .unwrap();
+        let mut report = validate(&graph, false);
+        report.merge(pre_report);
+
+        let aliases = report
+            .warnings()
+            .into_iter()
+            .filter(|w| matches!(w, SaraError::DeprecatedAlias { .. }))
+            .count();
+        assert_eq!(aliases, 1);
+    }
+}
@@ -8,7 +8,8 @@ use crate::validation::rule::ValidationRule;
 /// Broken reference detection rule.
 ///
 /// Detects broken references in the knowledge graph. A broken reference
-/// occurs when an item references another item that does not exist in the graph.
+/// occurs when an item references, or mentions in its body, another item that
+/// does not exist in the graph.
 pub struct BrokenReferencesRule;
 
 impl ValidationRule for BrokenReferencesRule {
@@ -24,6 +25,14 @@ impl ValidationRule for BrokenReferencesRule {
                     });
                 }
             }
+            for mentioned in &item.mentions {
+                if !graph.contains(mentioned) {
+                    errors.push(SaraError::BrokenMention {
+                        from: item.id.clone(),
+                        to: mentioned.clone(),
+                    });
+                }
+            }
         }
 
         errors
@@ -84,4 +93,27 @@ mod tests {
             panic!("Expected BrokenReference error");
         }
     }
+
+    #[test]
+    fn test_broken_mention_detected() {
+        let mut use_case = create_test_item("UC-001", builtin::USE_CASE);
+        use_case.mentions = vec![
+            ItemId::new_unchecked("SOL-001"),
+            ItemId::new_unchecked("SOL-MISSING"),
+        ];
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(create_test_item("SOL-001", builtin::SOLUTION))
+            .add_item(use_case)
+            .build()
+            .unwrap();
+
+        let rule = BrokenReferencesRule;
+        let errors = rule.validate(&graph, &ValidationConfig::default());
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            SaraError::BrokenMention { from, to }
+                if from.as_str() == "UC-001" && to.as_str() == "SOL-MISSING"
+        ));
+    }
 }
@@ -6,30 +6,42 @@ use petgraph::visit::EdgeFiltered;
 use crate::config::ValidationConfig;
 use crate::error::SaraError;
 use crate::graph::KnowledgeGraph;
+use crate::model::RelationshipType;
+use crate::schema::Schema;
 use crate::validation::rule::ValidationRule;
 
 /// Circular reference detection rule.
 ///
-/// Uses Tarjan's strongly connected components algorithm to find cycles.
-/// Only considers primary relationships (not inverse edges) when detecting
-/// cycles, since inverse edges are just for graph traversal and don't
-/// represent logical cycles.
+/// Uses Tarjan's strongly connected components algorithm to find cycles,
+/// separately for each relation the schema declares `acyclic` (the default).
+/// Only primary edges are considered, since inverse edges are just for graph
+/// traversal and don't represent logical cycles.
 pub struct CyclesRule;
 
 impl ValidationRule for CyclesRule {
     fn validate(&self, graph: &KnowledgeGraph, _config: &ValidationConfig) -> Vec<SaraError> {
-        let mut errors = Vec::new();
-        let inner = graph.inner();
+        cycle_errors(graph, graph.schema())
+    }
+}
+
+/// Reports the cycles formed by the links of each acyclic relation.
+fn cycle_errors(graph: &KnowledgeGraph, schema: &Schema) -> Vec<SaraError> {
+    let mut errors = Vec::new();
+    let inner = graph.inner();
 
-        // Filter the graph to only include primary relationships for cycle detection.
-        // Inverse relationships (IsRefinedBy, Derives, IsSatisfiedBy, etc.) are excluded
-        // because they're just for traversal and would cause false positives.
-        let filtered = EdgeFiltered::from_fn(inner, |edge| edge.weight().is_primary());
+    let acyclic = schema
+        .relations
+        .iter()
+        .filter(|rel| rel.primary && rel.acyclic)
+        .filter_map(|rel| RelationshipType::from_id_in(&rel.id, schema));
 
-        // Find strongly connected components on the filtered graph
-        let sccs = tarjan_scc(&filtered);
+    for relation in acyclic {
+        // Only the primary edges of this relation: inverse relationships
+        // (IsRefinedBy, Derives, IsSatisfiedBy, etc.) are just for traversal
+        // and would cause false positives.
+        let filtered = EdgeFiltered::from_fn(inner, |edge| *edge.weight() == relation);
 
-        for scc in sccs {
+        for scc in tarjan_scc(&filtered) {
             if scc.len() >= 2 {
                 // SCC with 2+ nodes indicates a cycle
                 let cycle_ids: Vec<String> = scc
@@ -42,11 +54,11 @@ impl ValidationRule for CyclesRule {
 
                 errors.push(SaraError::CircularReference { cycle: cycle_str });
             } else if scc.len() == 1 {
-                // Check for self-loop (only with primary relationships)
+                // Check for self-loop of this relation
                 let idx = scc[0];
                 let has_self_loop = inner
                     .edges_connecting(idx, idx)
-                    .any(|e| e.weight().is_primary());
+                    .any(|e| *e.weight() == relation);
 
                 if has_self_loop && let Some(item) = inner.node_weight(idx) {
                     errors.push(SaraError::CircularReference {
@@ -55,9 +67,9 @@ impl ValidationRule for CyclesRule {
                 }
             }
         }
-
-        errors
     }
+
+    errors
 }
 
 /// Checks if adding an edge would create a cycle.
@@ -231,6 +243,32 @@ mod tests {
         assert!(!errors.is_empty(), "supersedes cycle should be detected");
     }
 
+    #[test]
+    fn test_relation_declared_cyclic_is_not_checked() {
+        let adr = |id: &str, superseded: &str| {
+            create_test_item_with_relationships(
+                id,
+                builtin::ARCHITECTURE_DECISION_RECORD,
+                vec![Relationship::new(
+                    ItemId::new_unchecked(superseded),
+                    builtin::SUPERSEDES,
+                )],
+            )
+        };
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(adr("ADR-001", "ADR-002"))
+            .add_item(adr("ADR-002", "ADR-001"))
+            .build()
+            .unwrap();
+
+        let mut schema = Schema::builtin();
+        assert_eq!(cycle_errors(&graph, &schema).len(), 1);
+        for relation in &mut schema.relations {
+            relation.acyclic = relation.id != builtin::SUPERSEDES.as_str();
+        }
+        assert!(cycle_errors(&graph, &schema).is_empty());
+    }
+
     #[test]
     fn test_peer_self_reference_detected() {
         let req = create_test_item_with_relationships(
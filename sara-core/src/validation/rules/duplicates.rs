@@ -5,31 +5,44 @@ use std::collections::HashMap;
 use crate::config::ValidationConfig;
 use crate::error::SaraError;
 use crate::model::Item;
+use crate::schema::Schema;
 use crate::validation::rule::ValidationRule;
 
 /// Duplicate identifier detection rule.
 ///
-/// Each item ID must be unique across all repositories.
+/// Each item ID must be unique across all repositories. Items of namespaced
+/// repositories are compared by their qualified ID, so several namespaces
+/// may reuse the same local ID. A file defining several items may clash with
+/// itself; the error lists every location, with its line when known.
 /// This rule only implements pre-validation since the graph itself prevents
 /// duplicates by using a HashMap. Pre-validation catches duplicates before
 /// items are added to the graph.
 pub struct DuplicatesRule;
 
 impl ValidationRule for DuplicatesRule {
-    fn pre_validate(&self, items: &[Item], _config: &ValidationConfig) -> Vec<SaraError> {
-        // Count occurrences of each ID
-        let mut id_counts: HashMap<&str, usize> = HashMap::new();
+    fn pre_validate(
+        &self,
+        items: &[Item],
+        _schema: &Schema,
+        _config: &ValidationConfig,
+    ) -> Vec<SaraError> {
+        // Collect the definitions of each ID
+        let mut definitions: HashMap<&str, Vec<String>> = HashMap::new();
 
         for item in items {
-            *id_counts.entry(item.id.as_str()).or_default() += 1;
+            definitions
+                .entry(item.id.as_str())
+                .or_default()
+                .push(item.source.to_string());
         }
 
-        // Report duplicates (IDs with more than one occurrence)
-        id_counts
+        // Report duplicates (IDs with more than one definition)
+        definitions
             .into_iter()
-            .filter(|(_, count)| *count > 1)
-            .map(|(id, _)| SaraError::DuplicateIdentifier {
+            .filter(|(_, locations)| locations.len() > 1)
+            .map(|(id, locations)| SaraError::DuplicateIdentifier {
                 id: crate::model::ItemId::new_unchecked(id),
+                locations: locations.join(", "),
             })
             .collect()
     }
@@ -39,7 +52,7 @@ impl ValidationRule for DuplicatesRule {
 mod tests {
     use super::*;
 
-    use crate::schema::builtin;
+    use crate::schema::{self, builtin};
 
     use crate::test_utils::create_test_item_at;
 
@@ -51,7 +64,19 @@ mod tests {
         ];
 
         let rule = DuplicatesRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_same_local_id_in_different_namespaces() {
+        let items = vec![
+            create_test_item_at("platform:SOL-001", builtin::SOLUTION, "sol1.md"),
+            create_test_item_at("product:SOL-001", builtin::SOLUTION, "sol1.md"),
+        ];
+
+        let rule = DuplicatesRule;
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert!(errors.is_empty());
     }
 
@@ -63,16 +88,37 @@ mod tests {
         ];
 
         let rule = DuplicatesRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert_eq!(errors.len(), 1);
 
-        if let SaraError::DuplicateIdentifier { id } = &errors[0] {
+        if let SaraError::DuplicateIdentifier { id, locations } = &errors[0] {
             assert_eq!(id.as_str(), "SOL-001");
+            assert_eq!(locations, "sol1.md, sol2.md");
         } else {
             panic!("Expected DuplicateIdentifier error");
         }
     }
 
+    #[test]
+    fn test_duplicate_within_one_file() {
+        let mut first = create_test_item_at("SWREQ-010", builtin::SOFTWARE_REQUIREMENT, "srs.md");
+        first.source = first.source.with_lines(3..10);
+        let mut second = create_test_item_at("SWREQ-010", builtin::SOFTWARE_REQUIREMENT, "srs.md");
+        second.source = second.source.with_lines(10..18);
+
+        let errors = DuplicatesRule.pre_validate(
+            &[first, second],
+            schema::active(),
+            &ValidationConfig::default(),
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0]
+                .to_string()
+                .ends_with("defined in srs.md:3, srs.md:10")
+        );
+    }
+
     #[test]
     fn test_multiple_duplicates_same_id() {
         let items = vec![
@@ -82,7 +128,7 @@ mod tests {
         ];
 
         let rule = DuplicatesRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert_eq!(errors.len(), 1, "Should be one error for one duplicate ID");
     }
 
@@ -96,7 +142,7 @@ mod tests {
         ];
 
         let rule = DuplicatesRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert_eq!(errors.len(), 2, "Should detect two different duplicate IDs");
     }
 }
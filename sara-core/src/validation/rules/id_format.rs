@@ -4,13 +4,13 @@ use crate::config::ValidationConfig;
 use crate::error::SaraError;
 use crate::graph::KnowledgeGraph;
 use crate::model::Item;
-use crate::schema::{self, IdFormat};
+use crate::schema::{IdFormat, Schema};
 use crate::validation::rule::{Severity, ValidationRule};
 
 /// Identifier format conformance rule.
 ///
 /// Warns when an item's id does not match the `id_format` its type declares
-/// in the active schema. Temporal and unique placeholders are matched by
+/// in the graph schema. Temporal and unique placeholders are matched by
 /// shape, so an id generated under a previous period (or any UUID) stays
 /// conformant.
 ///
@@ -19,7 +19,10 @@ pub struct IdFormatRule;
 
 impl ValidationRule for IdFormatRule {
     fn validate(&self, graph: &KnowledgeGraph, _config: &ValidationConfig) -> Vec<SaraError> {
-        graph.items().filter_map(check_item).collect()
+        graph
+            .items()
+            .filter_map(|item| check_item(item, graph.schema()))
+            .collect()
     }
 
     fn severity(&self) -> Severity {
@@ -27,9 +30,9 @@ impl ValidationRule for IdFormatRule {
     }
 }
 
-/// Checks one item's id against its type's format.
-fn check_item(item: &Item) -> Option<SaraError> {
-    let def = schema::item_type_def(item.item_type.as_str())?;
+/// Checks one item's id against the format of its type in `schema`.
+fn check_item(item: &Item, schema: &Schema) -> Option<SaraError> {
+    let def = schema.item_type(item.item_type.as_str())?;
     let format = IdFormat::parse(&def.id_format).ok()?;
     if format.matches(item.id.as_str(), &def.prefix, &def.id) {
         return None;
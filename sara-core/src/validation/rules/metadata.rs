@@ -4,6 +4,7 @@ use crate::config::ValidationConfig;
 use crate::error::SaraError;
 use crate::graph::KnowledgeGraph;
 use crate::model::{FieldValue, Item};
+use crate::schema::Schema;
 use crate::validation::rule::ValidationRule;
 
 /// Field whose text is checked for RFC2119 requirement keywords.
@@ -30,6 +31,8 @@ const RFC2119_KEYWORDS: &[&str] = &[
 /// Checks:
 /// - Required fields are present (id, type, name already enforced by parsing)
 /// - Every field the schema marks as required carries a non-empty value
+/// - Every body section the schema marks as required is present and non-empty
+/// - Every link carries the link attributes its relation marks as required
 /// - A specification text contains at least one RFC2119 keyword
 ///
 /// This rule supports pre-validation (fail-fast) since it only examines
@@ -37,38 +40,91 @@ const RFC2119_KEYWORDS: &[&str] = &[
 pub struct MetadataRule;
 
 impl ValidationRule for MetadataRule {
-    fn pre_validate(&self, items: &[Item], _config: &ValidationConfig) -> Vec<SaraError> {
-        items.iter().flat_map(validate_item_metadata).collect()
+    fn pre_validate(
+        &self,
+        items: &[Item],
+        schema: &Schema,
+        _config: &ValidationConfig,
+    ) -> Vec<SaraError> {
+        items
+            .iter()
+            .flat_map(|item| validate_item_metadata(item, schema))
+            .collect()
     }
 
     fn validate(&self, graph: &KnowledgeGraph, _config: &ValidationConfig) -> Vec<SaraError> {
-        graph.items().flat_map(validate_item_metadata).collect()
+        graph
+            .items()
+            .flat_map(|item| validate_item_metadata(item, graph.schema()))
+            .collect()
     }
 }
 
-/// Validates metadata for a single item.
-fn validate_item_metadata(item: &Item) -> Vec<SaraError> {
+/// Validates metadata for a single item against `schema`.
+fn validate_item_metadata(item: &Item, schema: &Schema) -> Vec<SaraError> {
     let mut errors = Vec::new();
+    let type_name = item.item_type.display_name_in(schema);
 
     // Every required declared field must carry a non-empty value.
     for field in item
         .item_type
-        .declared_fields()
+        .declared_fields_in(schema)
         .iter()
         .filter(|f| f.required)
     {
         if item.attributes.get(&field.name).is_none_or(is_empty_value) {
+            errors.push(SaraError::InvalidMetadata {
+                file: item.source.file_path.display().to_string(),
+                reason: format!("{} requires a non-empty '{}' field", type_name, field.name),
+            });
+        }
+    }
+
+    // Every required body section must be present with some content.
+    for section in item
+        .item_type
+        .declared_sections_in(schema)
+        .iter()
+        .filter(|s| s.required)
+    {
+        if item
+            .attributes
+            .get(&section.field)
+            .is_none_or(is_empty_value)
+        {
             errors.push(SaraError::InvalidMetadata {
                 file: item.source.file_path.display().to_string(),
                 reason: format!(
-                    "{} requires a non-empty '{}' field",
-                    item.item_type.display_name(),
-                    field.name
+                    "{} requires a non-empty '{}' section (required section missing or empty)",
+                    type_name, section.heading
                 ),
             });
         }
     }
 
+    // Every link must carry the attributes its relation requires.
+    for rel in &item.relationships {
+        for attribute in schema
+            .link_attributes(rel.relationship_type.as_str())
+            .iter()
+            .filter(|a| a.required)
+        {
+            if rel
+                .attributes
+                .get(&attribute.name)
+                .is_none_or(is_empty_value)
+            {
+                errors.push(SaraError::InvalidMetadata {
+                    file: item.source.file_path.display().to_string(),
+                    reason: format!(
+                        "{} link '{}' to {} requires a non-empty '{}' attribute",
+                        item.id, rel.relationship_type, rel.to, attribute.name
+                    ),
+                });
+            }
+        }
+    }
+
     // Requirement-writing quality: a specification text must state its
     // obligation level with an RFC2119 keyword.
     if let Some(FieldValue::Text(spec)) = item.attributes.get(FIELD_SPECIFICATION)
@@ -79,7 +135,7 @@ fn validate_item_metadata(item: &Item) -> Vec<SaraError> {
             file: item.source.file_path.display().to_string(),
             reason: format!(
                 "{} specification must contain at least one RFC2119 keyword (MUST, SHALL, SHOULD, etc.)",
-                item.item_type.display_name()
+                type_name
             ),
         });
     }
@@ -90,9 +146,12 @@ fn validate_item_metadata(item: &Item) -> Vec<SaraError> {
 /// Returns true when a field value carries no information.
 fn is_empty_value(value: &FieldValue) -> bool {
     match value {
-        FieldValue::Text(s) | FieldValue::Enum(s) | FieldValue::Date(s) => s.is_empty(),
+        FieldValue::Text(s) | FieldValue::Enum(s) | FieldValue::Date(s) | FieldValue::Url(s) => {
+            s.is_empty()
+        }
         FieldValue::ItemRef(id) => id.as_str().is_empty(),
         FieldValue::List(values) => values.is_empty(),
+        FieldValue::Number(_) | FieldValue::Integer(_) | FieldValue::Boolean(_) => false,
     }
 }
 
@@ -116,7 +175,7 @@ mod tests {
 
     use crate::graph::KnowledgeGraphBuilder;
     use crate::model::{ItemBuilder, ItemId, ItemType, SourceLocation};
-    use crate::schema::builtin;
+    use crate::schema::{self, builtin};
 
     fn create_item_with_spec(id: &str, item_type: ItemType, spec: &str) -> crate::model::Item {
         let source = SourceLocation::new(PathBuf::from("/repo"), format!("{}.md", id));
@@ -246,7 +305,7 @@ mod tests {
         )];
 
         let rule = MetadataRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert!(errors.is_empty());
     }
 
@@ -259,7 +318,7 @@ mod tests {
         )];
 
         let rule = MetadataRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert_eq!(errors.len(), 1);
         assert!(matches!(
             &errors[0],
@@ -276,7 +335,7 @@ mod tests {
         )];
 
         let rule = MetadataRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert_eq!(errors.len(), 1);
         assert!(matches!(
             &errors[0],
@@ -297,7 +356,7 @@ mod tests {
             .unwrap();
 
         let rule = MetadataRule;
-        let errors = rule.pre_validate(&[item], &ValidationConfig::default());
+        let errors = rule.pre_validate(&[item], schema::active(), &ValidationConfig::default());
         assert!(
             errors.is_empty(),
             "Solution should not require specification"
@@ -325,7 +384,7 @@ mod tests {
         ];
 
         let rule = MetadataRule;
-        let errors = rule.pre_validate(&items, &ValidationConfig::default());
+        let errors = rule.pre_validate(&items, schema::active(), &ValidationConfig::default());
         assert_eq!(errors.len(), 1, "Should detect one invalid item");
         assert!(matches!(
             &errors[0],
@@ -3,6 +3,7 @@
 //! Each rule is a struct implementing the [`ValidationRule`](super::rule::ValidationRule) trait.
 //! The validator orchestrates running all rules and collecting results.
 
+mod aliases;
 mod broken_refs;
 mod cycles;
 mod duplicates;
@@ -13,6 +14,7 @@ mod redundant;
 mod relationships;
 
 // Export rule structs for the validator
+pub use aliases::AliasesRule;
 pub use broken_refs::BrokenReferencesRule;
 pub use cycles::CyclesRule;
 pub use duplicates::DuplicatesRule;
@@ -2,10 +2,15 @@
 
 use std::collections::HashSet;
 
+use petgraph::algo::has_path_connecting;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{EdgeFiltered, EdgeRef};
+
 use crate::config::ValidationConfig;
 use crate::error::SaraError;
 use crate::graph::KnowledgeGraph;
-use crate::model::ItemId;
+use crate::model::{ItemId, RelationshipType};
+use crate::schema::Schema;
 use crate::validation::rule::{Severity, ValidationRule};
 
 /// Redundant relationship detection rule (warning).
@@ -14,23 +19,29 @@ use crate::validation::rule::{Severity, ValidationRule};
 /// For example, if SARCH-001 has `satisfies: [SYSREQ-00001]` and SYSREQ-00001
 /// has `is_satisfied_by: [SARCH-001]`, this is redundant - only one declaration
 /// is needed since the inverse is automatically inferred.
+///
+/// Also reports, for relations the schema declares `transitive`, direct links
+/// already implied by a chain of links: if SWREQ-001 depends on SWREQ-002,
+/// which depends on SWREQ-003, declaring that SWREQ-001 depends on SWREQ-003
+/// is not needed.
 pub struct RedundantRelationshipsRule;
 
 impl ValidationRule for RedundantRelationshipsRule {
     fn validate(&self, graph: &KnowledgeGraph, _config: &ValidationConfig) -> Vec<SaraError> {
         let mut errors = Vec::new();
         let mut seen_pairs: HashSet<(String, String)> = HashSet::new();
+        let schema = graph.schema();
 
         for item in graph.items() {
             // Check downstream declarations against upstream declarations in target items
             // For each downstream relationship on this item, check if the target has the
             // corresponding upstream relationship back to this item.
             for rel in &item.relationships {
-                if !rel.relationship_type.is_downstream() {
+                if !rel.relationship_type.is_downstream_in(schema) {
                     continue;
                 }
 
-                let inverse_type = rel.relationship_type.inverse();
+                let inverse_type = rel.relationship_type.inverse_in(schema);
 
                 if let Some(target) = graph.get(&rel.to) {
                     let has_inverse = target
@@ -51,6 +62,7 @@ impl ValidationRule for RedundantRelationshipsRule {
             }
         }
 
+        errors.extend(implied_errors(graph, schema));
         errors
     }
 
@@ -59,6 +71,55 @@ impl ValidationRule for RedundantRelationshipsRule {
     }
 }
 
+/// Reports the direct links of transitive relations that a chain of links
+/// through another target already implies.
+fn implied_errors(graph: &KnowledgeGraph, schema: &Schema) -> Vec<SaraError> {
+    let mut errors = Vec::new();
+    let inner = graph.inner();
+
+    let transitive = schema
+        .relations
+        .iter()
+        .filter(|rel| rel.primary && rel.transitive)
+        .filter_map(|rel| RelationshipType::from_id_in(&rel.id, schema));
+    for relation in transitive {
+        let filtered = EdgeFiltered::from_fn(inner, |edge| *edge.weight() == relation);
+        for from in inner.node_indices() {
+            let mut targets: Vec<NodeIndex> = inner
+                .edges(from)
+                .filter(|edge| *edge.weight() == relation && edge.target() != from)
+                .map(|edge| edge.target())
+                .collect();
+            targets.sort();
+            targets.dedup();
+
+            for &to in &targets {
+                let Some(&via) = targets
+                    .iter()
+                    .find(|&&via| via != to && has_path_connecting(&filtered, via, to, None))
+                else {
+                    continue;
+                };
+                let (Some(source), Some(target), Some(via)) = (
+                    inner.node_weight(from),
+                    inner.node_weight(to),
+                    inner.node_weight(via),
+                ) else {
+                    continue;
+                };
+                errors.push(SaraError::ImpliedRelationship {
+                    from_id: source.id.clone(),
+                    to_id: target.id.clone(),
+                    relation,
+                    via: via.id.clone(),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
 /// Creates a canonical pair key for deduplication (smaller ID first).
 fn make_pair_key(id1: &ItemId, id2: &ItemId) -> (String, String) {
     let s1 = id1.as_str();
@@ -138,4 +199,39 @@ mod tests {
             if from_id.as_str() == "SYSREQ-001" && to_id.as_str() == "SARCH-001"
         ));
     }
+
+    #[test]
+    fn test_link_implied_by_transitive_relation() {
+        let depends_on = |id: &str, targets: &[&str]| {
+            create_test_item_with_relationships(
+                id,
+                builtin::SOFTWARE_REQUIREMENT,
+                targets
+                    .iter()
+                    .map(|t| Relationship::new(ItemId::new_unchecked(*t), builtin::DEPENDS_ON))
+                    .collect(),
+            )
+        };
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(depends_on("SWREQ-001", &["SWREQ-002", "SWREQ-003"]))
+            .add_item(depends_on("SWREQ-002", &["SWREQ-003"]))
+            .add_item(create_test_item("SWREQ-003", builtin::SOFTWARE_REQUIREMENT))
+            .build()
+            .unwrap();
+
+        let mut schema = Schema::builtin();
+        assert!(implied_errors(&graph, &schema).is_empty());
+        for relation in &mut schema.relations {
+            relation.transitive = relation.id == builtin::DEPENDS_ON.as_str();
+        }
+        let warnings = implied_errors(&graph, &schema);
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            &warnings[0],
+            SaraError::ImpliedRelationship { from_id, to_id, via, .. }
+            if from_id.as_str() == "SWREQ-001"
+                && to_id.as_str() == "SWREQ-003"
+                && via.as_str() == "SWREQ-002"
+        ));
+    }
 }
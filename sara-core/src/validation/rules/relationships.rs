@@ -1,9 +1,13 @@
 //! Relationship type validation rule.
 
+use petgraph::Direction;
+use petgraph::visit::EdgeRef;
+
 use crate::config::ValidationConfig;
 use crate::error::SaraError;
 use crate::graph::KnowledgeGraph;
-use crate::model::{Item, ItemId, RelationshipRules, RelationshipType};
+use crate::model::{Item, ItemId, RelationshipType};
+use crate::schema::Schema;
 use crate::validation::rule::ValidationRule;
 
 /// Relationship type validation rule.
@@ -13,6 +17,10 @@ use crate::validation::rule::ValidationRule;
 /// - UseCase can only refine Solution
 /// - Scenario can only refine UseCase
 /// - SystemRequirement can only derive_from Scenario
+///
+/// Also enforces the structural constraints declared by the schema: the
+/// `min`/`max` number of links per relation and type, and relations whose
+/// targets may be linked from a single item (`unique_target`).
 pub struct RelationshipsRule;
 
 impl ValidationRule for RelationshipsRule {
@@ -23,10 +31,102 @@ impl ValidationRule for RelationshipsRule {
             errors.extend(validate_item_relationships(graph, item));
         }
 
+        let schema = graph.schema();
+        errors.extend(cardinality_errors(graph, schema));
+        errors.extend(shared_target_errors(graph, schema));
+
         errors
     }
 }
 
+/// Checks every item against the number of links its type allows per
+/// relation.
+fn cardinality_errors(graph: &KnowledgeGraph, schema: &Schema) -> Vec<SaraError> {
+    let mut errors = Vec::new();
+
+    for item in graph.items() {
+        let Some(def) = schema.item_type(item.item_type.as_str()) else {
+            continue;
+        };
+        for allowed in &def.allowed_targets {
+            let Some(expected) = allowed.cardinality() else {
+                continue;
+            };
+            let Some(relation) = RelationshipType::from_id_in(&allowed.relation, schema) else {
+                continue;
+            };
+            let count = linked_items(graph, &item.id, relation, Direction::Outgoing).len();
+            if !allowed.allows_count(count) {
+                errors.push(SaraError::RelationCardinality {
+                    id: item.id.clone(),
+                    relation,
+                    count,
+                    expected,
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Reports items targeted from several items through a `unique_target`
+/// relation.
+fn shared_target_errors(graph: &KnowledgeGraph, schema: &Schema) -> Vec<SaraError> {
+    let mut errors = Vec::new();
+
+    let unique = schema
+        .relations
+        .iter()
+        .filter(|rel| rel.primary && rel.unique_target)
+        .filter_map(|rel| RelationshipType::from_id_in(&rel.id, schema));
+    for relation in unique {
+        for item in graph.items() {
+            let sources = linked_items(graph, &item.id, relation, Direction::Incoming);
+            if sources.len() > 1 {
+                let sources: Vec<&str> = sources.iter().map(|id| id.as_str()).collect();
+                errors.push(SaraError::SharedRelationTarget {
+                    to_id: item.id.clone(),
+                    relation,
+                    sources: sources.join(", "),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Returns the distinct items linked to an item through a relation, in the
+/// given direction, sorted by ID.
+///
+/// Links declared from the other side are counted once the graph holds their
+/// inverse edge.
+fn linked_items<'g>(
+    graph: &'g KnowledgeGraph,
+    id: &ItemId,
+    relation: RelationshipType,
+    direction: Direction,
+) -> Vec<&'g ItemId> {
+    let Some(idx) = graph.node_index(id) else {
+        return Vec::new();
+    };
+    let inner = graph.inner();
+    let mut ids: Vec<&ItemId> = inner
+        .edges_directed(idx, direction)
+        .filter(|edge| *edge.weight() == relation)
+        .map(|edge| match direction {
+            Direction::Outgoing => edge.target(),
+            Direction::Incoming => edge.source(),
+        })
+        .filter_map(|other| inner.node_weight(other))
+        .map(|item| &item.id)
+        .collect();
+    ids.sort_by_key(|id| id.as_str());
+    ids.dedup();
+    ids
+}
+
 /// Checks references of a specific relationship type and collects validation errors.
 fn check_references<'a>(
     item: &Item,
@@ -37,7 +137,11 @@ fn check_references<'a>(
 ) {
     for ref_id in refs {
         if let Some(target) = graph.get(ref_id)
-            && !RelationshipRules::is_valid_relationship(item.item_type, target.item_type, rel_type)
+            && !graph.schema().is_valid_relationship(
+                item.item_type.as_str(),
+                target.item_type.as_str(),
+                rel_type.as_str(),
+            )
         {
             errors.push(SaraError::InvalidRelationship {
                 from_id: item.id.clone(),
@@ -153,4 +257,104 @@ mod tests {
         let errors = rule.validate(&graph, &ValidationConfig::default());
         assert!(errors.is_empty());
     }
+
+    #[test]
+    fn test_cardinality_and_unique_target_constraints() {
+        let mut schema = Schema::builtin();
+        let swreq = schema
+            .item_types
+            .iter_mut()
+            .find(|t| t.id == builtin::SOFTWARE_REQUIREMENT.as_str())
+            .unwrap();
+        let derives = swreq
+            .allowed_targets
+            .iter_mut()
+            .find(|t| t.relation == builtin::DERIVES_FROM.as_str())
+            .unwrap();
+        derives.min = Some(1);
+        derives.max = Some(1);
+        schema
+            .relations
+            .iter_mut()
+            .find(|r| r.id == builtin::SUPERSEDES.as_str())
+            .unwrap()
+            .unique_target = true;
+
+        let derives_from = |ids: &[&str]| {
+            ids.iter()
+                .map(|id| Relationship::new(ItemId::new_unchecked(*id), builtin::DERIVES_FROM))
+                .collect()
+        };
+        let supersedes = |id: &str| {
+            vec![Relationship::new(
+                ItemId::new_unchecked(id),
+                builtin::SUPERSEDES,
+            )]
+        };
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(create_test_item(
+                "SYSARCH-001",
+                builtin::SYSTEM_ARCHITECTURE,
+            ))
+            .add_item(create_test_item(
+                "SYSARCH-002",
+                builtin::SYSTEM_ARCHITECTURE,
+            ))
+            .add_item(create_test_item_with_relationships(
+                "SWREQ-001",
+                builtin::SOFTWARE_REQUIREMENT,
+                derives_from(&["SYSARCH-001"]),
+            ))
+            .add_item(create_test_item_with_relationships(
+                "SWREQ-002",
+                builtin::SOFTWARE_REQUIREMENT,
+                derives_from(&["SYSARCH-001", "SYSARCH-002"]),
+            ))
+            .add_item(create_test_item("SWREQ-003", builtin::SOFTWARE_REQUIREMENT))
+            .add_item(create_test_item(
+                "ADR-001",
+                builtin::ARCHITECTURE_DECISION_RECORD,
+            ))
+            .add_item(create_test_item_with_relationships(
+                "ADR-002",
+                builtin::ARCHITECTURE_DECISION_RECORD,
+                supersedes("ADR-001"),
+            ))
+            .add_item(create_test_item_with_relationships(
+                "ADR-003",
+                builtin::ARCHITECTURE_DECISION_RECORD,
+                supersedes("ADR-001"),
+            ))
+            .build()
+            .unwrap();
+
+        let mut counts: Vec<(String, usize)> = cardinality_errors(&graph, &schema)
+            .into_iter()
+            .map(|e| match e {
+                SaraError::RelationCardinality {
+                    id,
+                    count,
+                    expected,
+                    ..
+                } => {
+                    assert_eq!(expected, "exactly 1");
+                    (id.as_str().to_string(), count)
+                }
+                other => panic!("unexpected error {other:?}"),
+            })
+            .collect();
+        counts.sort();
+        assert_eq!(
+            counts,
+            [("SWREQ-002".to_string(), 2), ("SWREQ-003".to_string(), 0)]
+        );
+
+        let shared = shared_target_errors(&graph, &schema);
+        assert_eq!(shared.len(), 1);
+        assert!(
+            shared[0]
+                .to_string()
+                .contains("ADR-001 is the target of 'supersedes' from ADR-002, ADR-003")
+        );
+    }
 }
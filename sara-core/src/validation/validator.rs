@@ -5,11 +5,13 @@ use std::collections::HashMap;
 use crate::config::ValidationConfig;
 use crate::graph::KnowledgeGraph;
 use crate::model::Item;
+use crate::schema::Schema;
+use crate::validation::plugin;
 use crate::validation::report::{ValidationReport, ValidationReportBuilder};
 use crate::validation::rule::{Severity, ValidationRule};
 use crate::validation::rules::{
-    BrokenReferencesRule, CyclesRule, DuplicatesRule, IdFormatRule, MetadataRule, OrphansRule,
-    RedundantRelationshipsRule, RelationshipsRule,
+    AliasesRule, BrokenReferencesRule, CyclesRule, DuplicatesRule, IdFormatRule, MetadataRule,
+    OrphansRule, RedundantRelationshipsRule, RelationshipsRule,
 };
 
 /// All validation rules.
@@ -22,9 +24,11 @@ static RULES: &[&dyn ValidationRule] = &[
     &RedundantRelationshipsRule,
     &OrphansRule,
     &IdFormatRule,
+    &AliasesRule,
 ];
 
-/// Orchestrates all validation rules.
+/// Orchestrates all validation rules, then the external plugins of the
+/// configuration.
 pub struct Validator {
     /// Configuration for validation behavior.
     config: ValidationConfig,
@@ -45,13 +49,14 @@ impl Validator {
     ///
     /// This enables fail-fast validation during parsing/loading. Only rules
     /// that can validate items independently (without graph context) will
-    /// produce errors here.
-    pub fn pre_validate(&self, items: &[Item]) -> ValidationReport {
+    /// produce errors here. `schema` is the schema the items were parsed
+    /// with.
+    pub fn pre_validate(&self, items: &[Item], schema: &Schema) -> ValidationReport {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
 
         for rule in RULES {
-            let issues = rule.pre_validate(items, &self.config);
+            let issues = rule.pre_validate(items, schema, &self.config);
             let severity = if self.config.strict_mode {
                 Severity::Error
             } else {
@@ -77,6 +82,9 @@ impl Validator {
     }
 
     /// Validates the knowledge graph and returns a report.
+    ///
+    /// Rules run against the schema the graph was built with (see
+    /// [`KnowledgeGraph::schema`]).
     pub fn validate(&self, graph: &KnowledgeGraph) -> ValidationReport {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
@@ -96,6 +104,17 @@ impl Validator {
             }
         }
 
+        // External plugins report the severity of each issue
+        for plugin in &self.config.plugins {
+            for issue in plugin::run(plugin, graph) {
+                match issue.severity {
+                    Severity::Error => errors.push(issue.error),
+                    Severity::Warning if self.config.strict_mode => errors.push(issue.error),
+                    Severity::Warning => warnings.push(issue.error),
+                }
+            }
+        }
+
         ValidationReportBuilder::new()
             .items_checked(graph.item_count())
             .relationships_checked(graph.relationship_count())
@@ -123,28 +142,30 @@ pub fn validate(graph: &KnowledgeGraph, strict: bool) -> ValidationReport {
     Validator::new(config).validate(graph)
 }
 
-/// Convenience function to pre-validate items before adding them to the graph.
+/// Convenience function to pre-validate items parsed with `schema` before
+/// adding them to the graph.
 ///
 /// When `strict` is true, all issues are treated as errors.
 /// If the report contains errors, the items should not be added to the graph.
-pub fn pre_validate(items: &[Item], strict: bool) -> ValidationReport {
+pub fn pre_validate(items: &[Item], schema: &Schema, strict: bool) -> ValidationReport {
     let config = ValidationConfig {
         strict_mode: strict,
         ..Default::default()
     };
-    Validator::new(config).pre_validate(items)
+    Validator::new(config).pre_validate(items, schema)
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::Arc;
 
     use super::*;
 
     use crate::error::SaraError;
     use crate::graph::KnowledgeGraphBuilder;
     use crate::model::{FieldValue, ItemBuilder, ItemId, Relationship, SourceLocation};
-    use crate::schema::builtin;
+    use crate::schema::{self, builtin};
     use crate::test_utils::{create_test_item, create_test_item_with_relationships};
 
     #[test]
@@ -202,6 +223,28 @@ mod tests {
         assert_eq!(report.warning_count(), 1);
     }
 
+    #[test]
+    fn test_validates_against_the_graph_schema() {
+        // A model in which use cases are roots of the hierarchy.
+        let mut rootless = Schema::builtin();
+        for def in &mut rootless.item_types {
+            if def.id == "use_case" {
+                def.parent_types.clear();
+                def.allowed_targets.clear();
+            }
+        }
+        let rootless = Arc::new(rootless);
+
+        let graph = KnowledgeGraphBuilder::new()
+            .schema(Arc::clone(&rootless))
+            .add_item(create_test_item("UC-001", builtin::USE_CASE))
+            .build()
+            .unwrap();
+
+        assert!(Arc::ptr_eq(graph.schema(), &rootless));
+        assert_eq!(validate(&graph, true).error_count(), 0);
+    }
+
     #[test]
     fn test_orphan_error_strict() {
         let graph = KnowledgeGraphBuilder::new()
@@ -283,7 +326,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let report = pre_validate(&[item], false);
+        let report = pre_validate(&[item], schema::active(), false);
         assert!(
             report.is_valid(),
             "Valid item should have no pre-validation errors"
@@ -310,7 +353,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let report = pre_validate(&[item], false);
+        let report = pre_validate(&[item], schema::active(), false);
         assert_eq!(
             report.error_count(),
             1,
@@ -335,7 +378,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let report = pre_validate(&[item], false);
+        let report = pre_validate(&[item], schema::active(), false);
         assert_eq!(report.error_count(), 1, "Should detect empty specification");
         let errors = report.errors();
         assert!(matches!(
@@ -356,7 +399,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let report = pre_validate(&[item], false);
+        let report = pre_validate(&[item], schema::active(), false);
         assert!(report.is_valid(), "Solution should pass pre-validation");
         assert_eq!(report.warning_count(), 0);
     }
@@ -392,8 +435,40 @@ mod tests {
                 .unwrap(),
         ];
 
-        let report = pre_validate(&items, false);
+        let report = pre_validate(&items, schema::active(), false);
         assert_eq!(report.error_count(), 1, "Should detect one invalid item");
         assert_eq!(report.warning_count(), 0);
     }
+
+    /// Plugin script reporting a warning on SOL-001.
+    #[cfg(unix)]
+    const GLOSSARY_WARNING: &str = r#"echo '{"issues": [{"severity": "warning", "item": "SOL-001", "message": "Unknown term"}]}'"#;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_warnings_become_errors_in_strict_mode() {
+        let graph = KnowledgeGraphBuilder::new()
+            .add_item(create_test_item("SOL-001", builtin::SOLUTION))
+            .build()
+            .unwrap();
+        let config = |strict_mode| ValidationConfig {
+            strict_mode,
+            plugins: vec![crate::config::PluginConfig {
+                name: "glossary".to_string(),
+                command: "sh".to_string(),
+                args: vec!["-c".to_string(), GLOSSARY_WARNING.to_string()],
+            }],
+            ..Default::default()
+        };
+
+        let report = Validator::new(config(false)).validate(&graph);
+        assert_eq!((report.error_count(), report.warning_count()), (0, 1));
+        assert!(matches!(
+            report.warnings()[0],
+            SaraError::PluginIssue { plugin, .. } if plugin == "glossary"
+        ));
+
+        let report = Validator::new(config(true)).validate(&graph);
+        assert_eq!((report.error_count(), report.warning_count()), (1, 0));
+    }
 }
@@ -0,0 +1,133 @@
+//! Verifies that body sections declared by a custom schema are read from the
+//! Markdown body as item fields and flow through validation and CSV export.
+//!
+//! Lives as a dedicated integration-test binary so it owns its own process:
+//! the active schema is a process-wide `OnceLock`, so installing here cannot
+//! leak into sibling tests.
+
+use std::path::Path;
+
+use sara_core::graph::KnowledgeGraphBuilder;
+use sara_core::interchange::csv;
+use sara_core::model::FieldValue;
+use sara_core::parser::{InputFormat, ParseContext, parse_metadata};
+use sara_core::schema::builtin;
+use sara_core::schema::{self, Schema};
+use sara_core::validation;
+
+/// Sections added to the built-in system requirement type.
+const SECTIONS_YAML: &str = r#"- id: system_requirement
+  sections:
+  - heading: Rationale
+    field: rationale
+  - heading: Verification
+    field: verification
+    required: true
+"#;
+
+/// Builds a schema extending the built-in system requirement with a
+/// `Rationale` and a required `Verification` section, going through YAML to
+/// exercise the public loading path.
+fn schema_with_sections() -> Schema {
+    let yaml = Schema::builtin()
+        .to_yaml()
+        .expect("serialize builtin")
+        .replace("- id: system_requirement\n", SECTIONS_YAML);
+    Schema::from_yaml_str(&yaml, Path::new("<test>")).expect("parse extended schema")
+}
+
+const VERIFIED_MD: &str = r#"---
+id: "SYSREQ-001"
+type: system_requirement
+name: "Response time"
+specification: "The hub SHALL respond within 100ms."
+---
+# System Requirement: Response time
+
+## Rationale
+
+Users perceive delays above 100ms.
+
+## Verification
+
+Measure the round trip on the reference hub.
+"#;
+
+const UNVERIFIED_MD: &str = r#"---
+id: "SYSREQ-002"
+type: system_requirement
+name: "Uptime"
+specification: "The hub SHALL stay available 99.9% of the time."
+---
+# System Requirement: Uptime
+
+## Verification
+"#;
+
+/// Sections must be parsed into attributes, reported when required and
+/// empty, and exported as CSV columns.
+///
+/// Bundled into a single `#[test]` because the schema singleton accepts one
+/// installation per process.
+#[test]
+fn body_sections_flow_through_parsing_validation_and_export() {
+    schema::install(schema_with_sections()).expect("install once at start of test");
+
+    let repo = Path::new("/repo");
+    let verified = parse_metadata(
+        VERIFIED_MD,
+        Path::new("SYSREQ-001.md"),
+        repo,
+        InputFormat::Markdown,
+        &ParseContext::default(),
+    )
+    .expect("parse verified requirement");
+    assert_eq!(
+        verified.attributes.get("rationale"),
+        Some(&FieldValue::Text(
+            "Users perceive delays above 100ms.".to_string()
+        ))
+    );
+    assert_eq!(
+        verified.attributes.get("verification"),
+        Some(&FieldValue::Text(
+            "Measure the round trip on the reference hub.".to_string()
+        ))
+    );
+
+    let unverified = parse_metadata(
+        UNVERIFIED_MD,
+        Path::new("SYSREQ-002.md"),
+        repo,
+        InputFormat::Markdown,
+        &ParseContext::default(),
+    )
+    .expect("a missing section does not fail parsing");
+    assert!(unverified.attributes.get("verification").is_none());
+
+    let graph = KnowledgeGraphBuilder::new()
+        .add_items([verified, unverified])
+        .build()
+        .expect("build graph");
+
+    let report = validation::validate(&graph, false);
+    let section_errors: Vec<String> = report
+        .errors()
+        .iter()
+        .map(ToString::to_string)
+        .filter(|e| e.contains("section"))
+        .collect();
+    assert_eq!(section_errors.len(), 1, "{section_errors:?}");
+    assert!(section_errors[0].contains("SYSREQ-002.md"));
+    assert!(section_errors[0].contains("'Verification'"));
+
+    let sheet = csv::export(&graph, builtin::SYSTEM_REQUIREMENT);
+    let header = sheet.lines().next().unwrap();
+    assert!(header.starts_with("id,name,description,specification,rationale,verification,"));
+    assert!(sheet.contains("Measure the round trip on the reference hub."));
+
+    // Section columns are read-only: a sheet carrying them still imports.
+    let records = csv::import(&sheet, builtin::SYSTEM_REQUIREMENT).expect("import own export");
+    assert_eq!(records.len(), 2);
+    assert!(!records[0].fields.contains_key("verification"));
+}
@@ -13,7 +13,7 @@ use sara_core::graph::KnowledgeGraphBuilder;
 use sara_core::model::{
     FieldValue, ItemBuilder, ItemId, ItemType, RelationshipRules, RelationshipType, SourceLocation,
 };
-use sara_core::parser::{InputFormat, parse_metadata};
+use sara_core::parser::{InputFormat, ParseContext, parse_metadata};
 use sara_core::schema::builtin;
 use sara_core::schema::{self, Schema};
 use sara_core::service::{EditOptions, EditService, InitOptions, InitService, TypeConfig};
@@ -72,6 +72,7 @@ fn test_source() -> SourceLocation {
         repository: PathBuf::from("/repo"),
         file_path: PathBuf::from("docs/test.md"),
         git_ref: None,
+        lines: None,
     }
 }
 
@@ -118,6 +119,7 @@ fn custom_relation_flows_through_the_whole_pipeline() {
         Path::new("docs/TC-001.md"),
         Path::new("/repo"),
         InputFormat::Markdown,
+        &ParseContext::default(),
     )
     .expect("parse custom-relation document");
     let targets: Vec<_> = item.relationship_ids(verifies).collect();
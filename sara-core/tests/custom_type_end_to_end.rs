@@ -10,7 +10,7 @@ use std::path::Path;
 
 use sara_core::generator::{self, OutputFormat};
 use sara_core::model::{ItemType, RelationshipRules};
-use sara_core::parser::{InputFormat, parse_metadata};
+use sara_core::parser::{InputFormat, ParseContext, parse_metadata};
 use sara_core::schema::builtin;
 use sara_core::schema::{self, Schema};
 use sara_core::service::{InitOptions, InitService, TypeConfig, parse_item_type};
@@ -109,6 +109,7 @@ fn custom_type_flows_through_the_whole_pipeline() {
         Path::new("docs/STKREQ-001.md"),
         Path::new("/repo"),
         InputFormat::Markdown,
+        &ParseContext::default(),
     )
     .expect("parse custom-type document");
     assert_eq!(item.item_type, custom);
@@ -138,6 +139,7 @@ fn custom_type_flows_through_the_whole_pipeline() {
             Path::new("bad.md"),
             Path::new("/repo"),
             InputFormat::Markdown,
+            &ParseContext::default(),
         )
         .is_err()
     );
@@ -27,9 +27,10 @@ fn schema_with_owner_field() -> Schema {
     solution.fields.push(FieldDef {
         name: "owner".to_string(),
         display_name: "Owner".to_string(),
-        field_type: FieldType::Text,
+        field_type: FieldType::TEXT,
         required: false,
         placeholder: None,
+        aliases: Vec::new(),
     });
     schema
 }
@@ -39,6 +40,7 @@ fn test_source() -> SourceLocation {
         repository: PathBuf::from("/repo"),
         file_path: PathBuf::from("docs/test.md"),
         git_ref: None,
+        lines: None,
     }
 }
 
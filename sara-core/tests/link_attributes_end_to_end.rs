@@ -0,0 +1,159 @@
+//! Verifies that links carry the attributes their relation declares: the
+//! extended `{id: ..., attribute: value}` syntax parses alongside plain ids,
+//! attributes survive regeneration of the metadata, required attributes are
+//! checked, and the traceability matrix reports them.
+//!
+//! Lives as a dedicated integration-test binary so it owns its own process:
+//! the active schema is a process-wide `OnceLock`, so installing here cannot
+//! leak into sibling tests.
+
+use std::path::Path;
+
+use sara_core::error::SaraError;
+use sara_core::generator::{self, OutputFormat};
+use sara_core::graph::KnowledgeGraphBuilder;
+use sara_core::model::{FieldValue, Item};
+use sara_core::parser::{InputFormat, ParseContext, parse_metadata};
+use sara_core::report::TraceabilityMatrix;
+use sara_core::schema::{self, Schema, builtin};
+use sara_core::validation::validate;
+
+/// Overlay declaring link attributes on the `satisfies` relation.
+const LINKS_YAML: &str = r#"extends: builtin
+relations:
+- id: satisfies
+  display_name: Satisfies
+  inverse: is_satisfied_by
+  direction: upstream
+  primary: true
+  attributes:
+  - name: coverage
+    display_name: Coverage
+    field_type: !enum { values: [full, partial] }
+    required: true
+  - name: note
+    display_name: Note
+    field_type: text
+"#;
+
+const REQUIREMENT_MD: &str = r#"---
+id: "SYSREQ-001"
+type: system_requirement
+name: "Latency"
+specification: "The system SHALL respond within 200ms."
+---
+# Latency
+"#;
+
+const ARCHITECTURE_MD: &str = r#"---
+id: "SYSARCH-001"
+type: system_architecture
+name: "Platform"
+satisfies:
+  - id: "SYSREQ-001"
+    coverage: partial
+    note: "Cold start is covered by SYSARCH-002."
+  - "SYSREQ-002"
+---
+# Platform
+"#;
+
+fn parse(content: &str, file: &str, format: InputFormat) -> Result<Item, String> {
+    parse_metadata(
+        content,
+        Path::new(file),
+        Path::new("/repo"),
+        format,
+        &ParseContext::default(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Bundled into a single `#[test]` because the schema singleton accepts one
+/// installation per process.
+#[test]
+fn link_attributes_parse_regenerate_and_report() {
+    schema::install(
+        Schema::from_yaml_str(LINKS_YAML, Path::new("<test>")).expect("parse link overlay"),
+    )
+    .expect("install once at start of test");
+
+    let architecture =
+        parse(ARCHITECTURE_MD, "SYSARCH-001.md", InputFormat::Markdown).expect("links parse");
+    let links: Vec<_> = architecture
+        .relationships
+        .iter()
+        .filter(|r| r.relationship_type == builtin::SATISFIES)
+        .collect();
+    assert_eq!(links.len(), 2);
+    assert_eq!(
+        links[0].attributes.get("coverage"),
+        Some(&FieldValue::Enum("partial".to_string()))
+    );
+    assert!(links[1].attributes.is_empty());
+
+    // Attribute values follow their declared type, names must be declared.
+    let invalid = ARCHITECTURE_MD.replace("coverage: partial", "coverage: most");
+    let err = parse(&invalid, "SYSARCH-001.md", InputFormat::Markdown).unwrap_err();
+    assert!(err.contains("link attribute `coverage`"), "{err}");
+    let unknown = ARCHITECTURE_MD.replace("note:", "remark:");
+    let err = parse(&unknown, "SYSARCH-001.md", InputFormat::Markdown).unwrap_err();
+    assert!(err.contains("unknown link attribute `remark`"), "{err}");
+
+    // Regenerated YAML and TOML metadata keep the attributes.
+    for (format, file, input) in [
+        (
+            OutputFormat::Markdown,
+            "SYSARCH-001.md",
+            InputFormat::Markdown,
+        ),
+        (
+            OutputFormat::Toml,
+            "SYSARCH-001.sara.toml",
+            InputFormat::Toml,
+        ),
+    ] {
+        let metadata = generator::generate_metadata(&architecture, format);
+        let reparsed = parse(&metadata, file, input).expect("regenerated metadata parses");
+        assert_eq!(
+            reparsed.relationships, architecture.relationships,
+            "{metadata}"
+        );
+    }
+
+    // Links missing a required attribute are reported.
+    let requirement =
+        parse(REQUIREMENT_MD, "SYSREQ-001.md", InputFormat::Markdown).expect("requirement");
+    let second = parse(
+        &REQUIREMENT_MD.replace("SYSREQ-001", "SYSREQ-002"),
+        "SYSREQ-002.md",
+        InputFormat::Markdown,
+    )
+    .expect("second requirement");
+    let graph = KnowledgeGraphBuilder::new()
+        .add_items([requirement, second, architecture])
+        .build()
+        .expect("build graph");
+    let report = validate(&graph, false);
+    assert!(report.errors().iter().any(|e| matches!(
+        e,
+        SaraError::InvalidMetadata { reason, .. }
+            if reason.contains("SYSREQ-002 requires a non-empty 'coverage' attribute")
+    )));
+
+    // The matrix lists the attributes of each link.
+    let matrix = TraceabilityMatrix::generate(&graph);
+    let target = matrix
+        .rows
+        .iter()
+        .flat_map(|row| &row.targets)
+        .find(|t| t.id == "SYSREQ-001")
+        .expect("matrix lists the link");
+    assert_eq!(
+        target.attributes_summary(),
+        "coverage: partial, note: Cold start is covered by SYSARCH-002."
+    );
+    let serialized = serde_yaml::to_string(&matrix).expect("matrix serializes");
+    assert!(serialized.contains("coverage: partial"), "{serialized}");
+    assert!(matrix.to_csv().contains("coverage: partial"));
+}
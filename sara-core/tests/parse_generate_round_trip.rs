@@ -8,7 +8,7 @@ use sara_core::generator::{self, OutputFormat};
 use sara_core::model::{
     FieldValue, Item, ItemBuilder, ItemId, Relationship, RelationshipType, SourceLocation,
 };
-use sara_core::parser::{InputFormat, parse_metadata};
+use sara_core::parser::{InputFormat, ParseContext, parse_metadata};
 use sara_core::schema::builtin;
 
 /// Builds an item exercising every frontmatter shape: a description, an enum
@@ -18,6 +18,7 @@ fn representative_item() -> Item {
         repository: PathBuf::from("/repo"),
         file_path: PathBuf::from("docs/ADR-042.md"),
         git_ref: None,
+        lines: None,
     };
 
     ItemBuilder::new()
@@ -57,6 +58,7 @@ fn generated_document_parses_back_into_an_equivalent_item() {
         Path::new("docs/ADR-042.md"),
         Path::new("/repo"),
         InputFormat::Markdown,
+        &ParseContext::default(),
     )
     .expect("generated document parses");
 
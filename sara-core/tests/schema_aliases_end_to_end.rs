@@ -0,0 +1,145 @@
+//! Verifies that schema aliases keep documents written with former names
+//! parsing: the item type, field and relation names resolve to the
+//! canonical ones, each use is reported as a warning, and regenerated
+//! metadata carries the canonical names.
+//!
+//! Lives as a dedicated integration-test binary so it owns its own process:
+//! the active schema is a process-wide `OnceLock`, so installing here cannot
+//! leak into sibling tests.
+
+use std::path::Path;
+
+use sara_core::error::SaraError;
+use sara_core::generator::{self, OutputFormat};
+use sara_core::graph::KnowledgeGraphBuilder;
+use sara_core::model::AliasUse;
+use sara_core::parser::{InputFormat, ParseContext, parse_metadata};
+use sara_core::schema::{self, Schema, builtin};
+use sara_core::validation::validate;
+
+/// Overlay renaming concepts of the built-in model, keeping the former
+/// names as aliases.
+const ALIASES_YAML: &str = r#"extends: builtin
+item_types:
+- id: system_requirement
+  aliases: [sysreq]
+  fields:
+  - name: specification
+    display_name: Specification
+    field_type: text
+    required: true
+    aliases: [statement]
+relations:
+- id: satisfies
+  display_name: Satisfies
+  inverse: is_satisfied_by
+  direction: upstream
+  primary: true
+  aliases: [implements]
+"#;
+
+const REQUIREMENT_MD: &str = r#"---
+id: "SYSREQ-001"
+type: sysreq
+name: "Latency"
+statement: "The system SHALL respond within 200ms."
+---
+# Latency
+"#;
+
+const ARCHITECTURE_MD: &str = r#"---
+id: "SYSARCH-001"
+type: system_architecture
+name: "Platform"
+implements:
+  - "SYSREQ-001"
+---
+# Platform
+"#;
+
+/// Bundled into a single `#[test]` because the schema singleton accepts one
+/// installation per process.
+#[test]
+fn aliases_resolve_to_canonical_names_with_a_warning() {
+    schema::install(
+        Schema::from_yaml_str(ALIASES_YAML, Path::new("<test>")).expect("parse alias overlay"),
+    )
+    .expect("install once at start of test");
+
+    let requirement = parse_metadata(
+        REQUIREMENT_MD,
+        Path::new("SYSREQ-001.md"),
+        Path::new("/repo"),
+        InputFormat::Markdown,
+        &ParseContext::default(),
+    )
+    .expect("parse aliased type and field");
+    assert_eq!(requirement.item_type, builtin::SYSTEM_REQUIREMENT);
+    assert!(requirement.attributes.get("specification").is_some());
+    assert_eq!(
+        requirement.aliases,
+        [
+            AliasUse {
+                alias: "sysreq".to_string(),
+                canonical: "system_requirement".to_string(),
+            },
+            AliasUse {
+                alias: "statement".to_string(),
+                canonical: "specification".to_string(),
+            },
+        ]
+    );
+
+    let architecture = parse_metadata(
+        ARCHITECTURE_MD,
+        Path::new("SYSARCH-001.md"),
+        Path::new("/repo"),
+        InputFormat::Markdown,
+        &ParseContext::default(),
+    )
+    .expect("parse aliased relation");
+    assert_eq!(architecture.relationship_ids(builtin::SATISFIES).count(), 1);
+
+    // Regenerated metadata, as written by `sara edit`, uses canonical names.
+    let metadata = generator::generate_metadata(&requirement, OutputFormat::Yaml);
+    assert!(metadata.contains("type: system_requirement"));
+    assert!(metadata.contains("specification:"));
+
+    let graph = KnowledgeGraphBuilder::new()
+        .add_items([requirement, architecture])
+        .build()
+        .expect("build graph");
+    let report = validate(&graph, false);
+    let aliases: Vec<(&str, &str)> = report
+        .warnings()
+        .into_iter()
+        .filter_map(|w| match w {
+            SaraError::DeprecatedAlias {
+                alias, canonical, ..
+            } => Some((alias.as_str(), canonical.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(aliases.len(), 3);
+    assert!(aliases.contains(&("implements", "satisfies")));
+
+    // Writing both the alias and the canonical name is ambiguous.
+    let twice = REQUIREMENT_MD.replace(
+        "---\n# Latency",
+        "specification: \"The system SHALL be fast.\"\n---\n# Latency",
+    );
+    assert!(
+        parse_metadata(
+            &twice,
+            Path::new("SYSREQ-001.md"),
+            Path::new("/repo"),
+            InputFormat::Markdown,
+            &ParseContext::default(),
+        )
+        .is_err()
+    );
+
+    // An alias may not shadow another name.
+    let clash = ALIASES_YAML.replace("aliases: [implements]", "aliases: [derives_from]");
+    assert!(Schema::from_yaml_str(&clash, Path::new("<test>")).is_err());
+}
@@ -0,0 +1,147 @@
+//! Verifies that number, integer, boolean, URL and pattern-constrained text
+//! fields parse to typed values, reject values breaking their constraints,
+//! and survive regeneration of the metadata.
+//!
+//! Lives as a dedicated integration-test binary so it owns its own process:
+//! the active schema is a process-wide `OnceLock`, so installing here cannot
+//! leak into sibling tests.
+
+use std::path::Path;
+
+use sara_core::generator::{self, OutputFormat};
+use sara_core::model::FieldValue;
+use sara_core::parser::{InputFormat, ParseContext, parse_metadata};
+use sara_core::schema::{self, Schema};
+
+/// Overlay adding typed fields to the built-in system requirement.
+const TYPED_YAML: &str = r#"extends: builtin
+item_types:
+- id: system_requirement
+  fields:
+  - name: latency_ms
+    display_name: Latency
+    field_type: !number { min: 0, unit: ms }
+  - name: priority
+    display_name: Priority
+    field_type: !integer { min: 1, max: 5 }
+  - name: safety_relevant
+    display_name: Safety Relevant
+    field_type: boolean
+  - name: reference
+    display_name: Reference
+    field_type: url
+  - name: tracker
+    display_name: Tracker
+    field_type: !text { pattern: "[A-Z]+-[0-9]+" }
+"#;
+
+const REQUIREMENT_MD: &str = r#"---
+id: "SYSREQ-001"
+type: system_requirement
+name: "Latency"
+specification: "The system SHALL respond within 500ms."
+latency_ms: 500
+priority: 2
+safety_relevant: true
+reference: "https://example.com/specs/latency"
+tracker: "SYS-42"
+---
+# Latency
+"#;
+
+const REQUIREMENT_ADOC: &str = "= Latency
+:id: SYSREQ-002
+:type: system_requirement
+:name: Latency
+:specification: The system SHALL respond within 500ms.
+:latency_ms: 12.5 ms
+:safety_relevant: false
+";
+
+fn parse(content: &str, format: InputFormat) -> Result<sara_core::model::Item, String> {
+    parse_metadata(
+        content,
+        Path::new("doc"),
+        Path::new("/repo"),
+        format,
+        &ParseContext::default(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Bundled into a single `#[test]` because the schema singleton accepts one
+/// installation per process.
+#[test]
+fn typed_fields_parse_check_and_regenerate() {
+    schema::install(
+        Schema::from_yaml_str(TYPED_YAML, Path::new("<test>")).expect("parse typed overlay"),
+    )
+    .expect("install once at start of test");
+
+    let item = parse(REQUIREMENT_MD, InputFormat::Markdown).expect("typed values parse");
+    let attribute = |name: &str| item.attributes.get(name).cloned();
+    assert_eq!(attribute("latency_ms"), Some(FieldValue::Number(500.0)));
+    assert_eq!(attribute("priority"), Some(FieldValue::Integer(2)));
+    assert_eq!(
+        attribute("safety_relevant"),
+        Some(FieldValue::Boolean(true))
+    );
+    assert_eq!(
+        attribute("reference"),
+        Some(FieldValue::Url(
+            "https://example.com/specs/latency".to_string()
+        ))
+    );
+    assert_eq!(attribute("tracker"), Some(FieldValue::text("SYS-42")));
+
+    // Field lists carry every value as text, unit suffix included.
+    let listed = parse(REQUIREMENT_ADOC, InputFormat::AsciiDoc).expect("field list parses");
+    assert_eq!(
+        listed.attributes.get("latency_ms"),
+        Some(&FieldValue::Number(12.5))
+    );
+    assert_eq!(
+        listed.attributes.get("safety_relevant"),
+        Some(&FieldValue::Boolean(false))
+    );
+
+    // Regenerated metadata writes numbers and booleans unquoted and parses
+    // back to the same values.
+    let metadata = generator::generate_metadata(&item, OutputFormat::Markdown);
+    assert!(metadata.contains("latency_ms: 500\n"));
+    assert!(metadata.contains("safety_relevant: true\n"));
+    let reparsed = parse(&format!("{metadata}\n# Latency\n"), InputFormat::Markdown)
+        .expect("regenerated metadata parses");
+    for name in [
+        "latency_ms",
+        "priority",
+        "safety_relevant",
+        "reference",
+        "tracker",
+    ] {
+        assert_eq!(reparsed.attributes.get(name), item.attributes.get(name));
+    }
+
+    for (written, broken, reason) in [
+        ("priority: 2", "priority: 9", "expected at most 5"),
+        ("latency_ms: 500", "latency_ms: fast", "expected a number"),
+        (
+            "safety_relevant: true",
+            "safety_relevant: maybe",
+            "expected true or false",
+        ),
+        (
+            "\"https://example.com/specs/latency\"",
+            "\"see the spec\"",
+            "expected an absolute URL",
+        ),
+        ("\"SYS-42\"", "\"sys-42\"", "expected a match"),
+    ] {
+        let err = parse(
+            &REQUIREMENT_MD.replace(written, broken),
+            InputFormat::Markdown,
+        )
+        .expect_err(broken);
+        assert!(err.contains(reason), "{broken}: got {err}");
+    }
+}
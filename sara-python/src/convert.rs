@@ -0,0 +1,96 @@
+//! Conversions from core values to Python objects.
+
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyImportError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDate, PyDict, PyList};
+use serde::Serialize;
+use serde_json::Value;
+
+use sara_core::model::FieldValue;
+
+/// Converts a field value to its natural Python type: `str`, `int`, `float`,
+/// `bool`, `datetime.date` or a `list` of those.
+pub fn field_value<'py>(py: Python<'py>, value: &FieldValue) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        FieldValue::Text(s) | FieldValue::Enum(s) | FieldValue::Url(s) => s.into_bound_py_any(py),
+        FieldValue::ItemRef(id) => id.as_str().into_bound_py_any(py),
+        FieldValue::Date(s) => match parse_date(s) {
+            Some((year, month, day)) => PyDate::new(py, year, month, day)?.into_bound_py_any(py),
+            None => s.into_bound_py_any(py),
+        },
+        FieldValue::Number(n) => n.into_bound_py_any(py),
+        FieldValue::Integer(n) => n.into_bound_py_any(py),
+        FieldValue::Boolean(b) => b.into_bound_py_any(py),
+        FieldValue::List(values) => {
+            let values = values
+                .iter()
+                .map(|v| field_value(py, v))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, values)?.into_bound_py_any(py)
+        }
+    }
+}
+
+/// Splits an ISO-8601 `YYYY-MM-DD` date.
+fn parse_date(date: &str) -> Option<(i32, u8, u8)> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+    Some((year, month, day))
+}
+
+/// Converts a serializable value to plain Python objects (`dict`, `list`,
+/// `str`, numbers, `bool` and `None`).
+pub fn to_python<'py>(py: Python<'py>, value: &impl Serialize) -> PyResult<Bound<'py, PyAny>> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    json(py, &value)
+}
+
+/// Converts a JSON value to plain Python objects.
+pub fn json<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        Value::Null => Ok(py.None().into_bound(py)),
+        Value::Bool(b) => b.into_bound_py_any(py),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => i.into_bound_py_any(py),
+            (None, Some(u)) => u.into_bound_py_any(py),
+            (None, None) => n.as_f64().unwrap_or(f64::NAN).into_bound_py_any(py),
+        },
+        Value::String(s) => s.into_bound_py_any(py),
+        Value::Array(values) => {
+            let values = values
+                .iter()
+                .map(|v| json(py, v))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, values)?.into_bound_py_any(py)
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, json(py, value)?)?;
+            }
+            dict.into_bound_py_any(py)
+        }
+    }
+}
+
+/// Builds a `pandas.DataFrame` with one row per record.
+///
+/// pandas is an optional dependency, imported on first use.
+pub fn dataframe<'py>(
+    py: Python<'py>,
+    records: Vec<Bound<'py, PyAny>>,
+    columns: &[&str],
+) -> PyResult<Bound<'py, PyAny>> {
+    let pandas = py.import("pandas").map_err(|_| {
+        PyImportError::new_err("pandas is required for DataFrames: pip install sara-graph[pandas]")
+    })?;
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("columns", columns.to_vec())?;
+    pandas
+        .getattr("DataFrame")?
+        .call((PyList::new(py, records)?,), Some(&kwargs))
+}
@@ -0,0 +1,410 @@
+//! The `Graph`, `Item` and `Traversal` classes.
+
+use std::sync::Arc;
+
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use sara_core::error::SaraError;
+use sara_core::graph::{
+    KnowledgeGraph, TraversalOptions, TraversalResult, traverse_downstream, traverse_upstream,
+};
+use sara_core::model::{self, ItemType};
+use sara_core::report::{CoverageReport, TraceabilityMatrix};
+use sara_core::schema::Schema;
+use sara_core::service::parse_item_type_in;
+
+use crate::convert;
+use crate::reports::{PyCoverageReport, PyGraphDiff, PyTraceabilityMatrix, PyValidationReport};
+
+/// Columns of the DataFrame of a graph's items.
+const ITEM_COLUMNS: &[&str] = &["id", "type", "name", "description", "file", "repository"];
+
+/// A knowledge graph of items and their relationships.
+#[pyclass(name = "Graph", module = "sara", frozen)]
+pub struct PyGraph {
+    graph: KnowledgeGraph,
+    warnings: Vec<String>,
+}
+
+impl PyGraph {
+    pub fn new(graph: KnowledgeGraph, warnings: Vec<String>) -> Self {
+        Self { graph, warnings }
+    }
+
+    /// Wraps an item of the graph.
+    fn item(&self, item: &model::Item) -> PyItem {
+        PyItem {
+            item: item.clone(),
+            schema: Arc::clone(self.graph.schema()),
+        }
+    }
+
+    /// Looks up an item, raising `KeyError` with similar ids when missing.
+    fn lookup(&self, id: &str) -> PyResult<&model::Item> {
+        self.graph.lookup_or_suggest(id).map_err(|e| match &e {
+            SaraError::ItemNotFound { .. } => match e.format_suggestions() {
+                Some(suggestions) => PyKeyError::new_err(format!("{e}. {suggestions}")),
+                None => PyKeyError::new_err(e.to_string()),
+            },
+            _ => crate::error(e),
+        })
+    }
+
+    /// Traverses the graph from an item in one direction.
+    fn traverse(
+        &self,
+        py: Python<'_>,
+        id: &str,
+        depth: Option<usize>,
+        types: Option<Vec<String>>,
+        traverse: fn(&KnowledgeGraph, &model::ItemId, &TraversalOptions) -> Option<TraversalResult>,
+    ) -> PyResult<PyTraversal> {
+        let graph = &self.graph;
+        let origin = self.lookup(id)?;
+        let mut options = TraversalOptions::new();
+        if let Some(depth) = depth {
+            options = options.with_max_depth(depth);
+        }
+        if let Some(types) = types {
+            options = options.with_types(item_types(&types, graph.schema())?);
+        }
+        let result = traverse(graph, &origin.id, &options)
+            .ok_or_else(|| PyKeyError::new_err(id.to_string()))?;
+        let nodes = result
+            .items
+            .iter()
+            .filter_map(|node| graph.get(&node.item_id).map(|item| (node, item)))
+            .map(|(node, item)| {
+                let node = PyTraversalNode {
+                    item: Py::new(py, self.item(item))?,
+                    depth: node.depth,
+                    relationship: node.relationship.map(|r| r.as_str().to_string()),
+                    parent: node.parent.as_ref().map(|p| p.as_str().to_string()),
+                };
+                Py::new(py, node)
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(PyTraversal {
+            origin: result.origin.as_str().to_string(),
+            max_depth: result.max_depth,
+            nodes,
+        })
+    }
+}
+
+#[pymethods]
+impl PyGraph {
+    /// Warnings for documents skipped while loading the graph.
+    #[getter]
+    fn warnings(&self) -> Vec<String> {
+        self.warnings.clone()
+    }
+
+    /// Returns every item of the graph.
+    fn items(&self) -> Vec<PyItem> {
+        self.graph.items().map(|item| self.item(item)).collect()
+    }
+
+    /// Returns the item with the given id, raising `KeyError` with similar
+    /// ids when it does not exist.
+    fn get(&self, id: &str) -> PyResult<PyItem> {
+        self.lookup(id).map(|item| self.item(item))
+    }
+
+    fn __getitem__(&self, id: &str) -> PyResult<PyItem> {
+        self.get(id)
+    }
+
+    fn __contains__(&self, id: &str) -> bool {
+        self.graph.lookup_or_suggest(id).is_ok()
+    }
+
+    fn __len__(&self) -> usize {
+        self.graph.item_count()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Graph items={} relationships={}>",
+            self.graph.item_count(),
+            self.graph.relationship_count()
+        )
+    }
+
+    /// Returns the items upstream of an item, up to `depth` levels and
+    /// keeping only the given item `types`.
+    #[pyo3(signature = (id, depth=None, types=None))]
+    fn upstream(
+        &self,
+        py: Python<'_>,
+        id: &str,
+        depth: Option<usize>,
+        types: Option<Vec<String>>,
+    ) -> PyResult<PyTraversal> {
+        self.traverse(py, id, depth, types, traverse_upstream)
+    }
+
+    /// Returns the items downstream of an item, up to `depth` levels and
+    /// keeping only the given item `types`.
+    #[pyo3(signature = (id, depth=None, types=None))]
+    fn downstream(
+        &self,
+        py: Python<'_>,
+        id: &str,
+        depth: Option<usize>,
+        types: Option<Vec<String>>,
+    ) -> PyResult<PyTraversal> {
+        self.traverse(py, id, depth, types, traverse_downstream)
+    }
+
+    /// Validates the graph; `strict` turns warnings into errors.
+    #[pyo3(signature = (strict=false))]
+    fn validate(&self, py: Python<'_>, strict: bool) -> PyValidationReport {
+        let report = py.detach(|| sara_core::validation::validate(&self.graph, strict));
+        PyValidationReport::new(report)
+    }
+
+    /// Returns the traceability coverage of the graph.
+    fn coverage(&self) -> PyCoverageReport {
+        PyCoverageReport::new(CoverageReport::generate(&self.graph))
+    }
+
+    /// Returns the traceability matrix of the graph.
+    fn matrix(&self) -> PyTraceabilityMatrix {
+        PyTraceabilityMatrix::new(TraceabilityMatrix::generate(&self.graph))
+    }
+
+    /// Returns the changes from `other` to this graph.
+    fn diff(&self, other: &PyGraph) -> PyGraphDiff {
+        PyGraphDiff::new(
+            sara_core::graph::GraphDiff::compute(&other.graph, &self.graph),
+            Vec::new(),
+        )
+    }
+
+    /// Returns the items as a `pandas.DataFrame`, one row per item.
+    fn to_dataframe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let records = self
+            .graph
+            .items()
+            .map(|item| self.item(item).summary(py))
+            .collect::<PyResult<Vec<_>>>()?;
+        convert::dataframe(py, records, ITEM_COLUMNS)
+    }
+}
+
+/// Resolves item type names, accepting the forms the CLI does.
+fn item_types(names: &[String], schema: &Schema) -> PyResult<Vec<ItemType>> {
+    names
+        .iter()
+        .map(|name| {
+            parse_item_type_in(name, schema)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown item type '{name}'")))
+        })
+        .collect()
+}
+
+/// An item of the graph.
+#[pyclass(name = "Item", module = "sara", frozen)]
+pub struct PyItem {
+    item: model::Item,
+    schema: Arc<Schema>,
+}
+
+impl PyItem {
+    /// Returns the columns of the item in a graph DataFrame.
+    fn summary<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let dict = PyDict::new(py);
+        dict.set_item("id", self.item.id.as_str())?;
+        dict.set_item("type", self.item.item_type.as_str())?;
+        dict.set_item("name", &self.item.name)?;
+        dict.set_item("description", self.item.description.as_deref())?;
+        dict.set_item("file", self.file())?;
+        dict.set_item("repository", self.repository())?;
+        dict.into_bound_py_any(py)
+    }
+}
+
+#[pymethods]
+impl PyItem {
+    /// Identifier of the item.
+    #[getter]
+    fn id(&self) -> &str {
+        self.item.id.as_str()
+    }
+
+    /// Type of the item, as its schema id (e.g. `system_requirement`).
+    #[getter]
+    fn r#type(&self) -> &str {
+        self.item.item_type.as_str()
+    }
+
+    /// Display name of the item type (e.g. `System Requirement`).
+    #[getter]
+    fn type_name(&self) -> &str {
+        self.item.item_type.display_name_in(&self.schema)
+    }
+
+    /// Human-readable name.
+    #[getter]
+    fn name(&self) -> &str {
+        &self.item.name
+    }
+
+    /// Description, if any.
+    #[getter]
+    fn description(&self) -> Option<&str> {
+        self.item.description.as_deref()
+    }
+
+    /// Path of the document, relative to its repository.
+    #[getter]
+    fn file(&self) -> String {
+        self.item.source.file_path.display().to_string()
+    }
+
+    /// Repository the document was read from.
+    #[getter]
+    fn repository(&self) -> String {
+        self.item.source.repository.display().to_string()
+    }
+
+    /// Attributes of the item, with typed values: dates as `datetime.date`,
+    /// numbers as `int` or `float`, flags as `bool`.
+    #[getter]
+    fn attributes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (name, value) in self.item.attributes.iter() {
+            dict.set_item(name, convert::field_value(py, value)?)?;
+        }
+        Ok(dict)
+    }
+
+    /// Relationships of the item, as dicts with `to`, `type` and
+    /// `attributes` keys.
+    #[getter]
+    fn relationships<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let relationships = self
+            .item
+            .relationships
+            .iter()
+            .map(|relationship| {
+                let dict = PyDict::new(py);
+                dict.set_item("to", relationship.to.as_str())?;
+                dict.set_item("type", relationship.relationship_type.as_str())?;
+                let attributes = PyDict::new(py);
+                for (name, value) in relationship.attributes.iter() {
+                    attributes.set_item(name, convert::field_value(py, value)?)?;
+                }
+                dict.set_item("attributes", attributes)?;
+                Ok(dict)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, relationships)
+    }
+
+    /// Ids of the items mentioned in the document body.
+    #[getter]
+    fn mentions(&self) -> Vec<&str> {
+        self.item.mentions.iter().map(|id| id.as_str()).collect()
+    }
+
+    /// Returns the item as plain Python objects.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.item)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Item {} ({})>", self.item.id.as_str(), self.item.name)
+    }
+}
+
+/// An item reached by a traversal.
+#[pyclass(name = "TraversalNode", module = "sara", frozen, get_all)]
+pub struct PyTraversalNode {
+    /// The item reached.
+    item: Py<PyItem>,
+    /// Distance from the origin (0 for the origin itself).
+    depth: usize,
+    /// Relationship followed to reach the item (None for the origin).
+    relationship: Option<String>,
+    /// Id of the closest listed ancestor (None for the origin).
+    parent: Option<String>,
+}
+
+/// The items reached walking the graph upstream or downstream of an item.
+#[pyclass(name = "Traversal", module = "sara", frozen)]
+pub struct PyTraversal {
+    origin: String,
+    max_depth: usize,
+    nodes: Vec<Py<PyTraversalNode>>,
+}
+
+#[pymethods]
+impl PyTraversal {
+    /// Id of the item the traversal started from.
+    #[getter]
+    fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    /// Deepest level reached.
+    #[getter]
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Nodes reached, in breadth-first order, starting with the origin.
+    #[getter]
+    fn nodes(&self, py: Python<'_>) -> Vec<Py<PyTraversalNode>> {
+        self.nodes.iter().map(|node| node.clone_ref(py)).collect()
+    }
+
+    /// Items reached, in breadth-first order, starting with the origin.
+    fn items(&self, py: Python<'_>) -> Vec<Py<PyItem>> {
+        self.nodes
+            .iter()
+            .map(|node| node.get().item.clone_ref(py))
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the nodes as a `pandas.DataFrame`, one row per node.
+    fn to_dataframe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let records = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let node = node.get();
+                let item = node.item.get();
+                let dict = PyDict::new(py);
+                dict.set_item("id", item.id())?;
+                dict.set_item("type", item.r#type())?;
+                dict.set_item("name", item.name())?;
+                dict.set_item("depth", node.depth)?;
+                dict.set_item("relationship", node.relationship.as_deref())?;
+                dict.set_item("parent", node.parent.as_deref())?;
+                dict.into_bound_py_any(py)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        convert::dataframe(
+            py,
+            records,
+            &["id", "type", "name", "depth", "relationship", "parent"],
+        )
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Traversal from {} nodes={}>",
+            self.origin,
+            self.nodes.len()
+        )
+    }
+}
@@ -0,0 +1,149 @@
+//! Python bindings for sara-core.
+//!
+//! Exposes graph loading, item access, traversal and the validation,
+//! coverage, traceability matrix and diff reports as the `sara` Python
+//! module. Reports convert to plain Python objects with `to_dict()` and to
+//! pandas DataFrames with `to_dataframe()`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use sara_core::config::{Config, RepositoryConfig, load_config};
+use sara_core::error::SaraError;
+use sara_core::parser::ParseContext;
+use sara_core::repository::MemorySource;
+use sara_core::schema::Schema;
+use sara_core::service::{self, DiffOptions, DiffService};
+
+mod convert;
+mod graph;
+mod reports;
+
+use graph::{PyGraph, PyItem, PyTraversal, PyTraversalNode};
+use reports::{PyCoverageReport, PyGraphDiff, PyTraceabilityMatrix, PyValidationReport};
+
+create_exception!(
+    sara,
+    SaraException,
+    PyException,
+    "Raised when documents, configuration or a schema cannot be read."
+);
+
+/// Converts a core error to a Python exception.
+pub(crate) fn error(error: impl std::fmt::Display) -> PyErr {
+    SaraException::new_err(error.to_string())
+}
+
+/// Loads a schema file, or the built-in schema when none is given.
+fn load_schema(path: Option<&Path>) -> PyResult<Arc<Schema>> {
+    let schema = match path {
+        Some(path) => Schema::from_path(path).map_err(error)?,
+        None => Schema::builtin(),
+    };
+    Ok(Arc::new(schema))
+}
+
+/// Loads the graph of repositories, reading them in `ctx`.
+fn load(py: Python<'_>, repositories: &RepositoryConfig, ctx: &ParseContext) -> PyResult<PyGraph> {
+    let (graph, warnings) = py
+        .detach(|| service::load_graph(repositories, ctx))
+        .map_err(error)?;
+    let warnings = warnings.iter().map(ToString::to_string).collect();
+    Ok(PyGraph::new(graph, warnings))
+}
+
+/// Loads the graph of the documents under `paths`, read against the schema
+/// file `schema` (default: the built-in model).
+#[pyfunction]
+#[pyo3(signature = (paths, schema=None))]
+fn load_graph(py: Python<'_>, paths: Vec<PathBuf>, schema: Option<PathBuf>) -> PyResult<PyGraph> {
+    let repositories = RepositoryConfig {
+        paths,
+        ..RepositoryConfig::default()
+    };
+    let ctx = ParseContext::new(load_schema(schema.as_deref())?);
+    load(py, &repositories, &ctx)
+}
+
+/// Loads the graph of the repositories configured in a `sara.toml` file,
+/// with their namespaces, model schema and mention pattern.
+#[pyfunction]
+#[pyo3(signature = (path=PathBuf::from("sara.toml")))]
+fn load_graph_from_config(py: Python<'_>, path: PathBuf) -> PyResult<PyGraph> {
+    let config = read_config(&path)?;
+    let schema = load_schema(config.model_schema.as_deref().map(Path::new))?;
+    let pattern = config.mentions.compile().map_err(error)?;
+    let ctx = ParseContext::new(schema).with_mention_pattern(pattern);
+    load(py, &config.repositories, &ctx)
+}
+
+/// Loads the graph of in-memory documents, given as a mapping of paths to
+/// contents, read against the schema file `schema` (default: the built-in
+/// model).
+#[pyfunction]
+#[pyo3(signature = (documents, schema=None))]
+fn load_graph_from_documents(
+    py: Python<'_>,
+    documents: HashMap<PathBuf, String>,
+    schema: Option<PathBuf>,
+) -> PyResult<PyGraph> {
+    let ctx = ParseContext::new(load_schema(schema.as_deref())?);
+    let mut documents: Vec<_> = documents.into_iter().collect();
+    documents.sort();
+    let source: MemorySource = documents.into_iter().collect();
+    let (graph, warnings) = py
+        .detach(|| service::load_graph_from_sources(&[&source], &ctx))
+        .map_err(error)?;
+    let warnings = warnings.iter().map(ToString::to_string).collect();
+    Ok(PyGraph::new(graph, warnings))
+}
+
+/// Compares the graph of the repositories under `paths` between two Git
+/// references, reading the schema file `schema` as of each reference.
+#[pyfunction]
+#[pyo3(signature = (ref1, ref2, paths, schema=None))]
+fn diff(
+    py: Python<'_>,
+    ref1: String,
+    ref2: String,
+    paths: Vec<PathBuf>,
+    schema: Option<PathBuf>,
+) -> PyResult<PyGraphDiff> {
+    let mut options = DiffOptions::new(ref1, ref2).with_repositories(paths);
+    if let Some(schema) = schema {
+        options = options.with_model_schema(schema);
+    }
+    let result = py
+        .detach(|| DiffService::new().diff(&options))
+        .map_err(error)?;
+    Ok(PyGraphDiff::new(result.diff, result.warnings))
+}
+
+/// Reads a configuration file.
+fn read_config(path: &Path) -> PyResult<Config> {
+    load_config(path).map_err(|e: SaraError| error(e))
+}
+
+/// The `sara` Python module.
+#[pymodule]
+fn sara(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("SaraError", m.py().get_type::<SaraException>())?;
+    m.add_class::<PyGraph>()?;
+    m.add_class::<PyItem>()?;
+    m.add_class::<PyTraversal>()?;
+    m.add_class::<PyTraversalNode>()?;
+    m.add_class::<PyValidationReport>()?;
+    m.add_class::<PyCoverageReport>()?;
+    m.add_class::<PyTraceabilityMatrix>()?;
+    m.add_class::<PyGraphDiff>()?;
+    m.add_function(wrap_pyfunction!(load_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(load_graph_from_config, m)?)?;
+    m.add_function(wrap_pyfunction!(load_graph_from_documents, m)?)?;
+    m.add_function(wrap_pyfunction!(diff, m)?)?;
+    Ok(())
+}
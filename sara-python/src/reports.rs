@@ -0,0 +1,397 @@
+//! Report classes: validation, coverage, traceability matrix and diff.
+
+use pyo3::IntoPyObjectExt;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use sara_core::graph::GraphDiff;
+use sara_core::report::{CoverageReport, TraceabilityMatrix};
+use sara_core::validation::ValidationReport;
+
+use crate::convert;
+
+/// Issues found validating a graph.
+#[pyclass(name = "ValidationReport", module = "sara", frozen)]
+pub struct PyValidationReport {
+    report: ValidationReport,
+}
+
+impl PyValidationReport {
+    pub fn new(report: ValidationReport) -> Self {
+        Self { report }
+    }
+
+    /// Returns one dict per issue, with its `severity`, `kind`, `message`
+    /// and `details`.
+    fn issue_records<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        self.report
+            .issues
+            .iter()
+            .map(|issue| {
+                let error = serde_json::to_value(&issue.error).unwrap_or_default();
+                let dict = PyDict::new(py);
+                dict.set_item("severity", convert::to_python(py, &issue.severity)?)?;
+                dict.set_item("kind", convert::json(py, &error["error_type"])?)?;
+                dict.set_item("message", issue.error.to_string())?;
+                dict.set_item("details", convert::json(py, &error["details"])?)?;
+                dict.into_bound_py_any(py)
+            })
+            .collect()
+    }
+}
+
+#[pymethods]
+impl PyValidationReport {
+    /// Whether the graph has no errors.
+    #[getter]
+    fn is_valid(&self) -> bool {
+        self.report.is_valid()
+    }
+
+    /// Number of errors.
+    #[getter]
+    fn error_count(&self) -> usize {
+        self.report.error_count()
+    }
+
+    /// Number of warnings.
+    #[getter]
+    fn warning_count(&self) -> usize {
+        self.report.warning_count()
+    }
+
+    /// Number of items checked.
+    #[getter]
+    fn items_checked(&self) -> usize {
+        self.report.items_checked
+    }
+
+    /// Number of relationships checked.
+    #[getter]
+    fn relationships_checked(&self) -> usize {
+        self.report.relationships_checked
+    }
+
+    /// Issues found, as dicts with `severity`, `kind`, `message` and
+    /// `details` keys.
+    #[getter]
+    fn issues<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyAny>>> {
+        self.issue_records(py)
+    }
+
+    /// Returns the report as plain Python objects.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.report)
+    }
+
+    /// Returns the issues as a `pandas.DataFrame`, one row per issue.
+    fn to_dataframe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let records = self.issue_records(py)?;
+        convert::dataframe(py, records, &["severity", "kind", "message"])
+    }
+
+    fn __bool__(&self) -> bool {
+        self.report.is_valid()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ValidationReport errors={} warnings={}>",
+            self.report.error_count(),
+            self.report.warning_count()
+        )
+    }
+}
+
+/// Traceability coverage of a graph, overall and by item type.
+#[pyclass(name = "CoverageReport", module = "sara", frozen)]
+pub struct PyCoverageReport {
+    report: CoverageReport,
+}
+
+impl PyCoverageReport {
+    pub fn new(report: CoverageReport) -> Self {
+        Self { report }
+    }
+}
+
+#[pymethods]
+impl PyCoverageReport {
+    /// Percentage of items with complete traceability.
+    #[getter]
+    fn overall_coverage(&self) -> f64 {
+        self.report.overall_coverage
+    }
+
+    /// Number of items.
+    #[getter]
+    fn total_items(&self) -> usize {
+        self.report.total_items
+    }
+
+    /// Number of items with complete traceability.
+    #[getter]
+    fn complete_items(&self) -> usize {
+        self.report.complete_items
+    }
+
+    /// Coverage of each item type, as dicts.
+    #[getter]
+    fn by_type<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.report.by_type)
+    }
+
+    /// Items with incomplete traceability, as dicts with the reason.
+    #[getter]
+    fn incomplete_items<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.report.incomplete_items)
+    }
+
+    /// Returns the report as plain Python objects.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.report)
+    }
+
+    /// Returns the coverage by item type as a `pandas.DataFrame`.
+    fn to_dataframe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let records = self
+            .report
+            .by_type
+            .iter()
+            .map(|coverage| convert::to_python(py, coverage))
+            .collect::<PyResult<Vec<_>>>()?;
+        convert::dataframe(
+            py,
+            records,
+            &[
+                "item_type",
+                "type_name",
+                "total",
+                "complete",
+                "incomplete",
+                "coverage_percent",
+            ],
+        )
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<CoverageReport {:.1}% of {} items>",
+            self.report.overall_coverage, self.report.total_items
+        )
+    }
+}
+
+/// Relationships between items, one row per source item.
+#[pyclass(name = "TraceabilityMatrix", module = "sara", frozen)]
+pub struct PyTraceabilityMatrix {
+    matrix: TraceabilityMatrix,
+}
+
+impl PyTraceabilityMatrix {
+    pub fn new(matrix: TraceabilityMatrix) -> Self {
+        Self { matrix }
+    }
+}
+
+#[pymethods]
+impl PyTraceabilityMatrix {
+    /// Rows of the matrix, as dicts with their targets.
+    #[getter]
+    fn rows<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.matrix.rows)
+    }
+
+    /// Names of the matrix columns.
+    #[getter]
+    fn columns(&self) -> Vec<String> {
+        self.matrix.columns.clone()
+    }
+
+    /// Number of relationships in the matrix.
+    #[getter]
+    fn total_relationships(&self) -> usize {
+        self.matrix.total_relationships
+    }
+
+    /// Returns the matrix as CSV.
+    fn to_csv(&self) -> String {
+        self.matrix.to_csv()
+    }
+
+    /// Returns the matrix as plain Python objects.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.matrix)
+    }
+
+    /// Returns the matrix as a `pandas.DataFrame`, one row per relationship;
+    /// items without relationships get a row with empty target columns.
+    fn to_dataframe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut records = Vec::new();
+        for row in &self.matrix.rows {
+            let record = |target: Option<&sara_core::report::MatrixTarget>| {
+                let dict = PyDict::new(py);
+                dict.set_item("source_id", &row.source_id)?;
+                dict.set_item("source_name", &row.source_name)?;
+                dict.set_item("source_type", &row.source_type)?;
+                dict.set_item("relationship", target.map(|t| &t.relationship))?;
+                dict.set_item("target_id", target.map(|t| &t.id))?;
+                dict.set_item("target_name", target.map(|t| &t.name))?;
+                dict.set_item("target_type", target.map(|t| &t.target_type))?;
+                dict.into_bound_py_any(py)
+            };
+            if row.targets.is_empty() {
+                records.push(record(None)?);
+            }
+            for target in &row.targets {
+                records.push(record(Some(target))?);
+            }
+        }
+        convert::dataframe(
+            py,
+            records,
+            &[
+                "source_id",
+                "source_name",
+                "source_type",
+                "relationship",
+                "target_id",
+                "target_name",
+                "target_type",
+            ],
+        )
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<TraceabilityMatrix rows={} relationships={}>",
+            self.matrix.rows.len(),
+            self.matrix.total_relationships
+        )
+    }
+}
+
+/// Changes between two graphs.
+#[pyclass(name = "GraphDiff", module = "sara", frozen)]
+pub struct PyGraphDiff {
+    diff: GraphDiff,
+    warnings: Vec<String>,
+}
+
+impl PyGraphDiff {
+    pub fn new(diff: GraphDiff, warnings: Vec<String>) -> Self {
+        Self { diff, warnings }
+    }
+}
+
+#[pymethods]
+impl PyGraphDiff {
+    /// Warnings raised while reading the references, such as a reference
+    /// read with the built-in model for want of a schema file.
+    #[getter]
+    fn warnings(&self) -> Vec<String> {
+        self.warnings.clone()
+    }
+
+    /// Items added, as dicts.
+    #[getter]
+    fn added_items<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.diff.added_items)
+    }
+
+    /// Items removed, as dicts.
+    #[getter]
+    fn removed_items<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.diff.removed_items)
+    }
+
+    /// Items modified, as dicts with their field changes.
+    #[getter]
+    fn modified_items<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.diff.modified_items)
+    }
+
+    /// Relationships added, as dicts.
+    #[getter]
+    fn added_relationships<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.diff.added_relationships)
+    }
+
+    /// Relationships removed, as dicts.
+    #[getter]
+    fn removed_relationships<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.diff.removed_relationships)
+    }
+
+    /// Counts of the changes.
+    #[getter]
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.diff.stats)
+    }
+
+    /// Whether the graphs are identical.
+    fn is_empty(&self) -> bool {
+        self.diff.is_empty()
+    }
+
+    /// Returns the diff as plain Python objects.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        convert::to_python(py, &self.diff)
+    }
+
+    /// Returns the item changes as a `pandas.DataFrame`, one row per added
+    /// or removed item and per modified field.
+    fn to_dataframe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let row =
+            |change: &str, id: &str, name: &str, item_type: &str, field: Option<[&str; 3]>| {
+                let dict = PyDict::new(py);
+                dict.set_item("change", change)?;
+                dict.set_item("id", id)?;
+                dict.set_item("name", name)?;
+                dict.set_item("type", item_type)?;
+                dict.set_item("field", field.map(|[field, _, _]| field))?;
+                dict.set_item("old_value", field.map(|[_, old, _]| old))?;
+                dict.set_item("new_value", field.map(|[_, _, new]| new))?;
+                dict.into_bound_py_any(py)
+            };
+        let mut records = Vec::new();
+        for item in &self.diff.added_items {
+            records.push(row("added", &item.id, &item.name, &item.item_type, None)?);
+        }
+        for item in &self.diff.removed_items {
+            records.push(row("removed", &item.id, &item.name, &item.item_type, None)?);
+        }
+        for item in &self.diff.modified_items {
+            for change in &item.changes {
+                records.push(row(
+                    "modified",
+                    &item.id,
+                    &item.name,
+                    &item.item_type,
+                    Some([&change.field, &change.old_value, &change.new_value]),
+                )?);
+            }
+        }
+        convert::dataframe(
+            py,
+            records,
+            &[
+                "change",
+                "id",
+                "name",
+                "type",
+                "field",
+                "old_value",
+                "new_value",
+            ],
+        )
+    }
+
+    fn __repr__(&self) -> String {
+        let stats = &self.diff.stats;
+        format!(
+            "<GraphDiff +{} -{} ~{} items>",
+            stats.items_added, stats.items_removed, stats.items_modified
+        )
+    }
+}
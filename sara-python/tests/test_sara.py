@@ -0,0 +1,165 @@
+"""Tests of the `sara` Python module, run with `pytest` after `maturin develop`."""
+
+import datetime
+import pathlib
+
+import pytest
+
+import sara
+
+FIXTURES = pathlib.Path(__file__).resolve().parents[2] / "tests" / "fixtures"
+
+TYPED_SCHEMA = """extends: builtin
+item_types:
+- id: system_requirement
+  fields:
+  - name: latency_ms
+    display_name: Latency
+    field_type: !number { min: 0, unit: ms }
+  - name: priority
+    display_name: Priority
+    field_type: !integer { min: 1, max: 5 }
+  - name: safety_relevant
+    display_name: Safety Relevant
+    field_type: boolean
+  - name: due
+    display_name: Due
+    field_type: date
+"""
+
+SOLUTION = """---
+id: "SOL-001"
+type: solution
+name: "Platform"
+---
+"""
+
+USE_CASE = """---
+id: "UC-001"
+type: use_case
+name: "Sign In"
+refines:
+  - "SOL-001"
+---
+"""
+
+
+@pytest.fixture(scope="module")
+def graph():
+    return sara.load_graph([FIXTURES / "valid_graph"])
+
+
+def test_load_graph_reads_every_item(graph):
+    assert len(graph) == 7
+    assert "SYSREQ-001" in graph
+    assert "SYSREQ-999" not in graph
+    assert not graph.warnings
+
+
+def test_item_exposes_metadata(graph):
+    item = graph.get("SWREQ-001")
+    assert item.id == "SWREQ-001"
+    assert item.type == "software_requirement"
+    assert item.type_name == "Software Requirement"
+    assert item.file == "SWREQ-001.md"
+    assert item.attributes["specification"].startswith("The software SHALL")
+    assert item.to_dict()["id"] == "SWREQ-001"
+
+
+def test_missing_item_raises_key_error_with_suggestions(graph):
+    with pytest.raises(KeyError, match="SYSREQ-001"):
+        graph["SYSREQ-01"]
+
+
+def test_traversal_follows_relationships(graph):
+    upstream = graph.upstream("SWDD-001")
+    assert upstream.origin == "SWDD-001"
+    assert [node.item.id for node in upstream.nodes][-1] == "SOL-001"
+
+    downstream = graph.downstream("SOL-001", depth=1)
+    assert {item.id for item in downstream.items()} == {"SOL-001", "UC-001"}
+
+    scenarios = graph.downstream("SOL-001", types=["scenario"])
+    assert [item.id for item in scenarios.items()] == ["SCEN-001"]
+
+    with pytest.raises(ValueError):
+        graph.downstream("SOL-001", types=["nonsense"])
+
+
+def test_reports(graph):
+    report = graph.validate()
+    assert report.is_valid
+    assert report.items_checked == 7
+    assert report.issues == []
+
+    coverage = graph.coverage()
+    assert coverage.total_items == 7
+    assert coverage.overall_coverage == 100.0
+
+    matrix = graph.matrix()
+    assert matrix.total_relationships > 0
+    assert matrix.to_dict()["rows"]
+
+
+def test_validation_reports_broken_references():
+    graph = sara.load_graph([FIXTURES / "broken_refs"])
+    report = graph.validate()
+    assert not report.is_valid
+    assert report.error_count == len(report.issues)
+    assert {issue["severity"] for issue in report.issues} == {"error"}
+
+
+def test_in_memory_documents_and_typed_attributes(tmp_path):
+    schema = tmp_path / "schema.yaml"
+    schema.write_text(TYPED_SCHEMA)
+    requirement = """---
+id: "SYSREQ-001"
+type: system_requirement
+name: "Latency"
+specification: "The system SHALL respond within 500ms."
+latency_ms: 12.5
+priority: 2
+safety_relevant: true
+due: 2026-03-01
+---
+"""
+    graph = sara.load_graph_from_documents(
+        {"SOL-001.md": SOLUTION, "SYSREQ-001.md": requirement}, schema=schema
+    )
+    attributes = graph.get("SYSREQ-001").attributes
+    assert attributes["latency_ms"] == 12.5
+    assert attributes["priority"] == 2
+    assert attributes["safety_relevant"] is True
+    assert attributes["due"] == datetime.date(2026, 3, 1)
+
+
+def test_diff_between_graphs():
+    old = sara.load_graph_from_documents({"SOL-001.md": SOLUTION})
+    new = sara.load_graph_from_documents(
+        {"SOL-001.md": SOLUTION, "UC-001.md": USE_CASE}
+    )
+    diff = new.diff(old)
+    assert not diff.is_empty()
+    assert [item["id"] for item in diff.added_items] == ["UC-001"]
+    assert diff.stats["relationships_added"] == 1
+    assert old.diff(old).is_empty()
+
+
+def test_unreadable_schema_raises_sara_error(tmp_path):
+    with pytest.raises(sara.SaraError):
+        sara.load_graph([FIXTURES / "valid_graph"], schema=tmp_path / "missing.yaml")
+
+
+def test_dataframes(graph):
+    pytest.importorskip("pandas")
+
+    items = graph.to_dataframe()
+    assert len(items) == 7
+    assert list(items.columns[:3]) == ["id", "type", "name"]
+
+    traversal = graph.downstream("SOL-001").to_dataframe()
+    assert traversal["depth"].max() > 0
+
+    assert len(graph.validate().to_dataframe()) == 0
+    assert set(graph.coverage().to_dataframe()["coverage_percent"]) == {100.0}
+    assert "target_id" in graph.matrix().to_dataframe().columns